@@ -0,0 +1,37 @@
+use blinksy::{
+    layout::Layout1d,
+    layout1d,
+    patterns::vu::{VuMeter, VuMeterParams},
+    ControlBuilder,
+};
+use blinksy_desktop::{
+    audio::Microphone,
+    driver::{Desktop, DesktopError},
+    time::elapsed_in_ms,
+};
+use std::{thread::sleep, time::Duration};
+
+layout1d!(StripLayout, 30);
+
+fn main() {
+    Desktop::new_1d::<StripLayout>().start(|driver| {
+        let mut microphone = Microphone::new().expect("failed to start microphone");
+
+        let mut control = ControlBuilder::new_1d()
+            .with_layout::<StripLayout, { StripLayout::PIXEL_COUNT }>()
+            .with_pattern::<VuMeter>(VuMeterParams::default())
+            .with_driver(driver)
+            .with_frame_buffer_size::<{ StripLayout::PIXEL_COUNT }>()
+            .build();
+
+        loop {
+            control.pattern_mut().update(&mut microphone);
+
+            if let Err(DesktopError::WindowClosed) = control.tick(elapsed_in_ms()) {
+                break;
+            }
+
+            sleep(Duration::from_millis(16));
+        }
+    });
+}
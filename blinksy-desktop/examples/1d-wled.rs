@@ -0,0 +1,45 @@
+use blinksy::{
+    layout::Layout1d,
+    layout1d,
+    patterns::switch::{BuiltinSwitch1d, BuiltinSwitchParams},
+    ControlBuilder,
+};
+use blinksy_desktop::{
+    driver::{Desktop, DesktopError},
+    time::elapsed_in_ms,
+    wled::{WledInfo, WledServer},
+};
+use std::{thread::sleep, time::Duration};
+
+layout1d!(StripLayout, 60);
+
+fn main() {
+    Desktop::new_1d::<StripLayout>().start(|driver| {
+        let wled = WledServer::start(
+            "0.0.0.0:8080",
+            WledInfo {
+                led_count: StripLayout::PIXEL_COUNT,
+                effects: vec!["Rainbow".into(), "Noise".into()],
+                ..Default::default()
+            },
+        )
+        .expect("failed to start WLED server");
+
+        let mut control = ControlBuilder::new_1d()
+            .with_layout::<StripLayout, { StripLayout::PIXEL_COUNT }>()
+            .with_pattern::<BuiltinSwitch1d>(BuiltinSwitchParams::default())
+            .with_driver(driver)
+            .with_frame_buffer_size::<{ StripLayout::PIXEL_COUNT }>()
+            .build();
+
+        loop {
+            wled.apply(&mut control, |_color| None);
+
+            if let Err(DesktopError::WindowClosed) = control.tick(elapsed_in_ms()) {
+                break;
+            }
+
+            sleep(Duration::from_millis(16));
+        }
+    });
+}
@@ -0,0 +1,179 @@
+//! # Audio Input
+//!
+//! This module provides an [`AudioSource`] from the default microphone, for audio-reactive
+//! patterns in the desktop simulation.
+//!
+//! Requires the `audio` feature.
+//!
+//! ## Example
+//!
+//! ```rust,no_run
+//! use blinksy::audio::AudioSource;
+//! use blinksy_desktop::audio::Microphone;
+//!
+//! let mut microphone = Microphone::new().unwrap();
+//!
+//! loop {
+//!     // Pass the microphone to your pattern, such as with `VuMeter::update`
+//!     println!("level: {}", microphone.level());
+//!
+//!     std::thread::sleep(std::time::Duration::from_millis(16));
+//! }
+//! ```
+
+use core::fmt;
+use std::sync::{
+    atomic::{AtomicU32, Ordering},
+    Arc,
+};
+
+use blinksy::audio::AudioSource;
+use cpal::{
+    traits::{DeviceTrait, HostTrait, StreamTrait},
+    FromSample, SampleFormat, SizedSample,
+};
+
+/// An audio source from the default microphone (input device).
+///
+/// The level is the root mean square (RMS) of the latest buffer of samples, multiplied by
+/// a gain. Speech at a normal distance has an RMS around 0.05 to 0.1, so the default gain
+/// is 8.0.
+///
+/// The microphone stops recording when dropped. On some platforms, the microphone can't be
+/// sent between threads, so create it on the thread which uses it.
+pub struct Microphone {
+    /// The input stream, kept alive while recording
+    _stream: cpal::Stream,
+    /// The latest RMS level, as `f32` bits
+    rms: Arc<AtomicU32>,
+    /// Multiplier from the RMS level to the normalized level
+    gain: f32,
+}
+
+impl Microphone {
+    /// The default gain, from the RMS level to the normalized level.
+    pub const DEFAULT_GAIN: f32 = 8.;
+
+    /// Starts recording from the default microphone.
+    pub fn new() -> Result<Self, MicrophoneError> {
+        let host = cpal::default_host();
+        let device = host
+            .default_input_device()
+            .ok_or(MicrophoneError::NoInputDevice)?;
+        let config = device.default_input_config()?;
+
+        let rms = Arc::new(AtomicU32::new(0f32.to_bits()));
+        let stream = match config.sample_format() {
+            SampleFormat::F32 => build_stream::<f32>(&device, &config.into(), rms.clone())?,
+            SampleFormat::I16 => build_stream::<i16>(&device, &config.into(), rms.clone())?,
+            SampleFormat::U16 => build_stream::<u16>(&device, &config.into(), rms.clone())?,
+            sample_format => return Err(MicrophoneError::UnsupportedSampleFormat(sample_format)),
+        };
+        stream.play()?;
+
+        Ok(Self {
+            _stream: stream,
+            rms,
+            gain: Self::DEFAULT_GAIN,
+        })
+    }
+
+    /// Returns the microphone with a gain, from the RMS level to the normalized level.
+    ///
+    /// # Arguments
+    ///
+    /// - `gain` - Multiplier for the RMS level
+    pub fn with_gain(self, gain: f32) -> Self {
+        Self { gain, ..self }
+    }
+}
+
+impl AudioSource for Microphone {
+    fn level(&mut self) -> f32 {
+        f32::from_bits(self.rms.load(Ordering::Relaxed)) * self.gain
+    }
+}
+
+/// Builds an input stream which stores the RMS level of each buffer of samples.
+fn build_stream<T>(
+    device: &cpal::Device,
+    config: &cpal::StreamConfig,
+    rms: Arc<AtomicU32>,
+) -> Result<cpal::Stream, cpal::BuildStreamError>
+where
+    T: SizedSample,
+    f32: FromSample<T>,
+{
+    device.build_input_stream(
+        config,
+        move |data: &[T], _: &cpal::InputCallbackInfo| {
+            if data.is_empty() {
+                return;
+            }
+            let sum_of_squares: f32 = data
+                .iter()
+                .map(|sample| {
+                    let sample = sample.to_sample::<f32>();
+                    sample * sample
+                })
+                .sum();
+            let level = (sum_of_squares / data.len() as f32).sqrt();
+            rms.store(level.to_bits(), Ordering::Relaxed);
+        },
+        |error| eprintln!("Microphone stream error: {error}"),
+        None,
+    )
+}
+
+/// Errors that can occur when starting the microphone.
+#[derive(Debug)]
+pub enum MicrophoneError {
+    /// No default input device is available.
+    NoInputDevice,
+
+    /// The default input config of the device is unavailable.
+    DefaultConfig(cpal::DefaultStreamConfigError),
+
+    /// The sample format of the device is not supported.
+    UnsupportedSampleFormat(SampleFormat),
+
+    /// Building the input stream failed.
+    BuildStream(cpal::BuildStreamError),
+
+    /// Starting the input stream failed.
+    PlayStream(cpal::PlayStreamError),
+}
+
+impl fmt::Display for MicrophoneError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            MicrophoneError::NoInputDevice => write!(f, "no input device available"),
+            MicrophoneError::DefaultConfig(error) => write!(f, "no input config: {error}"),
+            MicrophoneError::UnsupportedSampleFormat(sample_format) => {
+                write!(f, "unsupported sample format: {sample_format}")
+            }
+            MicrophoneError::BuildStream(error) => write!(f, "failed to build stream: {error}"),
+            MicrophoneError::PlayStream(error) => write!(f, "failed to play stream: {error}"),
+        }
+    }
+}
+
+impl core::error::Error for MicrophoneError {}
+
+impl From<cpal::DefaultStreamConfigError> for MicrophoneError {
+    fn from(error: cpal::DefaultStreamConfigError) -> Self {
+        MicrophoneError::DefaultConfig(error)
+    }
+}
+
+impl From<cpal::BuildStreamError> for MicrophoneError {
+    fn from(error: cpal::BuildStreamError) -> Self {
+        MicrophoneError::BuildStream(error)
+    }
+}
+
+impl From<cpal::PlayStreamError> for MicrophoneError {
+    fn from(error: cpal::PlayStreamError) -> Self {
+        MicrophoneError::PlayStream(error)
+    }
+}
@@ -9,6 +9,7 @@
 //! - LEDs are represented as small 3D objects
 //! - LED positions match the layout's physical arrangement
 //! - Colors and brightness updates are displayed in real-time
+//! - Bright LEDs glow, by [`DesktopConfig::glow`]
 //!
 //! ## Controls
 //!
@@ -16,6 +17,28 @@
 //! - Mouse wheel: Zoom in/out
 //! - R key: Reset camera to default position
 //! - O key: Toggle between orthographic and perspective projection
+//! - L key: Toggle the index labels of the LEDs
+//! - F key: Toggle the frame statistics: frames drawn per second, and the average time
+//!   between LED updates (the time per tick of your control loop)
+//! - Left click: Select an LED, to show its index, position, segment, and color
+//! - Escape key: Deselect the LED
+//! - Up / Down arrow keys: Increase / decrease the brightness
+//! - Right arrow key: Switch to the next pattern
+//! - Number keys 1 to 9: Select a pattern by number
+//!
+//! The brightness and pattern keys send a [`DesktopCommand`] back to your control loop, see
+//! [Commands](#commands).
+//!
+//! ## Wiring
+//!
+//! LEDs are shown in the order of the layout's points, which is the order of the LEDs along
+//! the chain: for a serpentine grid, every other row runs backwards. To check the wiring of
+//! your LEDs against the layout, show the index of each LED with
+//! [`DesktopConfig::show_index_labels`] (or the L key), or run an index-based pattern such as a
+//! chase.
+//!
+//! For a chained layout, name the segment of each LED with [`Desktop::with_segments`], to
+//! show which sub-strip or panel a selected LED is in.
 //!
 //! ## Usage
 //!
@@ -58,19 +81,75 @@
 //! });
 //! ```
 //!
+//! With the `async` feature, the driver also implements [`DriverAsync`], for an async
+//! control loop.
+//!
+//! ## Commands
+//!
+//! The driver and the simulator window talk over a channel in each direction:
+//!
+//! - The driver sends the colors, brightness, and color correction of each frame to the
+//!   window.
+//! - The window sends a [`DesktopCommand`] to the driver for each brightness or pattern key
+//!   pressed.
+//!
+//! The window can't change your [`Control`](blinksy::control::Control) itself, so receive
+//! the commands in your control loop, with [`DesktopDriver::try_recv_command`], and apply
+//! them, with [`DesktopCommand::apply`]. Selecting patterns needs a pattern switch, such as
+//! [`BuiltinSwitch`](blinksy::patterns::switch::BuiltinSwitch).
+//!
+//! ```rust,no_run
+//! use blinksy::{
+//!     ControlBuilder,
+//!     layout::Layout1d,
+//!     layout1d,
+//!     patterns::switch::{BuiltinSwitch1d, BuiltinSwitchParams}
+//! };
+//! use blinksy_desktop::{driver::Desktop, time::elapsed_in_ms};
+//!
+//! layout1d!(Layout, 60);
+//!
+//! Desktop::new_1d::<Layout>().start(|driver| {
+//!     let mut control = ControlBuilder::new_1d()
+//!         .with_layout::<Layout, { Layout::PIXEL_COUNT }>()
+//!         .with_pattern::<BuiltinSwitch1d>(BuiltinSwitchParams::default())
+//!         .with_driver(driver)
+//!         .with_frame_buffer_size::<{ Layout::PIXEL_COUNT }>()
+//!         .build();
+//!
+//!     loop {
+//!         while let Some(command) = control.driver().try_recv_command() {
+//!             command.apply(&mut control);
+//!         }
+//!         control.tick(elapsed_in_ms()).unwrap();
+//!         std::thread::sleep(std::time::Duration::from_millis(16));
+//!     }
+//! });
+//! ```
+//!
 //! [`Driver`]: blinksy::driver::Driver
+//! [`DriverAsync`]: blinksy::driver::DriverAsync
 
+#[cfg(feature = "async")]
+use blinksy::driver::DriverAsync;
 use blinksy::{
     color::{ColorCorrection, FromColor, LinearSrgb, Srgb},
+    control::Control,
     driver::Driver,
     layout::{Layout1d, Layout2d, Layout3d, LayoutForDim},
     markers::{Dim1d, Dim2d, Dim3d},
+    pattern::Pattern as PatternTrait,
+    patterns::switch::PatternSwitch,
 };
-use core::{fmt, marker::PhantomData};
+use core::{fmt, marker::PhantomData, ops::Range};
 use egui_miniquad as egui_mq;
 use glam::{vec3, Mat4, Vec3, Vec4, Vec4Swizzles};
 use miniquad::*;
-use std::sync::mpsc::{channel, Receiver, SendError, Sender};
+use std::{
+    collections::VecDeque,
+    sync::mpsc::{channel, Receiver, SendError, Sender},
+    time::{Duration, Instant},
+};
 
 /// Configuration options for the desktop simulator.
 ///
@@ -97,6 +176,16 @@ pub struct DesktopConfig {
 
     /// Background color (R, G, B, A) where each component is 0.0 - 1.0
     pub background_color: (f32, f32, f32, f32),
+
+    /// Whether to initially draw the index of each LED next to it (toggled with the L key)
+    pub show_index_labels: bool,
+
+    /// Whether to initially show the frame statistics (toggled with the F key)
+    pub show_frame_stats: bool,
+
+    /// Intensity of the glow around each LED, scaled by the LED's luminance, so bright LEDs
+    /// glow and dim LEDs don't (0.0 for no glow)
+    pub glow: f32,
 }
 
 impl Default for DesktopConfig {
@@ -109,6 +198,9 @@ impl Default for DesktopConfig {
             high_dpi: true,
             orthographic_view: true,
             background_color: (0.1, 0.1, 0.1, 1.0),
+            show_index_labels: false,
+            show_frame_stats: false,
+            glow: 0.3,
         }
     }
 }
@@ -169,6 +261,7 @@ impl Desktop<Dim1d, ()> {
         }
 
         let (sender, receiver) = channel();
+        let (command_sender, command_receiver) = channel();
         let is_window_closed = std::sync::Arc::new(std::sync::atomic::AtomicBool::new(false));
         let is_window_closed_2 = is_window_closed.clone();
 
@@ -178,12 +271,15 @@ impl Desktop<Dim1d, ()> {
             brightness: 1.0,
             correction: ColorCorrection::default(),
             sender,
+            command_receiver,
             is_window_closed,
         };
         let stage = DesktopStageOptions {
             positions,
             receiver,
+            command_sender,
             config,
+            segments: Vec::new(),
             is_window_closed: is_window_closed_2,
         };
 
@@ -234,6 +330,7 @@ impl Desktop<Dim2d, ()> {
         }
 
         let (sender, receiver) = channel();
+        let (command_sender, command_receiver) = channel();
         let is_window_closed = std::sync::Arc::new(std::sync::atomic::AtomicBool::new(false));
         let is_window_closed_2 = is_window_closed.clone();
 
@@ -243,12 +340,15 @@ impl Desktop<Dim2d, ()> {
             brightness: 1.0,
             correction: ColorCorrection::default(),
             sender,
+            command_receiver,
             is_window_closed,
         };
         let stage = DesktopStageOptions {
             positions,
             receiver,
+            command_sender,
             config,
+            segments: Vec::new(),
             is_window_closed: is_window_closed_2,
         };
 
@@ -299,6 +399,7 @@ impl Desktop<Dim3d, ()> {
         }
 
         let (sender, receiver) = channel();
+        let (command_sender, command_receiver) = channel();
         let is_window_closed = std::sync::Arc::new(std::sync::atomic::AtomicBool::new(false));
         let is_window_closed_2 = is_window_closed.clone();
 
@@ -308,12 +409,15 @@ impl Desktop<Dim3d, ()> {
             brightness: 1.0,
             correction: ColorCorrection::default(),
             sender,
+            command_receiver,
             is_window_closed,
         };
         let stage = DesktopStageOptions {
             positions,
             receiver,
+            command_sender,
             config,
+            segments: Vec::new(),
             is_window_closed: is_window_closed_2,
         };
 
@@ -321,6 +425,40 @@ impl Desktop<Dim3d, ()> {
     }
 }
 
+impl<Dim, Layout> Desktop<Dim, Layout> {
+    /// Names the segments of the layout, such as the sub-strips of a chained layout.
+    ///
+    /// When an LED is selected, the LED information shows the segment it's in.
+    ///
+    /// # Parameters
+    ///
+    /// - `segments` - The name and index range of each segment
+    ///
+    /// # Example
+    ///
+    /// ```rust,no_run
+    /// use blinksy::{layout::Layout1d, layout1d, layout_chain};
+    /// use blinksy_desktop::driver::Desktop;
+    ///
+    /// layout1d!(Desk, 30);
+    /// layout1d!(Shelf, 45);
+    /// layout_chain!(Layout, Layout1d, [Desk, Shelf]);
+    ///
+    /// let desktop = Desktop::new_1d::<Layout>()
+    ///     .with_segments(Layout::SEGMENT_NAMES.into_iter().zip(Layout::segments()));
+    /// ```
+    pub fn with_segments<Name: Into<String>>(
+        mut self,
+        segments: impl IntoIterator<Item = (Name, Range<usize>)>,
+    ) -> Self {
+        self.stage.segments = segments
+            .into_iter()
+            .map(|(name, range)| (name.into(), range))
+            .collect();
+        self
+    }
+}
+
 impl<Dim, Layout> Desktop<Dim, Layout>
 where
     Dim: 'static + Send,
@@ -340,7 +478,8 @@ where
 
 /// Desktop driver for simulating LED layouts in a desktop window.
 ///
-/// This struct implements the `Driver` trait.
+/// This struct implements the `Driver` trait, and the `DriverAsync` trait with the `async`
+/// feature.
 ///
 /// # Type Parameters
 ///
@@ -352,10 +491,18 @@ pub struct DesktopDriver<Dim, Layout> {
     brightness: f32,
     correction: ColorCorrection,
     sender: Sender<LedMessage>,
+    command_receiver: Receiver<DesktopCommand>,
     is_window_closed: std::sync::Arc<std::sync::atomic::AtomicBool>,
 }
 
 impl<Dim, Layout> DesktopDriver<Dim, Layout> {
+    /// Receives the next command from the simulator window, if any, without waiting.
+    ///
+    /// See [Commands](self#commands).
+    pub fn try_recv_command(&self) -> Option<DesktopCommand> {
+        self.command_receiver.try_recv().ok()
+    }
+
     fn send(&self, message: LedMessage) -> Result<(), DesktopError> {
         if self
             .is_window_closed
@@ -366,6 +513,51 @@ impl<Dim, Layout> DesktopDriver<Dim, Layout> {
         self.sender.send(message)?;
         Ok(())
     }
+
+    /// Sends the brightness and color correction to the render thread, if changed.
+    fn update_settings(
+        &mut self,
+        brightness: f32,
+        correction: ColorCorrection,
+    ) -> Result<(), DesktopError> {
+        if self.brightness != brightness {
+            self.brightness = brightness;
+            self.send(LedMessage::UpdateBrightness(brightness))?;
+        }
+
+        if self.correction != correction {
+            self.correction = correction;
+            self.send(LedMessage::UpdateColorCorrection(correction))?;
+        }
+
+        Ok(())
+    }
+
+    /// Converts the pixels to the colors sent to the render thread.
+    fn encode_colors<const FRAME_BUFFER_SIZE: usize, Pixels, Color>(
+        pixels: Pixels,
+    ) -> heapless::Vec<LinearSrgb, FRAME_BUFFER_SIZE>
+    where
+        Pixels: IntoIterator<Item = Color>,
+        LinearSrgb: FromColor<Color>,
+    {
+        pixels
+            .into_iter()
+            .map(|color| LinearSrgb::from_color(color))
+            .collect()
+    }
+
+    /// Sends the colors of a frame to the render thread.
+    fn send_colors<const FRAME_BUFFER_SIZE: usize>(
+        &self,
+        frame: heapless::Vec<LinearSrgb, FRAME_BUFFER_SIZE>,
+    ) -> Result<(), DesktopError> {
+        let colors: Vec<LinearSrgb> = frame.into_iter().collect();
+        self.send(LedMessage::UpdateColors {
+            colors,
+            sent_at: Instant::now(),
+        })
+    }
 }
 
 /// Errors that can occur when using the Desktop driver.
@@ -395,10 +587,98 @@ impl From<SendError<LedMessage>> for DesktopError {
     }
 }
 
+/// Commands from the simulator window, for the control loop, from the keys pressed.
+///
+/// See [Commands](self#commands).
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum DesktopCommand {
+    /// Change the brightness by an amount (Up or Down arrow keys)
+    AdjustBrightness(f32),
+
+    /// Switch to the next pattern (Right arrow key)
+    NextPattern,
+
+    /// Select a pattern by index (number keys, from 1 for index 0)
+    SelectPattern(usize),
+}
+
+/// The change in brightness for each press of the Up or Down arrow keys
+const BRIGHTNESS_STEP: f32 = 0.1;
+
+impl DesktopCommand {
+    /// Returns the command for a key, if any.
+    fn from_keycode(keycode: KeyCode) -> Option<Self> {
+        let command = match keycode {
+            KeyCode::Up => DesktopCommand::AdjustBrightness(BRIGHTNESS_STEP),
+            KeyCode::Down => DesktopCommand::AdjustBrightness(-BRIGHTNESS_STEP),
+            KeyCode::Right => DesktopCommand::NextPattern,
+            KeyCode::Key1 => DesktopCommand::SelectPattern(0),
+            KeyCode::Key2 => DesktopCommand::SelectPattern(1),
+            KeyCode::Key3 => DesktopCommand::SelectPattern(2),
+            KeyCode::Key4 => DesktopCommand::SelectPattern(3),
+            KeyCode::Key5 => DesktopCommand::SelectPattern(4),
+            KeyCode::Key6 => DesktopCommand::SelectPattern(5),
+            KeyCode::Key7 => DesktopCommand::SelectPattern(6),
+            KeyCode::Key8 => DesktopCommand::SelectPattern(7),
+            KeyCode::Key9 => DesktopCommand::SelectPattern(8),
+            _ => return None,
+        };
+        Some(command)
+    }
+
+    /// Applies the command to a control, with a pattern switch.
+    ///
+    /// The brightness stays between 0.0 and 1.0.
+    ///
+    /// # Arguments
+    ///
+    /// - `control` - The control to apply the command to
+    pub fn apply<
+        const PIXEL_COUNT: usize,
+        const FRAME_BUFFER_SIZE: usize,
+        Dim,
+        Exec,
+        Layout,
+        Pattern,
+        Driver,
+        Time,
+    >(
+        self,
+        control: &mut Control<
+            PIXEL_COUNT,
+            FRAME_BUFFER_SIZE,
+            Dim,
+            Exec,
+            Layout,
+            Pattern,
+            Driver,
+            Time,
+        >,
+    ) where
+        Layout: LayoutForDim<Dim>,
+        Pattern: PatternTrait<Dim, Layout> + PatternSwitch,
+    {
+        match self {
+            DesktopCommand::AdjustBrightness(amount) => {
+                let brightness = (control.brightness() + amount).clamp(0.0, 1.0);
+                control.set_brightness(brightness);
+            }
+            DesktopCommand::NextPattern => control.next_pattern(),
+            DesktopCommand::SelectPattern(index) => control.select_pattern(index),
+        }
+    }
+}
+
 /// Messages for communication with the rendering thread.
 enum LedMessage {
     /// Update the colors of all LEDs
-    UpdateColors(Vec<LinearSrgb>),
+    UpdateColors {
+        /// The colors of all LEDs
+        colors: Vec<LinearSrgb>,
+        /// When the driver sent the update, so the update rate doesn't depend on when the
+        /// render thread drains its messages
+        sent_at: Instant,
+    },
 
     /// Update the global brightness
     UpdateBrightness(f32),
@@ -428,10 +708,7 @@ where
         Pixels: IntoIterator<Item = Color>,
         Self::Color: FromColor<Color>,
     {
-        pixels
-            .into_iter()
-            .map(|color| LinearSrgb::from_color(color))
-            .collect()
+        Self::encode_colors(pixels)
     }
 
     fn write<const FRAME_BUFFER_SIZE: usize>(
@@ -440,20 +717,65 @@ where
         brightness: f32,
         correction: ColorCorrection,
     ) -> Result<(), Self::Error> {
-        if self.brightness != brightness {
-            self.brightness = brightness;
-            self.send(LedMessage::UpdateBrightness(brightness))?;
-        }
+        self.update_settings(brightness, correction)?;
+        self.send_colors(frame)
+    }
+}
 
-        if self.correction != correction {
-            self.correction = correction;
-            self.send(LedMessage::UpdateColorCorrection(correction))?;
-        }
+/// With the `async` feature, the desktop driver also works with an async [`Control`],
+/// such as from [`ControlBuilder::new_1d_async`], on any async runtime.
+///
+/// Sending a frame to the render thread never blocks, so each future is ready at once.
+///
+/// Unlike [`Driver::write`], [`DriverAsync::write`] doesn't receive the brightness and
+/// color correction, so they're only updated by [`DriverAsync::show`], as used by
+/// [`Control::tick`].
+///
+/// [`Control`]: blinksy::control::Control
+/// [`Control::tick`]: blinksy::control::Control::tick
+/// [`ControlBuilder::new_1d_async`]: blinksy::ControlBuilder::new_1d_async
+#[cfg(feature = "async")]
+impl<Dim, Layout> DriverAsync for DesktopDriver<Dim, Layout>
+where
+    Layout: LayoutForDim<Dim>,
+{
+    type Error = DesktopError;
+    type Color = LinearSrgb;
+    type Word = LinearSrgb;
 
-        let colors: Vec<LinearSrgb> = frame.into_iter().collect();
+    fn encode<const PIXEL_COUNT: usize, const FRAME_BUFFER_SIZE: usize, Pixels, Color>(
+        &mut self,
+        pixels: Pixels,
+        _brightness: f32,
+        _correction: ColorCorrection,
+    ) -> heapless::Vec<Self::Word, FRAME_BUFFER_SIZE>
+    where
+        Pixels: IntoIterator<Item = Color>,
+        Self::Color: FromColor<Color>,
+    {
+        Self::encode_colors(pixels)
+    }
 
-        self.send(LedMessage::UpdateColors(colors))?;
-        Ok(())
+    async fn write<const FRAME_BUFFER_SIZE: usize>(
+        &mut self,
+        frame: heapless::Vec<Self::Word, FRAME_BUFFER_SIZE>,
+    ) -> Result<(), Self::Error> {
+        self.send_colors(frame)
+    }
+
+    async fn show<const PIXEL_COUNT: usize, const FRAME_BUFFER_SIZE: usize, I, C>(
+        &mut self,
+        pixels: I,
+        brightness: f32,
+        correction: ColorCorrection,
+    ) -> Result<(), Self::Error>
+    where
+        I: IntoIterator<Item = C>,
+        Self::Color: FromColor<C>,
+    {
+        self.update_settings(brightness, correction)?;
+        let frame = Self::encode_colors::<FRAME_BUFFER_SIZE, _, _>(pixels);
+        self.send_colors(frame)
     }
 }
 
@@ -718,20 +1040,31 @@ impl UiManager {
         self.egui_mq.char_event(character);
     }
 
-    /// Render the LED information UI
+    /// Render the LED information UI, and the index labels if shown
     #[allow(clippy::too_many_arguments)]
     fn render_led_info(
         &mut self,
         ctx: &mut dyn RenderingBackend,
         led_picker: &mut LedPicker,
         positions: &[Vec3],
+        segments: &[(String, Range<usize>)],
         colors: &[LinearSrgb],
         brightness: f32,
         correction: ColorCorrection,
+        index_labels_view_proj: Option<Mat4>,
+        frame_stats: Option<&FrameStats>,
     ) {
         self.egui_mq.run(ctx, |_mq_ctx, egui_ctx| {
             self.want_mouse_capture = egui_ctx.wants_pointer_input();
 
+            if let Some(view_proj) = index_labels_view_proj {
+                Self::render_index_labels(egui_ctx, positions, view_proj);
+            }
+
+            if let Some(frame_stats) = frame_stats {
+                Self::render_frame_stats(egui_ctx, frame_stats);
+            }
+
             // Only show LED info window if an LED is selected
             if let Some(led_idx) = led_picker.selected_led {
                 let pos = positions[led_idx];
@@ -739,15 +1072,14 @@ impl UiManager {
 
                 let (red, green, blue) = (color.red, color.green, color.blue);
 
-                // Apply brightness
-                let (bright_red, bright_green, bright_blue) =
-                    (red * brightness, green * brightness, blue * brightness);
-
                 // Apply color correction
-                let (correct_red, correct_green, correct_blue) = (
-                    bright_red * correction.red,
-                    bright_green * correction.green,
-                    bright_blue * correction.blue,
+                let (correct_red, correct_green, correct_blue) = correction.apply(red, green, blue);
+
+                // Apply brightness
+                let (bright_red, bright_green, bright_blue) = (
+                    correct_red * brightness,
+                    correct_green * brightness,
+                    correct_blue * brightness,
                 );
 
                 // Convert to sRGB
@@ -755,17 +1087,25 @@ impl UiManager {
                     red: srgb_red,
                     green: srgb_green,
                     blue: srgb_blue,
-                } = LinearSrgb::new(correct_red, correct_green, correct_blue).to_srgb();
+                } = LinearSrgb::new(bright_red, bright_green, bright_blue).to_srgb();
 
                 egui::Window::new("LED Information")
                     .collapsible(false)
                     .resizable(false)
                     .show(egui_ctx, |ui| {
-                        ui.label(format!("LED Index: {}", led_idx));
+                        ui.horizontal(|ui| {
+                            ui.label(format!("LED Index: {}", led_idx));
+                            if ui.button("Copy Index").clicked() {
+                                ui.output_mut(|output| output.copied_text = led_idx.to_string());
+                            }
+                        });
                         ui.label(format!(
                             "Position: ({:.3}, {:.3}, {:.3})",
                             pos.x, pos.y, pos.z
                         ));
+                        if let Some(segment) = segment_label(segments, led_idx) {
+                            ui.label(segment);
+                        }
 
                         // Display raw RGB values
                         ui.label(format!(
@@ -773,27 +1113,34 @@ impl UiManager {
                             red, green, blue,
                         ));
 
-                        // Display global brightness
-                        ui.label(format!("Global Brightness: {:.3}", brightness));
-
-                        // Display brightness-adjusted RGB values
-                        ui.label(format!(
-                            "Brightness-adjusted RGB: R={:.3}, G={:.3}, B={:.3}",
-                            bright_red, bright_green, bright_blue
-                        ));
-
                         // Display global color correction
                         ui.label(format!(
                             "Global Color Correction: R={:.3}, G={:.3}, B={:.3}",
                             correction.red, correction.green, correction.blue
                         ));
+                        ui.label(format!(
+                            "Global Scale: {:.3}, Gamma: R={:.3}, G={:.3}, B={:.3}",
+                            correction.global,
+                            correction.gamma_red,
+                            correction.gamma_green,
+                            correction.gamma_blue
+                        ));
 
-                        // Display brightness-adjusted RGB values
+                        // Display correction-adjusted RGB values
                         ui.label(format!(
                             "Correction-adjusted RGB: R={:.3}, G={:.3}, B={:.3}",
                             correct_red, correct_green, correct_blue
                         ));
 
+                        // Display global brightness
+                        ui.label(format!("Global Brightness: {:.3}", brightness));
+
+                        // Display brightness-adjusted RGB values
+                        ui.label(format!(
+                            "Brightness-adjusted RGB: R={:.3}, G={:.3}, B={:.3}",
+                            bright_red, bright_green, bright_blue
+                        ));
+
                         // Display sRGB values
                         ui.label(format!(
                             "Final sRGB: R={:.3}, G={:.3}, B={:.3}",
@@ -820,22 +1167,156 @@ impl UiManager {
         });
     }
 
+    /// Draw the index of each LED next to it, behind any windows
+    fn render_index_labels(egui_ctx: &egui::Context, positions: &[Vec3], view_proj: Mat4) {
+        let painter = egui_ctx.layer_painter(egui::LayerId::new(
+            egui::Order::Background,
+            egui::Id::new("led_index_labels"),
+        ));
+        let screen_size = egui_ctx.screen_rect().size();
+        for (index, &position) in positions.iter().enumerate() {
+            let Some(screen_pos) = project_to_screen(view_proj, position, screen_size) else {
+                continue;
+            };
+            painter.text(
+                // Above and to the right of the LED
+                screen_pos + egui::vec2(4.0, -4.0),
+                egui::Align2::LEFT_BOTTOM,
+                index.to_string(),
+                egui::FontId::monospace(10.0),
+                egui::Color32::from_gray(220),
+            );
+        }
+    }
+
+    /// Draw the frame statistics in the left top corner
+    fn render_frame_stats(egui_ctx: &egui::Context, frame_stats: &FrameStats) {
+        let format_rate = |interval: Option<Duration>| match interval {
+            Some(interval) if !interval.is_zero() => format!(
+                "{:.1} per second ({:.1} ms)",
+                1.0 / interval.as_secs_f32(),
+                interval.as_secs_f32() * 1000.0
+            ),
+            _ => "-".to_string(),
+        };
+        egui::Area::new(egui::Id::new("frame_stats"))
+            .anchor(egui::Align2::LEFT_TOP, egui::vec2(8.0, 8.0))
+            .interactable(false)
+            .show(egui_ctx, |ui| {
+                ui.label(format!(
+                    "Frames drawn: {}",
+                    format_rate(frame_stats.average_draw_interval())
+                ));
+                ui.label(format!(
+                    "LED updates: {}",
+                    format_rate(frame_stats.average_update_interval())
+                ));
+            });
+    }
+
     /// Draw egui content
     fn draw(&mut self, ctx: &mut dyn RenderingBackend) {
         self.egui_mq.draw(ctx);
     }
 }
 
+/// The number of recent frames to average the frame statistics over
+const FRAME_STATS_WINDOW: usize = 60;
+
+/// Rolling timing of the frames drawn, and of the LED updates from the driver
+#[derive(Debug, Default)]
+struct FrameStats {
+    /// The times of recent frames drawn
+    draws: VecDeque<Instant>,
+    /// The times of recent LED updates sent by the driver
+    updates: VecDeque<Instant>,
+}
+
+impl FrameStats {
+    /// Record a frame drawn
+    fn record_draw(&mut self, now: Instant) {
+        Self::record(&mut self.draws, now);
+    }
+
+    /// Record an LED update, at the time the driver sent it
+    fn record_update(&mut self, now: Instant) {
+        Self::record(&mut self.updates, now);
+    }
+
+    /// The average time between recent frames drawn
+    fn average_draw_interval(&self) -> Option<Duration> {
+        Self::average_interval(&self.draws)
+    }
+
+    /// The average time between recent LED updates, the time per tick of the control loop
+    fn average_update_interval(&self) -> Option<Duration> {
+        Self::average_interval(&self.updates)
+    }
+
+    fn record(times: &mut VecDeque<Instant>, now: Instant) {
+        if times.len() == FRAME_STATS_WINDOW {
+            times.pop_front();
+        }
+        times.push_back(now);
+    }
+
+    fn average_interval(times: &VecDeque<Instant>) -> Option<Duration> {
+        let (first, last) = (times.front()?, times.back()?);
+        let intervals = u32::try_from(times.len() - 1)
+            .ok()
+            .filter(|&count| count > 0)?;
+        Some(last.duration_since(*first) / intervals)
+    }
+}
+
+/// Describes the segment an LED is in, if any: its name, and the index of the LED within it.
+fn segment_label(segments: &[(String, Range<usize>)], index: usize) -> Option<String> {
+    let (name, range) = segments.iter().find(|(_, range)| range.contains(&index))?;
+    Some(format!(
+        "Segment: {} (LED {} of {})",
+        name,
+        index - range.start,
+        range.len()
+    ))
+}
+
+/// Project a point in world space to a position on the screen, in egui points.
+///
+/// Returns `None` if the point is behind the camera.
+fn project_to_screen(
+    view_proj: Mat4,
+    position: Vec3,
+    screen_size: egui::Vec2,
+) -> Option<egui::Pos2> {
+    let clip = view_proj * position.extend(1.0);
+    if clip.w <= 0.0 {
+        return None;
+    }
+    let ndc = clip.xyz() / clip.w;
+    Some(egui::pos2(
+        (ndc.x + 1.0) / 2.0 * screen_size.x,
+        (1.0 - ndc.y) / 2.0 * screen_size.y,
+    ))
+}
+
+/// The radius of the glow around each LED, relative to the LED's radius
+const GLOW_RADIUS_SCALE: f32 = 4.0;
+
 /// Manages rendering of LEDs
 struct Renderer {
     pipeline: Pipeline,
     bindings: Bindings,
+    glow_pipeline: Pipeline,
+    glow_vertex_buffer: BufferId,
+    glow_index_buffer: BufferId,
+    led_radius: f32,
 }
 
 impl Renderer {
     fn new(ctx: &mut dyn RenderingBackend, led_radius: f32) -> Self {
         let vertex_buffer = Self::create_vertex_buffer(ctx, led_radius);
         let index_buffer = Self::create_index_buffer(ctx);
+        let (glow_pipeline, glow_vertex_buffer, glow_index_buffer) = Self::create_glow(ctx);
 
         let bindings = Bindings {
             vertex_buffers: vec![vertex_buffer],
@@ -879,7 +1360,81 @@ impl Renderer {
             },
         );
 
-        Self { pipeline, bindings }
+        Self {
+            pipeline,
+            bindings,
+            glow_pipeline,
+            glow_vertex_buffer,
+            glow_index_buffer,
+            led_radius,
+        }
+    }
+
+    /// Create the pipeline and buffers to draw a glow around each LED: a square facing the
+    /// camera, which fades out from the center, added onto what's behind it.
+    fn create_glow(ctx: &mut dyn RenderingBackend) -> (Pipeline, BufferId, BufferId) {
+        #[rustfmt::skip]
+        let corners: &[f32] = &[
+            -1.0, -1.0,
+            1.0, -1.0,
+            1.0, 1.0,
+            -1.0, 1.0,
+        ];
+        let vertex_buffer = ctx.new_buffer(
+            BufferType::VertexBuffer,
+            BufferUsage::Immutable,
+            BufferSource::slice(corners),
+        );
+
+        let indices: &[u16] = &[0, 1, 2, 0, 2, 3];
+        let index_buffer = ctx.new_buffer(
+            BufferType::IndexBuffer,
+            BufferUsage::Immutable,
+            BufferSource::slice(indices),
+        );
+
+        let shader = ctx
+            .new_shader(
+                ShaderSource::Glsl {
+                    vertex: glow_shader::VERTEX,
+                    fragment: glow_shader::FRAGMENT,
+                },
+                glow_shader::meta(),
+            )
+            .unwrap();
+
+        let pipeline = ctx.new_pipeline(
+            &[
+                BufferLayout::default(),
+                BufferLayout {
+                    step_func: VertexStep::PerInstance,
+                    ..Default::default()
+                },
+                BufferLayout {
+                    step_func: VertexStep::PerInstance,
+                    ..Default::default()
+                },
+            ],
+            &[
+                VertexAttribute::with_buffer("in_corner", VertexFormat::Float2, 0),
+                VertexAttribute::with_buffer("in_inst_pos", VertexFormat::Float3, 1),
+                VertexAttribute::with_buffer("in_inst_color", VertexFormat::Float4, 2),
+            ],
+            shader,
+            PipelineParams {
+                depth_test: Comparison::LessOrEqual,
+                // Glows overlap each other, so don't hide each other.
+                depth_write: false,
+                color_blend: Some(BlendState::new(
+                    Equation::Add,
+                    BlendFactor::One,
+                    BlendFactor::One,
+                )),
+                ..Default::default()
+            },
+        );
+
+        (pipeline, vertex_buffer, index_buffer)
     }
 
     fn create_vertex_buffer(ctx: &mut dyn RenderingBackend, r: f32) -> BufferId {
@@ -946,9 +1501,11 @@ impl Renderer {
         &self,
         ctx: &mut dyn RenderingBackend,
         positions: &[Vec3],
-        view_proj: Mat4,
+        camera: &Camera,
         background_color: (f32, f32, f32, f32),
+        glow: f32,
     ) {
+        let view_proj = camera.view_projection_matrix();
         let (r, g, b, a) = background_color;
 
         // Clear the background
@@ -960,6 +1517,29 @@ impl Renderer {
         ctx.apply_uniforms(UniformsSource::table(&shader::Uniforms { mvp: view_proj }));
 
         ctx.draw(0, 24, positions.len() as i32);
+
+        // Draw the glows, facing the camera
+        if glow > 0.0 {
+            let view = camera.view_matrix();
+            ctx.apply_pipeline(&self.glow_pipeline);
+            ctx.apply_bindings(&Bindings {
+                vertex_buffers: vec![
+                    self.glow_vertex_buffer,
+                    self.bindings.vertex_buffers[1],
+                    self.bindings.vertex_buffers[2],
+                ],
+                index_buffer: self.glow_index_buffer,
+                images: vec![],
+            });
+            ctx.apply_uniforms(UniformsSource::table(&glow_shader::Uniforms {
+                mvp: view_proj,
+                right: view.row(0),
+                up: view.row(1),
+                params: Vec4::new(self.led_radius * GLOW_RADIUS_SCALE, glow, 0.0, 0.0),
+            }));
+            ctx.draw(0, 6, positions.len() as i32);
+        }
+
         ctx.end_render_pass();
     }
 }
@@ -968,7 +1548,9 @@ impl Renderer {
 struct DesktopStageOptions {
     pub positions: Vec<Vec3>,
     pub receiver: Receiver<LedMessage>,
+    pub command_sender: Sender<DesktopCommand>,
     pub config: DesktopConfig,
+    pub segments: Vec<(String, Range<usize>)>,
     pub is_window_closed: std::sync::Arc<std::sync::atomic::AtomicBool>,
 }
 
@@ -981,8 +1563,11 @@ struct DesktopStage {
     brightness: f32,
     correction: ColorCorrection,
     receiver: Receiver<LedMessage>,
+    command_sender: Sender<DesktopCommand>,
     camera: Camera,
     config: DesktopConfig,
+    segments: Vec<(String, Range<usize>)>,
+    frame_stats: FrameStats,
     is_window_closed: std::sync::Arc<std::sync::atomic::AtomicBool>,
     mouse_down: bool,
     last_mouse_x: f32,
@@ -1014,7 +1599,9 @@ impl DesktopStage {
         let DesktopStageOptions {
             positions,
             receiver,
+            command_sender,
             config,
+            segments,
             is_window_closed,
         } = options;
 
@@ -1047,8 +1634,11 @@ impl DesktopStage {
             brightness: 1.0,
             correction: ColorCorrection::default(),
             receiver,
+            command_sender,
             camera,
             config,
+            segments,
+            frame_stats: FrameStats::default(),
             is_window_closed,
             mouse_down: false,
             last_mouse_x: 0.0,
@@ -1073,8 +1663,9 @@ impl DesktopStage {
     fn process_messages(&mut self) {
         while let Ok(message) = self.receiver.try_recv() {
             match message {
-                LedMessage::UpdateColors(colors) => {
+                LedMessage::UpdateColors { colors, sent_at } => {
                     self.colors = colors;
+                    self.frame_stats.record_update(sent_at);
                 }
                 LedMessage::UpdateBrightness(brightness) => {
                     self.brightness = brightness;
@@ -1098,6 +1689,12 @@ impl DesktopStage {
             KeyCode::O => {
                 self.camera.toggle_projection_mode();
             }
+            KeyCode::L => {
+                self.config.show_index_labels = !self.config.show_index_labels;
+            }
+            KeyCode::F => {
+                self.config.show_frame_stats = !self.config.show_frame_stats;
+            }
             KeyCode::Escape => {
                 // Clear selection when Escape is pressed
                 self.led_picker.clear_selection();
@@ -1105,6 +1702,14 @@ impl DesktopStage {
             _ => {}
         }
     }
+
+    /// Sends a command for the control loop, if the key has one
+    fn handle_command_input(&mut self, keycode: KeyCode) {
+        if let Some(command) = DesktopCommand::from_keycode(keycode) {
+            // The control loop may have ended, in which case there's no one to command.
+            let _ = self.command_sender.send(command);
+        }
+    }
 }
 
 impl EventHandler for DesktopStage {
@@ -1113,11 +1718,14 @@ impl EventHandler for DesktopStage {
     }
 
     fn draw(&mut self) {
+        self.frame_stats.record_draw(Instant::now());
+
         let colors_buffer: Vec<Vec4> = self
             .colors
             .iter()
             .map(|color| {
-                let (red, green, blue) = (color.red, color.green, color.blue);
+                // Apply color correction
+                let (red, green, blue) = self.correction.apply(color.red, color.green, color.blue);
 
                 // Apply brightness
                 let (red, green, blue) = (
@@ -1126,13 +1734,6 @@ impl EventHandler for DesktopStage {
                     blue * self.brightness,
                 );
 
-                // Apply color correction
-                let (red, green, blue) = (
-                    red * self.correction.red,
-                    green * self.correction.green,
-                    blue * self.correction.blue,
-                );
-
                 // Convert to sRGB
                 let Srgb { red, green, blue } = LinearSrgb::new(red, green, blue).to_srgb();
 
@@ -1152,8 +1753,9 @@ impl EventHandler for DesktopStage {
         self.renderer.render(
             &mut *self.ctx,
             &self.positions,
-            view_proj,
+            &self.camera,
             self.config.background_color,
+            self.config.glow,
         );
 
         // Render UI with LED info if needed
@@ -1161,9 +1763,12 @@ impl EventHandler for DesktopStage {
             &mut *self.ctx,
             &mut self.led_picker,
             &self.positions,
+            &self.segments,
             &self.colors,
             self.brightness,
             self.correction,
+            self.config.show_index_labels.then_some(view_proj),
+            self.config.show_frame_stats.then_some(&self.frame_stats),
         );
 
         // Draw egui
@@ -1225,6 +1830,7 @@ impl EventHandler for DesktopStage {
 
         if !self.ui_manager.want_mouse_capture {
             self.handle_camera_input(keycode);
+            self.handle_command_input(keycode);
         }
     }
 
@@ -1289,3 +1895,238 @@ mod shader {
         pub mvp: glam::Mat4,
     }
 }
+
+/// Shader definitions for rendering the glow around LEDs
+mod glow_shader {
+    use miniquad::*;
+
+    /// Vertex shader for glow rendering
+    ///
+    /// Each corner is offset from the LED along the camera's right and up axes, and the
+    /// glow's color is the LED's color scaled by its luminance and the glow intensity.
+    pub const VERTEX: &str = r#"#version 100
+    attribute vec2 in_corner;
+    attribute vec3 in_inst_pos;
+    attribute vec4 in_inst_color;
+
+    varying lowp vec4 color;
+    varying mediump vec2 uv;
+
+    uniform mat4 mvp;
+    uniform vec4 right;
+    uniform vec4 up;
+    uniform vec4 params;
+
+    void main() {
+        vec3 offset = (in_corner.x * right.xyz + in_corner.y * up.xyz) * params.x;
+        gl_Position = mvp * vec4(in_inst_pos + offset, 1.0);
+        float luminance = dot(in_inst_color.rgb, vec3(0.2126, 0.7152, 0.0722));
+        color = vec4(in_inst_color.rgb * luminance * params.y, 1.0);
+        uv = in_corner;
+    }
+    "#;
+
+    /// Fragment shader for glow rendering, fading out from the center
+    pub const FRAGMENT: &str = r#"#version 100
+    varying lowp vec4 color;
+    varying mediump vec2 uv;
+
+    void main() {
+        mediump float falloff = max(0.0, 1.0 - length(uv));
+        gl_FragColor = vec4(color.rgb * falloff * falloff, 1.0);
+    }
+    "#;
+
+    /// Shader metadata describing uniforms
+    pub fn meta() -> ShaderMeta {
+        ShaderMeta {
+            images: vec![],
+            uniforms: UniformBlockLayout {
+                uniforms: vec![
+                    UniformDesc::new("mvp", UniformType::Mat4),
+                    UniformDesc::new("right", UniformType::Float4),
+                    UniformDesc::new("up", UniformType::Float4),
+                    UniformDesc::new("params", UniformType::Float4),
+                ],
+            },
+        }
+    }
+
+    /// Uniform structure for shader
+    #[repr(C)]
+    pub struct Uniforms {
+        pub mvp: glam::Mat4,
+        /// The camera's right axis, in world space
+        pub right: glam::Vec4,
+        /// The camera's up axis, in world space
+        pub up: glam::Vec4,
+        /// The glow's radius (x) and intensity (y)
+        pub params: glam::Vec4,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use blinksy::{
+        layout::{Shape2d, Vec2},
+        layout2d,
+    };
+
+    use super::*;
+
+    layout2d!(
+        SerpentineLayout,
+        [Shape2d::Grid {
+            start: Vec2::new(-1., -1.),
+            horizontal_end: Vec2::new(1., -1.),
+            vertical_end: Vec2::new(-1., 1.),
+            horizontal_pixel_count: 3,
+            vertical_pixel_count: 2,
+            serpentine: true,
+        }]
+    );
+
+    #[test]
+    fn test_2d_positions_follow_serpentine_chain() {
+        let desktop = Desktop::new_2d::<SerpentineLayout>();
+        // The second row runs back from the right, as the LEDs are wired.
+        assert_eq!(
+            desktop.stage.positions,
+            [
+                vec3(-1., -1., 0.),
+                vec3(0., -1., 0.),
+                vec3(1., -1., 0.),
+                vec3(1., 1., 0.),
+                vec3(0., 1., 0.),
+                vec3(-1., 1., 0.),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_segment_label() {
+        let segments = [("Desk".to_string(), 0..30), ("Shelf".to_string(), 30..75)];
+        assert_eq!(
+            segment_label(&segments, 32).as_deref(),
+            Some("Segment: Shelf (LED 2 of 45)")
+        );
+        assert_eq!(segment_label(&segments, 75), None);
+        assert_eq!(segment_label(&[], 0), None);
+    }
+
+    #[test]
+    fn test_commands_apply_to_control() {
+        use blinksy::{
+            layout1d,
+            patterns::switch::{BuiltinSwitch1d, BuiltinSwitchParams},
+            ControlBuilder,
+        };
+
+        layout1d!(Strip, 4);
+
+        let Desktop { driver, stage } = Desktop::new_1d::<Strip>();
+        let mut control = ControlBuilder::new_1d()
+            .with_layout::<Strip, { Strip::PIXEL_COUNT }>()
+            .with_pattern::<BuiltinSwitch1d>(BuiltinSwitchParams::default())
+            .with_driver(driver)
+            .with_frame_buffer_size::<{ Strip::PIXEL_COUNT }>()
+            .build();
+
+        // As if from the window, over the channel.
+        for keycode in [KeyCode::Down, KeyCode::Down, KeyCode::Right, KeyCode::A] {
+            if let Some(command) = DesktopCommand::from_keycode(keycode) {
+                stage.command_sender.send(command).unwrap();
+            }
+        }
+        while let Some(command) = control.driver().try_recv_command() {
+            command.apply(&mut control);
+        }
+        assert!((control.brightness() - 0.8).abs() < 1e-6);
+        assert_eq!(control.pattern().pattern_index(), 1);
+
+        DesktopCommand::SelectPattern(0).apply(&mut control);
+        assert_eq!(control.pattern().pattern_index(), 0);
+
+        // The brightness stays in range.
+        for _ in 0..20 {
+            DesktopCommand::AdjustBrightness(BRIGHTNESS_STEP).apply(&mut control);
+        }
+        assert_eq!(control.brightness(), 1.0);
+    }
+
+    #[test]
+    fn test_updates_timestamped_when_sent() {
+        use blinksy::layout1d;
+
+        layout1d!(Strip, 2);
+
+        let Desktop { mut driver, stage } = Desktop::new_1d::<Strip>();
+        let pixels = [LinearSrgb::new(1., 0., 0.); 2];
+        for _ in 0..3 {
+            Driver::show::<2, 2, _, _>(&mut driver, pixels, 1., ColorCorrection::default())
+                .unwrap();
+            std::thread::sleep(Duration::from_millis(10));
+        }
+
+        // Drained all at once, as by a draw, long after the updates were sent.
+        std::thread::sleep(Duration::from_millis(50));
+        let drained_at = Instant::now();
+        let mut frame_stats = FrameStats::default();
+        while let Ok(message) = stage.receiver.try_recv() {
+            if let LedMessage::UpdateColors { sent_at, .. } = message {
+                frame_stats.record_update(sent_at);
+            }
+        }
+
+        let interval = frame_stats.average_update_interval().unwrap();
+        assert!(interval >= Duration::from_millis(10));
+        assert!(frame_stats
+            .updates
+            .iter()
+            .all(|&sent_at| sent_at < drained_at));
+    }
+
+    #[test]
+    fn test_frame_stats_rolling_average() {
+        let start = Instant::now();
+        let mut frame_stats = FrameStats::default();
+        assert_eq!(frame_stats.average_update_interval(), None);
+
+        frame_stats.record_update(start);
+        assert_eq!(frame_stats.average_update_interval(), None);
+
+        // 10 ms apart, then 20 ms apart for a whole window.
+        for index in 1..=10 {
+            frame_stats.record_update(start + Duration::from_millis(10 * index));
+        }
+        assert_eq!(
+            frame_stats.average_update_interval(),
+            Some(Duration::from_millis(10))
+        );
+        for index in 1..=FRAME_STATS_WINDOW as u64 {
+            frame_stats.record_update(start + Duration::from_millis(100 + 20 * index));
+        }
+        assert_eq!(
+            frame_stats.average_update_interval(),
+            Some(Duration::from_millis(20))
+        );
+        assert_eq!(frame_stats.average_draw_interval(), None);
+    }
+
+    #[test]
+    fn test_project_to_screen() {
+        let screen_size = egui::vec2(200.0, 100.0);
+        let project = |position| project_to_screen(Mat4::IDENTITY, position, screen_size);
+        assert_eq!(project(Vec3::ZERO), Some(egui::pos2(100.0, 50.0)));
+        // Screen y increases downwards.
+        assert_eq!(project(vec3(-1.0, 1.0, 0.0)), Some(egui::pos2(0.0, 0.0)));
+        assert_eq!(
+            project_to_screen(
+                Mat4::from_cols(Vec4::X, Vec4::Y, Vec4::Z, Vec4::ZERO),
+                Vec3::ZERO,
+                screen_size
+            ),
+            None
+        );
+    }
+}
@@ -0,0 +1,124 @@
+//! Shared encoding for drivers which send each frame as bytes, such as over a serial port or
+//! the network.
+
+use blinksy::color::{ColorCorrection, FromColor, LedChannels, LedColor, LinearSrgb};
+
+/// A compile-time function to get a `FRAME_BUFFER_SIZE`, given a `PIXEL_COUNT`.
+pub(crate) const fn frame_buffer_size(pixel_count: usize, channels: LedChannels) -> usize {
+    pixel_count * channels.channel_count()
+}
+
+/// Encodes pixels into bytes, in the channel order of the LEDs.
+pub(crate) fn encode_pixels<const FRAME_BUFFER_SIZE: usize, I, C>(
+    pixels: I,
+    channels: LedChannels,
+    brightness: f32,
+    correction: ColorCorrection,
+) -> heapless::Vec<u8, FRAME_BUFFER_SIZE>
+where
+    I: IntoIterator<Item = C>,
+    LinearSrgb: FromColor<C>,
+{
+    heapless::Vec::from_iter(pixels.into_iter().flat_map(move |pixel| {
+        let linear_srgb = LinearSrgb::from_color(pixel);
+        let data: LedColor<u8> = linear_srgb.to_led(channels, brightness, correction);
+        data.into_iter()
+    }))
+}
+
+/// Implements [`Driver`](blinksy::driver::Driver) and, with the `async` feature,
+/// [`DriverAsync`](blinksy::driver::DriverAsync) for a driver which sends each frame as
+/// bytes.
+///
+/// The driver needs a `channels: LedChannels` field, and a method which sends a frame of
+/// bytes, returning the error type of the driver. Any attributes (such as doc comments) are
+/// for the `DriverAsync` impl.
+macro_rules! impl_byte_driver {
+    (
+        $(#[$async_meta:meta])*
+        impl$(<$($generic:ident: $bound:path),*>)? for $driver:ty {
+            error: $error:ty,
+            send: $send:ident,
+        }
+    ) => {
+        impl$(<$($generic: $bound),*>)? blinksy::driver::Driver for $driver {
+            type Error = $error;
+            type Color = blinksy::color::LinearSrgb;
+            type Word = u8;
+
+            fn encode<const PIXEL_COUNT: usize, const FRAME_BUFFER_SIZE: usize, Pixels, Color>(
+                &mut self,
+                pixels: Pixels,
+                brightness: f32,
+                correction: blinksy::color::ColorCorrection,
+            ) -> heapless::Vec<Self::Word, FRAME_BUFFER_SIZE>
+            where
+                Pixels: IntoIterator<Item = Color>,
+                Self::Color: blinksy::color::FromColor<Color>,
+            {
+                $crate::frame::encode_pixels(pixels, self.channels, brightness, correction)
+            }
+
+            fn write<const FRAME_BUFFER_SIZE: usize>(
+                &mut self,
+                frame: heapless::Vec<Self::Word, FRAME_BUFFER_SIZE>,
+                _brightness: f32,
+                _correction: blinksy::color::ColorCorrection,
+            ) -> Result<(), Self::Error> {
+                self.$send(&frame)
+            }
+        }
+
+        $(#[$async_meta])*
+        #[cfg(feature = "async")]
+        impl$(<$($generic: $bound),*>)? blinksy::driver::DriverAsync for $driver {
+            type Error = $error;
+            type Color = blinksy::color::LinearSrgb;
+            type Word = u8;
+
+            fn encode<const PIXEL_COUNT: usize, const FRAME_BUFFER_SIZE: usize, Pixels, Color>(
+                &mut self,
+                pixels: Pixels,
+                brightness: f32,
+                correction: blinksy::color::ColorCorrection,
+            ) -> heapless::Vec<Self::Word, FRAME_BUFFER_SIZE>
+            where
+                Pixels: IntoIterator<Item = Color>,
+                Self::Color: blinksy::color::FromColor<Color>,
+            {
+                $crate::frame::encode_pixels(pixels, self.channels, brightness, correction)
+            }
+
+            async fn write<const FRAME_BUFFER_SIZE: usize>(
+                &mut self,
+                frame: heapless::Vec<Self::Word, FRAME_BUFFER_SIZE>,
+            ) -> Result<(), Self::Error> {
+                self.$send(&frame)
+            }
+        }
+    };
+}
+
+pub(crate) use impl_byte_driver;
+
+#[cfg(test)]
+mod tests {
+    use blinksy::color::{RgbChannels, RgbwChannels, Srgb};
+
+    use super::*;
+
+    #[test]
+    fn test_encode_pixels_in_channel_order() {
+        let pixels = [Srgb::new(1., 0., 0.), Srgb::new(0., 0., 1.)];
+        let channels = LedChannels::Rgb(RgbChannels::GRB);
+        let frame: heapless::Vec<u8, 6> =
+            encode_pixels(pixels, channels, 1., ColorCorrection::default());
+
+        assert_eq!(frame, [0, 255, 0, 0, 0, 255]);
+        assert_eq!(frame_buffer_size(2, channels), 6);
+        assert_eq!(
+            frame_buffer_size(2, LedChannels::Rgbw(RgbwChannels::RGBW)),
+            8
+        );
+    }
+}
@@ -48,8 +48,29 @@
 //! });
 //! ```
 
+/// Audio input from the microphone
+#[cfg(feature = "audio")]
+pub mod audio;
+
 /// Desktop LED simulation
 pub mod driver;
 
+/// Frame encoding for byte drivers
+mod frame;
+
+/// Serial frame sink
+#[cfg(feature = "serial")]
+pub mod serial;
+
+/// Golden image testing
+pub mod testing;
+
 /// Time utilities
 pub mod time;
+
+/// TPM2 and TPM2.net output
+pub mod tpm2;
+
+/// WLED JSON API server
+#[cfg(feature = "wled")]
+pub mod wled;
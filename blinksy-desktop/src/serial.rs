@@ -0,0 +1,460 @@
+//! # Serial Frame Sink
+//!
+//! This module provides a [`SerialDriver`], which sends each frame over a serial port (such
+//! as a USB CDC serial port to a microcontroller), instead of showing it in a window. The
+//! desktop computes the patterns, and the microcontroller only receives frames and pushes
+//! them to the LEDs.
+//!
+//! Requires the `serial` feature.
+//!
+//! ## Protocol
+//!
+//! Each frame is sent as a packet:
+//!
+//! | Bytes  | Field   | Description                                                   |
+//! |--------|---------|---------------------------------------------------------------|
+//! | 4      | Magic   | [`SERIAL_MAGIC`]: `b"BLNK"`                                   |
+//! | 2      | Length  | Length of the payload in bytes, as a big-endian `u16`         |
+//! | Length | Payload | Color bytes of each LED, in the channel order of the LEDs     |
+//! | 2      | CRC     | [CRC-16/CCITT-FALSE] of the length and payload, big-endian    |
+//!
+//! The payload is ready for the LEDs: the brightness and color correction are applied, and
+//! each channel is a linear 8-bit value, as for any other LED driver.
+//!
+//! ## Backpressure
+//!
+//! A serial port is often slower than the animation loop. Frames are written by a
+//! background thread, so [`Driver::write`](blinksy::driver::Driver::write) never blocks on the port. If a frame is still
+//! waiting when the next frame arrives, the waiting (stale) frame is dropped, so the LEDs
+//! always show the latest frame. See [`SerialDriver::dropped_frames`].
+//!
+//! ## Decoding
+//!
+//! A receiver scans for the magic, reads the length, the payload, and the CRC, and checks
+//! the CRC before showing the payload. On a mismatch, it drops the packet and scans for the
+//! next magic. For example, in `no_std` firmware:
+//!
+//! ```rust
+//! use blinksy_desktop::serial::{serial_crc, SERIAL_MAGIC};
+//!
+//! /// Receives packets, one byte at a time.
+//! struct Decoder<const N: usize> {
+//!     buffer: heapless::Vec<u8, N>,
+//! }
+//!
+//! impl<const N: usize> Decoder<N> {
+//!     /// Pushes a received byte, returning a payload once a packet is complete.
+//!     fn push(&mut self, byte: u8) -> Option<&[u8]> {
+//!         // Start over after a complete (or dropped) packet.
+//!         let is_complete = |buffer: &[u8]| {
+//!             buffer.len() >= 6
+//!                 && buffer.len() == 8 + u16::from_be_bytes([buffer[4], buffer[5]]) as usize
+//!         };
+//!         if is_complete(&self.buffer) {
+//!             self.buffer.clear();
+//!         }
+//!
+//!         // Match the magic, byte by byte.
+//!         let index = self.buffer.len();
+//!         if index < 4 && byte != SERIAL_MAGIC[index] {
+//!             self.buffer.clear();
+//!             if byte == SERIAL_MAGIC[0] {
+//!                 let _ = self.buffer.push(byte);
+//!             }
+//!             return None;
+//!         }
+//!         if self.buffer.push(byte).is_err() {
+//!             // Too long for the buffer, so drop the packet.
+//!             self.buffer.clear();
+//!             return None;
+//!         }
+//!
+//!         if !is_complete(&self.buffer) {
+//!             return None;
+//!         }
+//!         let (packet, crc) = self.buffer.split_at(self.buffer.len() - 2);
+//!         if serial_crc(&packet[4..]) != u16::from_be_bytes([crc[0], crc[1]]) {
+//!             return None;
+//!         }
+//!         Some(&packet[6..])
+//!     }
+//! }
+//!
+//! // Decode a packet for one red LED.
+//! let mut decoder = Decoder::<64> { buffer: heapless::Vec::new() };
+//! let packet = [b'B', b'L', b'N', b'K', 0, 3, 255, 0, 0, 0x45, 0xB3];
+//! let payloads: Vec<Vec<u8>> = packet
+//!     .iter()
+//!     .filter_map(|&byte| decoder.push(byte).map(|payload| payload.to_vec()))
+//!     .collect();
+//! assert_eq!(payloads, [[255, 0, 0]]);
+//! ```
+//!
+//! Then write the payload to the LEDs, such as with a [`ClocklessWriter`] for clockless
+//! LEDs (in the channel order of the [`SerialDriver`], which should match the LEDs), or
+//! decode each pixel for another driver.
+//!
+//! ## Example
+//!
+//! ```rust,no_run
+//! use blinksy::{
+//!     color::{LedChannels, RgbChannels},
+//!     layout::Layout1d,
+//!     layout1d,
+//!     patterns::rainbow::{Rainbow, RainbowParams},
+//!     ControlBuilder,
+//! };
+//! use blinksy_desktop::{serial::SerialDriver, time::elapsed_in_ms};
+//!
+//! layout1d!(Layout, 60);
+//!
+//! let driver = SerialDriver::open("/dev/ttyACM0", 921_600)
+//!     .unwrap()
+//!     .with_channels(LedChannels::Rgb(RgbChannels::GRB));
+//!
+//! let mut control = ControlBuilder::new_1d()
+//!     .with_layout::<Layout, { Layout::PIXEL_COUNT }>()
+//!     .with_pattern::<Rainbow>(RainbowParams::default())
+//!     .with_driver(driver)
+//!     .with_frame_buffer_size::<{ Layout::PIXEL_COUNT * 3 }>()
+//!     .build();
+//!
+//! loop {
+//!     control.tick(elapsed_in_ms()).unwrap();
+//!     std::thread::sleep(std::time::Duration::from_millis(16));
+//! }
+//! ```
+//!
+//! [CRC-16/CCITT-FALSE]: https://reveng.sourceforge.io/crc-catalogue/16.htm#crc.cat.crc-16-ibm-3740
+//! [`ClocklessWriter`]: blinksy::driver::ClocklessWriter
+
+use core::fmt;
+use std::{
+    io::{self, Write},
+    sync::{Arc, Condvar, Mutex},
+    thread::{self, JoinHandle},
+    time::Duration,
+};
+
+use blinksy::color::{LedChannels, RgbChannels};
+
+use crate::frame::{self, impl_byte_driver};
+
+/// The magic bytes at the start of each packet.
+pub const SERIAL_MAGIC: [u8; 4] = *b"BLNK";
+
+/// The most payload bytes in a packet.
+pub const SERIAL_MAX_PAYLOAD_LENGTH: usize = u16::MAX as usize;
+
+/// How long a write to the serial port may block, before it fails.
+const SERIAL_TIMEOUT: Duration = Duration::from_secs(1);
+
+/// Returns the [CRC-16/CCITT-FALSE] of some bytes, as used in each packet.
+///
+/// For the CRC of a packet, the bytes are the length and the payload.
+///
+/// [CRC-16/CCITT-FALSE]: https://reveng.sourceforge.io/crc-catalogue/16.htm#crc.cat.crc-16-ibm-3740
+pub fn serial_crc(bytes: &[u8]) -> u16 {
+    bytes.iter().fold(0xFFFF, |crc, &byte| {
+        (0..8).fold(crc ^ ((byte as u16) << 8), |crc, _| {
+            if crc & 0x8000 != 0 {
+                (crc << 1) ^ 0x1021
+            } else {
+                crc << 1
+            }
+        })
+    })
+}
+
+/// Returns the packet of a payload.
+fn encode_packet(payload: &[u8]) -> Result<Vec<u8>, SerialError> {
+    let length = u16::try_from(payload.len()).map_err(|_| SerialError::FrameTooLong)?;
+
+    let mut packet = Vec::with_capacity(payload.len() + 8);
+    packet.extend_from_slice(&SERIAL_MAGIC);
+    packet.extend_from_slice(&length.to_be_bytes());
+    packet.extend_from_slice(payload);
+    let crc = serial_crc(&packet[SERIAL_MAGIC.len()..]);
+    packet.extend_from_slice(&crc.to_be_bytes());
+    Ok(packet)
+}
+
+/// State shared between the driver and its writer thread.
+#[derive(Default)]
+struct Shared {
+    /// The mailbox between the driver and its writer thread
+    mailbox: Mutex<Mailbox>,
+    /// Notifies the writer thread of a change to the mailbox
+    changed: Condvar,
+}
+
+/// The mailbox between the driver and its writer thread.
+#[derive(Default)]
+struct Mailbox {
+    /// The latest packet, waiting to be written
+    packet: Option<Vec<u8>>,
+    /// The number of packets replaced before they were written
+    dropped_frames: u64,
+    /// The error of the latest failed write, not yet returned
+    error: Option<io::Error>,
+    /// Whether the writer thread should stop
+    quit: bool,
+}
+
+/// A driver which sends frames over a serial port, in a simple framed protocol.
+///
+/// For details, see [serial module](self).
+pub struct SerialDriver {
+    /// Color channel order of the LEDs
+    channels: LedChannels,
+    /// State shared with the writer thread
+    shared: Arc<Shared>,
+    /// The writer thread
+    thread: Option<JoinHandle<()>>,
+}
+
+impl SerialDriver {
+    /// Opens a serial port, and creates a driver which writes to it, for LEDs with RGB
+    /// channel order.
+    ///
+    /// # Arguments
+    ///
+    /// - `path` - The path of the serial port, such as `/dev/ttyACM0` or `COM3`
+    /// - `baud_rate` - The baud rate of the serial port (ignored by USB CDC serial ports)
+    pub fn open(path: &str, baud_rate: u32) -> Result<Self, SerialError> {
+        let port = serialport::new(path, baud_rate)
+            .timeout(SERIAL_TIMEOUT)
+            .open()?;
+        Ok(Self::new(port))
+    }
+
+    /// Creates a driver which writes to a connection, for LEDs with RGB channel order.
+    ///
+    /// The connection is usually a serial port, but may be anything which can be written to,
+    /// such as a TCP stream.
+    ///
+    /// # Arguments
+    ///
+    /// - `connection` - The connection to write packets to
+    pub fn new<W>(mut connection: W) -> Self
+    where
+        W: Write + Send + 'static,
+    {
+        let shared = Arc::new(Shared::default());
+
+        let thread_shared = shared.clone();
+        let thread = thread::spawn(move || loop {
+            let packet = {
+                let mut mailbox = thread_shared.mailbox.lock().unwrap();
+                while mailbox.packet.is_none() && !mailbox.quit {
+                    mailbox = thread_shared.changed.wait(mailbox).unwrap();
+                }
+                if mailbox.quit {
+                    return;
+                }
+                mailbox.packet.take()
+            };
+            let Some(packet) = packet else {
+                continue;
+            };
+
+            if let Err(error) = connection
+                .write_all(&packet)
+                .and_then(|_| connection.flush())
+            {
+                thread_shared.mailbox.lock().unwrap().error = Some(error);
+            }
+        });
+
+        Self {
+            channels: LedChannels::Rgb(RgbChannels::RGB),
+            shared,
+            thread: Some(thread),
+        }
+    }
+
+    /// Sets the color channel order of the LEDs.
+    pub fn with_channels(mut self, channels: LedChannels) -> Self {
+        self.channels = channels;
+        self
+    }
+
+    /// A compile-time function to get a `FRAME_BUFFER_SIZE`, given a `PIXEL_COUNT`.
+    pub const fn frame_buffer_size(pixel_count: usize, channels: LedChannels) -> usize {
+        frame::frame_buffer_size(pixel_count, channels)
+    }
+
+    /// Returns the number of frames dropped so far, since the serial port was too slow.
+    pub fn dropped_frames(&self) -> u64 {
+        self.shared.mailbox.lock().unwrap().dropped_frames
+    }
+
+    /// Queues a frame for the writer thread, replacing any stale frame.
+    fn send_frame(&self, frame: &[u8]) -> Result<(), SerialError> {
+        let packet = encode_packet(frame)?;
+
+        let mut mailbox = self.shared.mailbox.lock().unwrap();
+        if let Some(error) = mailbox.error.take() {
+            return Err(SerialError::Io(error));
+        }
+        if mailbox.packet.replace(packet).is_some() {
+            mailbox.dropped_frames += 1;
+        }
+        self.shared.changed.notify_one();
+        Ok(())
+    }
+}
+
+impl_byte_driver! {
+    /// With the `async` feature, the serial driver also implements [`DriverAsync`].
+    ///
+    /// Queuing a frame for the writer thread never blocks, so each future is ready at once.
+    ///
+    /// [`DriverAsync`]: blinksy::driver::DriverAsync
+    impl for SerialDriver {
+        error: SerialError,
+        send: send_frame,
+    }
+}
+
+impl Drop for SerialDriver {
+    fn drop(&mut self) {
+        self.shared.mailbox.lock().unwrap().quit = true;
+        self.shared.changed.notify_one();
+        if let Some(thread) = self.thread.take() {
+            let _ = thread.join();
+        }
+    }
+}
+
+/// Errors from the [`SerialDriver`].
+#[derive(Debug)]
+pub enum SerialError {
+    /// The serial port failed to open
+    Open(serialport::Error),
+    /// A write to the serial port failed
+    Io(io::Error),
+    /// The frame is longer than [`SERIAL_MAX_PAYLOAD_LENGTH`] bytes
+    FrameTooLong,
+}
+
+impl fmt::Display for SerialError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            SerialError::Open(error) => write!(f, "Failed to open serial port: {error}"),
+            SerialError::Io(error) => write!(f, "Failed to write to serial port: {error}"),
+            SerialError::FrameTooLong => {
+                write!(f, "Frame is longer than {SERIAL_MAX_PAYLOAD_LENGTH} bytes")
+            }
+        }
+    }
+}
+
+impl std::error::Error for SerialError {}
+
+impl From<serialport::Error> for SerialError {
+    fn from(error: serialport::Error) -> Self {
+        SerialError::Open(error)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Instant;
+
+    use blinksy::{
+        color::{ColorCorrection, Srgb},
+        driver::Driver,
+    };
+
+    use super::*;
+
+    /// A connection which keeps the written bytes, shared with the test.
+    #[derive(Clone, Default)]
+    struct SharedBuffer(Arc<Mutex<Vec<u8>>>);
+
+    impl Write for SharedBuffer {
+        fn write(&mut self, bytes: &[u8]) -> io::Result<usize> {
+            self.0.lock().unwrap().extend_from_slice(bytes);
+            Ok(bytes.len())
+        }
+
+        fn flush(&mut self) -> io::Result<()> {
+            Ok(())
+        }
+    }
+
+    /// A connection which fails every write.
+    struct FailingConnection;
+
+    impl Write for FailingConnection {
+        fn write(&mut self, _bytes: &[u8]) -> io::Result<usize> {
+            Err(io::Error::other("disconnected"))
+        }
+
+        fn flush(&mut self) -> io::Result<()> {
+            Ok(())
+        }
+    }
+
+    /// Waits for the writer thread, until a condition holds.
+    fn wait_until(mut condition: impl FnMut() -> bool) {
+        let start = Instant::now();
+        while !condition() {
+            assert!(start.elapsed() < SERIAL_TIMEOUT, "timed out");
+            thread::sleep(Duration::from_millis(1));
+        }
+    }
+
+    #[test]
+    fn test_encode_packet() {
+        assert_eq!(
+            encode_packet(&[255, 0, 0]).unwrap(),
+            [b'B', b'L', b'N', b'K', 0, 3, 255, 0, 0, 0x45, 0xB3]
+        );
+    }
+
+    #[test]
+    fn test_encode_packet_too_long() {
+        let payload = vec![0; SERIAL_MAX_PAYLOAD_LENGTH + 1];
+        assert!(matches!(
+            encode_packet(&payload),
+            Err(SerialError::FrameTooLong)
+        ));
+    }
+
+    #[test]
+    fn test_driver_writes_packet_in_channel_order() {
+        let buffer = SharedBuffer::default();
+        let mut driver =
+            SerialDriver::new(buffer.clone()).with_channels(LedChannels::Rgb(RgbChannels::GRB));
+        let pixels = [Srgb::new(1., 0., 0.)];
+        Driver::show::<1, 3, _, _>(&mut driver, pixels, 1., ColorCorrection::default()).unwrap();
+
+        let mut expected = encode_packet(&[0, 255, 0]).unwrap();
+        wait_until(|| buffer.0.lock().unwrap().len() >= expected.len());
+        assert_eq!(*buffer.0.lock().unwrap(), expected);
+
+        // The next frame follows the first.
+        let pixels = [Srgb::new(0., 0., 1.)];
+        Driver::show::<1, 3, _, _>(&mut driver, pixels, 1., ColorCorrection::default()).unwrap();
+
+        expected.extend(encode_packet(&[0, 0, 255]).unwrap());
+        wait_until(|| buffer.0.lock().unwrap().len() >= expected.len());
+        assert_eq!(*buffer.0.lock().unwrap(), expected);
+        assert_eq!(driver.dropped_frames(), 0);
+    }
+
+    #[test]
+    fn test_driver_returns_write_error() {
+        let mut driver = SerialDriver::new(FailingConnection);
+        let pixels = [Srgb::new(1., 0., 0.)];
+        Driver::show::<1, 3, _, _>(&mut driver, pixels, 1., ColorCorrection::default()).unwrap();
+
+        // The error of the failed write is returned by the next write.
+        wait_until(|| driver.shared.mailbox.lock().unwrap().error.is_some());
+        let result =
+            Driver::show::<1, 3, _, _>(&mut driver, pixels, 1., ColorCorrection::default());
+        assert!(matches!(result, Err(SerialError::Io(_))));
+    }
+}
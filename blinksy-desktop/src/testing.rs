@@ -0,0 +1,181 @@
+//! # Golden Image Testing
+//!
+//! This module renders the colors of a 2D layout into an image, to test patterns against
+//! golden images without a window or a GPU.
+//!
+//! [`render_to_ppm`] renders a frame of a [`Control`], as the LEDs would show it, into a
+//! binary [PPM] image: each LED is drawn as one image pixel at its position in the layout.
+//! PPM is a simple format which most image viewers open, so a golden image can be checked
+//! by eye, and a changed image shows what a change to a pattern looks like.
+//!
+//! ## Example
+//!
+//! ```rust,no_run
+//! use blinksy::{
+//!     driver::MockDriver,
+//!     layout::{Layout2d, Shape2d, Vec2},
+//!     layout2d,
+//!     patterns::rainbow::{Rainbow, RainbowParams},
+//!     ControlBuilder,
+//! };
+//! use blinksy_desktop::testing::render_to_ppm;
+//!
+//! layout2d!(
+//!     Layout,
+//!     [Shape2d::Grid {
+//!         start: Vec2::new(-1., -1.),
+//!         horizontal_end: Vec2::new(1., -1.),
+//!         vertical_end: Vec2::new(-1., 1.),
+//!         horizontal_pixel_count: 4,
+//!         vertical_pixel_count: 4,
+//!         serpentine: true,
+//!     }]
+//! );
+//!
+//! let control = ControlBuilder::new_2d()
+//!     .with_layout::<Layout, { Layout::PIXEL_COUNT }>()
+//!     .with_pattern::<Rainbow>(RainbowParams::default())
+//!     // The driver isn't written to.
+//!     .with_driver(MockDriver::<{ Layout::PIXEL_COUNT }>::new())
+//!     .with_frame_buffer_size::<{ Layout::PIXEL_COUNT }>()
+//!     .build();
+//!
+//! let image = render_to_ppm(&control, 1000, 4, 4);
+//! assert_eq!(image, std::fs::read("tests/golden/rainbow-4x4.ppm").unwrap());
+//! ```
+//!
+//! [PPM]: https://netpbm.sourceforge.net/doc/ppm.html
+
+use blinksy::{
+    color::{FromColor, LinearSrgb, Srgb},
+    control::Control,
+    layout::Layout2d,
+    markers::Dim2d,
+    pattern::Pattern as PatternTrait,
+};
+
+/// Renders a frame of a 2D layout into a binary PPM (P6) image.
+///
+/// Each LED is drawn as one image pixel, at its position in the layout: the layout's left
+/// bottom (-1.0, -1.0) is the image's left bottom, and the layout's right top (1.0, 1.0) is
+/// the image's right top. Image pixels without an LED are black.
+///
+/// The colors are as the LEDs would show them, with brightness and color correction (see
+/// [`Control::render_into`]), encoded as 8-bit sRGB.
+///
+/// # Arguments
+///
+/// - `control` - The control to render
+/// - `time_in_ms` - The time to render, in milliseconds
+/// - `width` - The width of the image, in pixels (for a grid, its horizontal pixel count)
+/// - `height` - The height of the image, in pixels (for a grid, its vertical pixel count)
+///
+/// # Returns
+///
+/// The PPM image: a header, then 3 bytes (red, green, blue) per pixel, by row from the top
+pub fn render_to_ppm<
+    const PIXEL_COUNT: usize,
+    const FRAME_BUFFER_SIZE: usize,
+    Exec,
+    Layout,
+    Pattern,
+    Driver,
+    Time,
+>(
+    control: &Control<PIXEL_COUNT, FRAME_BUFFER_SIZE, Dim2d, Exec, Layout, Pattern, Driver, Time>,
+    time_in_ms: u64,
+    width: usize,
+    height: usize,
+) -> Vec<u8>
+where
+    Layout: Layout2d,
+    Pattern: PatternTrait<Dim2d, Layout>,
+    LinearSrgb: FromColor<Pattern::Color>,
+{
+    let mut colors = [LinearSrgb::new(0., 0., 0.); PIXEL_COUNT];
+    control.render_into(time_in_ms, &mut colors);
+
+    let mut pixels = vec![0; width * height * 3];
+    for (point, color) in Layout::points().zip(colors) {
+        // Image rows increase towards the bottom, layout y increases towards the top.
+        let column = image_index(point.x, width);
+        let row = image_index(-point.y, height);
+        let Srgb { red, green, blue } = color.to_srgb();
+        let index = (row * width + column) * 3;
+        pixels[index..index + 3].copy_from_slice(&[to_byte(red), to_byte(green), to_byte(blue)]);
+    }
+
+    let mut image = format!("P6\n{width} {height}\n255\n").into_bytes();
+    image.extend(pixels);
+    image
+}
+
+/// Maps a position between -1.0 and 1.0 to the nearest of `count` image indices.
+fn image_index(position: f32, count: usize) -> usize {
+    let max_index = count.saturating_sub(1);
+    let index = ((position + 1.) / 2. * max_index as f32).round();
+    (index.max(0.) as usize).min(max_index)
+}
+
+/// Converts a color component (0.0 to 1.0) to a byte.
+fn to_byte(component: f32) -> u8 {
+    (component.clamp(0., 1.) * 255.).round() as u8
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{env, fs, path::Path};
+
+    use blinksy::{
+        driver::MockDriver,
+        layout::{Shape2d, Vec2},
+        layout2d,
+        patterns::rainbow::{Rainbow, RainbowParams},
+        ControlBuilder,
+    };
+
+    use super::*;
+
+    layout2d!(
+        Layout,
+        [Shape2d::Grid {
+            start: Vec2::new(-1., -1.),
+            horizontal_end: Vec2::new(1., -1.),
+            vertical_end: Vec2::new(-1., 1.),
+            horizontal_pixel_count: 4,
+            vertical_pixel_count: 4,
+            serpentine: true,
+        }]
+    );
+
+    /// Compares an image to a golden image, or writes the golden image if `BLESS` is set.
+    fn assert_golden(image: &[u8], name: &str) {
+        let path = Path::new(env!("CARGO_MANIFEST_DIR"))
+            .join("tests/golden")
+            .join(name);
+        if env::var_os("BLESS").is_some() {
+            fs::create_dir_all(path.parent().unwrap()).unwrap();
+            fs::write(&path, image).unwrap();
+        }
+        let golden = fs::read(&path).unwrap();
+        assert!(
+            image == golden,
+            "{} differs from the golden image, rerun with BLESS=1 to update it",
+            path.display()
+        );
+    }
+
+    #[test]
+    fn test_rainbow_golden() {
+        let control = ControlBuilder::new_2d()
+            .with_layout::<Layout, { Layout::PIXEL_COUNT }>()
+            .with_pattern::<Rainbow>(RainbowParams::default())
+            .with_driver(MockDriver::<{ Layout::PIXEL_COUNT }>::new())
+            .with_frame_buffer_size::<{ Layout::PIXEL_COUNT }>()
+            .build();
+
+        let image = render_to_ppm(&control, 1000, 4, 4);
+        assert!(image.starts_with(b"P6\n4 4\n255\n"));
+        assert_golden(&image, "rainbow-4x4.ppm");
+    }
+}
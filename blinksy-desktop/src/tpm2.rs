@@ -0,0 +1,386 @@
+//! # TPM2 Output
+//!
+//! This module provides drivers which stream each frame in the [TPM2] protocol, supported
+//! by many LED tools and controllers:
+//!
+//! - [`Tpm2Driver`]: TPM2 over a serial port (or any other connection)
+//! - [`Tpm2NetDriver`]: TPM2.net over UDP
+//!
+//! The payload is ready for the LEDs: the brightness and color correction are applied, and
+//! each channel is a linear 8-bit value, in the channel order of the LEDs. Both RGB and
+//! RGBW channels are supported, with [`LedChannels`].
+//!
+//! ## TPM2 Packets
+//!
+//! Each frame is one data packet:
+//!
+//! | Bytes  | Field       | Value                                     |
+//! |--------|-------------|-------------------------------------------|
+//! | 1      | Block start | `0xC9`                                    |
+//! | 1      | Type        | `0xDA` (data)                             |
+//! | 2      | Length      | Length of the payload, big-endian         |
+//! | Length | Payload     | Color bytes of each LED                   |
+//! | 1      | Block end   | `0x36`                                    |
+//!
+//! ## TPM2.net Packets
+//!
+//! Each frame is split into one or more UDP packets, of at most
+//! [`TPM2_NET_MAX_PAYLOAD_LENGTH`] payload bytes each:
+//!
+//! | Bytes  | Field         | Value                                   |
+//! |--------|---------------|-----------------------------------------|
+//! | 1      | Block start   | `0x9C`                                  |
+//! | 1      | Type          | `0xDA` (data)                           |
+//! | 2      | Length        | Length of the payload, big-endian       |
+//! | 1      | Packet number | Index of the packet in the frame, from 1 |
+//! | 1      | Packet count  | Number of packets in the frame          |
+//! | Length | Payload       | Color bytes of the packet's LEDs        |
+//! | 1      | Block end     | `0x36`                                  |
+//!
+//! ## Example
+//!
+//! ```rust,no_run
+//! use blinksy::{
+//!     layout::Layout1d,
+//!     layout1d,
+//!     patterns::rainbow::{Rainbow, RainbowParams},
+//!     ControlBuilder,
+//! };
+//! use blinksy_desktop::{time::elapsed_in_ms, tpm2::Tpm2NetDriver};
+//!
+//! layout1d!(Layout, 60);
+//!
+//! // TPM2.net receivers listen on UDP port 65506.
+//! let driver = Tpm2NetDriver::connect("192.168.1.50:65506").unwrap();
+//!
+//! let mut control = ControlBuilder::new_1d()
+//!     .with_layout::<Layout, { Layout::PIXEL_COUNT }>()
+//!     .with_pattern::<Rainbow>(RainbowParams::default())
+//!     .with_driver(driver)
+//!     .with_frame_buffer_size::<{ Layout::PIXEL_COUNT * 3 }>()
+//!     .build();
+//!
+//! loop {
+//!     control.tick(elapsed_in_ms()).unwrap();
+//!     std::thread::sleep(std::time::Duration::from_millis(16));
+//! }
+//! ```
+//!
+//! [TPM2]: https://gist.github.com/jblang/89e24e2655be6c463c56
+
+use core::fmt;
+use std::{
+    io::{self, Write},
+    net::{ToSocketAddrs, UdpSocket},
+};
+
+use blinksy::color::{LedChannels, RgbChannels};
+
+use crate::frame::{self, impl_byte_driver};
+
+/// TPM2 block start byte, for serial packets.
+const TPM2_BLOCK_START: u8 = 0xC9;
+
+/// TPM2.net block start byte, for UDP packets.
+const TPM2_NET_BLOCK_START: u8 = 0x9C;
+
+/// TPM2 packet type for data frames.
+const TPM2_TYPE_DATA: u8 = 0xDA;
+
+/// TPM2 block end byte.
+const TPM2_BLOCK_END: u8 = 0x36;
+
+/// The UDP port TPM2.net receivers listen on.
+pub const TPM2_NET_PORT: u16 = 65506;
+
+/// The most payload bytes in a TPM2.net packet.
+pub const TPM2_NET_MAX_PAYLOAD_LENGTH: usize = 1490;
+
+/// Returns the TPM2 data packet of a frame.
+fn tpm2_packet(payload: &[u8]) -> Result<Vec<u8>, Tpm2Error> {
+    let length = u16::try_from(payload.len()).map_err(|_| Tpm2Error::FrameTooLong)?;
+
+    let mut packet = Vec::with_capacity(payload.len() + 5);
+    packet.extend_from_slice(&[TPM2_BLOCK_START, TPM2_TYPE_DATA]);
+    packet.extend_from_slice(&length.to_be_bytes());
+    packet.extend_from_slice(payload);
+    packet.push(TPM2_BLOCK_END);
+    Ok(packet)
+}
+
+/// Returns the TPM2.net data packets of a frame.
+///
+/// # Arguments
+///
+/// - `payload` - The color bytes of the frame
+/// - `max_payload_length` - The most payload bytes in each packet
+fn tpm2_net_packets(
+    payload: &[u8],
+    max_payload_length: usize,
+) -> Result<impl Iterator<Item = Vec<u8>> + '_, Tpm2Error> {
+    let chunks = payload.chunks(max_payload_length.max(1));
+    let packet_count = u8::try_from(chunks.len()).map_err(|_| Tpm2Error::FrameTooLong)?;
+
+    Ok(chunks.enumerate().map(move |(index, chunk)| {
+        let mut packet = Vec::with_capacity(chunk.len() + 7);
+        packet.extend_from_slice(&[TPM2_NET_BLOCK_START, TPM2_TYPE_DATA]);
+        // Each chunk is at most `max_payload_length` bytes, so fits in a u16.
+        packet.extend_from_slice(&(chunk.len() as u16).to_be_bytes());
+        packet.extend_from_slice(&[index as u8 + 1, packet_count]);
+        packet.extend_from_slice(chunk);
+        packet.push(TPM2_BLOCK_END);
+        packet
+    }))
+}
+
+/// A driver which writes each frame as a TPM2 data packet, such as to a serial port.
+///
+/// For details, see [tpm2 module](self).
+///
+/// Each write blocks until the packet is written.
+///
+/// # Type Parameters
+///
+/// - `W` - The connection to write packets to
+pub struct Tpm2Driver<W> {
+    /// The connection to write packets to
+    connection: W,
+    /// Color channel order of the LEDs
+    channels: LedChannels,
+}
+
+impl<W: Write> Tpm2Driver<W> {
+    /// Creates a driver which writes to a connection, for LEDs with RGB channel order.
+    ///
+    /// # Arguments
+    ///
+    /// - `connection` - The connection to write packets to, such as a serial port
+    pub fn new(connection: W) -> Self {
+        Self {
+            connection,
+            channels: LedChannels::Rgb(RgbChannels::RGB),
+        }
+    }
+
+    /// Sets the color channel order of the LEDs.
+    pub fn with_channels(self, channels: LedChannels) -> Self {
+        Self { channels, ..self }
+    }
+
+    /// Releases the connection.
+    pub fn release(self) -> W {
+        self.connection
+    }
+
+    /// A compile-time function to get a `FRAME_BUFFER_SIZE`, given a `PIXEL_COUNT`.
+    pub const fn frame_buffer_size(pixel_count: usize, channels: LedChannels) -> usize {
+        frame::frame_buffer_size(pixel_count, channels)
+    }
+
+    /// Writes a frame as a packet.
+    fn write_frame(&mut self, frame: &[u8]) -> Result<(), Tpm2Error> {
+        let packet = tpm2_packet(frame)?;
+        self.connection.write_all(&packet)?;
+        self.connection.flush()?;
+        Ok(())
+    }
+}
+
+#[cfg(feature = "serial")]
+impl Tpm2Driver<Box<dyn serialport::SerialPort>> {
+    /// Opens a serial port, and creates a driver which writes to it, for LEDs with RGB
+    /// channel order.
+    ///
+    /// Requires the `serial` feature.
+    ///
+    /// # Arguments
+    ///
+    /// - `path` - The path of the serial port, such as `/dev/ttyACM0` or `COM3`
+    /// - `baud_rate` - The baud rate of the serial port (ignored by USB CDC serial ports)
+    pub fn open(path: &str, baud_rate: u32) -> Result<Self, Tpm2Error> {
+        let port = serialport::new(path, baud_rate)
+            .timeout(std::time::Duration::from_secs(1))
+            .open()
+            .map_err(|error| Tpm2Error::Open(error.into()))?;
+        Ok(Self::new(port))
+    }
+}
+
+impl_byte_driver! {
+    /// With the `async` feature, the TPM2 driver also implements [`DriverAsync`].
+    ///
+    /// The write blocks until the packet is written, so is best for fast connections.
+    ///
+    /// [`DriverAsync`]: blinksy::driver::DriverAsync
+    impl<W: Write> for Tpm2Driver<W> {
+        error: Tpm2Error,
+        send: write_frame,
+    }
+}
+
+/// A driver which sends each frame as TPM2.net data packets over UDP.
+///
+/// For details, see [tpm2 module](self).
+pub struct Tpm2NetDriver {
+    /// The UDP socket, connected to the receiver
+    socket: UdpSocket,
+    /// Color channel order of the LEDs
+    channels: LedChannels,
+    /// The most payload bytes in each packet
+    max_payload_length: usize,
+}
+
+impl Tpm2NetDriver {
+    /// Creates a driver which sends to a receiver, for LEDs with RGB channel order.
+    ///
+    /// # Arguments
+    ///
+    /// - `address` - The address of the receiver, usually on port [`TPM2_NET_PORT`]
+    pub fn connect(address: impl ToSocketAddrs) -> Result<Self, Tpm2Error> {
+        let socket = UdpSocket::bind(("0.0.0.0", 0)).map_err(Tpm2Error::Open)?;
+        socket.connect(address).map_err(Tpm2Error::Open)?;
+        Ok(Self::new(socket))
+    }
+
+    /// Creates a driver which sends on a connected UDP socket, for LEDs with RGB channel
+    /// order.
+    ///
+    /// # Arguments
+    ///
+    /// - `socket` - The UDP socket, connected to the receiver
+    pub fn new(socket: UdpSocket) -> Self {
+        Self {
+            socket,
+            channels: LedChannels::Rgb(RgbChannels::RGB),
+            max_payload_length: TPM2_NET_MAX_PAYLOAD_LENGTH,
+        }
+    }
+
+    /// Sets the color channel order of the LEDs.
+    pub fn with_channels(self, channels: LedChannels) -> Self {
+        Self { channels, ..self }
+    }
+
+    /// Sets the most payload bytes in each packet.
+    ///
+    /// Some receivers expect each packet to hold whole rows or universes of LEDs.
+    ///
+    /// # Arguments
+    ///
+    /// - `max_payload_length` - The most payload bytes, up to [`TPM2_NET_MAX_PAYLOAD_LENGTH`]
+    pub fn with_max_payload_length(self, max_payload_length: usize) -> Self {
+        Self {
+            max_payload_length: max_payload_length.min(TPM2_NET_MAX_PAYLOAD_LENGTH),
+            ..self
+        }
+    }
+
+    /// A compile-time function to get a `FRAME_BUFFER_SIZE`, given a `PIXEL_COUNT`.
+    pub const fn frame_buffer_size(pixel_count: usize, channels: LedChannels) -> usize {
+        frame::frame_buffer_size(pixel_count, channels)
+    }
+
+    /// Sends a frame as packets.
+    fn send_frame(&mut self, frame: &[u8]) -> Result<(), Tpm2Error> {
+        for packet in tpm2_net_packets(frame, self.max_payload_length)? {
+            self.socket.send(&packet)?;
+        }
+        Ok(())
+    }
+}
+
+impl_byte_driver! {
+    /// With the `async` feature, the TPM2.net driver also implements [`DriverAsync`].
+    ///
+    /// Sending a UDP packet doesn't wait for the receiver, so each future is ready at once.
+    ///
+    /// [`DriverAsync`]: blinksy::driver::DriverAsync
+    impl for Tpm2NetDriver {
+        error: Tpm2Error,
+        send: send_frame,
+    }
+}
+
+/// Errors from the TPM2 drivers.
+#[derive(Debug)]
+pub enum Tpm2Error {
+    /// The connection failed to open, such as a serial port or a UDP socket
+    Open(io::Error),
+    /// A write to the connection failed
+    Io(io::Error),
+    /// The frame is too long for the packets
+    FrameTooLong,
+}
+
+impl fmt::Display for Tpm2Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Tpm2Error::Open(error) => write!(f, "Failed to open connection: {error}"),
+            Tpm2Error::Io(error) => write!(f, "Failed to write TPM2 packet: {error}"),
+            Tpm2Error::FrameTooLong => write!(f, "Frame is too long for TPM2 packets"),
+        }
+    }
+}
+
+impl std::error::Error for Tpm2Error {}
+
+impl From<io::Error> for Tpm2Error {
+    fn from(error: io::Error) -> Self {
+        Tpm2Error::Io(error)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use blinksy::{
+        color::{ColorCorrection, RgbwChannels, Srgb},
+        driver::Driver,
+    };
+
+    use super::*;
+
+    #[test]
+    fn test_tpm2_packet_two_pixels() {
+        let pixels = [Srgb::new(1., 0., 0.), Srgb::new(0., 0., 1.)];
+        let mut driver = Tpm2Driver::new(Vec::new());
+        Driver::show::<2, 6, _, _>(&mut driver, pixels, 1., ColorCorrection::default()).unwrap();
+
+        assert_eq!(
+            driver.release(),
+            [0xC9, 0xDA, 0x00, 0x06, 255, 0, 0, 0, 0, 255, 0x36]
+        );
+    }
+
+    #[test]
+    fn test_tpm2_packet_two_pixels_rgbw() {
+        let pixels = [Srgb::new(1., 1., 1.), Srgb::new(0., 1., 0.)];
+        let mut driver =
+            Tpm2Driver::new(Vec::new()).with_channels(LedChannels::Rgbw(RgbwChannels::RGBW));
+        Driver::show::<2, 8, _, _>(&mut driver, pixels, 1., ColorCorrection::default()).unwrap();
+
+        assert_eq!(
+            driver.release(),
+            [0xC9, 0xDA, 0x00, 0x08, 0, 0, 0, 255, 0, 255, 0, 0, 0x36]
+        );
+    }
+
+    #[test]
+    fn test_tpm2_net_connect_error() {
+        let result = Tpm2NetDriver::connect("not an address");
+        assert!(matches!(result, Err(Tpm2Error::Open(_))));
+    }
+
+    #[test]
+    fn test_tpm2_net_packets_split_frame() {
+        let payload = [1, 2, 3, 4, 5, 6];
+        let packets: Vec<Vec<u8>> = tpm2_net_packets(&payload, 3).unwrap().collect();
+
+        assert_eq!(
+            packets,
+            [
+                [0x9C, 0xDA, 0x00, 0x03, 1, 2, 1, 2, 3, 0x36],
+                [0x9C, 0xDA, 0x00, 0x03, 2, 2, 4, 5, 6, 0x36],
+            ]
+        );
+    }
+}
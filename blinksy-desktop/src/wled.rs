@@ -0,0 +1,561 @@
+//! # WLED JSON API
+//!
+//! This module provides a [`WledServer`], an HTTP server with a subset of the [WLED JSON
+//! API], so apps and tools made for WLED can drive the desktop simulation.
+//!
+//! Requires the `wled` feature.
+//!
+//! ## Endpoints
+//!
+//! - `GET /json/state`: The current state
+//! - `POST /json/state` (or `POST /json`): Updates the state, responding with the new state
+//! - `GET /json/info`: A minimal info object: name, version, LED count, and effect count
+//! - `GET /json/eff`: The names of the effects, by index
+//! - `GET /json`: The state, info, and effects together
+//!
+//! ## State
+//!
+//! Of the WLED state, the server supports:
+//!
+//! - `on`: Whether the LEDs are on, as a boolean, or `"t"` to toggle
+//! - `bri`: The brightness, from 0 to 255
+//! - `seg`: The main segment (an object, or the first of an array), with:
+//!   - `fx`: The effect index, ignored unless less than the number of effects in the
+//!     [`WledInfo`]
+//!   - `col`: The colors, where the first is the primary color as `[red, green, blue]`
+//!
+//! Other fields are ignored.
+//!
+//! The server runs on its own thread. Each update is queued for the animation loop, which
+//! applies it to the [`Control`] with [`WledServer::apply`]:
+//!
+//! - `on` and `bri` set the brightness, with [`Control::set_brightness`]
+//! - `fx` selects a pattern, with [`Control::select_pattern`], for patterns which implement
+//!   [`PatternSwitch`], such as [`BuiltinSwitch`](blinksy::patterns::switch::BuiltinSwitch)
+//! - `col` maps the primary color to new pattern parameters, with [`Control::set_params`]
+//!
+//! ## Example
+//!
+//! ```rust,no_run
+//! use blinksy::{
+//!     layout::Layout1d,
+//!     layout1d,
+//!     patterns::switch::{BuiltinSwitch1d, BuiltinSwitchParams},
+//!     ControlBuilder,
+//! };
+//! use blinksy_desktop::{
+//!     driver::Desktop,
+//!     time::elapsed_in_ms,
+//!     wled::{WledInfo, WledServer},
+//! };
+//!
+//! layout1d!(Layout, 60);
+//!
+//! Desktop::new_1d::<Layout>().start(|driver| {
+//!     let wled = WledServer::start(
+//!         "0.0.0.0:8080",
+//!         WledInfo {
+//!             led_count: Layout::PIXEL_COUNT,
+//!             effects: vec!["Rainbow".into(), "Noise".into()],
+//!             ..Default::default()
+//!         },
+//!     )
+//!     .unwrap();
+//!
+//!     let mut control = ControlBuilder::new_1d()
+//!         .with_layout::<Layout, { Layout::PIXEL_COUNT }>()
+//!         .with_pattern::<BuiltinSwitch1d>(BuiltinSwitchParams::default())
+//!         .with_driver(driver)
+//!         .with_frame_buffer_size::<{ Layout::PIXEL_COUNT }>()
+//!         .build();
+//!
+//!     loop {
+//!         // The built-in patterns have no color, so ignore the color.
+//!         wled.apply(&mut control, |_color| None);
+//!
+//!         control.tick(elapsed_in_ms()).unwrap();
+//!         std::thread::sleep(std::time::Duration::from_millis(16));
+//!     }
+//! });
+//! ```
+//!
+//! [WLED JSON API]: https://kno.wled.ge/interfaces/json-api/
+//! [`Control`]: blinksy::control::Control
+//! [`Control::set_brightness`]: blinksy::control::Control::set_brightness
+//! [`Control::select_pattern`]: blinksy::control::Control::select_pattern
+//! [`Control::set_params`]: blinksy::control::Control::set_params
+
+use core::fmt;
+use std::{
+    io::Read,
+    net::ToSocketAddrs,
+    sync::{
+        mpsc::{channel, Receiver, Sender},
+        Arc, Mutex,
+    },
+    thread,
+};
+
+use blinksy::{
+    color::Srgb, control::Control, layout::LayoutForDim, pattern::Pattern as PatternTrait,
+    patterns::switch::PatternSwitch,
+};
+use serde::Deserialize;
+use serde_json::json;
+use tiny_http::{Header, Method, Request, Response, Server};
+
+/// The WLED version reported in `/json/info`, for apps which check the version.
+const WLED_VERSION: &str = "0.14.0";
+
+/// The largest request body accepted, in bytes.
+const MAX_BODY_LENGTH: u64 = 64 * 1024;
+
+/// Information about the LEDs, reported in `/json/info` and `/json/eff`.
+#[derive(Debug, Clone)]
+pub struct WledInfo {
+    /// The name of the device, shown in WLED apps
+    pub name: String,
+    /// The number of LEDs
+    pub led_count: usize,
+    /// The names of the effects, by index, which also bounds the effect index of requests
+    pub effects: Vec<String>,
+}
+
+impl Default for WledInfo {
+    fn default() -> Self {
+        Self {
+            name: "Blinksy".into(),
+            led_count: 0,
+            effects: Vec::new(),
+        }
+    }
+}
+
+/// The current WLED state, as reported in `/json/state`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct WledState {
+    /// Whether the LEDs are on
+    pub on: bool,
+    /// The brightness, from 0 to 255
+    pub brightness: u8,
+    /// The effect index
+    pub effect: usize,
+    /// The primary color, as `[red, green, blue]`
+    pub color: [u8; 3],
+}
+
+impl Default for WledState {
+    fn default() -> Self {
+        Self {
+            on: true,
+            brightness: 255,
+            effect: 0,
+            color: [255, 160, 0],
+        }
+    }
+}
+
+impl WledState {
+    /// Returns the brightness for [`Control::set_brightness`](blinksy::control::Control::set_brightness), from 0.0 (off) to 1.0 (full).
+    pub fn control_brightness(&self) -> f32 {
+        if self.on {
+            self.brightness as f32 / 255.
+        } else {
+            0.
+        }
+    }
+
+    /// Returns the primary color.
+    pub fn srgb(&self) -> Srgb {
+        let [red, green, blue] = self.color;
+        Srgb::new(red as f32 / 255., green as f32 / 255., blue as f32 / 255.)
+    }
+
+    /// Applies a request to the state.
+    ///
+    /// An effect index beyond the number of effects is ignored.
+    ///
+    /// # Arguments
+    ///
+    /// - `request` - The state request
+    /// - `effect_count` - The number of effects
+    ///
+    /// # Returns
+    ///
+    /// Which parts of the state changed
+    fn update(&mut self, request: StateRequest, effect_count: usize) -> WledUpdate {
+        let mut update = WledUpdate::default();
+
+        match request.on {
+            Some(OnRequest::Set(on)) => self.on = on,
+            Some(OnRequest::Toggle(ref toggle)) if toggle == "t" => self.on = !self.on,
+            _ => {}
+        }
+        if let Some(brightness) = request.bri {
+            self.brightness = brightness;
+        }
+        update.brightness =
+            (request.on.is_some() || request.bri.is_some()).then(|| self.control_brightness());
+
+        let segment = match request.seg {
+            Some(SegmentsRequest::One(segment)) => Some(segment),
+            Some(SegmentsRequest::Many(segments)) => segments.into_iter().next(),
+            None => None,
+        };
+        if let Some(segment) = segment {
+            if let Some(effect) = segment.fx.filter(|&effect| effect < effect_count) {
+                self.effect = effect;
+                update.effect = Some(effect);
+            }
+            if let Some(color) = segment.col.and_then(|colors| colors.into_iter().next()) {
+                if let [red, green, blue, ..] = color[..] {
+                    self.color = [red, green, blue];
+                    update.color = Some(self.srgb());
+                }
+            }
+        }
+
+        update
+    }
+
+    /// Returns the state as WLED JSON.
+    fn to_json(self) -> serde_json::Value {
+        json!({
+            "on": self.on,
+            "bri": self.brightness,
+            "seg": [{
+                "id": 0,
+                "on": self.on,
+                "fx": self.effect,
+                "col": [self.color, [0, 0, 0], [0, 0, 0]],
+            }],
+        })
+    }
+}
+
+/// An update to the state, from a WLED request.
+///
+/// Each field is `Some` if the request changed that part of the state.
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct WledUpdate {
+    /// The new brightness, from 0.0 (off) to 1.0 (full)
+    pub brightness: Option<f32>,
+    /// The new effect index
+    pub effect: Option<usize>,
+    /// The new primary color
+    pub color: Option<Srgb>,
+}
+
+/// The `on` field of a state request.
+#[derive(Debug, Deserialize)]
+#[serde(untagged)]
+enum OnRequest {
+    Set(bool),
+    Toggle(String),
+}
+
+/// The `seg` field of a state request: one segment, or an array of segments.
+#[derive(Debug, Deserialize)]
+#[serde(untagged)]
+enum SegmentsRequest {
+    One(SegmentRequest),
+    Many(Vec<SegmentRequest>),
+}
+
+/// A segment of a state request.
+#[derive(Debug, Default, Deserialize)]
+struct SegmentRequest {
+    fx: Option<usize>,
+    col: Option<Vec<Vec<u8>>>,
+}
+
+/// A state request.
+#[derive(Debug, Default, Deserialize)]
+struct StateRequest {
+    on: Option<OnRequest>,
+    bri: Option<u8>,
+    seg: Option<SegmentsRequest>,
+}
+
+impl WledInfo {
+    /// Returns the info as WLED JSON.
+    fn to_json(&self) -> serde_json::Value {
+        json!({
+            "ver": WLED_VERSION,
+            "name": self.name,
+            "brand": "Blinksy",
+            "product": "blinksy-desktop",
+            "leds": {
+                "count": self.led_count,
+                "rgbw": false,
+            },
+            "fxcount": self.effects.len(),
+        })
+    }
+}
+
+/// An HTTP server with a subset of the WLED JSON API.
+///
+/// See the [module docs](self) for the supported API.
+///
+/// The server stops when dropped, after its next request.
+pub struct WledServer {
+    /// The current state
+    state: Arc<Mutex<WledState>>,
+    /// Updates from requests, not yet applied
+    updates: Receiver<WledUpdate>,
+}
+
+impl WledServer {
+    /// Starts the server, on its own thread, with the default state.
+    ///
+    /// # Arguments
+    ///
+    /// - `address` - The address to listen on, such as `"0.0.0.0:80"`
+    /// - `info` - Information about the LEDs
+    pub fn start(address: impl ToSocketAddrs, info: WledInfo) -> Result<Self, WledError> {
+        Self::start_with_state(address, info, WledState::default())
+    }
+
+    /// Starts the server, on its own thread, with an initial state.
+    ///
+    /// # Arguments
+    ///
+    /// - `address` - The address to listen on, such as `"0.0.0.0:80"`
+    /// - `info` - Information about the LEDs
+    /// - `state` - The initial state
+    pub fn start_with_state(
+        address: impl ToSocketAddrs,
+        info: WledInfo,
+        state: WledState,
+    ) -> Result<Self, WledError> {
+        let server = Server::http(address).map_err(WledError::Server)?;
+        let state = Arc::new(Mutex::new(state));
+        let (sender, updates) = channel();
+
+        let server_state = state.clone();
+        thread::spawn(move || {
+            for request in server.incoming_requests() {
+                if !handle_request(request, &info, &server_state, &sender) {
+                    break;
+                }
+            }
+        });
+
+        Ok(Self { state, updates })
+    }
+
+    /// Returns the current state.
+    pub fn state(&self) -> WledState {
+        *self.state.lock().unwrap()
+    }
+
+    /// Returns the updates received since the last call, in order.
+    pub fn updates(&self) -> impl Iterator<Item = WledUpdate> + '_ {
+        self.updates.try_iter()
+    }
+
+    /// Applies the updates received since the last call to a control system.
+    ///
+    /// Call this once per frame, before ticking the control system.
+    ///
+    /// # Arguments
+    ///
+    /// - `control` - The control system
+    /// - `color_params` - Maps a new primary color to new pattern parameters, or `None` to
+    ///   ignore the color
+    pub fn apply<
+        const PIXEL_COUNT: usize,
+        const FRAME_BUFFER_SIZE: usize,
+        Dim,
+        Exec,
+        Layout,
+        Pattern,
+        Driver,
+        Time,
+    >(
+        &self,
+        control: &mut Control<
+            PIXEL_COUNT,
+            FRAME_BUFFER_SIZE,
+            Dim,
+            Exec,
+            Layout,
+            Pattern,
+            Driver,
+            Time,
+        >,
+        mut color_params: impl FnMut(Srgb) -> Option<Pattern::Params>,
+    ) where
+        Layout: LayoutForDim<Dim>,
+        Pattern: PatternTrait<Dim, Layout> + PatternSwitch,
+    {
+        for update in self.updates() {
+            if let Some(brightness) = update.brightness {
+                control.set_brightness(brightness);
+            }
+            if let Some(params) = update.color.and_then(&mut color_params) {
+                control.set_params(params);
+            }
+            if let Some(effect) = update.effect {
+                control.select_pattern(effect);
+            }
+        }
+    }
+}
+
+/// Responds to a request.
+///
+/// # Returns
+///
+/// Whether to keep serving, which is false once the [`WledServer`] is dropped
+fn handle_request(
+    mut request: Request,
+    info: &WledInfo,
+    state: &Mutex<WledState>,
+    sender: &Sender<WledUpdate>,
+) -> bool {
+    let path = request
+        .url()
+        .split('?')
+        .next()
+        .unwrap_or_default()
+        .to_owned();
+    let path = path.trim_end_matches('/');
+    let mut is_connected = true;
+
+    let response = match (request.method(), path) {
+        (Method::Get, "/json/state") => Ok(state.lock().unwrap().to_json()),
+        (Method::Get, "/json/info") => Ok(info.to_json()),
+        (Method::Get, "/json/eff") => Ok(json!(info.effects)),
+        (Method::Get, "/json") => Ok(json!({
+            "state": state.lock().unwrap().to_json(),
+            "info": info.to_json(),
+            "effects": info.effects,
+        })),
+        (Method::Post, "/json/state" | "/json") => {
+            let mut body = Vec::new();
+            match request
+                .as_reader()
+                .take(MAX_BODY_LENGTH)
+                .read_to_end(&mut body)
+            {
+                Ok(_) => match serde_json::from_slice::<StateRequest>(&body) {
+                    Ok(state_request) => {
+                        let mut state = state.lock().unwrap();
+                        let update = state.update(state_request, info.effects.len());
+                        is_connected = sender.send(update).is_ok();
+                        Ok(state.to_json())
+                    }
+                    Err(error) => Err((400, error.to_string())),
+                },
+                Err(error) => Err((400, error.to_string())),
+            }
+        }
+        _ => Err((404, "Not found".into())),
+    };
+
+    let content_type = Header::from_bytes("Content-Type", "application/json").unwrap();
+    let response = match response {
+        Ok(body) => Response::from_string(body.to_string()),
+        Err((status, message)) => {
+            Response::from_string(json!({ "error": message }).to_string()).with_status_code(status)
+        }
+    };
+    // The client may have disconnected, which doesn't stop the server.
+    let _ = request.respond(response.with_header(content_type));
+
+    is_connected
+}
+
+/// Errors from the [`WledServer`].
+#[derive(Debug)]
+pub enum WledError {
+    /// The server failed to listen on the address
+    Server(Box<dyn std::error::Error + Send + Sync + 'static>),
+}
+
+impl fmt::Display for WledError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            WledError::Server(error) => write!(f, "Failed to start WLED server: {error}"),
+        }
+    }
+}
+
+impl std::error::Error for WledError {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn update(state: &mut WledState, request: serde_json::Value) -> WledUpdate {
+        state.update(serde_json::from_value(request).unwrap(), 2)
+    }
+
+    #[test]
+    fn test_update_effect() {
+        let mut state = WledState::default();
+
+        let effect = update(&mut state, json!({ "seg": { "fx": 1 } }));
+        assert_eq!(effect.effect, Some(1));
+        assert_eq!(state.effect, 1);
+
+        // The first of an array of segments.
+        let effect = update(&mut state, json!({ "seg": [{ "fx": 0 }, { "fx": 1 }] }));
+        assert_eq!(effect.effect, Some(0));
+        assert_eq!(state.effect, 0);
+
+        // Beyond the number of effects, so ignored.
+        let effect = update(&mut state, json!({ "seg": { "fx": 2 } }));
+        assert_eq!(effect, WledUpdate::default());
+        assert_eq!(state.effect, 0);
+    }
+
+    #[test]
+    fn test_update_color() {
+        let mut state = WledState::default();
+
+        let color = update(
+            &mut state,
+            json!({ "seg": { "col": [[255, 0, 51], [0, 0, 0]] } }),
+        );
+        assert_eq!(state.color, [255, 0, 51]);
+        assert_eq!(color.color, Some(Srgb::new(1., 0., 0.2)));
+
+        // Too few channels, so ignored.
+        let color = update(&mut state, json!({ "seg": { "col": [[255, 255]] } }));
+        assert_eq!(color.color, None);
+        assert_eq!(state.color, [255, 0, 51]);
+    }
+
+    #[test]
+    fn test_update_brightness() {
+        let mut state = WledState::default();
+
+        let brightness = update(&mut state, json!({ "bri": 51 }));
+        assert_eq!(state.brightness, 51);
+        assert_eq!(brightness.brightness, Some(0.2));
+
+        let nothing = update(&mut state, json!({}));
+        assert_eq!(nothing, WledUpdate::default());
+    }
+
+    #[test]
+    fn test_update_on() {
+        let mut state = WledState::default();
+
+        let off = update(&mut state, json!({ "on": false }));
+        assert!(!state.on);
+        assert_eq!(off.brightness, Some(0.));
+
+        let toggle = update(&mut state, json!({ "on": "t" }));
+        assert!(state.on);
+        assert_eq!(toggle.brightness, Some(1.));
+
+        // An unknown string doesn't change the state, but still reports the brightness.
+        let unknown = update(&mut state, json!({ "on": "x" }));
+        assert!(state.on);
+        assert_eq!(unknown.brightness, Some(1.));
+    }
+}
@@ -0,0 +1,92 @@
+//! Measures a tick of [`Control`] with 1024 pixels, which renders a rainbow into the frame
+//! buffer of [`Control`], then gives the frame to a driver which only converts each color.
+//!
+//! Run with `cargo bench -p blinksy --bench control`.
+
+use std::hint::black_box;
+
+use blinksy::{
+    color::{ColorCorrection, FromColor, LinearSrgb},
+    control::{Control, ControlBuilder},
+    driver::Driver,
+    layout::Layout1d,
+    layout1d,
+    markers::{Blocking, Dim1d},
+    patterns::rainbow::{Rainbow, RainbowParams},
+};
+use criterion::{criterion_group, criterion_main, Criterion};
+use heapless::Vec;
+
+layout1d!(Layout, 1024);
+
+/// A driver which converts each color, then discards the frame.
+struct NoopDriver;
+
+impl Driver for NoopDriver {
+    type Error = ();
+    type Color = LinearSrgb;
+    type Word = u8;
+
+    fn encode<const PIXEL_COUNT: usize, const FRAME_BUFFER_SIZE: usize, Pixels, Color>(
+        &mut self,
+        pixels: Pixels,
+        _brightness: f32,
+        _correction: ColorCorrection,
+    ) -> Vec<Self::Word, FRAME_BUFFER_SIZE>
+    where
+        Pixels: IntoIterator<Item = Color>,
+        Self::Color: FromColor<Color>,
+    {
+        for color in pixels {
+            black_box(LinearSrgb::from_color(color));
+        }
+        Vec::new()
+    }
+
+    fn write<const FRAME_BUFFER_SIZE: usize>(
+        &mut self,
+        frame: Vec<Self::Word, FRAME_BUFFER_SIZE>,
+        _brightness: f32,
+        _correction: ColorCorrection,
+    ) -> Result<(), Self::Error> {
+        black_box(frame);
+        Ok(())
+    }
+}
+
+fn build() -> Control<1024, 0, Dim1d, Blocking, Layout, Rainbow, NoopDriver> {
+    ControlBuilder::new_1d()
+        .with_layout::<Layout, { Layout::PIXEL_COUNT }>()
+        .with_pattern::<Rainbow>(RainbowParams::default())
+        .with_driver(NoopDriver)
+        .with_frame_buffer_size::<0>()
+        .build()
+}
+
+fn control(c: &mut Criterion) {
+    let mut group = c.benchmark_group("control tick, 1024 pixels");
+
+    let mut control = build();
+    let mut time_in_ms = 0;
+    group.bench_function("rainbow", |b| {
+        b.iter(|| {
+            time_in_ms += 16;
+            control.tick(black_box(time_in_ms)).unwrap();
+        })
+    });
+
+    let mut control = build();
+    control.set_power_limit(5_000, 20.);
+    let mut time_in_ms = 0;
+    group.bench_function("rainbow with power limit", |b| {
+        b.iter(|| {
+            time_in_ms += 16;
+            control.tick(black_box(time_in_ms)).unwrap();
+        })
+    });
+
+    group.finish();
+}
+
+criterion_group!(benches, control);
+criterion_main!(benches);
@@ -0,0 +1,39 @@
+//! Compares mapping 8-bit values through a gamma with `powf` and with a [`GammaLut256`].
+//!
+//! Run with `cargo bench -p blinksy --bench gamma`.
+
+use std::hint::black_box;
+
+use blinksy::color::GammaLut256;
+use criterion::{criterion_group, criterion_main, Criterion};
+
+const GAMMA: f32 = 2.2;
+
+fn gamma(c: &mut Criterion) {
+    let mut group = c.benchmark_group("gamma 2.2, 256 values");
+
+    // As in `no_std`, where `powf` is from `libm`.
+    group.bench_function("libm powf", |b| {
+        b.iter(|| {
+            for value in 0..=255u8 {
+                let normalized = black_box(value) as f32 / 255.;
+                let output = libm::powf(normalized, GAMMA);
+                black_box((output * 255.).round() as u8);
+            }
+        })
+    });
+
+    let lut = GammaLut256::new(GAMMA);
+    group.bench_function("GammaLut256::apply", |b| {
+        b.iter(|| {
+            for value in 0..=255u8 {
+                black_box(lut.apply(black_box(value)));
+            }
+        })
+    });
+
+    group.finish();
+}
+
+criterion_group!(benches, gamma);
+criterion_main!(benches);
@@ -0,0 +1,86 @@
+//! # Audio Input
+//!
+//! Audio-reactive patterns read from an [`AudioSource`], such as a microphone.
+//!
+//! Like other inputs, audio is pushed into a pattern from your main loop, for example with
+//! [`VuMeter::update`](crate::patterns::vu::VuMeter::update):
+//!
+//! ```rust,ignore
+//! loop {
+//!     control.pattern_mut().update(&mut audio_source);
+//!     control.tick(/* current time in milliseconds */).unwrap();
+//! }
+//! ```
+//!
+//! Implementations:
+//!
+//! - [`ScriptedAudio`]: Plays back scripted levels, for tests and demos
+//! - `blinksy_desktop::audio::Microphone`: The default microphone on desktop (with the
+//!   `audio` feature of `blinksy-desktop`)
+
+/// Trait for a source of audio input.
+///
+/// Values are normalized, so patterns don't depend on the source: `0.0` is silence, and
+/// `1.0` is as loud as the source expects to get. Sources may return values above `1.0`
+/// for clipping audio.
+pub trait AudioSource {
+    /// Returns the current overall loudness of the audio (0.0 to 1.0).
+    fn level(&mut self) -> f32;
+
+    /// Returns the current magnitude of the audio in each of `BINS` frequency bins, from
+    /// lowest to highest frequency (each 0.0 to 1.0).
+    ///
+    /// The source decides how to group its frequency analysis into the requested number of
+    /// bins.
+    ///
+    /// The default implementation is for sources without frequency analysis, and returns
+    /// the overall level in every bin.
+    fn spectrum<const BINS: usize>(&mut self) -> [f32; BINS] {
+        [self.level(); BINS]
+    }
+}
+
+/// An audio source which plays back scripted levels, one per call to
+/// [`AudioSource::level`].
+///
+/// After the last level, the script repeats from the start.
+///
+/// # Example
+///
+/// ```
+/// use blinksy::audio::{AudioSource, ScriptedAudio};
+///
+/// let mut audio = ScriptedAudio::new(&[0.2, 0.8]);
+/// assert_eq!(audio.level(), 0.2);
+/// assert_eq!(audio.level(), 0.8);
+/// assert_eq!(audio.level(), 0.2);
+/// ```
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct ScriptedAudio<'a> {
+    /// The scripted levels
+    levels: &'a [f32],
+    /// Index of the next level
+    index: usize,
+}
+
+impl<'a> ScriptedAudio<'a> {
+    /// Creates a new scripted audio source.
+    ///
+    /// # Arguments
+    ///
+    /// - `levels` - The levels to play back, in order
+    pub fn new(levels: &'a [f32]) -> Self {
+        Self { levels, index: 0 }
+    }
+}
+
+impl AudioSource for ScriptedAudio<'_> {
+    fn level(&mut self) -> f32 {
+        let Some(&level) = self.levels.get(self.index) else {
+            return 0.;
+        };
+        self.index = (self.index + 1) % self.levels.len();
+        level
+    }
+}
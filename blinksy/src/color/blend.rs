@@ -0,0 +1,184 @@
+//! # Color Blending
+//!
+//! Shared primitives to interpolate and composite colors, for gradients, crossfades, and
+//! layered patterns.
+//!
+//! - [`lerp`] - Interpolate in [`LinearSrgb`], physically mixing the light
+//! - [`lerp_oklab`] - Interpolate in [`Oklab`], perceptually even
+//! - [`add`], [`multiply`], [`screen`] - Blend modes, per channel in [`LinearSrgb`]
+//!
+//! ## Example
+//!
+//! ```
+//! use blinksy::color::{blend, LinearSrgb};
+//!
+//! let red = LinearSrgb::new(1., 0., 0.);
+//! let blue = LinearSrgb::new(0., 0., 1.);
+//!
+//! assert_eq!(blend::lerp(red, blue, 0.5), LinearSrgb::new(0.5, 0., 0.5));
+//! assert_eq!(blend::add(red, blue), LinearSrgb::new(1., 0., 1.));
+//! ```
+
+use super::{LinearSrgb, Oklab};
+
+/// Linearly interpolates from `a` to `b`, in [`LinearSrgb`].
+///
+/// Interpolating in linear light mixes colors as overlapping lights do, so a crossfade
+/// keeps a constant light output.
+///
+/// # Arguments
+///
+/// - `a` - The color at `t` = 0.0
+/// - `b` - The color at `t` = 1.0
+/// - `t` - How far from `a` to `b` (not clamped, so extrapolates outside 0.0 to 1.0)
+#[inline]
+pub fn lerp(a: LinearSrgb, b: LinearSrgb, t: f32) -> LinearSrgb {
+    // Unclamped arithmetic, so `t` outside 0.0 to 1.0 extrapolates.
+    a + (b - a) * t
+}
+
+/// Linearly interpolates from `a` to `b`, in [`Oklab`].
+///
+/// Interpolating in a perceptually uniform space looks even to the eye: the midpoint of
+/// a gradient looks halfway between the ends, without the dark or muddy middles of
+/// interpolating in RGB.
+///
+/// # Arguments
+///
+/// - `a` - The color at `t` = 0.0
+/// - `b` - The color at `t` = 1.0
+/// - `t` - How far from `a` to `b` (not clamped, so extrapolates outside 0.0 to 1.0 in
+///   Oklab, though the result is clamped to 0.0 to 1.0 in [`LinearSrgb`])
+#[inline]
+pub fn lerp_oklab(a: LinearSrgb, b: LinearSrgb, t: f32) -> LinearSrgb {
+    let a = Oklab::from_linear_srgb(a);
+    let b = Oklab::from_linear_srgb(b);
+    Oklab::new(
+        a.l + (b.l - a.l) * t,
+        a.a + (b.a - a.a) * t,
+        a.b + (b.b - a.b) * t,
+    )
+    .to_linear_srgb()
+}
+
+/// Adds the colors, like overlapping lights: `a + b`.
+///
+/// Each channel is clamped to at most 1.0, so bright overlaps saturate rather than
+/// overflow.
+#[inline]
+pub fn add(a: LinearSrgb, b: LinearSrgb) -> LinearSrgb {
+    a.saturating_add(b)
+}
+
+/// Multiplies the colors, like a mask: `a * b`.
+#[inline]
+pub fn multiply(a: LinearSrgb, b: LinearSrgb) -> LinearSrgb {
+    LinearSrgb::new(a.red * b.red, a.green * b.green, a.blue * b.blue)
+}
+
+/// Inverts, multiplies, and inverts again, a softer add: `1 - (1 - a) * (1 - b)`.
+///
+/// Each channel of both colors is clamped to between 0.0 and 1.0 first, so the result is
+/// also between 0.0 and 1.0.
+#[inline]
+pub fn screen(a: LinearSrgb, b: LinearSrgb) -> LinearSrgb {
+    let channel = |a: f32, b: f32| 1. - (1. - a.clamp(0., 1.)) * (1. - b.clamp(0., 1.));
+    LinearSrgb::new(
+        channel(a.red, b.red),
+        channel(a.green, b.green),
+        channel(a.blue, b.blue),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const BLACK: LinearSrgb = LinearSrgb {
+        red: 0.,
+        green: 0.,
+        blue: 0.,
+    };
+    const RED: LinearSrgb = LinearSrgb {
+        red: 1.,
+        green: 0.,
+        blue: 0.,
+    };
+    const BLUE: LinearSrgb = LinearSrgb {
+        red: 0.,
+        green: 0.,
+        blue: 1.,
+    };
+    const WHITE: LinearSrgb = LinearSrgb {
+        red: 1.,
+        green: 1.,
+        blue: 1.,
+    };
+
+    fn assert_close(actual: LinearSrgb, expected: LinearSrgb) {
+        let close = |a: f32, b: f32| (a - b).abs() < 1e-4;
+        assert!(
+            close(actual.red, expected.red)
+                && close(actual.green, expected.green)
+                && close(actual.blue, expected.blue),
+            "{actual:?} != {expected:?}"
+        );
+    }
+
+    #[test]
+    fn test_lerp() {
+        assert_eq!(lerp(RED, BLUE, 0.), RED);
+        assert_eq!(lerp(RED, BLUE, 1.), BLUE);
+        assert_eq!(lerp(RED, BLUE, 0.5), LinearSrgb::new(0.5, 0., 0.5));
+        assert_eq!(lerp(BLACK, WHITE, 0.25), LinearSrgb::new(0.25, 0.25, 0.25));
+
+        // Extrapolates outside 0.0 to 1.0.
+        let gray = LinearSrgb::new(0.5, 0.5, 0.5);
+        assert_close(
+            lerp(BLACK, gray, 1.5),
+            LinearSrgb {
+                red: 0.75,
+                green: 0.75,
+                blue: 0.75,
+            },
+        );
+        assert_close(
+            lerp(gray, WHITE, -2.),
+            LinearSrgb {
+                red: -0.5,
+                green: -0.5,
+                blue: -0.5,
+            },
+        );
+    }
+
+    #[test]
+    fn test_lerp_oklab() {
+        assert_close(lerp_oklab(RED, BLUE, 0.), RED);
+        assert_close(lerp_oklab(RED, BLUE, 1.), BLUE);
+
+        // The midpoint is halfway in Oklab.
+        let (red, blue) = (Oklab::from_linear_srgb(RED), Oklab::from_linear_srgb(BLUE));
+        let midpoint = Oklab::from_linear_srgb(lerp_oklab(RED, BLUE, 0.5));
+        assert!((midpoint.l - (red.l + blue.l) / 2.).abs() < 1e-4);
+        assert!((midpoint.a - (red.a + blue.a) / 2.).abs() < 1e-4);
+        assert!((midpoint.b - (red.b + blue.b) / 2.).abs() < 1e-4);
+
+        // Perceptually halfway from black to white is an eighth of the light.
+        assert_close(
+            lerp_oklab(BLACK, WHITE, 0.5),
+            LinearSrgb::new(0.125, 0.125, 0.125),
+        );
+    }
+
+    #[test]
+    fn test_blend_modes() {
+        let gray = LinearSrgb::new(0.5, 0.5, 0.5);
+        assert_eq!(add(RED, BLUE), LinearSrgb::new(1., 0., 1.));
+        assert_eq!(add(WHITE, gray), WHITE);
+        assert_eq!(multiply(WHITE, gray), gray);
+        assert_eq!(multiply(RED, BLUE), BLACK);
+        assert_eq!(screen(gray, gray), LinearSrgb::new(0.75, 0.75, 0.75));
+        assert_eq!(screen(BLACK, RED), RED);
+    }
+}
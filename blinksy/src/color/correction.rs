@@ -1,12 +1,26 @@
 #[allow(unused_imports)]
 use num_traits::Float;
 
+use super::{
+    temperature::{self, kelvin_to_srgb},
+    LinearSrgb, Srgb, WhiteMode,
+};
+
 /// Defines color correction factors for LED hardware.
 ///
 /// `ColorCorrection` contains scaling factors for each RGB component to
 /// compensate for differences in LED brightness and color balance.
 /// This allows for more accurate color reproduction on specific LED hardware.
 ///
+/// It also contains a global scaling factor, and a gamma for each RGB component to
+/// compensate for LEDs whose output is not linear to their input. Each component is
+/// corrected as `value.powf(gamma) * scale * global`.
+///
+/// The default is the identity: every factor and gamma is 1.0.
+///
+/// For RGBW LEDs, it also contains how to extract the white channel from RGB colors, as a
+/// [`WhiteMode`].
+///
 /// # When to Use
 ///
 /// Use `ColorCorrection` when:
@@ -16,6 +30,7 @@ use num_traits::Float;
 /// - Compensating for RGB LED intensity differences
 #[derive(Debug, Copy, Clone, PartialEq)]
 #[cfg_attr(feature = "defmt", derive(defmt::Format))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct ColorCorrection {
     /// Scaling factor for red channel
     pub red: f32,
@@ -23,6 +38,16 @@ pub struct ColorCorrection {
     pub green: f32,
     /// Scaling factor for blue channel
     pub blue: f32,
+    /// Scaling factor for every channel (including the white channel of RGBW LEDs)
+    pub global: f32,
+    /// Gamma exponent for red channel
+    pub gamma_red: f32,
+    /// Gamma exponent for green channel
+    pub gamma_green: f32,
+    /// Gamma exponent for blue channel
+    pub gamma_blue: f32,
+    /// How to extract the white channel of RGBW LEDs
+    pub white_mode: WhiteMode,
 }
 
 impl ColorCorrection {
@@ -51,6 +76,183 @@ impl ColorCorrection {
             red: red.max(0.0),
             green: green.max(0.0),
             blue: blue.max(0.0),
+            global: 1.0,
+            gamma_red: 1.0,
+            gamma_green: 1.0,
+            gamma_blue: 1.0,
+            white_mode: WhiteMode::default(),
+        }
+    }
+
+    /// Returns the correction with a global scaling factor, for every channel.
+    ///
+    /// # Arguments
+    ///
+    /// - `global` - Scaling factor for every channel
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use blinksy::color::ColorCorrection;
+    ///
+    /// // Strip LEDs at 80% of their full output
+    /// let correction = ColorCorrection::typical_led_strip().with_global(0.8);
+    /// ```
+    pub fn with_global(self, global: f32) -> Self {
+        Self {
+            global: global.max(0.0),
+            ..self
+        }
+    }
+
+    /// Returns the correction with the same gamma exponent for every RGB channel.
+    ///
+    /// # Arguments
+    ///
+    /// - `gamma` - Gamma exponent for every RGB channel
+    pub fn with_gamma(self, gamma: f32) -> Self {
+        self.with_channel_gamma(gamma, gamma, gamma)
+    }
+
+    /// Returns the correction with a gamma exponent for each RGB channel.
+    ///
+    /// # Arguments
+    ///
+    /// - `red` - Gamma exponent for the red channel
+    /// - `green` - Gamma exponent for the green channel
+    /// - `blue` - Gamma exponent for the blue channel
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use blinksy::color::ColorCorrection;
+    ///
+    /// // LEDs whose blue channel is too bright at low levels
+    /// let correction = ColorCorrection::default().with_channel_gamma(1.0, 1.0, 1.2);
+    /// ```
+    pub fn with_channel_gamma(self, red: f32, green: f32, blue: f32) -> Self {
+        Self {
+            gamma_red: red.max(0.0),
+            gamma_green: green.max(0.0),
+            gamma_blue: blue.max(0.0),
+            ..self
+        }
+    }
+
+    /// Returns the correction with a way to extract the white channel of RGBW LEDs.
+    ///
+    /// # Arguments
+    ///
+    /// - `white_mode` - How to extract the white channel
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use blinksy::color::{temperature, ColorCorrection, WhiteMode};
+    ///
+    /// // RGBW LEDs with a warm white LED
+    /// let correction =
+    ///     ColorCorrection::default().with_white_mode(WhiteMode::accurate(temperature::TUNGSTEN));
+    /// ```
+    pub fn with_white_mode(self, white_mode: WhiteMode) -> Self {
+        Self { white_mode, ..self }
+    }
+
+    /// Applies the correction to linear RGB components.
+    ///
+    /// # Arguments
+    ///
+    /// - `red` - Red component
+    /// - `green` - Green component
+    /// - `blue` - Blue component
+    ///
+    /// # Returns
+    ///
+    /// The corrected `(red, green, blue)` components.
+    #[inline]
+    pub fn apply(&self, red: f32, green: f32, blue: f32) -> (f32, f32, f32) {
+        (
+            correct(red, self.gamma_red, self.red * self.global),
+            correct(green, self.gamma_green, self.green * self.global),
+            correct(blue, self.gamma_blue, self.blue * self.global),
+        )
+    }
+
+    /// No correction: every channel at full scale.
+    ///
+    /// Factors: `(1.0, 1.0, 1.0)`, same as FastLED's `UncorrectedColor` (`0xFFFFFF`).
+    pub fn uncorrected() -> Self {
+        Self::new(1.0, 1.0, 1.0)
+    }
+
+    /// Correction for typical LED strips (such as WS2812B strips).
+    ///
+    /// Factors: `(255, 176, 240) / 255`, same as FastLED's `TypicalLEDStrip` (`0xFFB0F0`).
+    pub fn typical_led_strip() -> Self {
+        Self::new(1.0, 176.0 / 255.0, 240.0 / 255.0)
+    }
+
+    /// Correction for typical SMD5050 LEDs.
+    ///
+    /// Factors: `(255, 176, 240) / 255`, same as FastLED's `TypicalSMD5050` (`0xFFB0F0`).
+    pub fn typical_smd5050() -> Self {
+        Self::new(1.0, 176.0 / 255.0, 240.0 / 255.0)
+    }
+
+    /// Correction for typical 8mm "pixels" (through-hole LEDs), and pixel strings.
+    ///
+    /// Factors: `(255, 224, 140) / 255`, same as FastLED's `Typical8mmPixel` (`0xFFE08C`).
+    pub fn typical_8mm_pixel() -> Self {
+        Self::new(1.0, 224.0 / 255.0, 140.0 / 255.0)
+    }
+
+    /// Creates a color correction which shifts the white point to a color temperature.
+    ///
+    /// The factors are the linear color of a black-body radiator at the color temperature
+    /// (see [`temperature::from_kelvin`]), so full white is output as that color.
+    ///
+    /// Unlike [`ColorCorrection::from_temperature`], which uses the gamma-encoded color as
+    /// factors (like FastLED's color temperature presets), this gives physically correct
+    /// factors, since correction is applied to linear values.
+    ///
+    /// # Arguments
+    ///
+    /// - `kelvin` - Color temperature in Kelvin, clamped to 1000K to 12000K
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use blinksy::color::{temperature, ColorCorrection};
+    ///
+    /// let candle = ColorCorrection::from_kelvin(temperature::CANDLE);
+    /// ```
+    pub fn from_kelvin(kelvin: f32) -> Self {
+        let LinearSrgb { red, green, blue } = temperature::from_kelvin(kelvin);
+        Self::new(red, green, blue)
+    }
+
+    /// Returns the correction tinted toward a white point: each RGB factor multiplied by the
+    /// linear color of a black-body radiator at the color temperature (see
+    /// [`temperature::from_kelvin`]).
+    ///
+    /// # Arguments
+    ///
+    /// - `kelvin` - Color temperature in Kelvin, clamped to 1000K to 12000K
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use blinksy::color::{temperature, ColorCorrection};
+    ///
+    /// let correction = ColorCorrection::typical_led_strip().with_white_point(temperature::TUNGSTEN);
+    /// ```
+    pub fn with_white_point(self, kelvin: f32) -> Self {
+        let LinearSrgb { red, green, blue } = temperature::from_kelvin(kelvin);
+        Self {
+            red: self.red * red,
+            green: self.green * green,
+            blue: self.blue * blue,
+            ..self
         }
     }
 
@@ -82,31 +284,9 @@ impl ColorCorrection {
         // Clamp to a reasonable range for the approximation.
         let k = kelvin.clamp(1000, 40000) as f32;
 
-        // Use Tanner Helland's approximation (T in hundreds of Kelvin):
-        // https://tannerhelland.com/2012/09/18/convert-temperature-rgb-algorithm-code.html
-        let t = k / 100.0;
-
-        let r = if t <= 66.0 {
-            255.0
-        } else {
-            (329.698_73 * (t - 60.0).powf(-0.133_204_76)).clamp(0.0, 255.0)
-        };
-
-        let g = if t <= 66.0 {
-            (99.470_8 * t.ln() - 161.119_57).clamp(0.0, 255.0)
-        } else {
-            (288.122_16 * (t - 60.0).powf(-0.075_514_846)).clamp(0.0, 255.0)
-        };
-
-        let b = if t >= 66.0 {
-            255.0
-        } else if t <= 19.0 {
-            0.0
-        } else {
-            (138.517_73 * (t - 10.0).ln() - 305.044_8).clamp(0.0, 255.0)
-        };
+        let Srgb { red, green, blue } = kelvin_to_srgb(k);
 
-        ColorCorrection::new(r / 255.0, g / 255.0, b / 255.0)
+        ColorCorrection::new(red, green, blue)
     }
 }
 
@@ -116,6 +296,41 @@ impl Default for ColorCorrection {
             red: 1.,
             green: 1.,
             blue: 1.,
+            global: 1.,
+            gamma_red: 1.,
+            gamma_green: 1.,
+            gamma_blue: 1.,
+            white_mode: WhiteMode::default(),
         }
     }
 }
+
+/// Corrects a component with a gamma exponent and a scaling factor.
+#[inline]
+fn correct(value: f32, gamma: f32, scale: f32) -> f32 {
+    // Skip `powf` for the common case of no gamma.
+    if gamma == 1.0 {
+        value * scale
+    } else {
+        value.max(0.0).powf(gamma) * scale
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_default_is_identity() {
+        let correction = ColorCorrection::default();
+        assert_eq!(correction.apply(0.1, 0.5, 0.9), (0.1, 0.5, 0.9));
+    }
+
+    #[test]
+    fn test_global_and_gamma() {
+        let correction = ColorCorrection::new(1.0, 0.5, 1.0)
+            .with_global(0.5)
+            .with_channel_gamma(2.0, 1.0, 1.0);
+        assert_eq!(correction.apply(0.5, 0.5, 0.5), (0.125, 0.125, 0.25));
+    }
+}
@@ -0,0 +1,360 @@
+//! # Fixed-Point Colors
+//!
+//! Color types in fixed point, for storing colors as integers, and converting between
+//! them with integer math: HSV to RGB, scaling, and gamma (via [`GammaLut256`]).
+//!
+//! Each component is a fraction in unsigned Q0.16 format: a `u16` where `0` is 0.0 and
+//! `u16::MAX` is 1.0. The conversions are integer multiplies and shifts, with no division.
+//!
+//! - [`LinearSrgbFixed`]: Mirrors [`LinearSrgb`]
+//! - [`HsvFixed`]: Mirrors [`Hsv`](super::Hsv), with the rainbow hue map
+//!
+//! Requires the `fixed-point` feature.
+//!
+//! ## Opting In
+//!
+//! A pattern opts in by using a fixed-point color as its `Pattern::Color`. Through
+//! [`Control`](crate::control::Control), the colors are converted to `f32` [`LinearSrgb`]
+//! once per pixel, and the rest of the pipeline (brightness, color correction, and encoding
+//! to words) runs in `f32`.
+//!
+//! To encode a frame without `f32` math, such as on a microcontroller without a floating
+//! point unit, skip [`Control`](crate::control::Control): encode the colors with
+//! [`ClocklessDriver::encode_fixed`](crate::driver::ClocklessDriver::encode_fixed), which
+//! scales the brightness and applies any [`GammaLut256`] in integer math, then write the
+//! frame to the driver. Color correction and dithering aren't supported in fixed point.
+//!
+//! ## Precision
+//!
+//! Compared to the `f32` pipeline, each component of [`HsvFixed`] to [`LinearSrgbFixed`]
+//! is within `1e-3` (about a quarter of an 8-bit step).
+//!
+//! ## Example
+//!
+//! ```rust
+//! use blinksy::color::{
+//!     fixed::{HsvFixed, LinearSrgbFixed},
+//!     FromColor,
+//! };
+//!
+//! let yellow = LinearSrgbFixed::from_color(HsvFixed::new(0x4000, u16::MAX, u16::MAX));
+//! let dim_yellow = yellow.scale(u16::MAX / 2);
+//! assert_eq!(dim_yellow.to_rgb8(), [85, 85, 0]);
+//! ```
+
+use super::{FromColor, GammaLut256, LedChannels, LedColor, LedRgb, LedRgbw, LinearSrgb};
+
+/// One third, in Q0.16.
+const FRAC_1_3: u16 = u16::MAX / 3;
+
+/// Two thirds, in Q0.16.
+const FRAC_2_3: u16 = u16::MAX / 3 * 2;
+
+/// Multiplies two Q0.16 fractions.
+#[inline]
+const fn mul(a: u16, b: u16) -> u16 {
+    let product = a as u32 * b as u32;
+    // Dividing by u16::MAX, as a multiply by (1 + 2^-16) and a shift, rounded.
+    ((product + (product >> 16) + 0x8000) >> 16) as u16
+}
+
+/// Converts a normalized `f32` (0.0 to 1.0) to a Q0.16 fraction.
+#[inline]
+fn from_f32(value: f32) -> u16 {
+    (value.clamp(0., 1.) * u16::MAX as f32 + 0.5) as u16
+}
+
+/// Converts a Q0.16 fraction to a normalized `f32` (0.0 to 1.0).
+#[inline]
+fn to_f32(value: u16) -> f32 {
+    value as f32 / u16::MAX as f32
+}
+
+/// # Fixed-Point Linear RGB Color
+///
+/// `LinearSrgbFixed` mirrors [`LinearSrgb`], with each component in Q0.16 fixed point.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct LinearSrgbFixed {
+    /// Red component (0 to `u16::MAX`)
+    pub red: u16,
+    /// Green component (0 to `u16::MAX`)
+    pub green: u16,
+    /// Blue component (0 to `u16::MAX`)
+    pub blue: u16,
+}
+
+impl LinearSrgbFixed {
+    /// Creates a new LinearSrgbFixed color
+    ///
+    /// # Arguments
+    ///
+    /// - `red` - Red component (0 to `u16::MAX`)
+    /// - `green` - Green component (0 to `u16::MAX`)
+    /// - `blue` - Blue component (0 to `u16::MAX`)
+    pub const fn new(red: u16, green: u16, blue: u16) -> Self {
+        Self { red, green, blue }
+    }
+
+    /// Scales each component by a brightness.
+    ///
+    /// # Arguments
+    ///
+    /// - `brightness` - Brightness (0 to `u16::MAX`)
+    pub const fn scale(self, brightness: u16) -> Self {
+        Self {
+            red: mul(self.red, brightness),
+            green: mul(self.green, brightness),
+            blue: mul(self.blue, brightness),
+        }
+    }
+
+    /// Converts to 8-bit components.
+    pub const fn to_rgb8(self) -> [u8; 3] {
+        [
+            (self.red >> 8) as u8,
+            (self.green >> 8) as u8,
+            (self.blue >> 8) as u8,
+        ]
+    }
+
+    /// Converts to 8-bit components, mapped through a gamma lookup table.
+    ///
+    /// # Arguments
+    ///
+    /// - `lut` - The gamma lookup table
+    pub fn to_rgb8_with_lut(self, lut: &GammaLut256) -> [u8; 3] {
+        self.to_rgb8().map(|value| lut.apply(value))
+    }
+
+    /// Converts to 8-bit LED output values, in integer math.
+    ///
+    /// Scales each channel by a brightness, then maps it through a gamma lookup table, if
+    /// any. For RGBW LEDs, the white channel is the minimum of the RGB channels, as with
+    /// [`WhiteMode::Min`](super::WhiteMode::Min).
+    ///
+    /// # Arguments
+    ///
+    /// - `channels` - The LED channel format specification
+    /// - `brightness` - Brightness (0 to `u16::MAX`)
+    /// - `lut` - The gamma lookup table, if any
+    pub fn to_led(
+        self,
+        channels: LedChannels,
+        brightness: u16,
+        lut: Option<&GammaLut256>,
+    ) -> LedColor<u8> {
+        let channel = |value: u16| {
+            let value = (mul(value, brightness) >> 8) as u8;
+            match lut {
+                Some(lut) => lut.apply(value),
+                None => value,
+            }
+        };
+        let Self { red, green, blue } = self;
+        match channels {
+            LedChannels::Rgb(rgb_channels) => {
+                let rgb = LedRgb::new([channel(red), channel(green), channel(blue)]);
+                LedColor::Rgb(rgb.reorder(rgb_channels))
+            }
+            LedChannels::Rgbw(rgbw_channels) => {
+                let white = red.min(green).min(blue);
+                let rgbw = LedRgbw::new([
+                    channel(red - white),
+                    channel(green - white),
+                    channel(blue - white),
+                    channel(white),
+                ]);
+                LedColor::Rgbw(rgbw.reorder(rgbw_channels))
+            }
+        }
+    }
+}
+
+impl FromColor<LinearSrgb> for LinearSrgbFixed {
+    fn from_color(color: LinearSrgb) -> Self {
+        Self::new(
+            from_f32(color.red),
+            from_f32(color.green),
+            from_f32(color.blue),
+        )
+    }
+}
+
+impl FromColor<LinearSrgbFixed> for LinearSrgb {
+    fn from_color(color: LinearSrgbFixed) -> Self {
+        LinearSrgb::new(to_f32(color.red), to_f32(color.green), to_f32(color.blue))
+    }
+}
+
+/// # Fixed-Point HSV Color
+///
+/// `HsvFixed` mirrors [`Hsv`](super::Hsv) with the rainbow hue map
+/// ([`HsvHueRainbow`](super::HsvHueRainbow)), with each component in fixed point.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct HsvFixed {
+    /// Hue, as a fraction of a full turn (wraps around at `u16::MAX + 1`)
+    pub hue: u16,
+    /// Saturation component (0 to `u16::MAX`)
+    pub saturation: u16,
+    /// Value component (0 to `u16::MAX`)
+    pub value: u16,
+}
+
+impl HsvFixed {
+    /// Creates a new HsvFixed color
+    ///
+    /// # Arguments
+    ///
+    /// - `hue` - Hue, as a fraction of a full turn (wraps around at `u16::MAX + 1`)
+    /// - `saturation` - Saturation component (0 to `u16::MAX`)
+    /// - `value` - Value component (0 to `u16::MAX`)
+    pub const fn new(hue: u16, saturation: u16, value: u16) -> Self {
+        Self {
+            hue,
+            saturation,
+            value,
+        }
+    }
+
+    /// Converts the hue to a fully saturated RGB color, with the rainbow hue map.
+    fn hue_to_rgb(hue: u16) -> LinearSrgbFixed {
+        const MAX: u16 = u16::MAX;
+
+        let h8 = hue as u32 * 8;
+        let section = h8 >> 16; // 0-7
+        let pos = (h8 & 0xFFFF) as u16; // Position within section
+
+        let (red, green, blue) = match section {
+            // Red to Orange
+            0 => (MAX - mul(pos, FRAC_1_3), mul(pos, FRAC_1_3), 0),
+            // Orange to Yellow
+            1 => (FRAC_2_3, FRAC_1_3 + mul(pos, FRAC_1_3), 0),
+            // Yellow to Green
+            2 => (mul(FRAC_2_3, MAX - pos), FRAC_2_3 + mul(pos, FRAC_1_3), 0),
+            // Green to Aqua
+            3 => (0, MAX - mul(pos, FRAC_1_3), mul(pos, FRAC_1_3)),
+            // Aqua to Blue
+            4 => (0, mul(FRAC_2_3, MAX - pos), FRAC_1_3 + mul(pos, FRAC_2_3)),
+            // Blue to Purple
+            5 => (mul(pos, FRAC_1_3), 0, MAX - mul(pos, FRAC_1_3)),
+            // Purple to Pink
+            6 => (
+                FRAC_1_3 + mul(pos, FRAC_1_3),
+                0,
+                FRAC_2_3 - mul(pos, FRAC_1_3),
+            ),
+            // Pink to Red
+            _ => (FRAC_2_3 + mul(pos, FRAC_1_3), 0, mul(FRAC_1_3, MAX - pos)),
+        };
+        LinearSrgbFixed::new(red, green, blue)
+    }
+}
+
+impl FromColor<HsvFixed> for LinearSrgbFixed {
+    fn from_color(color: HsvFixed) -> Self {
+        let HsvFixed {
+            hue,
+            saturation,
+            value,
+        } = color;
+        let rgb = HsvFixed::hue_to_rgb(hue);
+
+        // Blend the pure hue with gray, by saturation, then scale by value.
+        let gray = mul(value, u16::MAX - saturation);
+        let channel = |component: u16| mul(mul(component, saturation), value).saturating_add(gray);
+        Self::new(channel(rgb.red), channel(rgb.green), channel(rgb.blue))
+    }
+}
+
+impl FromColor<HsvFixed> for LinearSrgb {
+    fn from_color(color: HsvFixed) -> Self {
+        LinearSrgb::from_color(LinearSrgbFixed::from_color(color))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::color::RainbowHsv;
+
+    #[test]
+    fn test_mul() {
+        assert_eq!(mul(u16::MAX, u16::MAX), u16::MAX);
+        assert_eq!(mul(u16::MAX, 1234), 1234);
+        assert_eq!(mul(0, u16::MAX), 0);
+        assert_eq!(mul(u16::MAX / 2, u16::MAX / 2), 16383);
+    }
+
+    #[test]
+    fn test_matches_float() {
+        const TOLERANCE: f32 = 1e-3;
+
+        for hue in (0..=u16::MAX).step_by(97) {
+            for saturation in [0, 0x4000, 0xC000, u16::MAX] {
+                for value in [0, 0x1000, 0x8000, u16::MAX] {
+                    let fixed = LinearSrgb::from_color(HsvFixed::new(hue, saturation, value));
+                    let float = LinearSrgb::from_color(RainbowHsv::new(
+                        hue as f32 / 65536.,
+                        to_f32(saturation),
+                        to_f32(value),
+                    ));
+                    for (fixed, float) in [
+                        (fixed.red, float.red),
+                        (fixed.green, float.green),
+                        (fixed.blue, float.blue),
+                    ] {
+                        assert!(
+                            (fixed - float).abs() < TOLERANCE,
+                            "hue {hue}, saturation {saturation}, value {value}: {fixed} != {float}"
+                        );
+                    }
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_scale_and_lut() {
+        let white = LinearSrgbFixed::new(u16::MAX, u16::MAX, u16::MAX);
+        assert_eq!(white.scale(u16::MAX).to_rgb8(), [255; 3]);
+        assert_eq!(white.scale(0).to_rgb8(), [0; 3]);
+
+        let lut = GammaLut256::new(2.);
+        assert_eq!(
+            white.scale(u16::MAX / 2).to_rgb8_with_lut(&lut),
+            [lut.apply(127); 3]
+        );
+    }
+
+    #[test]
+    fn test_to_led_matches_float() {
+        use crate::color::{ColorCorrection, RgbChannels, RgbwChannels};
+
+        let lut = GammaLut256::new(2.2);
+        for channels in [
+            LedChannels::Rgb(RgbChannels::GRB),
+            LedChannels::Rgbw(RgbwChannels::RGBW),
+        ] {
+            for hue in (0..=u16::MAX).step_by(997) {
+                let color = LinearSrgbFixed::from_color(HsvFixed::new(hue, 0xC000, 0xE000));
+                let fixed = color.to_led(channels, 0x8000, None);
+                let float: LedColor<u8> = LinearSrgb::from_color(color).to_led(
+                    channels,
+                    to_f32(0x8000),
+                    ColorCorrection::default(),
+                );
+                // Within one 8-bit step, as the fixed-point words are rounded down.
+                for (fixed, float) in fixed.as_ref().iter().zip(float.as_ref()) {
+                    assert!(fixed.abs_diff(*float) <= 1, "hue {hue}: {fixed} != {float}");
+                }
+
+                let with_lut = color.to_led(channels, 0x8000, Some(&lut));
+                for (with_lut, fixed) in with_lut.as_ref().iter().zip(fixed.as_ref()) {
+                    assert_eq!(*with_lut, lut.apply(*fixed));
+                }
+            }
+        }
+    }
+}
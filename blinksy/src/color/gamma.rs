@@ -0,0 +1,145 @@
+#[allow(unused_imports)]
+use num_traits::Float;
+
+use super::srgb::{srgb_decode, srgb_encode};
+
+/// # Gamma Lookup Table
+///
+/// `GammaLut256` is a precomputed table which maps every 8-bit value through a transfer
+/// function, so applying the function is a single lookup instead of a `powf` per channel.
+///
+/// `powf` is expensive on microcontrollers without a floating point unit, where each call
+/// is emulated in software. The table costs 512 bytes, and the `powf` calls are only made
+/// once, when creating the table.
+///
+/// Each output is stored at 16-bit precision, so a normalized value (see
+/// [`GammaLut256::apply_normalized`]) can be mapped with the fraction of a word kept, such
+/// as for dithering.
+///
+/// To apply a table to the output of a clockless driver, see
+/// [`ClocklessDriver::with_gamma_lut`](crate::driver::ClocklessDriver::with_gamma_lut).
+///
+/// ## Performance
+///
+/// Measured with `cargo bench -p blinksy --bench gamma` on a desktop (x86_64, release
+/// build), mapping 8-bit values with a gamma of 2.2:
+///
+/// - `powf` (from `libm`, as used in `no_std`): ~66 ns per value
+/// - `GammaLut256::apply`: ~0.6 ns per value
+///
+/// So about 100x faster, and the difference is even larger on microcontrollers without a
+/// floating point unit.
+///
+/// ## Example
+///
+/// ```
+/// use blinksy::color::GammaLut256;
+///
+/// // Decode 8-bit sRGB values to 8-bit linear values
+/// let lut = GammaLut256::srgb_decode();
+/// assert_eq!(lut.apply(0), 0);
+/// assert_eq!(lut.apply(128), 55);
+/// assert_eq!(lut.apply(255), 255);
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct GammaLut256 {
+    /// Output value for each 8-bit input value, at 16-bit precision
+    table: [u16; 256],
+}
+
+impl GammaLut256 {
+    /// Creates a table for a power-law gamma: `output = input^gamma`.
+    ///
+    /// A gamma above 1.0 decodes (darkens midtones), a gamma below 1.0 encodes (brightens
+    /// midtones).
+    ///
+    /// # Arguments
+    ///
+    /// - `gamma` - Gamma exponent
+    pub fn new(gamma: f32) -> Self {
+        Self::from_fn(|value| value.powf(gamma))
+    }
+
+    /// Creates a table which decodes sRGB values to linear values, with the standard
+    /// piecewise sRGB transfer function.
+    pub fn srgb_decode() -> Self {
+        Self::from_fn(srgb_decode)
+    }
+
+    /// Creates a table which encodes linear values to sRGB values, with the standard
+    /// piecewise sRGB transfer function.
+    pub fn srgb_encode() -> Self {
+        Self::from_fn(srgb_encode)
+    }
+
+    /// Creates a table from a transfer function of normalized values (0.0 to 1.0).
+    ///
+    /// # Arguments
+    ///
+    /// - `f` - Transfer function
+    pub fn from_fn(f: impl Fn(f32) -> f32) -> Self {
+        Self {
+            table: core::array::from_fn(|index| {
+                let value = f(index as f32 / 255.);
+                (value.clamp(0., 1.) * 65535.).round() as u16
+            }),
+        }
+    }
+
+    /// Maps a value through the table.
+    #[inline]
+    pub fn apply(&self, value: u8) -> u8 {
+        let output = self.table[value as usize] as u32;
+        ((output * 255 + 32767) / 65535) as u8
+    }
+
+    /// Maps a normalized value (0.0 to 1.0) through the table, interpolating between
+    /// entries.
+    ///
+    /// Unlike [`GammaLut256::apply`], the output isn't rounded to 8 bits, so it can be
+    /// quantized afterwards, such as with dithering.
+    #[inline]
+    pub fn apply_normalized(&self, value: f32) -> f32 {
+        let position = value.clamp(0., 1.) * 255.;
+        let index = (position as usize).min(254);
+        let fraction = position - index as f32;
+        let low = self.table[index] as f32;
+        let high = self.table[index + 1] as f32;
+        (low + (high - low) * fraction) / 65535.
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_matches_powf() {
+        let lut = GammaLut256::new(2.2);
+        for value in 0..=255u8 {
+            let expected = ((value as f32 / 255.).powf(2.2) * 255.).round() as u8;
+            assert_eq!(lut.apply(value), expected);
+        }
+    }
+
+    #[test]
+    fn test_apply_normalized_matches_powf() {
+        let lut = GammaLut256::new(2.2);
+        for value in [0., 0.01, 0.1, 0.25, 0.5, 0.75, 1.] {
+            let expected = value.powf(2.2);
+            assert!((lut.apply_normalized(value) - expected).abs() < 0.5 / 255.);
+        }
+    }
+
+    #[test]
+    fn test_srgb_round_trip() {
+        let decode = GammaLut256::srgb_decode();
+        let encode = GammaLut256::srgb_encode();
+        for value in [0, 64, 128, 192, 255] {
+            let round_trip = encode.apply(decode.apply(value)) as i16;
+            // Decoding to 8 bits loses precision in the darks.
+            assert!((round_trip - value as i16).abs() <= 8);
+        }
+    }
+}
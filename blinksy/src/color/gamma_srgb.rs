@@ -16,6 +16,9 @@ use num_traits::Float;
 ///
 /// Unlike the standard sRGB transfer function which uses a piecewise curve,
 /// GammaSrgb uses a simple power function: C_gamma = C_linear^(1/gamma)
+///
+/// Note: A gamma of 2.2 only approximates sRGB, so results differ from [`Srgb`](super::Srgb),
+/// especially near black. For standard sRGB colors, always use [`Srgb`](super::Srgb).
 #[derive(Debug, Clone, Copy, PartialEq)]
 #[cfg_attr(feature = "defmt", derive(defmt::Format))]
 pub struct GammaSrgb {
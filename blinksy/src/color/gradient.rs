@@ -0,0 +1,174 @@
+//! # Color Gradients
+//!
+//! [`ColorStops`] is a gradient of any number of colors, each at a position, like the color
+//! stops of a CSS gradient. Sample it at a position to interpolate between the stops on
+//! either side.
+//!
+//! ## Example
+//!
+//! ```
+//! use blinksy::color::{gradient::ColorStops, LinearSrgb};
+//!
+//! let stops = ColorStops::from([
+//!     (0., LinearSrgb::new(1., 0., 0.)),
+//!     (0.5, LinearSrgb::new(0., 1., 0.)),
+//!     (1., LinearSrgb::new(0., 0., 1.)),
+//! ]);
+//!
+//! assert_eq!(stops.sample(0.25), LinearSrgb::new(0.5, 0.5, 0.));
+//! ```
+
+use heapless::Vec;
+
+use super::{blend, LinearSrgb};
+
+/// A gradient of up to `N` colors, each at a position.
+///
+/// The stops are kept sorted by position. Positions are usually from 0.0 to 1.0, but any
+/// range works.
+///
+/// # Type Parameters
+///
+/// - `N` - The maximum number of stops
+#[derive(Debug, Clone, PartialEq)]
+pub struct ColorStops<const N: usize> {
+    /// The stops, sorted by position
+    stops: Vec<(f32, LinearSrgb), N>,
+}
+
+impl<const N: usize> ColorStops<N> {
+    /// Creates a gradient with no stops.
+    pub const fn new() -> Self {
+        Self { stops: Vec::new() }
+    }
+
+    /// Adds a stop, keeping the stops sorted by position.
+    ///
+    /// A stop at the same position as an existing stop is added after it, for a hard
+    /// edge between the two colors.
+    ///
+    /// # Arguments
+    ///
+    /// - `position` - The position of the stop
+    /// - `color` - The color at the position
+    ///
+    /// # Returns
+    ///
+    /// The stop back as an error, if the gradient already has `N` stops.
+    pub fn insert(&mut self, position: f32, color: LinearSrgb) -> Result<(), (f32, LinearSrgb)> {
+        let index = self
+            .stops
+            .iter()
+            .position(|(other, _)| *other > position)
+            .unwrap_or(self.stops.len());
+        self.stops.insert(index, (position, color))
+    }
+
+    /// Returns the stops, sorted by position.
+    pub fn stops(&self) -> &[(f32, LinearSrgb)] {
+        &self.stops
+    }
+
+    /// Returns the color at a position, interpolated (in [`LinearSrgb`]) between the stops
+    /// on either side.
+    ///
+    /// A position before the first stop is the color of the first stop, and a position
+    /// after the last stop is the color of the last stop. With no stops, the color is
+    /// black.
+    ///
+    /// # Arguments
+    ///
+    /// - `t` - The position to sample
+    pub fn sample(&self, t: f32) -> LinearSrgb {
+        let (Some(first), Some(last)) = (self.stops.first(), self.stops.last()) else {
+            return LinearSrgb::new(0., 0., 0.);
+        };
+        if t >= last.0 {
+            return last.1;
+        }
+        if t <= first.0 {
+            return first.1;
+        }
+        for pair in self.stops.windows(2) {
+            let [(start, a), (end, b)] = [pair[0], pair[1]];
+            if t < end {
+                let alpha = (t - start) / (end - start);
+                return blend::lerp(a, b, alpha);
+            }
+        }
+        last.1
+    }
+}
+
+impl<const N: usize> Default for ColorStops<N> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<const N: usize> From<[(f32, LinearSrgb); N]> for ColorStops<N> {
+    /// Creates a gradient from stops in any order.
+    fn from(stops: [(f32, LinearSrgb); N]) -> Self {
+        let mut gradient = Self::new();
+        for (position, color) in stops {
+            // The gradient has room for exactly N stops.
+            let _ = gradient.insert(position, color);
+        }
+        gradient
+    }
+}
+
+#[cfg(feature = "defmt")]
+impl<const N: usize> defmt::Format for ColorStops<N> {
+    fn format(&self, f: defmt::Formatter) {
+        defmt::write!(f, "ColorStops({=[?]})", self.stops.as_slice())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn colors() -> [LinearSrgb; 3] {
+        [
+            LinearSrgb::new(1., 0., 0.),
+            LinearSrgb::new(0., 1., 0.),
+            LinearSrgb::new(0., 0., 1.),
+        ]
+    }
+
+    #[test]
+    fn test_three_stops() {
+        let [red, green, blue] = colors();
+        let stops = ColorStops::from([(1., blue), (0., red), (0.5, green)]);
+        assert_eq!(stops.stops(), [(0., red), (0.5, green), (1., blue)]);
+
+        assert_eq!(stops.sample(0.), red);
+        assert_eq!(stops.sample(0.25), LinearSrgb::new(0.5, 0.5, 0.));
+        assert_eq!(stops.sample(0.5), green);
+        assert_eq!(stops.sample(0.75), LinearSrgb::new(0., 0.5, 0.5));
+        assert_eq!(stops.sample(1.), blue);
+
+        // Clamped beyond the first and last stops.
+        assert_eq!(stops.sample(-1.), red);
+        assert_eq!(stops.sample(2.), blue);
+    }
+
+    #[test]
+    fn test_insert() {
+        let [red, green, blue] = colors();
+        let mut stops = ColorStops::<2>::new();
+        assert_eq!(stops.sample(0.5), LinearSrgb::new(0., 0., 0.));
+
+        stops.insert(0.5, green).unwrap();
+        assert_eq!(stops.sample(0.), green);
+        assert_eq!(stops.sample(1.), green);
+
+        // A hard edge, at the same position.
+        stops.insert(0.5, blue).unwrap();
+        assert_eq!(stops.sample(0.49), green);
+        assert_eq!(stops.sample(0.5), blue);
+
+        assert_eq!(stops.insert(1., red), Err((1., red)));
+    }
+}
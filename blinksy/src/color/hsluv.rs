@@ -0,0 +1,234 @@
+use core::f32::consts::TAU;
+
+use num_traits::Euclid;
+#[allow(unused_imports)]
+use num_traits::Float;
+
+use super::{LinearSrgb, Xyz};
+
+/// # HSLuv Color Space
+///
+/// HSLuv is a human-friendly alternative to HSL, built on CIELUV (through its polar form,
+/// LCh(uv)). Unlike HSV or HSL, changing the hue at a fixed lightness keeps the perceived
+/// brightness even, so hue sweeps look smooth rather than flashing between bright yellows
+/// and dark blues.
+///
+/// For every hue and lightness, the saturation is scaled to the maximum chroma within the
+/// sRGB gamut, so every HSLuv color with components in range is displayable.
+///
+/// - `h`: Hue component (0.0 to 1.0) representing the color's position on the color wheel
+/// - `s`: Saturation component (0.0 to 1.0), as a fraction of the maximum chroma
+/// - `l`: Lightness component (0.0 to 1.0), the CIELUV lightness
+///
+/// ## Color Space Properties
+///
+/// - **White Point**: D65 (6500K), same as sRGB
+///
+/// Reference: <https://www.hsluv.org/>
+#[derive(Debug, Copy, Clone, PartialEq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct Hsluv {
+    /// Hue component [0.0, 1.0] where 0 and 1 both represent the same hue
+    pub h: f32,
+    /// Saturation component [0.0, 1.0]
+    pub s: f32,
+    /// Lightness component [0.0, 1.0]
+    pub l: f32,
+}
+
+/// Reference white (D65) u' chromaticity.
+const REF_U: f32 = 0.197_83;
+
+/// Reference white (D65) v' chromaticity.
+const REF_V: f32 = 0.468_32;
+
+/// CIE constant kappa, (29/3)^3.
+const KAPPA: f32 = 903.296_3;
+
+/// CIE constant epsilon, (6/29)^3.
+const EPSILON: f32 = 0.008_856_452;
+
+/// The XYZ to linear sRGB matrix, used to find the edges of the sRGB gamut.
+const XYZ_TO_LINEAR_SRGB: [[f32; 3]; 3] = [
+    [3.240_97, -1.537_383_2, -0.498_610_76],
+    [-0.969_243_65, 1.875_967_5, 0.041_555_06],
+    [0.055_630_08, -0.203_976_96, 1.056_971_5],
+];
+
+impl Hsluv {
+    /// Creates a new HSLuv color.
+    pub fn new(h: f32, s: f32, l: f32) -> Self {
+        Hsluv { h, s, l }
+    }
+
+    /// Converts from linear sRGB to HSLuv color space.
+    ///
+    /// This conversion goes through CIE XYZ, CIELUV, and LCh(uv).
+    pub fn from_linear_srgb(linear_srgb: LinearSrgb) -> Self {
+        let xyz = Xyz::from_linear_srgb(linear_srgb);
+        let (l, u, v) = xyz_to_luv(xyz);
+        let (l, c, h) = luv_to_lch(l, u, v);
+
+        if l > 99.999_99 {
+            return Hsluv::new(h, 0., 1.);
+        }
+        if l < 1e-5 {
+            return Hsluv::new(h, 0., 0.);
+        }
+        let max_chroma = max_chroma_for_lh(l, h);
+        Hsluv::new(h, (c / max_chroma).clamp(0., 1.), l / 100.)
+    }
+
+    /// Converts from HSLuv to linear sRGB color space.
+    pub fn to_linear_srgb(self) -> LinearSrgb {
+        let h = Euclid::rem_euclid(&self.h, &1.);
+        let s = self.s.clamp(0., 1.);
+        let l = self.l.clamp(0., 1.) * 100.;
+
+        let c = if !(1e-5..=99.999_99).contains(&l) {
+            0.
+        } else {
+            max_chroma_for_lh(l, h) * s
+        };
+        let (l, u, v) = lch_to_luv(l, c, h);
+        luv_to_xyz(l, u, v).to_linear_srgb()
+    }
+}
+
+/// Converts CIE XYZ to CIELUV (L from 0 to 100).
+fn xyz_to_luv(xyz: Xyz) -> (f32, f32, f32) {
+    let Xyz { x, y, z } = xyz;
+    let l = y_to_l(y);
+    let divider = x + 15. * y + 3. * z;
+    if l == 0. || divider == 0. {
+        return (0., 0., 0.);
+    }
+    let var_u = 4. * x / divider;
+    let var_v = 9. * y / divider;
+    (l, 13. * l * (var_u - REF_U), 13. * l * (var_v - REF_V))
+}
+
+/// Converts CIELUV (L from 0 to 100) to CIE XYZ.
+fn luv_to_xyz(l: f32, u: f32, v: f32) -> Xyz {
+    if l == 0. {
+        return Xyz::new(0., 0., 0.);
+    }
+    let var_u = u / (13. * l) + REF_U;
+    let var_v = v / (13. * l) + REF_V;
+    let y = l_to_y(l);
+    let x = -(9. * y * var_u) / ((var_u - 4.) * var_v - var_u * var_v);
+    let z = (9. * y - 15. * var_v * y - var_v * x) / (3. * var_v);
+    Xyz::new(x, y, z)
+}
+
+/// Converts CIELUV to LCh(uv), with hue from 0.0 to 1.0.
+fn luv_to_lch(l: f32, u: f32, v: f32) -> (f32, f32, f32) {
+    let c = (u * u + v * v).sqrt();
+    let h = if c < 1e-5 {
+        0.
+    } else {
+        Euclid::rem_euclid(&(v.atan2(u) / TAU), &1.)
+    };
+    (l, c, h)
+}
+
+/// Converts LCh(uv), with hue from 0.0 to 1.0, to CIELUV.
+fn lch_to_luv(l: f32, c: f32, h: f32) -> (f32, f32, f32) {
+    let (sin, cos) = (h * TAU).sin_cos();
+    (l, cos * c, sin * c)
+}
+
+/// Converts relative luminance (Y) to CIE lightness (L from 0 to 100).
+fn y_to_l(y: f32) -> f32 {
+    if y <= EPSILON {
+        y * KAPPA
+    } else {
+        116. * y.cbrt() - 16.
+    }
+}
+
+/// Converts CIE lightness (L from 0 to 100) to relative luminance (Y).
+fn l_to_y(l: f32) -> f32 {
+    if l <= 8. {
+        l / KAPPA
+    } else {
+        let y = (l + 16.) / 116.;
+        y * y * y
+    }
+}
+
+/// Returns the maximum chroma within the sRGB gamut, for a lightness and hue.
+///
+/// For a lightness, each edge of the sRGB gamut (each channel at 0 or 1) is a line in the
+/// UV plane. The maximum chroma is the distance to the nearest line along the hue.
+fn max_chroma_for_lh(l: f32, h: f32) -> f32 {
+    let sub1 = (l + 16.) * (l + 16.) * (l + 16.) / 1_560_896.;
+    let sub2 = if sub1 > EPSILON { sub1 } else { l / KAPPA };
+    let (sin, cos) = (h * TAU).sin_cos();
+
+    let mut max_chroma = f32::MAX;
+    for [m1, m2, m3] in XYZ_TO_LINEAR_SRGB {
+        for t in [0., 1.] {
+            let top1 = (284_517. * m1 - 94_839. * m3) * sub2;
+            let top2 =
+                (838_422. * m3 + 769_860. * m2 + 731_718. * m1) * l * sub2 - 769_860. * t * l;
+            let bottom = (632_260. * m3 - 126_452. * m2) * sub2 + 126_452. * t;
+            let slope = top1 / bottom;
+            let intercept = top2 / bottom;
+
+            let length = intercept / (sin - slope * cos);
+            if length >= 0. {
+                max_chroma = max_chroma.min(length);
+            }
+        }
+    }
+    max_chroma
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn assert_close(a: LinearSrgb, b: LinearSrgb) {
+        assert!((a.red - b.red).abs() < 1e-3, "{a:?} != {b:?}");
+        assert!((a.green - b.green).abs() < 1e-3, "{a:?} != {b:?}");
+        assert!((a.blue - b.blue).abs() < 1e-3, "{a:?} != {b:?}");
+    }
+
+    #[test]
+    fn test_round_trip() {
+        let colors = [
+            LinearSrgb::new(0., 0., 0.),
+            LinearSrgb::new(1., 1., 1.),
+            LinearSrgb::new(1., 0., 0.),
+            LinearSrgb::new(0., 1., 0.),
+            LinearSrgb::new(0., 0., 1.),
+            LinearSrgb::new(0.2, 0.5, 0.8),
+            LinearSrgb::new(0.9, 0.6, 0.1),
+            LinearSrgb::new(0.05, 0.05, 0.04),
+        ];
+        for color in colors {
+            let hsluv = Hsluv::from_linear_srgb(color);
+            assert_close(hsluv.to_linear_srgb(), color);
+        }
+    }
+
+    #[test]
+    fn test_reference_values() {
+        // From the HSLuv reference implementation: sRGB red is (12.177, 100, 53.237).
+        let red = Hsluv::from_linear_srgb(LinearSrgb::new(1., 0., 0.));
+        assert!((red.h * 360. - 12.177).abs() < 0.01);
+        assert!((red.s - 1.).abs() < 1e-3);
+        assert!((red.l * 100. - 53.237).abs() < 0.01);
+    }
+
+    #[test]
+    fn test_full_saturation_is_in_gamut() {
+        for index in 0..36 {
+            let color = Hsluv::new(index as f32 / 36., 1., 0.6).to_linear_srgb();
+            let luminance = Xyz::from_linear_srgb(color).y;
+            // Constant lightness means constant luminance, for every hue.
+            assert!((y_to_l(luminance) - 60.).abs() < 0.1);
+        }
+    }
+}
@@ -5,7 +5,7 @@ use num_traits::float::FloatCore;
 #[allow(unused_imports)]
 use num_traits::Euclid;
 
-use super::{FromColor, LinearSrgb};
+use super::{FromColor, LinearSrgb, Srgb};
 
 /// HSV color model (Hue, Saturation, Value)
 ///
@@ -19,6 +19,25 @@ use super::{FromColor, LinearSrgb};
 /// you can control how a hue is mapped to a color. The default mapping [`HsvHueRainbow`] provides
 /// more evenly-spaced color bands, including enhanced yellow and deep purple bands.
 ///
+/// ## Conversion
+///
+/// Like FastLED's HSV, the RGB color of a hue, saturation, and value is in linear light:
+/// [`LinearSrgb`] implements [`FromColor<Hsv>`] directly, with the crate's own hue maps. Other
+/// color types convert from that linear color, so [`Srgb`] gamma-encodes it. Every color type
+/// which implements `FromColor<LinearSrgb>` can be built from an [`Hsv`] this way, without
+/// round trips through gamma-encoded values.
+///
+/// ```
+/// use blinksy::color::{FromColor, Hsv, HsvHueSpectrum, LinearSrgb, Srgb};
+///
+/// let green = Hsv::<HsvHueSpectrum>::new(1. / 3., 1., 0.5);
+/// assert_eq!(LinearSrgb::from_color(green), LinearSrgb::new(0., 0.5, 0.));
+/// assert_eq!(
+///     Srgb::from_color(green),
+///     Srgb::from_linear_srgb(LinearSrgb::new(0., 0.5, 0.))
+/// );
+/// ```
+///
 /// [FastLED's HSV]: https://github.com/FastLED/FastLED/wiki/FastLED-HSV-Colors
 #[derive(Debug, Clone, Copy, PartialEq)]
 #[cfg_attr(feature = "defmt", derive(defmt::Format))]
@@ -31,6 +50,18 @@ pub struct Hsv<M: HsvHueMap = HsvHueRainbow> {
     pub value: f32,
 }
 
+/// HSV with FastLED's rainbow hue mapping (as in `hsv2rgb_rainbow`).
+///
+/// The rainbow mapping boosts the yellow band and balances the perceived brightness of the
+/// hues, which is the look people expect when porting FastLED sketches. This is the same as
+/// [`Hsv`] with its default hue map, [`HsvHueRainbow`].
+pub type RainbowHsv = Hsv<HsvHueRainbow>;
+
+/// HSV with FastLED's spectrum hue mapping (as in `hsv2rgb_spectrum`).
+///
+/// This is the same as [`Hsv`] with the [`HsvHueSpectrum`] hue map.
+pub type SpectrumHsv = Hsv<HsvHueSpectrum>;
+
 impl<M: HsvHueMap> Hsv<M> {
     /// Creates a new HSV color
     ///
@@ -65,14 +96,17 @@ impl<M: HsvHueMap> Hsv<M> {
 
 impl<M: HsvHueMap> FromColor<Hsv<M>> for LinearSrgb {
     fn from_color(color: Hsv<M>) -> Self {
+        // The fields are public, so may be out of range.
+        let saturation = color.saturation.clamp(0.0, 1.0);
+        let value = color.value.clamp(0.0, 1.0);
+
         // Special case for zero saturation (grayscale)
-        if color.saturation <= 0.0 {
-            let v = color.value;
-            return LinearSrgb::new(v, v, v);
+        if saturation <= 0.0 {
+            return LinearSrgb::new(value, value, value);
         }
 
         // Special case for zero value (black)
-        if color.value <= 0.0 {
+        if value <= 0.0 {
             return LinearSrgb::new(0.0, 0.0, 0.0);
         }
 
@@ -80,26 +114,28 @@ impl<M: HsvHueMap> FromColor<Hsv<M>> for LinearSrgb {
         let rgb = color.hue.to_rgb();
 
         // If fully saturated, just scale by value
-        if color.saturation >= 1.0 {
-            return LinearSrgb::new(
-                rgb.red * color.value,
-                rgb.green * color.value,
-                rgb.blue * color.value,
-            );
+        if saturation >= 1.0 {
+            return LinearSrgb::new(rgb.red * value, rgb.green * value, rgb.blue * value);
         }
 
         // For partial saturation, blend with gray
-        let gray = color.value;
-        let s = color.saturation;
+        let gray = value * (1.0 - saturation);
 
         LinearSrgb::new(
-            rgb.red * s * color.value + gray * (1.0 - s),
-            rgb.green * s * color.value + gray * (1.0 - s),
-            rgb.blue * s * color.value + gray * (1.0 - s),
+            rgb.red * saturation * value + gray,
+            rgb.green * saturation * value + gray,
+            rgb.blue * saturation * value + gray,
         )
     }
 }
 
+impl<M: HsvHueMap> FromColor<Hsv<M>> for Srgb {
+    fn from_color(color: Hsv<M>) -> Self {
+        // HSV is in linear light, so gamma-encode its linear color.
+        Srgb::from_linear_srgb(LinearSrgb::from_color(color))
+    }
+}
+
 /// Representation of a color hue with a specific mapping method
 ///
 /// The [`HsvHue`] type represents a position on the color wheel using a mapping
@@ -123,15 +159,18 @@ impl<M: HsvHueMap> HsvHue<M> {
     ///
     /// # Arguments
     ///
-    /// - `hue` - HsvHue value (0.0 to 1.0)
+    /// - `hue` - HsvHue value (0.0 to 1.0), wrapped around, so -0.25 is the same as 0.75
     pub fn new(hue: f32) -> Self {
+        let inner = Euclid::rem_euclid(&hue, &1.0);
+        // A tiny negative hue rounds up to 1.0, which is the same as 0.0.
+        let inner = if inner >= 1.0 { 0.0 } else { inner };
         Self {
             map: PhantomData,
-            inner: Euclid::rem_euclid(&hue, &1.0),
+            inner,
         }
     }
 
-    /// Returns the raw hue value (0.0 to 1.0)
+    /// Returns the raw hue value (0.0 inclusive to 1.0 exclusive)
     pub fn inner(self) -> f32 {
         self.inner
     }
@@ -287,3 +326,114 @@ impl HsvHueMap for HsvHueRainbow {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Returns the largest difference between the channels of two colors.
+    fn difference(a: LinearSrgb, b: LinearSrgb) -> f32 {
+        (a.red - b.red)
+            .abs()
+            .max((a.green - b.green).abs())
+            .max((a.blue - b.blue).abs())
+    }
+
+    /// Sweeps the hue from -360° to 720° in 1° steps, asserting neighboring colors are
+    /// close, and hues a full turn apart are the same.
+    fn assert_continuous<M: HsvHueMap>(saturation: f32) {
+        // The steepest slope is the rainbow map's ⅓→1 blue rise over an eighth of a turn.
+        const EPSILON: f32 = 2. / 3. * 8. / 360. + 1e-4;
+        let color = |degrees: i32| {
+            LinearSrgb::from_color(Hsv::<M>::new(degrees as f32 / 360., saturation, 1.))
+        };
+        for degrees in -360..720 {
+            let step = difference(color(degrees), color(degrees + 1));
+            assert!(step < EPSILON, "step of {step} at {degrees}°");
+            let turn = difference(color(degrees), color(degrees + 360));
+            assert!(turn < 1e-4, "difference of {turn} a turn from {degrees}°");
+        }
+    }
+
+    #[test]
+    fn test_hue_continuity() {
+        for saturation in [1., 0.5] {
+            assert_continuous::<HsvHueRainbow>(saturation);
+            assert_continuous::<HsvHueSpectrum>(saturation);
+        }
+    }
+
+    #[test]
+    fn test_hue_wrap() {
+        assert_eq!(HsvHue::<HsvHueRainbow>::new(1.25).inner(), 0.25);
+        assert_eq!(HsvHue::<HsvHueRainbow>::new(-0.25).inner(), 0.75);
+        assert_eq!(HsvHue::<HsvHueRainbow>::new(-1e-9).inner(), 0.);
+        assert_eq!(HsvHue::<HsvHueRainbow>::new(1.).inner(), 0.);
+    }
+
+    #[test]
+    fn test_clamp() {
+        let red = LinearSrgb::new(1., 0., 0.);
+        let color = |saturation: f32, value: f32| {
+            LinearSrgb::from_color(RainbowHsv {
+                hue: HsvHue::new(0.),
+                saturation,
+                value,
+            })
+        };
+        assert_eq!(color(2., 2.), red);
+        assert_eq!(color(-1., 1.), LinearSrgb::new(1., 1., 1.));
+        assert_eq!(color(1., -1.), LinearSrgb::new(0., 0., 0.));
+        assert_eq!(LinearSrgb::from_color(RainbowHsv::new(0., 2., 2.)), red);
+    }
+
+    #[test]
+    fn test_primaries() {
+        let linear = |hue: f32| LinearSrgb::from_color(SpectrumHsv::new(hue, 1., 1.));
+        assert_eq!(linear(0.), LinearSrgb::new(1., 0., 0.));
+        assert_eq!(linear(1. / 3.), LinearSrgb::new(0., 1., 0.));
+        assert_eq!(linear(2. / 3.), LinearSrgb::new(0., 0., 1.));
+
+        // Full and empty channels are (near enough) the same in sRGB.
+        let srgb = |hue: f32| Srgb::from_color(SpectrumHsv::new(hue, 1., 1.));
+        let is_near = |color: Srgb, expected: [f32; 3]| {
+            [color.red, color.green, color.blue]
+                .into_iter()
+                .zip(expected)
+                .all(|(actual, expected)| (actual - expected).abs() < 1e-5)
+        };
+        assert!(is_near(srgb(0.), [1., 0., 0.]));
+        assert!(is_near(srgb(1. / 3.), [0., 1., 0.]));
+        assert!(is_near(srgb(2. / 3.), [0., 0., 1.]));
+    }
+
+    #[test]
+    fn test_saturation() {
+        for (saturation, expected) in [(0., 0.5), (0.5, 0.25), (1., 0.)] {
+            // Half value red: desaturating mixes in gray, in linear light.
+            let color = SpectrumHsv::new(0., saturation, 0.5);
+            let linear = LinearSrgb::from_color(color);
+            assert_eq!(linear, LinearSrgb::new(0.5, expected, expected));
+
+            // sRGB is the gamma-encoded linear color, not the linear values as-is.
+            let srgb = Srgb::from_color(color);
+            assert_eq!(srgb, Srgb::from_linear_srgb(linear));
+            assert!(srgb.red > linear.red);
+        }
+    }
+
+    #[test]
+    fn test_rainbow_boosts_yellow() {
+        // Yellow is a quarter of the way around the rainbow hue map.
+        let yellow = LinearSrgb::from_color(RainbowHsv::new(0.25, 1., 1.));
+        assert!((yellow.red - 2. / 3.).abs() < 1e-5);
+        assert!((yellow.green - 2. / 3.).abs() < 1e-5);
+        assert_eq!(yellow.blue, 0.);
+
+        // Whereas the spectrum hue map only has a muddy yellow, half way to green.
+        let yellow = LinearSrgb::from_color(SpectrumHsv::new(1. / 6., 1., 1.));
+        assert!((yellow.red - 0.5).abs() < 1e-5);
+        assert!((yellow.green - 0.5).abs() < 1e-5);
+        assert_eq!(yellow.blue, 0.);
+    }
+}
@@ -2,7 +2,7 @@ use core::{array::IntoIter, iter::Iterator, ops::Index};
 
 use crate::util::component::Component;
 
-use super::{ColorCorrection, LinearSrgb};
+use super::{temperature, ColorCorrection, LinearSrgb};
 
 /// Color data ready for output to LED hardware
 #[derive(Debug, Clone, Copy, PartialEq)]
@@ -92,6 +92,14 @@ impl<C> IntoIterator for LedColor<C> {
 #[cfg_attr(feature = "defmt", derive(defmt::Format))]
 pub struct LedRgb<C>([C; 3]);
 
+#[cfg(feature = "fixed-point")]
+impl<C> LedRgb<C> {
+    /// Creates RGB LED output values from components, in canonical order.
+    pub(crate) const fn new(rgb: [C; 3]) -> Self {
+        Self(rgb)
+    }
+}
+
 impl<C: Component> LedRgb<C> {
     /// Creates RGB LED output values from a linear sRGB color
     ///
@@ -112,9 +120,7 @@ impl<C: Component> LedRgb<C> {
         let LinearSrgb { red, green, blue } = linear_srgb;
 
         // Apply color correction
-        let red = red * correction.red;
-        let green = green * correction.green;
-        let blue = blue * correction.blue;
+        let (red, green, blue) = correction.apply(red, green, blue);
 
         // Apply brightness
         let red = red * brightness;
@@ -165,17 +171,95 @@ impl<C> Index<usize> for LedRgb<C> {
     }
 }
 
+/// How to extract the white channel of RGBW LEDs from an RGB color.
+///
+/// Set with [`ColorCorrection::with_white_mode`], or
+/// [`Control::set_white_mode`](crate::control::Control::set_white_mode).
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum WhiteMode {
+    /// No white: the white LED is off, and the RGB LEDs show the whole color
+    Off,
+    /// The white is the minimum of the RGB channels, which is subtracted from each RGB
+    /// channel
+    ///
+    /// Exact for a white LED the same color as full RGB white, such as a neutral (about
+    /// 6500K) white LED.
+    #[default]
+    Min,
+    /// The white LED has a color, in linear sRGB: the white is as much of that color as fits
+    /// in the RGB color, which is subtracted from the RGB channels
+    ///
+    /// For a warm or cool white LED, use [`WhiteMode::accurate`] with its measured color
+    /// temperature, so the RGB LEDs make up the difference to the color.
+    Accurate(LinearSrgb),
+}
+
+impl WhiteMode {
+    /// Creates a [`WhiteMode::Accurate`] for a white LED with the color of a color
+    /// temperature.
+    ///
+    /// The color is computed once here, rather than for each pixel.
+    ///
+    /// # Arguments
+    ///
+    /// - `kelvin` - Color temperature of the white LED in Kelvin, clamped to 1000K to 12000K
+    pub fn accurate(kelvin: f32) -> Self {
+        WhiteMode::Accurate(temperature::from_kelvin(kelvin))
+    }
+
+    /// Splits a linear RGB color into the light of the RGB LEDs and the white LED.
+    ///
+    /// # Returns
+    ///
+    /// The remaining RGB color, and the level of the white LED (0.0 to 1.0)
+    pub fn extract(self, linear_srgb: LinearSrgb) -> (LinearSrgb, f32) {
+        let LinearSrgb { red, green, blue } = linear_srgb;
+        match self {
+            WhiteMode::Off => (linear_srgb, 0.),
+            WhiteMode::Min => {
+                let white = red.min(green).min(blue).max(0.);
+                (linear_srgb - LinearSrgb::new(white, white, white), white)
+            }
+            WhiteMode::Accurate(white_led) => {
+                // The most of the white LED's color which fits within each channel.
+                let fit = |value: f32, white_value: f32| {
+                    if white_value > 0. {
+                        value / white_value
+                    } else {
+                        f32::INFINITY
+                    }
+                };
+                let white = fit(red, white_led.red)
+                    .min(fit(green, white_led.green))
+                    .min(fit(blue, white_led.blue))
+                    .clamp(0., 1.);
+                (linear_srgb - white_led * white, white)
+            }
+        }
+    }
+}
+
 /// RGBW color values ready for output to LED hardware
 #[derive(Debug, Clone, Copy, PartialEq)]
 #[cfg_attr(feature = "defmt", derive(defmt::Format))]
 pub struct LedRgbw<C>([C; 4]);
 
+#[cfg(feature = "fixed-point")]
+impl<C> LedRgbw<C> {
+    /// Creates RGBW LED output values from components, in canonical order.
+    pub(crate) const fn new(rgbw: [C; 4]) -> Self {
+        Self(rgbw)
+    }
+}
+
 impl<C: Component> LedRgbw<C> {
     /// Creates RGBW LED output values from a linear sRGB color
     ///
-    /// This performs white channel extraction using the common minimum method,
-    /// where the white component is the minimum of R,G,B, and those values are
-    /// then subtracted from the RGB components.
+    /// This extracts the white channel by the white mode of the color correction (see
+    /// [`WhiteMode`]), by default the common minimum method, where the white component is
+    /// the minimum of R,G,B, and those values are then subtracted from the RGB components.
     ///
     /// # Arguments
     ///
@@ -191,26 +275,18 @@ impl<C: Component> LedRgbw<C> {
         brightness: f32,
         correction: ColorCorrection,
     ) -> Self {
-        let LinearSrgb { red, green, blue } = linear_srgb;
-
-        // Extract white component (minimum of RGB)
-        let white = red.min(green).min(blue);
-
-        // Subtract white from RGB to get true RGB components
-        let red = red - white;
-        let green = green - white;
-        let blue = blue - white;
+        // Extract white component, and subtract it from RGB to get true RGB components
+        let (rgb, white) = correction.white_mode.extract(linear_srgb);
+        let LinearSrgb { red, green, blue } = rgb;
 
         // Apply color correction
-        let red = red * correction.red;
-        let green = green * correction.green;
-        let blue = blue * correction.blue;
+        let (red, green, blue) = correction.apply(red, green, blue);
 
         // Apply brightness
         let red = red * brightness;
         let green = green * brightness;
         let blue = blue * brightness;
-        let white = white * brightness;
+        let white = white * correction.global * brightness;
 
         // Clamp values
         let red = red.clamp(0., 1.);
@@ -262,7 +338,7 @@ impl<C> Index<usize> for LedRgbw<C> {
 ///
 /// Different LED chipsets have different ordering of color channels.
 /// This enum represents the possible arrangements.
-#[derive(Debug)]
+#[derive(Debug, Clone, Copy, PartialEq)]
 #[cfg_attr(feature = "defmt", derive(defmt::Format))]
 pub enum LedChannels {
     /// RGB with 3 channels
@@ -284,7 +360,7 @@ impl LedChannels {
 /// Enumeration of RGB channel orders.
 ///
 /// Different RGB LED chipsets may use different ordering of the R, G, and B channels.
-#[derive(Debug)]
+#[derive(Debug, Clone, Copy, PartialEq)]
 #[cfg_attr(feature = "defmt", derive(defmt::Format))]
 pub enum RgbChannels {
     /// Red, Green, Blue
@@ -304,7 +380,7 @@ pub enum RgbChannels {
 /// Enumeration of RGBW channel orders.
 ///
 /// Different RGBW LED chipsets may use different ordering of the R, G, B, and W channels.
-#[derive(Debug)]
+#[derive(Debug, Clone, Copy, PartialEq)]
 #[cfg_attr(feature = "defmt", derive(defmt::Format))]
 pub enum RgbwChannels {
     // RGB
@@ -418,3 +494,38 @@ impl RgbwChannels {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Returns the white channel, from 0 to 255, for pure white.
+    fn white_of_white(white_mode: WhiteMode) -> LedRgbw<u8> {
+        let correction = ColorCorrection::default().with_white_mode(white_mode);
+        LedRgbw::from_linear_srgb(LinearSrgb::new(1., 1., 1.), 1., correction)
+    }
+
+    #[test]
+    fn test_white_mode_off() {
+        assert_eq!(white_of_white(WhiteMode::Off).as_ref(), [255, 255, 255, 0]);
+    }
+
+    #[test]
+    fn test_white_mode_min() {
+        assert_eq!(white_of_white(WhiteMode::Min).as_ref(), [0, 0, 0, 255]);
+    }
+
+    #[test]
+    fn test_white_mode_accurate() {
+        // A warm white LED is full on, with the RGB LEDs making up the blue and green.
+        let rgbw = white_of_white(WhiteMode::accurate(temperature::TUNGSTEN));
+        assert_eq!(rgbw[0], 0);
+        assert!(rgbw[1] > 0 && rgbw[2] > rgbw[1], "{rgbw:?}");
+        assert_eq!(rgbw[3], 255);
+
+        // A neutral white LED is nearly the same as the minimum.
+        let rgbw = white_of_white(WhiteMode::accurate(temperature::DAYLIGHT));
+        assert_eq!(rgbw[3], 255);
+        assert!(rgbw[0] + rgbw[1] + rgbw[2] < 16, "{rgbw:?}");
+    }
+}
@@ -1,8 +1,10 @@
+use core::ops::{Add, Mul, Sub};
+
 use crate::util::component::Component;
 
 use super::{
-    ColorCorrection, FromColor, GammaSrgb, LedChannels, LedColor, Lms, Okhsl, Okhsv, Oklab, Srgb,
-    Xyz,
+    ColorCorrection, FromColor, GammaSrgb, Hsluv, LedChannels, LedColor, Lms, Okhsl, Okhsv, Oklab,
+    Oklch, Srgb, Xyz,
 };
 
 /// # Linear RGB Color Space
@@ -18,8 +20,28 @@ use super::{
 ///
 /// Mathematical operations on linear RGB values (like averaging or interpolation) will
 /// produce physically correct results, unlike operations on gamma-encoded sRGB values.
+///
+/// ## Arithmetic
+///
+/// `LinearSrgb` supports per-channel `+` and `-` with another color, and `*` with a
+/// scale factor. Like [`f32`] arithmetic, these don't clamp, so intermediate results
+/// (such as the sum of many colors, before averaging) stay exact. To composite colors
+/// for output, use [`LinearSrgb::saturating_add`] and [`LinearSrgb::saturating_sub`],
+/// which clamp each channel to 0.0 to 1.0.
+///
+/// ```
+/// use blinksy::color::LinearSrgb;
+///
+/// let orange = LinearSrgb::new(1., 0.5, 0.);
+/// let teal = LinearSrgb::new(0., 0.5, 1.);
+///
+/// assert_eq!(orange * 0.5, LinearSrgb::new(0.5, 0.25, 0.));
+/// assert_eq!(orange.saturating_add(orange), LinearSrgb::new(1., 1., 0.));
+/// assert_eq!(orange.saturating_sub(teal), LinearSrgb::new(1., 0., 0.));
+/// ```
 #[derive(Debug, Clone, Copy, PartialEq)]
 #[cfg_attr(feature = "defmt", derive(defmt::Format))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct LinearSrgb {
     /// Red component (0.0 to 1.0)
     pub red: f32,
@@ -53,6 +75,24 @@ impl LinearSrgb {
         }
     }
 
+    /// Adds the colors, like overlapping lights, clamping each channel to 0.0 to 1.0.
+    ///
+    /// Bright overlaps saturate at full brightness rather than overflow.
+    #[inline]
+    pub fn saturating_add(self, other: Self) -> Self {
+        let sum = self + other;
+        LinearSrgb::new(sum.red, sum.green, sum.blue)
+    }
+
+    /// Subtracts `other` from the color, clamping each channel to 0.0 to 1.0.
+    ///
+    /// Useful for subtractive effects, such as darkening one pattern by another.
+    #[inline]
+    pub fn saturating_sub(self, other: Self) -> Self {
+        let difference = self - other;
+        LinearSrgb::new(difference.red, difference.green, difference.blue)
+    }
+
     /// Converts from linear RGB to sRGB color space
     ///
     /// This applies gamma encoding to make the color values perceptually uniform.
@@ -86,12 +126,60 @@ impl LinearSrgb {
     }
 }
 
+impl Add for LinearSrgb {
+    type Output = Self;
+
+    /// Adds each channel, without clamping.
+    #[inline]
+    fn add(self, other: Self) -> Self {
+        LinearSrgb {
+            red: self.red + other.red,
+            green: self.green + other.green,
+            blue: self.blue + other.blue,
+        }
+    }
+}
+
+impl Sub for LinearSrgb {
+    type Output = Self;
+
+    /// Subtracts each channel, without clamping.
+    #[inline]
+    fn sub(self, other: Self) -> Self {
+        LinearSrgb {
+            red: self.red - other.red,
+            green: self.green - other.green,
+            blue: self.blue - other.blue,
+        }
+    }
+}
+
+impl Mul<f32> for LinearSrgb {
+    type Output = Self;
+
+    /// Scales each channel, without clamping.
+    #[inline]
+    fn mul(self, scale: f32) -> Self {
+        LinearSrgb {
+            red: self.red * scale,
+            green: self.green * scale,
+            blue: self.blue * scale,
+        }
+    }
+}
+
 impl FromColor<GammaSrgb> for LinearSrgb {
     fn from_color(color: GammaSrgb) -> Self {
         color.to_linear_srgb()
     }
 }
 
+impl FromColor<Hsluv> for LinearSrgb {
+    fn from_color(color: Hsluv) -> Self {
+        color.to_linear_srgb()
+    }
+}
+
 impl FromColor<Lms> for LinearSrgb {
     fn from_color(color: Lms) -> Self {
         color.to_linear_srgb()
@@ -116,6 +204,12 @@ impl FromColor<Oklab> for LinearSrgb {
     }
 }
 
+impl FromColor<Oklch> for LinearSrgb {
+    fn from_color(color: Oklch) -> Self {
+        color.to_linear_srgb()
+    }
+}
+
 impl FromColor<Srgb> for LinearSrgb {
     fn from_color(color: Srgb) -> Self {
         color.to_linear_srgb()
@@ -127,3 +221,30 @@ impl FromColor<Xyz> for LinearSrgb {
         color.to_linear_srgb()
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_saturating_add_clamps_at_one() {
+        let color = LinearSrgb::new(0.75, 0.5, 0.);
+        assert_eq!(color.saturating_add(color), LinearSrgb::new(1., 1., 0.));
+        // Plain addition doesn't clamp.
+        assert_eq!((color + color).red, 1.5);
+    }
+
+    #[test]
+    fn test_saturating_sub_clamps_at_zero() {
+        let color = LinearSrgb::new(0.75, 0.5, 0.);
+        let other = LinearSrgb::new(0.25, 1., 0.5);
+        assert_eq!(color.saturating_sub(other), LinearSrgb::new(0.5, 0., 0.));
+    }
+
+    #[test]
+    fn test_mul_scales_each_channel() {
+        let color = LinearSrgb::new(0.8, 0.4, 0.2);
+        assert_eq!(color * 0.5, LinearSrgb::new(0.4, 0.2, 0.1));
+        assert_eq!(color * 0., LinearSrgb::new(0., 0., 0.));
+    }
+}
@@ -7,13 +7,17 @@
 //!
 //! - [`Srgb`] - Standard RGB color space (gamma-corrected)
 //! - [`LinearSrgb`] - Linear RGB color space (no gamma correction)
-//! - [`GammaSrgb`] - RGB with custom gamma correction
-//! - [`Hsv`] - HSV color space
+//! - [`GammaSrgb`] - RGB with custom (power-law) gamma correction, for non-sRGB displays
+//! - [`Hsv`] - HSV color space, with FastLED's hue maps ([`RainbowHsv`], [`SpectrumHsv`])
+//! - [`Hsluv`] - Perceptual HSL color space based on CIELUV
 //! - [`Oklab`] - Perceptually uniform LAB space
+//! - [`Oklch`] - Polar form of Oklab, with chroma and hue angle
 //! - [`Okhsl`] - Perceptual HSL color space based on Oklab
 //! - [`Okhsv`] - Perceptual HSV color space based on Oklab
 //! - [`Xyz`] - CIE XYZ color space
 //! - [`Lms`] - LMS cone response space
+//! - `fixed` - Fixed-point colors, stored and converted with integer math
+//!   (Requires the `fixed-point` feature.)
 //!
 //! ## Conversion Traits
 //!
@@ -23,6 +27,14 @@
 //! ## LED Output Modifiers
 //!
 //! - [`ColorCorrection`] - Correction factors for LED output
+//! - [`GammaLut256`] - Lookup table for fast 8-bit gamma
+//! - [`temperature`] - Color temperature (Kelvin) to RGB
+//!
+//! ## Gradients
+//!
+//! - [`gradient`] - Gradients of color stops
+//! - [`palette`] - Gradient palettes in FastLED's format
+//! - [`blend`] - Interpolation and blend modes, shared by gradients and crossfades
 //!
 //! ## LED Output
 //!
@@ -31,11 +43,18 @@
 //!   - [`LedRgbw`]
 //! - [`LedChannels`] - Color channel formats for different LED chipsets
 //!   - [`RgbChannels`]
+//!     - [`rgb_order`] - RGB channel orders as types
 //!   - [`RgbwChannels`]
 
+pub mod blend;
 mod convert;
 mod correction;
+#[cfg(feature = "fixed-point")]
+pub mod fixed;
+mod gamma;
 mod gamma_srgb;
+pub mod gradient;
+mod hsluv;
 mod hsv;
 mod led;
 mod linear_srgb;
@@ -43,12 +62,18 @@ mod lms;
 mod okhsl;
 mod okhsv;
 mod oklab;
+mod oklch;
+pub mod palette;
+pub mod rgb_order;
 mod srgb;
+pub mod temperature;
 mod xyz;
 
 pub use self::convert::*;
 pub use self::correction::*;
+pub use self::gamma::*;
 pub use self::gamma_srgb::*;
+pub use self::hsluv::*;
 pub use self::hsv::*;
 pub use self::led::*;
 pub use self::linear_srgb::*;
@@ -56,5 +81,6 @@ pub use self::lms::*;
 pub use self::okhsl::*;
 pub use self::okhsv::*;
 pub use self::oklab::*;
+pub use self::oklch::*;
 pub use self::srgb::*;
 pub use self::xyz::*;
@@ -0,0 +1,132 @@
+#[allow(unused_imports)]
+use num_traits::Float;
+
+use super::{FromColor, LinearSrgb, Oklab};
+
+/// # Oklch Color Space
+///
+/// Oklch is the polar form of [`Oklab`]: the same perceptual color space, but with the
+/// cartesian `a` and `b` components expressed as a chroma (distance from grey) and a hue
+/// angle.
+///
+/// This makes Oklch a good fit for hue-based patterns: sweeping the hue at a fixed
+/// lightness and chroma changes only the hue, with an even perceived brightness, unlike
+/// sweeping the hue of HSV.
+///
+/// - `l`: **Perceptual Lightness**, from 0.0 (black) to 1.0 (white), same as Oklab.
+/// - `c`: **Chroma**, the colorfulness, from 0.0 (grey) upwards. Colors within the sRGB
+///   gamut have a chroma below about 0.33, and the maximum depends on the lightness and hue.
+/// - `h`: **Hue** angle in radians, from -π to π, where 0 is towards red (+`a`).
+///
+/// ## Color Space Properties
+///
+/// - **White Point**: D65 (6500K), same as sRGB
+///
+/// Reference: <https://bottosson.github.io/posts/oklab/#the-oklab-color-space>
+#[derive(Debug, Copy, Clone, PartialEq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct Oklch {
+    /// Lightness component [0.0, 1.0]
+    pub l: f32,
+    /// Chroma component [0.0, ~0.33]
+    pub c: f32,
+    /// Hue angle in radians, where any multiple of 2π apart represent the same hue
+    pub h: f32,
+}
+
+impl Oklch {
+    /// Creates a new Oklch color.
+    pub fn new(l: f32, c: f32, h: f32) -> Self {
+        Oklch { l, c, h }
+    }
+
+    /// Converts from Oklab to Oklch, where the hue is from -π to π.
+    pub fn from_oklab(oklab: Oklab) -> Self {
+        let Oklab { l, a, b } = oklab;
+        Oklch {
+            l,
+            c: a.hypot(b),
+            h: b.atan2(a),
+        }
+    }
+
+    /// Converts from Oklch to Oklab.
+    pub fn to_oklab(self) -> Oklab {
+        let Oklch { l, c, h } = self;
+        let (sin, cos) = h.sin_cos();
+        Oklab::new(l, c * cos, c * sin)
+    }
+
+    /// Converts from linear sRGB to Oklch color space.
+    pub fn from_linear_srgb(linear_srgb: LinearSrgb) -> Self {
+        Self::from_oklab(Oklab::from_linear_srgb(linear_srgb))
+    }
+
+    /// Converts from Oklch to linear sRGB color space.
+    ///
+    /// Note that the result may contain values outside the standard sRGB gamut.
+    pub fn to_linear_srgb(self) -> LinearSrgb {
+        self.to_oklab().to_linear_srgb()
+    }
+}
+
+impl FromColor<Oklab> for Oklch {
+    fn from_color(color: Oklab) -> Self {
+        Oklch::from_oklab(color)
+    }
+}
+
+impl FromColor<Oklch> for Oklab {
+    fn from_color(color: Oklch) -> Self {
+        color.to_oklab()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use core::f32::consts::PI;
+
+    use super::*;
+
+    fn assert_oklab_close(a: Oklab, b: Oklab) {
+        assert!((a.l - b.l).abs() < 1e-5, "{a:?} != {b:?}");
+        assert!((a.a - b.a).abs() < 1e-5, "{a:?} != {b:?}");
+        assert!((a.b - b.b).abs() < 1e-5, "{a:?} != {b:?}");
+    }
+
+    #[test]
+    fn test_oklab_round_trip() {
+        let colors = [
+            Oklab::new(0.5, 0., 0.),
+            Oklab::new(0.628, 0.225, 0.126),
+            Oklab::new(0.866, -0.234, 0.179),
+            Oklab::new(0.452, -0.032, -0.312),
+            Oklab::new(0.7, -0.1, -0.0001),
+        ];
+        for oklab in colors {
+            let oklch = Oklch::from_color(oklab);
+            assert!((-PI..=PI).contains(&oklch.h));
+            assert_oklab_close(Oklab::from_color(oklch), oklab);
+        }
+    }
+
+    #[test]
+    fn test_hue_wraparound() {
+        // Just above and below the negative `a` axis, the hue wraps from π to -π.
+        let above = Oklch::from_oklab(Oklab::new(0.5, -0.1, 1e-6));
+        let below = Oklch::from_oklab(Oklab::new(0.5, -0.1, -1e-6));
+        assert!((above.h - PI).abs() < 1e-4);
+        assert!((below.h + PI).abs() < 1e-4);
+
+        // Both ends of the hue range are the same color.
+        assert_oklab_close(
+            Oklch::new(0.5, 0.1, PI).to_oklab(),
+            Oklch::new(0.5, 0.1, -PI).to_oklab(),
+        );
+        // And hues outside the range wrap around.
+        assert_oklab_close(
+            Oklch::new(0.5, 0.1, 0.5 + 2. * PI).to_oklab(),
+            Oklch::new(0.5, 0.1, 0.5).to_oklab(),
+        );
+    }
+}
@@ -0,0 +1,127 @@
+//! # Gradient Palettes
+//!
+//! [`GradientPalette`] reads a gradient palette in FastLED's format: control points of
+//! `(index, red, green, blue)` bytes, with indices from 0 to 255. This is the format of
+//! FastLED's `DEFINE_GRADIENT_PALETTE`, so the thousands of [cpt-city] gradients
+//! converted for FastLED can be pasted in, grouping each 4 bytes into a tuple.
+//!
+//! [cpt-city]: http://seaviewsensing.com/pub/cpt-city/
+//!
+//! ## Example
+//!
+//! ```
+//! use blinksy::color::{palette::GradientPalette, LinearSrgb};
+//!
+//! // DEFINE_GRADIENT_PALETTE( heatmap_gp ) {
+//! //     0,     0,  0,  0,   // black
+//! //   128,   255,  0,  0,   // red
+//! //   224,   255,255,  0,   // bright yellow
+//! //   255,   255,255,255 }; // full white
+//! const HEATMAP: GradientPalette = GradientPalette::from_fastled(&[
+//!     (0, 0, 0, 0),
+//!     (128, 255, 0, 0),
+//!     (224, 255, 255, 0),
+//!     (255, 255, 255, 255),
+//! ]);
+//!
+//! assert_eq!(HEATMAP.sample(128), LinearSrgb::new(1., 0., 0.));
+//! ```
+
+use super::{LinearSrgb, Srgb};
+
+/// A gradient palette of control points, in FastLED's format.
+///
+/// The palette borrows its control points, so a palette in a `static` or `const` costs no
+/// RAM.
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct GradientPalette<'a> {
+    /// Control points of `(index, red, green, blue)`, sorted by index
+    points: &'a [(u8, u8, u8, u8)],
+}
+
+impl<'a> GradientPalette<'a> {
+    /// Creates a palette from control points in FastLED's format.
+    ///
+    /// # Arguments
+    ///
+    /// - `points` - Control points of `(index, red, green, blue)`, sorted by index, with
+    ///   the colors in sRGB (as in FastLED)
+    pub const fn from_fastled(points: &'a [(u8, u8, u8, u8)]) -> Self {
+        Self { points }
+    }
+
+    /// Returns the control points.
+    pub fn points(&self) -> &'a [(u8, u8, u8, u8)] {
+        self.points
+    }
+
+    /// Returns the color at an index, interpolated between the control points on either
+    /// side.
+    ///
+    /// As in FastLED, colors are interpolated in sRGB, then converted to [`LinearSrgb`].
+    /// An index before the first control point is the color of the first control point,
+    /// and an index after the last control point is the color of the last control point.
+    /// With no control points, the color is black.
+    ///
+    /// # Arguments
+    ///
+    /// - `index` - The index to sample (0 to 255)
+    pub fn sample(&self, index: u8) -> LinearSrgb {
+        let (Some(first), Some(last)) = (self.points.first(), self.points.last()) else {
+            return LinearSrgb::new(0., 0., 0.);
+        };
+        if index <= first.0 {
+            return to_linear_srgb(*first, *first, 0.);
+        }
+        if index >= last.0 {
+            return to_linear_srgb(*last, *last, 0.);
+        }
+        for pair in self.points.windows(2) {
+            let (start, end) = (pair[0], pair[1]);
+            if index <= end.0 {
+                let span = (end.0 - start.0) as f32;
+                let alpha = (index - start.0) as f32 / span;
+                return to_linear_srgb(start, end, alpha);
+            }
+        }
+        to_linear_srgb(*last, *last, 0.)
+    }
+}
+
+/// Interpolates between two control points in sRGB, and converts to linear sRGB.
+fn to_linear_srgb(a: (u8, u8, u8, u8), b: (u8, u8, u8, u8), alpha: f32) -> LinearSrgb {
+    let lerp = |a: u8, b: u8| {
+        let (a, b) = (a as f32 / 255., b as f32 / 255.);
+        a + (b - a) * alpha
+    };
+    Srgb::new(lerp(a.1, b.1), lerp(a.2, b.2), lerp(a.3, b.3)).to_linear_srgb()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const BLACK_TO_RED: GradientPalette =
+        GradientPalette::from_fastled(&[(64, 0, 0, 0), (192, 255, 0, 0)]);
+
+    #[test]
+    fn test_interpolation() {
+        let red = |index: u8| Srgb::from_linear_srgb(BLACK_TO_RED.sample(index)).red;
+
+        // Interpolated in sRGB, between the control points.
+        assert!((red(128) - 0.5).abs() < 1e-5);
+        assert!((red(96) - 0.25).abs() < 1e-5);
+        assert!((red(192) - 1.).abs() < 1e-5);
+
+        // Clamped to the first and last control points.
+        assert_eq!(red(0), 0.);
+        assert!((red(255) - 1.).abs() < 1e-5);
+    }
+
+    #[test]
+    fn test_empty() {
+        let palette = GradientPalette::from_fastled(&[]);
+        assert_eq!(palette.sample(100), LinearSrgb::new(0., 0., 0.));
+    }
+}
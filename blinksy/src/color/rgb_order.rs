@@ -0,0 +1,48 @@
+//! # RGB Channel Orders as Types
+//!
+//! Some LED chipsets, such as [`Apa102`](crate::leds::Apa102), take their RGB channel
+//! order as a type parameter, since the chipset itself is a type. The channel order is
+//! then known at compile time, so reordering costs nothing.
+//!
+//! Each type here implements [`RgbOrder`] for the matching [`RgbChannels`] variant.
+//!
+//! ## Example
+//!
+//! ```rust
+//! use blinksy::{color::rgb_order, leds::Apa102};
+//!
+//! // An APA102 clone with RGB channels, instead of the usual BGR
+//! type Apa102Rgb = Apa102<rgb_order::Rgb>;
+//! ```
+
+use super::RgbChannels;
+
+/// Trait for a type-level RGB channel order.
+pub trait RgbOrder {
+    /// The channel order.
+    const RGB_CHANNELS: RgbChannels;
+}
+
+macro_rules! impl_rgb_order {
+    ($($name:ident => $channels:ident),* $(,)?) => {
+        $(
+            #[doc = concat!("The [`RgbChannels::", stringify!($channels), "`] channel order.")]
+            #[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+            #[cfg_attr(feature = "defmt", derive(defmt::Format))]
+            pub struct $name;
+
+            impl RgbOrder for $name {
+                const RGB_CHANNELS: RgbChannels = RgbChannels::$channels;
+            }
+        )*
+    };
+}
+
+impl_rgb_order!(
+    Rgb => RGB,
+    Rbg => RBG,
+    Grb => GRB,
+    Gbr => GBR,
+    Brg => BRG,
+    Bgr => BGR,
+);
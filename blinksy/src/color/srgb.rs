@@ -1,4 +1,4 @@
-use super::LinearSrgb;
+use super::{FromColor, LinearSrgb};
 
 #[allow(unused_imports)]
 use num_traits::Float;
@@ -10,13 +10,18 @@ use num_traits::Float;
 ///
 /// ## Color Space Properties
 ///
-/// - **Gamma Encoding**: Uses a non-linear transfer function (approximately gamma 2.2)
+/// - **Gamma Encoding**: Uses the standard piecewise sRGB transfer function (approximately
+///   gamma 2.2, but with a linear segment near zero)
 /// - **RGB Primaries**: Uses the sRGB primaries as defined in IEC 61966-2-1
 /// - **White Point**: D65 (6500K)
 ///
 /// sRGB values are non-linear (gamma-encoded) to account for human perception. This means
 /// that arithmetic operations on sRGB values (like averaging or interpolation) will not
 /// produce perceptually correct results. For such operations, convert to `LinearSrgb` first.
+///
+/// This is the single source of truth for the sRGB transfer function: convert between
+/// [`Srgb`] and [`LinearSrgb`] with [`FromColor`] (or [`Srgb::to_linear_srgb`] and
+/// [`Srgb::from_linear_srgb`]).
 #[derive(Debug, Clone, Copy, PartialEq)]
 #[cfg_attr(feature = "defmt", derive(defmt::Format))]
 pub struct Srgb {
@@ -78,6 +83,12 @@ impl Srgb {
     }
 }
 
+impl FromColor<LinearSrgb> for Srgb {
+    fn from_color(color: LinearSrgb) -> Self {
+        Srgb::from_linear_srgb(color)
+    }
+}
+
 /// Convert sRGB gamma-encoded component to linear RGB component
 ///
 /// The sRGB standard uses a piece-wise function that's approximately
@@ -91,7 +102,7 @@ impl Srgb {
 /// - http://color.org/sRGB.pdf
 /// - http://www.brucelindbloom.com/index.html?Eqn_XYZ_to_RGB.html
 #[inline]
-fn srgb_decode(c: f32) -> f32 {
+pub(crate) fn srgb_decode(c: f32) -> f32 {
     if c <= 0.04045 {
         c / 12.92
     } else {
@@ -112,10 +123,35 @@ fn srgb_decode(c: f32) -> f32 {
 /// - http://color.org/sRGB.pdf
 /// - http://www.brucelindbloom.com/index.html?Eqn_RGB_to_XYZ.html
 #[inline]
-fn srgb_encode(c: f32) -> f32 {
+pub(crate) fn srgb_encode(c: f32) -> f32 {
     if c <= 0.0031308 {
         12.92 * c
     } else {
         1.055 * c.powf(1.0 / 2.4) - 0.055
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_decode_uses_piecewise_transfer() {
+        // Piecewise sRGB, not a pure gamma 2.2 (which would give 0.2176).
+        let linear = LinearSrgb::from_color(Srgb::new(0.5, 0.5, 0.5));
+        assert!((linear.red - 0.214_041_14).abs() < 1e-6);
+
+        // Linear segment near zero
+        let linear = LinearSrgb::from_color(Srgb::new(0.04, 0.0, 0.0));
+        assert!((linear.red - 0.04 / 12.92).abs() < 1e-7);
+    }
+
+    #[test]
+    fn test_round_trip() {
+        for value in [0.0, 0.002, 0.04045, 0.2, 0.5, 0.9, 1.0] {
+            let srgb = Srgb::new(value, value, value);
+            let round_trip = Srgb::from_color(LinearSrgb::from_color(srgb));
+            assert!((round_trip.red - value).abs() < 1e-5);
+        }
+    }
+}
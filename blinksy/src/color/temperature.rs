@@ -0,0 +1,109 @@
+//! # Color Temperature
+//!
+//! Convert a color temperature, in Kelvin, to the color of a black-body radiator at that
+//! temperature. Useful for "white" effects, from a warm candle glow to cool daylight.
+//!
+//! Pairs with [`ColorCorrection::from_temperature`](super::ColorCorrection::from_temperature)
+//! for white balancing LEDs.
+//!
+//! ## Example
+//!
+//! ```
+//! use blinksy::color::temperature::{self, from_kelvin};
+//!
+//! let warm_white = from_kelvin(temperature::TUNGSTEN);
+//! assert!(warm_white.red > warm_white.blue);
+//! ```
+
+#[allow(unused_imports)]
+use num_traits::Float;
+
+use super::{LinearSrgb, Srgb};
+
+/// Candle flame, 1900K.
+pub const CANDLE: f32 = 1900.;
+
+/// Tungsten (incandescent) bulb, 2700K.
+pub const TUNGSTEN: f32 = 2700.;
+
+/// Daylight (D65), 6500K.
+pub const DAYLIGHT: f32 = 6500.;
+
+/// Lowest supported color temperature, in Kelvin.
+pub const MIN_KELVIN: f32 = 1000.;
+
+/// Highest supported color temperature, in Kelvin.
+pub const MAX_KELVIN: f32 = 12000.;
+
+/// Converts a color temperature to the color of a black-body radiator.
+///
+/// Uses Tanner Helland's approximation of the Planckian locus. The brightest channel is
+/// always at full brightness.
+///
+/// # Arguments
+///
+/// - `kelvin` - Color temperature in Kelvin, clamped to [`MIN_KELVIN`] to [`MAX_KELVIN`]
+///
+/// # Returns
+///
+/// The color in linear sRGB.
+pub fn from_kelvin(kelvin: f32) -> LinearSrgb {
+    kelvin_to_srgb(kelvin.clamp(MIN_KELVIN, MAX_KELVIN)).to_linear_srgb()
+}
+
+/// Tanner Helland's approximation of a black-body color, in sRGB.
+///
+/// Reference: <https://tannerhelland.com/2012/09/18/convert-temperature-rgb-algorithm-code.html>
+pub(crate) fn kelvin_to_srgb(kelvin: f32) -> Srgb {
+    // In hundreds of Kelvin
+    let t = kelvin / 100.0;
+
+    let r = if t <= 66.0 {
+        255.0
+    } else {
+        (329.698_73 * (t - 60.0).powf(-0.133_204_76)).clamp(0.0, 255.0)
+    };
+
+    let g = if t <= 66.0 {
+        (99.470_8 * t.ln() - 161.119_57).clamp(0.0, 255.0)
+    } else {
+        (288.122_16 * (t - 60.0).powf(-0.075_514_846)).clamp(0.0, 255.0)
+    };
+
+    let b = if t >= 66.0 {
+        255.0
+    } else if t <= 19.0 {
+        0.0
+    } else {
+        (138.517_73 * (t - 10.0).ln() - 305.044_8).clamp(0.0, 255.0)
+    };
+
+    Srgb::new(r / 255.0, g / 255.0, b / 255.0)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_presets() {
+        let candle = from_kelvin(CANDLE);
+        assert_eq!(candle.red, 1.);
+        assert!(candle.green < 0.3);
+        assert!(candle.blue < 0.05);
+
+        let daylight = from_kelvin(DAYLIGHT);
+        assert!(daylight.red > 0.95);
+        assert!(daylight.green > 0.95);
+        assert!(daylight.blue > 0.9);
+    }
+
+    #[test]
+    fn test_clamped() {
+        assert_eq!(from_kelvin(0.), from_kelvin(MIN_KELVIN));
+        assert_eq!(from_kelvin(40000.), from_kelvin(MAX_KELVIN));
+        // Cooler than daylight is blue.
+        let cool = from_kelvin(MAX_KELVIN);
+        assert!(cool.blue > cool.red);
+    }
+}
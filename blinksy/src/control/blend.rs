@@ -0,0 +1,200 @@
+use crate::{
+    color::{blend, FromColor, LinearSrgb},
+    layout::LayoutForDim,
+    pattern::{FrameTime, Pattern as PatternTrait},
+};
+
+/// How to combine the colors of two layered patterns.
+///
+/// Colors are combined per channel, in [`LinearSrgb`], with `a` as the bottom layer and
+/// `b` as the top layer.
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub enum BlendMode {
+    /// Adds the layers, like overlapping lights: `a + b`.
+    ///
+    /// Each channel is clamped to at most 1.0, so bright overlaps saturate rather than
+    /// overflow.
+    Add,
+    /// Multiplies the layers, like a mask: `a * b`.
+    Multiply,
+    /// Inverts, multiplies, and inverts again, a softer add: `1 - (1 - a) * (1 - b)`.
+    ///
+    /// Each channel of both layers is clamped to between 0.0 and 1.0 first, so the result
+    /// is also between 0.0 and 1.0.
+    Screen,
+    /// Interpolates from `a` to `b` by an alpha (0.0 for only `a`, 1.0 for only `b`),
+    /// clamped to between 0.0 and 1.0.
+    Lerp(f32),
+}
+
+impl BlendMode {
+    /// Combines the colors of two layers.
+    ///
+    /// # Arguments
+    ///
+    /// - `a` - The bottom layer
+    /// - `b` - The top layer
+    pub fn blend(self, a: LinearSrgb, b: LinearSrgb) -> LinearSrgb {
+        match self {
+            BlendMode::Add => blend::add(a, b),
+            BlendMode::Multiply => blend::multiply(a, b),
+            BlendMode::Screen => blend::screen(a, b),
+            BlendMode::Lerp(alpha) => blend::lerp(a, b, alpha.clamp(0., 1.)),
+        }
+    }
+}
+
+/// Configuration parameters for a [`Blend`] of two patterns.
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct BlendParams<AParams, BParams> {
+    /// Parameters for the bottom pattern
+    pub a: AParams,
+    /// Parameters for the top pattern
+    pub b: BParams,
+    /// How to combine the patterns
+    pub mode: BlendMode,
+}
+
+/// A pattern which layers two patterns, combining their colors with a [`BlendMode`].
+///
+/// Unlike a transition, which crossfades from one pattern to another, a blend is
+/// permanent: both patterns are ticked every frame. For example, a slow noise base with
+/// sparkles on top.
+///
+/// # Type Parameters
+///
+/// - `A` - The bottom pattern
+/// - `B` - The top pattern
+///
+/// # Example
+///
+/// ```rust,ignore
+/// use blinksy::{
+///     ControlBuilder,
+///     control::BlendMode,
+///     layout::Layout1d,
+///     layout1d,
+///     patterns::{
+///         noise::{noise_fns, Noise1d, NoiseParams},
+///         meteor::{Meteor, MeteorParams},
+///     },
+/// };
+///
+/// layout1d!(Layout, 60);
+///
+/// let mut control = ControlBuilder::new_1d()
+///     .with_layout::<Layout, { Layout::PIXEL_COUNT }>()
+///     .with_blend::<Noise1d<noise_fns::Perlin>, Meteor>(
+///         NoiseParams::default(),
+///         MeteorParams::default(),
+///         BlendMode::Screen,
+///     )
+///     .with_driver(/* Your driver */)
+///     .with_frame_buffer_size::</* Length of frame buffer */>()
+///     .build();
+/// ```
+#[derive(Debug, Clone)]
+pub struct Blend<A, B> {
+    /// The bottom pattern
+    a: A,
+    /// The top pattern
+    b: B,
+    /// How to combine the patterns
+    mode: BlendMode,
+}
+
+impl<A, B> Blend<A, B> {
+    /// Returns the blend mode.
+    pub fn mode(&self) -> BlendMode {
+        self.mode
+    }
+
+    /// Sets the blend mode.
+    ///
+    /// # Arguments
+    ///
+    /// - `mode` - How to combine the patterns
+    pub fn set_mode(&mut self, mode: BlendMode) {
+        self.mode = mode;
+    }
+
+    /// Returns a mutable reference to the bottom pattern.
+    pub fn a_mut(&mut self) -> &mut A {
+        &mut self.a
+    }
+
+    /// Returns a mutable reference to the top pattern.
+    pub fn b_mut(&mut self) -> &mut B {
+        &mut self.b
+    }
+}
+
+impl<Dim, Layout, A, B> PatternTrait<Dim, Layout> for Blend<A, B>
+where
+    Layout: LayoutForDim<Dim>,
+    A: PatternTrait<Dim, Layout>,
+    B: PatternTrait<Dim, Layout>,
+    LinearSrgb: FromColor<A::Color> + FromColor<B::Color>,
+{
+    type Params = BlendParams<A::Params, B::Params>;
+    type Color = LinearSrgb;
+
+    /// Creates a new blend of two patterns.
+    fn new(params: Self::Params) -> Self {
+        Self {
+            a: A::new(params.a),
+            b: B::new(params.b),
+            mode: params.mode,
+        }
+    }
+
+    /// Ticks both patterns, combining their colors per pixel.
+    fn tick(&self, time_in_ms: u64) -> impl Iterator<Item = Self::Color> {
+        let mode = self.mode;
+        self.a
+            .tick(time_in_ms)
+            .zip(self.b.tick(time_in_ms))
+            .map(move |(a, b)| mode.blend(LinearSrgb::from_color(a), LinearSrgb::from_color(b)))
+    }
+
+    /// Ticks both patterns for a frame, combining their colors per pixel.
+    fn tick_frame(&self, frame: FrameTime) -> impl Iterator<Item = Self::Color> {
+        let mode = self.mode;
+        self.a
+            .tick_frame(frame)
+            .zip(self.b.tick_frame(frame))
+            .map(move |(a, b)| mode.blend(LinearSrgb::from_color(a), LinearSrgb::from_color(b)))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{control::test_patterns::Fill, layout1d, markers::Dim1d};
+
+    layout1d!(Layout, 2);
+
+    fn blend(mode: BlendMode) -> LinearSrgb {
+        let pattern: Blend<Fill, Fill> = PatternTrait::<Dim1d, Layout>::new(BlendParams {
+            a: LinearSrgb::new(0.5, 0.8, 0.),
+            b: LinearSrgb::new(0.5, 0.5, 1.),
+            mode,
+        });
+        let color = PatternTrait::<Dim1d, Layout>::tick(&pattern, 0)
+            .next()
+            .unwrap();
+        color
+    }
+
+    #[test]
+    fn test_blend_modes() {
+        // Add clamps at full brightness.
+        assert_eq!(blend(BlendMode::Add), LinearSrgb::new(1., 1., 1.));
+        assert_eq!(blend(BlendMode::Multiply), LinearSrgb::new(0.25, 0.4, 0.));
+        assert_eq!(blend(BlendMode::Screen), LinearSrgb::new(0.75, 0.9, 1.));
+        assert_eq!(blend(BlendMode::Lerp(0.5)), LinearSrgb::new(0.5, 0.65, 0.5));
+        assert_eq!(blend(BlendMode::Lerp(2.)), LinearSrgb::new(0.5, 0.5, 1.));
+    }
+}
@@ -0,0 +1,195 @@
+use crate::{
+    color::{FromColor, LinearSrgb},
+    layout::{Layout1d, Oversampled},
+    markers::Dim1d,
+    pattern::{FrameTime, Pattern as PatternTrait},
+};
+
+/// A pattern which renders another pattern at a higher resolution, then averages it down
+/// to the LEDs.
+///
+/// The wrapped pattern runs on an [`Oversampled`] layout, with `FACTOR` virtual LEDs in
+/// place of each LED. Each LED is the average of its virtual LEDs, in [`LinearSrgb`].
+///
+/// This anti-aliases sharp features on sparse strips: a comet or chase moves smoothly
+/// between LEDs, rather than flickering as it jumps from one LED to the next.
+///
+/// Note: Each frame ticks the wrapped pattern for `FACTOR` times as many LEDs.
+///
+/// # Type Parameters
+///
+/// - `FACTOR` - The number of virtual LEDs per LED
+/// - `Pattern` - The wrapped pattern
+///
+/// # Example
+///
+/// ```rust,ignore
+/// use blinksy::{
+///     ControlBuilder,
+///     control::Downsample,
+///     layout::Layout1d,
+///     layout1d,
+///     patterns::meteor::{Meteor, MeteorParams},
+/// };
+///
+/// layout1d!(Layout, 12);
+///
+/// let mut control = ControlBuilder::new_1d()
+///     .with_layout::<Layout, { Layout::PIXEL_COUNT }>()
+///     .with_pattern::<Downsample<8, Meteor>>(MeteorParams::default())
+///     .with_driver(/* Your driver */)
+///     .with_frame_buffer_size::</* Length of frame buffer */>()
+///     .build();
+/// ```
+#[derive(Debug, Clone)]
+pub struct Downsample<const FACTOR: usize, Pattern> {
+    /// The wrapped pattern
+    pattern: Pattern,
+}
+
+impl<const FACTOR: usize, Pattern> Downsample<FACTOR, Pattern> {
+    /// The number of virtual LEDs per LED.
+    pub const FACTOR: usize = FACTOR;
+
+    /// Returns a reference to the wrapped pattern.
+    pub fn pattern(&self) -> &Pattern {
+        &self.pattern
+    }
+
+    /// Returns a mutable reference to the wrapped pattern.
+    pub fn pattern_mut(&mut self) -> &mut Pattern {
+        &mut self.pattern
+    }
+
+    /// Averages each `FACTOR` virtual LEDs into one LED, for `pixel_count` LEDs.
+    fn average<Color>(
+        samples: impl Iterator<Item = Color>,
+        pixel_count: usize,
+    ) -> impl Iterator<Item = LinearSrgb>
+    where
+        LinearSrgb: FromColor<Color>,
+    {
+        let mut samples = samples.map(LinearSrgb::from_color);
+        (0..pixel_count).map(move |_| {
+            let (sum, count) = samples
+                .by_ref()
+                .take(FACTOR)
+                .fold((LinearSrgb::new(0., 0., 0.), 0), |(sum, count), sample| {
+                    (sum + sample, count + 1)
+                });
+            sum * (1. / count.max(1) as f32)
+        })
+    }
+}
+
+impl<const FACTOR: usize, Layout, Pattern> PatternTrait<Dim1d, Layout>
+    for Downsample<FACTOR, Pattern>
+where
+    Layout: Layout1d,
+    Pattern: PatternTrait<Dim1d, Oversampled<Layout, FACTOR>>,
+    LinearSrgb: FromColor<Pattern::Color>,
+{
+    type Params = Pattern::Params;
+    type Color = LinearSrgb;
+
+    /// Creates the wrapped pattern, on the oversampled layout.
+    fn new(params: Self::Params) -> Self {
+        Self {
+            pattern: Pattern::new(params),
+        }
+    }
+
+    /// Ticks the wrapped pattern, averaging each LED's virtual LEDs.
+    fn tick(&self, time_in_ms: u64) -> impl Iterator<Item = Self::Color> {
+        Self::average(self.pattern.tick(time_in_ms), Layout::PIXEL_COUNT)
+    }
+
+    /// Ticks the wrapped pattern for a frame, averaging each LED's virtual LEDs.
+    fn tick_frame(&self, frame: FrameTime) -> impl Iterator<Item = Self::Color> {
+        Self::average(self.pattern.tick_frame(frame), Layout::PIXEL_COUNT)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use heapless::Vec;
+
+    use super::*;
+    use crate::layout1d;
+
+    layout1d!(Layout, 2);
+
+    /// Pattern which lights each LED by its index: red on even LEDs, blue on odd LEDs.
+    #[derive(Debug, Clone)]
+    struct Alternate;
+
+    impl<Layout: Layout1d> PatternTrait<Dim1d, Layout> for Alternate {
+        type Params = ();
+        type Color = LinearSrgb;
+
+        fn new(_params: Self::Params) -> Self {
+            Self
+        }
+
+        fn tick(&self, _time_in_ms: u64) -> impl Iterator<Item = Self::Color> {
+            (0..Layout::PIXEL_COUNT).map(|index| {
+                if index % 2 == 0 {
+                    LinearSrgb::new(1., 0., 0.)
+                } else {
+                    LinearSrgb::new(0., 0., 1.)
+                }
+            })
+        }
+    }
+
+    #[test]
+    fn test_2x_downsample_averages_adjacent_samples() {
+        assert_eq!(Downsample::<2, Alternate>::FACTOR, 2);
+
+        let pattern: Downsample<2, Alternate> = PatternTrait::<Dim1d, Layout>::new(());
+        let colors: Vec<LinearSrgb, 2> = PatternTrait::<Dim1d, Layout>::tick(&pattern, 0).collect();
+
+        assert_eq!(
+            colors.as_slice(),
+            [LinearSrgb::new(0.5, 0., 0.5), LinearSrgb::new(0.5, 0., 0.5)]
+        );
+    }
+
+    /// Pattern which lights every LED white.
+    #[derive(Debug, Clone)]
+    struct White;
+
+    impl<Layout: Layout1d> PatternTrait<Dim1d, Layout> for White {
+        type Params = ();
+        type Color = LinearSrgb;
+
+        fn new(_params: Self::Params) -> Self {
+            Self
+        }
+
+        fn tick(&self, _time_in_ms: u64) -> impl Iterator<Item = Self::Color> {
+            (0..Layout::PIXEL_COUNT).map(|_| LinearSrgb::new(1., 1., 1.))
+        }
+    }
+
+    #[test]
+    fn test_bright_average_is_not_clamped() {
+        // The sum of the samples is above 1.0, so must not be clamped before averaging.
+        let pattern: Downsample<4, White> = PatternTrait::<Dim1d, Layout>::new(());
+        let colors: Vec<LinearSrgb, 2> = PatternTrait::<Dim1d, Layout>::tick(&pattern, 0).collect();
+
+        assert_eq!(
+            colors.as_slice(),
+            [LinearSrgb::new(1., 1., 1.), LinearSrgb::new(1., 1., 1.)]
+        );
+    }
+
+    #[test]
+    fn test_oversampled_points_stay_in_range() {
+        let points: Vec<f32, 8> = <Oversampled<Layout, 4> as Layout1d>::points().collect();
+
+        assert!(points.iter().all(|x| (-1. ..=1.).contains(x)));
+        assert_eq!(points[0], -1.);
+        assert_eq!(points[7], 1.);
+    }
+}
@@ -0,0 +1,266 @@
+use core::f32::consts::TAU;
+
+#[allow(unused_imports)]
+use num_traits::Float;
+
+use crate::{
+    color::{FromColor, LinearSrgb},
+    layout::LayoutForDim,
+    pattern::{FrameTime, Pattern as PatternTrait},
+};
+
+/// The shape of an [`Envelope`]: a level from 0.0 to 1.0 over time, which repeats.
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub enum EnvelopeShape {
+    /// A smooth pulse: 0.0 at the start of each period, 1.0 halfway through.
+    Sine {
+        /// The duration of each pulse, in milliseconds
+        period_in_ms: u64,
+    },
+    /// A linear ramp up and down: 0.0 at the start of each period, 1.0 halfway through.
+    Triangle {
+        /// The duration of each ramp up and down, in milliseconds
+        period_in_ms: u64,
+    },
+    /// An attack, decay, sustain, release envelope, like a note on a synthesizer.
+    ///
+    /// Each note rises from 0.0 to 1.0 over the attack, falls to the sustain level over the
+    /// decay, holds, then falls to 0.0 over the release. The next note starts straight
+    /// after.
+    Adsr {
+        /// How long to rise from 0.0 to 1.0, in milliseconds
+        attack_in_ms: u64,
+        /// How long to fall from 1.0 to the sustain level, in milliseconds
+        decay_in_ms: u64,
+        /// The level to hold (0.0 to 1.0)
+        sustain: f32,
+        /// How long to hold the sustain level, in milliseconds
+        sustain_in_ms: u64,
+        /// How long to fall from the sustain level to 0.0, in milliseconds
+        release_in_ms: u64,
+    },
+}
+
+impl EnvelopeShape {
+    /// Returns the level of the envelope at a time, from 0.0 to 1.0.
+    ///
+    /// An envelope with a duration of zero is always 1.0.
+    ///
+    /// # Arguments
+    ///
+    /// - `time_in_ms` - The current time in milliseconds
+    pub fn level(&self, time_in_ms: u64) -> f32 {
+        match *self {
+            EnvelopeShape::Sine { period_in_ms } => match phase(time_in_ms, period_in_ms) {
+                Some(phase) => 0.5 - 0.5 * (TAU * phase).cos(),
+                None => 1.,
+            },
+            EnvelopeShape::Triangle { period_in_ms } => match phase(time_in_ms, period_in_ms) {
+                Some(phase) => 1. - (2. * phase - 1.).abs(),
+                None => 1.,
+            },
+            EnvelopeShape::Adsr {
+                attack_in_ms,
+                decay_in_ms,
+                sustain,
+                sustain_in_ms,
+                release_in_ms,
+            } => {
+                let period_in_ms = attack_in_ms + decay_in_ms + sustain_in_ms + release_in_ms;
+                if period_in_ms == 0 {
+                    return 1.;
+                }
+                let sustain = sustain.clamp(0., 1.);
+                // Progress through a stage, from 0.0 to 1.0.
+                let progress = |elapsed_in_ms: u64, duration_in_ms: u64| {
+                    elapsed_in_ms as f32 / duration_in_ms as f32
+                };
+
+                let mut elapsed_in_ms = time_in_ms % period_in_ms;
+                if elapsed_in_ms < attack_in_ms {
+                    return progress(elapsed_in_ms, attack_in_ms);
+                }
+                elapsed_in_ms -= attack_in_ms;
+                if elapsed_in_ms < decay_in_ms {
+                    return 1. - (1. - sustain) * progress(elapsed_in_ms, decay_in_ms);
+                }
+                elapsed_in_ms -= decay_in_ms;
+                if elapsed_in_ms < sustain_in_ms {
+                    return sustain;
+                }
+                elapsed_in_ms -= sustain_in_ms;
+                sustain * (1. - progress(elapsed_in_ms, release_in_ms))
+            }
+        }
+    }
+}
+
+/// Returns the phase (0.0 to 1.0) through a period, or `None` if the period is zero.
+fn phase(time_in_ms: u64, period_in_ms: u64) -> Option<f32> {
+    if period_in_ms == 0 {
+        return None;
+    }
+    Some((time_in_ms % period_in_ms) as f32 / period_in_ms as f32)
+}
+
+/// Configuration parameters for an [`Envelope`] around a pattern.
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct EnvelopeParams<Params> {
+    /// Parameters for the wrapped pattern
+    pub pattern: Params,
+    /// The shape of the envelope
+    pub shape: EnvelopeShape,
+}
+
+/// A pattern which scales the brightness of another pattern by an [`EnvelopeShape`] over
+/// time.
+///
+/// The colors of the wrapped pattern are converted to [`LinearSrgb`] and multiplied by the
+/// level of the envelope, so any pattern can pulse or swell without changing it.
+///
+/// # Type Parameters
+///
+/// - `Pattern` - The wrapped pattern
+///
+/// # Example
+///
+/// ```rust,ignore
+/// use blinksy::{
+///     ControlBuilder,
+///     control::EnvelopeShape,
+///     layout::Layout1d,
+///     layout1d,
+///     patterns::rainbow::{Rainbow, RainbowParams},
+/// };
+///
+/// layout1d!(Layout, 60);
+///
+/// let mut control = ControlBuilder::new_1d()
+///     .with_layout::<Layout, { Layout::PIXEL_COUNT }>()
+///     .with_envelope::<Rainbow>(
+///         RainbowParams::default(),
+///         EnvelopeShape::Sine { period_in_ms: 2000 },
+///     )
+///     .with_driver(/* Your driver */)
+///     .with_frame_buffer_size::</* Length of frame buffer */>()
+///     .build();
+/// ```
+#[derive(Debug, Clone)]
+pub struct Envelope<Pattern> {
+    /// The wrapped pattern
+    pattern: Pattern,
+    /// The shape of the envelope
+    shape: EnvelopeShape,
+}
+
+impl<Pattern> Envelope<Pattern> {
+    /// Returns the shape of the envelope.
+    pub fn shape(&self) -> EnvelopeShape {
+        self.shape
+    }
+
+    /// Sets the shape of the envelope.
+    ///
+    /// # Arguments
+    ///
+    /// - `shape` - The shape of the envelope
+    pub fn set_shape(&mut self, shape: EnvelopeShape) {
+        self.shape = shape;
+    }
+
+    /// Returns a mutable reference to the wrapped pattern.
+    pub fn pattern_mut(&mut self) -> &mut Pattern {
+        &mut self.pattern
+    }
+}
+
+impl<Dim, Layout, Pattern> PatternTrait<Dim, Layout> for Envelope<Pattern>
+where
+    Layout: LayoutForDim<Dim>,
+    Pattern: PatternTrait<Dim, Layout>,
+    LinearSrgb: FromColor<Pattern::Color>,
+{
+    type Params = EnvelopeParams<Pattern::Params>;
+    type Color = LinearSrgb;
+
+    /// Creates a new envelope around a pattern.
+    fn new(params: Self::Params) -> Self {
+        Self {
+            pattern: Pattern::new(params.pattern),
+            shape: params.shape,
+        }
+    }
+
+    /// Ticks the wrapped pattern, scaling its colors by the level of the envelope.
+    fn tick(&self, time_in_ms: u64) -> impl Iterator<Item = Self::Color> {
+        let level = self.shape.level(time_in_ms);
+        self.pattern
+            .tick(time_in_ms)
+            .map(move |color| LinearSrgb::from_color(color) * level)
+    }
+
+    /// Ticks the wrapped pattern for a frame, scaling its colors by the level of the
+    /// envelope.
+    fn tick_frame(&self, frame: FrameTime) -> impl Iterator<Item = Self::Color> {
+        let level = self.shape.level(frame.time_in_ms);
+        self.pattern
+            .tick_frame(frame)
+            .map(move |color| LinearSrgb::from_color(color) * level)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{control::test_patterns::Fill, layout1d, markers::Dim1d};
+
+    layout1d!(Layout, 2);
+
+    #[test]
+    fn test_sine_envelope() {
+        let pattern: Envelope<Fill> = PatternTrait::<Dim1d, Layout>::new(EnvelopeParams {
+            pattern: LinearSrgb::new(1., 0.5, 0.),
+            shape: EnvelopeShape::Sine { period_in_ms: 1000 },
+        });
+        let color = |time_in_ms: u64| {
+            let color = PatternTrait::<Dim1d, Layout>::tick(&pattern, time_in_ms)
+                .next()
+                .unwrap();
+            color
+        };
+
+        // Minimum at the start of each period, maximum halfway through.
+        assert_eq!(color(0), LinearSrgb::new(0., 0., 0.));
+        assert_eq!(color(500), LinearSrgb::new(1., 0.5, 0.));
+        assert_eq!(color(1000), LinearSrgb::new(0., 0., 0.));
+        assert_eq!(color(2500), LinearSrgb::new(1., 0.5, 0.));
+        assert!((color(250).red - 0.5).abs() < 1e-6);
+    }
+
+    #[test]
+    fn test_triangle_and_adsr_levels() {
+        let triangle = EnvelopeShape::Triangle { period_in_ms: 100 };
+        assert_eq!(triangle.level(0), 0.);
+        assert_eq!(triangle.level(25), 0.5);
+        assert_eq!(triangle.level(50), 1.);
+        assert_eq!(triangle.level(75), 0.5);
+
+        let adsr = EnvelopeShape::Adsr {
+            attack_in_ms: 10,
+            decay_in_ms: 10,
+            sustain: 0.5,
+            sustain_in_ms: 20,
+            release_in_ms: 10,
+        };
+        assert_eq!(adsr.level(0), 0.);
+        assert_eq!(adsr.level(5), 0.5);
+        assert_eq!(adsr.level(10), 1.);
+        assert_eq!(adsr.level(15), 0.75);
+        assert_eq!(adsr.level(30), 0.5);
+        assert_eq!(adsr.level(45), 0.25);
+        // The next note starts after the release.
+        assert_eq!(adsr.level(55), 0.5);
+    }
+}
@@ -0,0 +1,2544 @@
+//! # Control System
+//!
+//! [`Control`] is the central control system for Blinksy: connecting a layout,
+//! pattern, and driver together to form a complete LED control pipeline.
+//!
+//! As [`Control`] has a complex generic type signature, [`ControlBuilder`] is a
+//! builder to help you create [`Control`] instances.
+//!
+//! # Example (Blocking)
+//!
+//! ```rust,ignore
+//! // Define a 1d layout of 60 LEDs
+//! layout1d!(Layout, 60);
+//!
+//! // Create a control system
+//! let mut control = ControlBuilder::new_1d()
+//!     .with_layout::<Layout, { Layout::PIXEL_COUNT }>()
+//!     // Choose an animation pattern
+//!     .with_pattern::</* Pattern type */>(/* Pattern params */)
+//!     // Choose an LED driver
+//!     .with_driver(/* LED driver */)
+//!     // Set frame buffer size for your driver and LEDs
+//!     .with_frame_buffer_size::</* Length of frame buffer */>()
+//!     .build();
+//!
+//! // Use the control system
+//! control.set_brightness(0.5);
+//!
+//! // Main control loop
+//! loop {
+//!     control.tick(/* current time in milliseconds */).unwrap();
+//! }
+//! ```
+//!
+//! # Example (Async)
+//!
+//! ```rust,ignore
+//! // Define a 1d layout of 60 LEDs
+//! layout1d!(Layout, 60);
+//!
+//! // Create a control system
+//! let mut control = ControlBuilder::new_1d_async()
+//!     .with_layout::<Layout, { Layout::PIXEL_COUNT }>()
+//!     // Choose an animation pattern
+//!     .with_pattern::</* Pattern type */>(/* Pattern params */)
+//!     // Choose a driver type
+//!     .with_driver(/* LED driver */)
+//!     // Set frame buffer size for your driver and LEDs
+//!     .with_frame_buffer_size::</* Length of frame buffer */>()
+//!     .build();
+//!
+//! // Use the control system
+//! control.set_brightness(0.5);
+//!
+//! // Main control loop
+//! loop {
+//!     control.tick(/* current time in milliseconds */).await.unwrap();
+//! }
+//! ```
+use core::marker::PhantomData;
+
+use heapless::Vec;
+
+use crate::{
+    color::{blend::lerp, ColorCorrection, FromColor, LinearSrgb, WhiteMode},
+    driver::Driver as DriverTrait,
+    layout::LayoutForDim,
+    markers::{Blocking, Dim1d, Dim2d, Dim3d, Set, Unset},
+    pattern::{FrameTime, Pattern as PatternTrait},
+    patterns::switch::PatternSwitch,
+    time::{ms_to_frames, TimeSource},
+};
+#[cfg(feature = "async")]
+use crate::{driver::DriverAsync as DriverAsyncTrait, markers::Async};
+
+mod blend;
+mod downsample;
+mod envelope;
+mod segment;
+#[cfg(test)]
+mod test_patterns;
+
+pub use self::blend::*;
+pub use self::downsample::*;
+pub use self::envelope::*;
+pub use self::segment::*;
+
+/// Central LED control system.
+///
+/// A [`Control`] is made up of:
+///
+/// - A [`layout`](crate::layout)
+/// - A [`pattern`](crate::pattern)
+/// - A [`driver`](crate::driver)
+///
+/// You can use [`Control`] to
+///
+/// - Set a global brightness, on a linear or perceptual curve
+/// - Set a global color correction.
+/// - Tint the output toward a white point, as a color temperature.
+/// - Choose how to extract the white channel for RGBW LEDs.
+/// - Send a frame of colors from the pattern to the driver.
+/// - Turn off the LEDs, such as on shutdown.
+/// - Snapshot and restore its state, such as for presets.
+/// - Crossfade to new pattern parameters.
+/// - Fade in from black, and fade out to black.
+/// - Limit the rate of frames sent to the driver.
+/// - Retry or ignore errors from the driver.
+/// - Limit the estimated power draw of the LEDs.
+/// - Dim or mask each pixel.
+/// - Blur each pixel with its neighbors.
+/// - Hold a constant test frame, such as for burn-in.
+/// - Pull the current time from a [`TimeSource`].
+/// - Render a frame into a buffer, without driving the LEDs.
+/// - Switch between patterns at runtime, with a [`PatternSwitch`].
+///
+/// Note: [`Control`] owns a frame buffer of `PIXEL_COUNT` [`LinearSrgb`] colors (12 bytes
+/// per pixel), which each tick renders into in place. To measure a tick, run
+/// `cargo bench -p blinksy --bench control`.
+///
+/// Tip: Use [`ControlBuilder`] to build your [`Control`] struct.
+///
+// # Type Parameters
+//
+// * `PIXEL_COUNT` - The number of LEDs in the layout
+// * `FRAME_BUFFER_SIZE` - The per-call frame buffer size used by the driver
+// * `Dim` - The dimension marker ([`Dim1d`] or [`Dim2d`] or [`Dim3d`])
+// * `Exec` - The execution mode marker ([`Blocking`] or `Async`)
+// * `Layout` - The [`layout`](crate::layout) type
+// * `Pattern` - The [`pattern`](crate::pattern) type
+// * `Driver` - The LED [`driver`](crate::driver) type
+// * `Time` - The [`TimeSource`] type, or `()` if none
+pub struct Control<
+    const PIXEL_COUNT: usize,
+    const FRAME_BUFFER_SIZE: usize,
+    Dim,
+    Exec,
+    Layout,
+    Pattern,
+    Driver,
+    Time = (),
+> where
+    Layout: LayoutForDim<Dim>,
+    Pattern: PatternTrait<Dim, Layout>,
+{
+    dim: PhantomData<Dim>,
+    exec: PhantomData<Exec>,
+    layout: PhantomData<Layout>,
+    pattern: Pattern,
+    driver: Driver,
+    brightness: f32,
+    brightness_curve: BrightnessCurve,
+    correction: ColorCorrection,
+    white_point: Option<f32>,
+    white_mode: Option<WhiteMode>,
+    transition: Option<Transition<Pattern>>,
+    max_fps: Option<u32>,
+    error_policy: ErrorPolicy,
+    last_render_in_ms: Option<u64>,
+    frame_count: u64,
+    power_limit: Option<PowerLimit>,
+    estimated_milliamps: Option<f32>,
+    mask: Option<[f32; PIXEL_COUNT]>,
+    blur: f32,
+    test_frame: Option<LinearSrgb>,
+    is_static: bool,
+    fade: Option<Fade>,
+    frame: Vec<LinearSrgb, PIXEL_COUNT>,
+    time_source: Time,
+}
+
+impl<
+        const PIXEL_COUNT: usize,
+        const FRAME_BUFFER_SIZE: usize,
+        Dim,
+        Exec,
+        Layout,
+        Pattern,
+        Driver,
+    > Control<PIXEL_COUNT, FRAME_BUFFER_SIZE, Dim, Exec, Layout, Pattern, Driver>
+where
+    Layout: LayoutForDim<Dim>,
+    Pattern: PatternTrait<Dim, Layout>,
+{
+    /// Creates a new control system.
+    ///
+    /// # Arguments
+    ///
+    /// - `pattern` - The pattern to use
+    /// - `driver` - The LED driver to use
+    ///
+    /// # Returns
+    ///
+    /// A new Control instance with default brightness
+    pub fn new(pattern: Pattern, driver: Driver) -> Self {
+        Self {
+            dim: PhantomData,
+            exec: PhantomData,
+            layout: PhantomData,
+            pattern,
+            driver,
+            brightness: 1.0,
+            brightness_curve: BrightnessCurve::default(),
+            correction: ColorCorrection::default(),
+            white_point: None,
+            white_mode: None,
+            transition: None,
+            max_fps: None,
+            error_policy: ErrorPolicy::default(),
+            last_render_in_ms: None,
+            frame_count: 0,
+            power_limit: None,
+            estimated_milliamps: None,
+            mask: None,
+            blur: 0.,
+            test_frame: None,
+            is_static: false,
+            fade: None,
+            frame: Vec::new(),
+            time_source: (),
+        }
+    }
+}
+
+impl<
+        const PIXEL_COUNT: usize,
+        const FRAME_BUFFER_SIZE: usize,
+        Dim,
+        Exec,
+        Layout,
+        Pattern,
+        Driver,
+        Time,
+    > Control<PIXEL_COUNT, FRAME_BUFFER_SIZE, Dim, Exec, Layout, Pattern, Driver, Time>
+where
+    Layout: LayoutForDim<Dim>,
+    Pattern: PatternTrait<Dim, Layout>,
+{
+    /// Sets the source of the current time, for [`Control::tick_auto`].
+    ///
+    /// The time source must be monotonic: the time must never go backwards.
+    ///
+    /// # Arguments
+    ///
+    /// - `time_source` - The [`TimeSource`], such as a function returning the time since
+    ///   boot in milliseconds
+    ///
+    /// # Example
+    ///
+    /// ```rust,ignore
+    /// let mut control = ControlBuilder::new_1d()
+    ///     /* ... */
+    ///     .build()
+    ///     .with_time_source(|| /* current time in milliseconds */);
+    ///
+    /// loop {
+    ///     control.tick_auto().unwrap();
+    /// }
+    /// ```
+    pub fn with_time_source<NewTime: TimeSource>(
+        self,
+        time_source: NewTime,
+    ) -> Control<PIXEL_COUNT, FRAME_BUFFER_SIZE, Dim, Exec, Layout, Pattern, Driver, NewTime> {
+        let Self {
+            dim,
+            exec,
+            layout,
+            pattern,
+            driver,
+            brightness,
+            brightness_curve,
+            correction,
+            white_point,
+            white_mode,
+            transition,
+            max_fps,
+            error_policy,
+            last_render_in_ms,
+            frame_count,
+            power_limit,
+            estimated_milliamps,
+            mask,
+            blur,
+            test_frame,
+            is_static,
+            fade,
+            frame,
+            time_source: _,
+        } = self;
+        Control {
+            dim,
+            exec,
+            layout,
+            pattern,
+            driver,
+            brightness,
+            brightness_curve,
+            correction,
+            white_point,
+            white_mode,
+            transition,
+            max_fps,
+            error_policy,
+            last_render_in_ms,
+            frame_count,
+            power_limit,
+            estimated_milliamps,
+            mask,
+            blur,
+            test_frame,
+            is_static,
+            fade,
+            frame,
+            time_source,
+        }
+    }
+
+    /// Sets the overall brightness level.
+    ///
+    /// The level is mapped through the brightness curve (see
+    /// [`Control::set_brightness_curve`]) to a multiplier of the output.
+    ///
+    /// # Arguments
+    ///
+    /// - `brightness` - Brightness level from 0.0 (off) to 1.0 (full)
+    pub fn set_brightness(&mut self, brightness: f32) {
+        self.brightness = brightness;
+    }
+
+    /// Returns the overall brightness level, from 0.0 (off) to 1.0 (full).
+    pub fn brightness(&self) -> f32 {
+        self.brightness
+    }
+
+    /// Sets the curve which maps the brightness level to a multiplier of the output.
+    ///
+    /// Defaults to [`BrightnessCurve::Linear`]. Use [`BrightnessCurve::Perceptual`] so
+    /// brightness levels look evenly spaced, such as for a dimmer knob.
+    ///
+    /// # Arguments
+    ///
+    /// - `curve` - The brightness curve
+    pub fn set_brightness_curve(&mut self, curve: BrightnessCurve) {
+        self.brightness_curve = curve;
+    }
+
+    /// Sets a color correction.
+    ///
+    /// # Arguments
+    ///
+    /// - `correction` - Color correction factors
+    pub fn set_color_correction(&mut self, correction: ColorCorrection) {
+        self.correction = correction;
+    }
+
+    /// Sets a white point: tints the whole output toward a color temperature.
+    ///
+    /// The RGB factors of the color correction are multiplied by the linear color of a
+    /// black-body radiator at the color temperature (see [`temperature::from_kelvin`]), so
+    /// full white is output as that color.
+    ///
+    /// The white point is kept separate from the color correction: setting a color correction
+    /// with [`Control::set_color_correction`] keeps the white point, and the white point is
+    /// always applied on top of the current color correction. Since both are multiplicative,
+    /// the order they are set in doesn't matter.
+    ///
+    /// # Arguments
+    ///
+    /// - `kelvin` - Color temperature in Kelvin, clamped to 1000K to 12000K
+    ///
+    /// [`temperature::from_kelvin`]: crate::color::temperature::from_kelvin
+    pub fn set_white_point(&mut self, kelvin: f32) {
+        self.white_point = Some(kelvin);
+    }
+
+    /// Clears the white point, so the output is only tinted by the color correction.
+    pub fn clear_white_point(&mut self) {
+        self.white_point = None;
+    }
+
+    /// Sets how to extract the white channel of RGBW LEDs from the RGB colors of the
+    /// pattern.
+    ///
+    /// Like the white point, this is kept separate from the color correction: it overrides
+    /// the [`ColorCorrection::white_mode`] of the current color correction. RGB LEDs are
+    /// unaffected.
+    ///
+    /// # Arguments
+    ///
+    /// - `white_mode` - How to extract the white channel
+    pub fn set_white_mode(&mut self, white_mode: WhiteMode) {
+        self.white_mode = Some(white_mode);
+    }
+
+    /// Sets how errors from the driver are handled on each tick.
+    ///
+    /// Defaults to [`ErrorPolicy::Propagate`].
+    ///
+    /// Only ignore errors you know are transient, such as a dropped transmission: with
+    /// [`ErrorPolicy::Ignore`], a driver which always fails leaves the LEDs stuck on the
+    /// last frame written, with no error to notice.
+    ///
+    /// # Arguments
+    ///
+    /// - `policy` - The error policy
+    pub fn set_error_policy(&mut self, policy: ErrorPolicy) {
+        self.error_policy = policy;
+    }
+
+    /// Sets the maximum rate of frames sent to the driver.
+    ///
+    /// When limited, frames are rendered on a fixed timestep of `fps` frames per second,
+    /// measured from the `time_in_ms` given to each tick. A tick before the next frame is
+    /// due does nothing, so you can keep calling tick in a tight loop. If the time goes back,
+    /// such as after the clock is reset, the timestep starts again from the new time.
+    ///
+    /// # Arguments
+    ///
+    /// - `fps` - Maximum frames per second, or `0` for no limit
+    pub fn set_max_fps(&mut self, fps: u32) {
+        self.max_fps = if fps == 0 { None } else { Some(fps) };
+    }
+
+    /// Sets a limit on the estimated current drawn by the LEDs.
+    ///
+    /// Before each frame is written, the current is estimated from the colors of every
+    /// channel, after brightness and color correction. If the estimate exceeds the limit,
+    /// the brightness of that frame is scaled down to stay within the limit.
+    ///
+    /// # Arguments
+    ///
+    /// - `max_milliamps` - Maximum current for all LEDs, in milliamps, or `0` for no limit
+    /// - `milliamps_per_channel` - Current of a single channel at full power, in milliamps
+    ///   (commonly 20 mA for WS2812 LEDs)
+    pub fn set_power_limit(&mut self, max_milliamps: u32, milliamps_per_channel: f32) {
+        self.power_limit = if max_milliamps == 0 {
+            None
+        } else {
+            Some(PowerLimit {
+                max_milliamps,
+                milliamps_per_channel,
+            })
+        };
+        self.estimated_milliamps = None;
+    }
+
+    /// Returns the estimated current drawn by the LEDs for the last frame, in milliamps.
+    ///
+    /// Only estimated while a power limit is set, see [`Control::set_power_limit`].
+    pub fn estimated_milliamps(&self) -> Option<f32> {
+        self.estimated_milliamps
+    }
+
+    /// Sets a per-pixel brightness mask.
+    ///
+    /// After the pattern generates colors, each pixel is multiplied (in [`LinearSrgb`]) by
+    /// its value in the mask, from 0.0 (masked) to 1.0 (unchanged). This can dim or mask
+    /// regions of the layout without changing the pattern, such as to fade the edges of a
+    /// panel.
+    ///
+    /// The mask should have exactly `PIXEL_COUNT` values, in the order of the layout. If
+    /// the mask is longer, the extra values are ignored. If the mask is shorter, the
+    /// remaining pixels are unchanged.
+    ///
+    /// Note: [`Control`] copies the mask into `PIXEL_COUNT` floats of its own.
+    ///
+    /// # Arguments
+    ///
+    /// - `mask` - Multiplier for each pixel (0.0 to 1.0)
+    pub fn set_mask(&mut self, mask: &[f32]) {
+        let mut values = [1.; PIXEL_COUNT];
+        for (value, mask) in values.iter_mut().zip(mask) {
+            *value = mask.clamp(0., 1.);
+        }
+        self.mask = Some(values);
+    }
+
+    /// Removes the per-pixel brightness mask.
+    pub fn clear_mask(&mut self) {
+        self.mask = None;
+    }
+
+    /// Sets how much to blur each pixel with its neighbors.
+    ///
+    /// After the pattern generates colors, each pixel is blended (in [`LinearSrgb`]) toward
+    /// the average of its adjacent pixels, from 0.0 (no blur) to 1.0 (only the average). This
+    /// softens hard edges, such as from index-based patterns on 2D grids.
+    ///
+    /// Blur requires neighbor data from the layout, see [`LayoutForDim::adjacent`]: In a 1D
+    /// layout, pixels are adjacent to the pixels before and after them. In a 2D or 3D
+    /// layout, pixels are only adjacent to pixels within the same shape, so a layout of
+    /// single points isn't blurred.
+    ///
+    /// Note: While blurred, [`Control`] copies each frame into a buffer of `PIXEL_COUNT`
+    /// [`LinearSrgb`] colors on the stack.
+    ///
+    /// # Arguments
+    ///
+    /// - `amount` - How much to blur (0.0 to 1.0), clamped
+    pub fn set_blur(&mut self, amount: f32) {
+        self.blur = amount.clamp(0., 1.);
+    }
+
+    /// Sets a constant test frame, or resumes the pattern.
+    ///
+    /// While a test frame is set, each tick shows the color on every pixel, bypassing the
+    /// pattern (and any transition or mask). Brightness, color correction, and any power
+    /// limit still apply. This is useful to validate power supplies and thermals.
+    ///
+    /// A test frame is rendered into the same buffer as a static frame (see
+    /// [`Control::play_static`]), so setting a test frame ends any static frame.
+    ///
+    /// # Arguments
+    ///
+    /// - `color` - The color for every pixel, or `None` to resume the pattern
+    pub fn set_test_frame(&mut self, color: Option<LinearSrgb>) {
+        if color.is_some() {
+            self.is_static = false;
+        }
+        self.test_frame = color;
+    }
+
+    /// Returns a copy of the last rendered frame.
+    ///
+    /// The colors are as rendered by [`Control::tick`]: after the pattern, any transition,
+    /// blur, mask, test frame, or static frame, but before brightness, color correction,
+    /// fade, and power limit, which are applied by the driver. Before the first tick, the
+    /// frame is empty.
+    ///
+    /// Give the frame to [`Control::play_static`] to hold or replay it later.
+    pub fn snapshot_frame(&self) -> Vec<LinearSrgb, PIXEL_COUNT> {
+        self.frame.clone()
+    }
+
+    /// Sets a static frame to show, or resumes the pattern.
+    ///
+    /// While a static frame is set, each tick shows the frame as is, bypassing the pattern
+    /// (and any transition, blur, or mask), until changed. Brightness, color correction,
+    /// fade, and any power limit still apply. This is useful to freeze the LEDs while
+    /// reconfiguring the pattern.
+    ///
+    /// A frame with fewer than `PIXEL_COUNT` colors is padded with black.
+    ///
+    /// The frame is copied into the buffer of the last rendered frame, so holding a static
+    /// frame costs no more memory. A test frame (see [`Control::set_test_frame`]) is
+    /// rendered into the same buffer, so playing a static frame ends any test frame.
+    ///
+    /// # Arguments
+    ///
+    /// - `frame` - The frame to show, such as from [`Control::snapshot_frame`], or `None`
+    ///   to resume the pattern
+    pub fn play_static(&mut self, frame: Option<Vec<LinearSrgb, PIXEL_COUNT>>) {
+        self.is_static = frame.is_some();
+        if let Some(frame) = frame {
+            self.test_frame = None;
+            self.frame = frame;
+            let padding = PIXEL_COUNT - self.frame.len();
+            self.frame
+                .extend(core::iter::repeat_n(LinearSrgb::new(0., 0., 0.), padding));
+        }
+    }
+
+    /// Returns a reference to the pattern.
+    pub fn pattern(&self) -> &Pattern {
+        &self.pattern
+    }
+
+    /// Returns a mutable reference to the pattern.
+    ///
+    /// Useful for patterns which receive input, such as
+    /// [`TouchZones`](crate::patterns::touch::TouchZones).
+    pub fn pattern_mut(&mut self) -> &mut Pattern {
+        &mut self.pattern
+    }
+
+    /// Returns a reference to the driver.
+    ///
+    /// Useful for drivers which record their output, such as
+    /// `MockDriver` (with the `mock` feature).
+    pub fn driver(&self) -> &Driver {
+        &self.driver
+    }
+
+    /// Returns a mutable reference to the driver.
+    pub fn driver_mut(&mut self) -> &mut Driver {
+        &mut self.driver
+    }
+
+    /// Switches to the next pattern, wrapping around to the first.
+    ///
+    /// For patterns which switch between multiple patterns, such as
+    /// [`BuiltinSwitch`](crate::patterns::switch::BuiltinSwitch).
+    pub fn next_pattern(&mut self)
+    where
+        Pattern: PatternSwitch,
+    {
+        let index = self.pattern.pattern_index() + 1;
+        self.pattern.select_pattern(index);
+    }
+
+    /// Selects a pattern by index.
+    ///
+    /// For patterns which switch between multiple patterns, such as
+    /// [`BuiltinSwitch`](crate::patterns::switch::BuiltinSwitch).
+    ///
+    /// # Arguments
+    ///
+    /// - `index` - The index of the pattern, wrapped around the number of patterns
+    pub fn select_pattern(&mut self, index: usize)
+    where
+        Pattern: PatternSwitch,
+    {
+        self.pattern.select_pattern(index);
+    }
+
+    /// Captures the current state of the control system.
+    ///
+    /// The state includes everything which can be set on the control system: the
+    /// brightness and brightness curve, the color correction, the white point and white
+    /// mode, the frame rate limit, the error policy, the power limit, the mask, the blur,
+    /// any test frame, static frame, or fade, and the pattern (including its parameters).
+    ///
+    /// # Returns
+    ///
+    /// A [`ControlState`] which can be given to [`Control::restore`]
+    pub fn snapshot(&self) -> ControlState<PIXEL_COUNT, Pattern>
+    where
+        Pattern: Clone,
+    {
+        ControlState {
+            brightness: self.brightness,
+            brightness_curve: self.brightness_curve,
+            correction: self.correction,
+            white_point: self.white_point,
+            white_mode: self.white_mode,
+            max_fps: self.max_fps,
+            error_policy: self.error_policy,
+            power_limit: self.power_limit,
+            mask: self.mask,
+            blur: self.blur,
+            test_frame: self.test_frame,
+            static_frame: self.is_static.then(|| self.frame.clone()),
+            fade: self.fade,
+            pattern: self.pattern.clone(),
+        }
+    }
+
+    /// Restores a previously captured state of the control system.
+    ///
+    /// # Arguments
+    ///
+    /// - `state` - The state to restore, from [`Control::snapshot`]
+    pub fn restore(&mut self, state: ControlState<PIXEL_COUNT, Pattern>) {
+        let ControlState {
+            brightness,
+            brightness_curve,
+            correction,
+            white_point,
+            white_mode,
+            max_fps,
+            error_policy,
+            power_limit,
+            mask,
+            blur,
+            test_frame,
+            static_frame,
+            fade,
+            pattern,
+        } = state;
+        self.brightness = brightness;
+        self.brightness_curve = brightness_curve;
+        self.correction = correction;
+        self.white_point = white_point;
+        self.white_mode = white_mode;
+        self.max_fps = max_fps;
+        self.error_policy = error_policy;
+        self.power_limit = power_limit;
+        self.estimated_milliamps = None;
+        self.mask = mask;
+        self.blur = blur;
+        self.test_frame = test_frame;
+        self.play_static(static_frame);
+        self.fade = fade;
+        self.pattern = pattern;
+        self.transition = None;
+    }
+
+    /// Replaces the pattern with a new pattern with the given parameters.
+    ///
+    /// Any transition in progress is cancelled. To crossfade instead, see
+    /// [`Control::transition_to_params`].
+    ///
+    /// With the `serde` feature, the parameters of built-in patterns can be deserialized,
+    /// such as from a JSON preset.
+    ///
+    /// # Arguments
+    ///
+    /// - `params` - The parameters of the new pattern
+    pub fn set_params(&mut self, params: Pattern::Params) {
+        self.pattern = Pattern::new(params);
+        self.transition = None;
+    }
+
+    /// Crossfades from the current pattern to a new pattern with the given parameters.
+    ///
+    /// For the duration of the transition, both the outgoing and incoming patterns are
+    /// ticked, and their colors are blended per-pixel in [`LinearSrgb`]. The transition
+    /// starts on the next tick.
+    ///
+    /// Note: To hold both patterns during a transition, [`Control`] reserves memory for a
+    /// second instance of the pattern, and each transition frame costs two pattern ticks.
+    ///
+    /// # Arguments
+    ///
+    /// - `params` - The parameters of the incoming pattern
+    /// - `duration_in_ms` - The duration of the crossfade, in milliseconds
+    pub fn transition_to_params(&mut self, params: Pattern::Params, duration_in_ms: u64) {
+        let from = core::mem::replace(&mut self.pattern, Pattern::new(params));
+        self.transition = Some(Transition {
+            from,
+            duration_in_ms,
+            start_in_ms: None,
+        });
+    }
+
+    /// Fades the brightness in from black.
+    ///
+    /// The brightness ramps from zero to the brightness (see [`Control::set_brightness`]),
+    /// starting from the next tick, such as to avoid a sudden bright frame on power-up.
+    /// Replaces any fade in progress.
+    ///
+    /// # Arguments
+    ///
+    /// - `duration_in_ms` - The duration of the fade, in milliseconds
+    pub fn fade_in(&mut self, duration_in_ms: u64) {
+        self.fade = Some(Fade {
+            is_out: false,
+            duration_in_ms,
+            start_in_ms: None,
+        });
+    }
+
+    /// Fades the brightness out to black.
+    ///
+    /// The brightness ramps from the brightness (see [`Control::set_brightness`]) to zero,
+    /// starting from the next tick, then stays at zero until [`Control::fade_in`]. Replaces
+    /// any fade in progress.
+    ///
+    /// Use [`Control::is_faded_out`] to know when the fade is complete, such as to stop
+    /// ticking on shutdown.
+    ///
+    /// # Arguments
+    ///
+    /// - `duration_in_ms` - The duration of the fade, in milliseconds
+    pub fn fade_out(&mut self, duration_in_ms: u64) {
+        self.fade = Some(Fade {
+            is_out: true,
+            duration_in_ms,
+            start_in_ms: None,
+        });
+    }
+
+    /// Returns whether a fade out (see [`Control::fade_out`]) has completed, as of the last
+    /// frame shown.
+    pub fn is_faded_out(&self) -> bool {
+        match (self.fade, self.last_render_in_ms) {
+            (Some(fade), Some(time_in_ms)) => fade.is_out && fade.level(time_in_ms) <= 0.,
+            _ => false,
+        }
+    }
+
+    /// Returns the time of the next frame, at the given time.
+    fn next_frame_time(&self, time_in_ms: u64) -> FrameTime {
+        FrameTime {
+            time_in_ms,
+            frame: self.frame_count,
+            delta_in_ms: self.last_render_in_ms.map_or(0, |last_render_in_ms| {
+                time_in_ms.saturating_sub(last_render_in_ms)
+            }),
+        }
+    }
+
+    /// Returns whether a frame is due at the given time, and if so, records it as rendered.
+    ///
+    /// If the time is before the last frame, such as after the clock is reset, the frame is
+    /// due, so the fixed timestep starts again from the new time.
+    fn update_frame_due(&mut self, time_in_ms: u64) -> bool {
+        if let (Some(fps), Some(last_render_in_ms)) = (self.max_fps, self.last_render_in_ms) {
+            if time_in_ms >= last_render_in_ms
+                && ms_to_frames(time_in_ms, fps) <= ms_to_frames(last_render_in_ms, fps)
+            {
+                return false;
+            }
+        }
+        self.last_render_in_ms = Some(time_in_ms);
+        true
+    }
+
+    /// Returns the color correction for the output, tinted by any white point, with any
+    /// white mode.
+    fn output_correction(&self) -> ColorCorrection {
+        let correction = match self.white_point {
+            Some(kelvin) => self.correction.with_white_point(kelvin),
+            None => self.correction,
+        };
+        match self.white_mode {
+            Some(white_mode) => correction.with_white_mode(white_mode),
+            None => correction,
+        }
+    }
+
+    /// Returns the brightness for the frame within any power limit, recording the estimate.
+    fn limit_power(&mut self, brightness: f32) -> f32 {
+        let Some(power_limit) = self.power_limit else {
+            return brightness;
+        };
+        let correction = self.output_correction();
+        let brightness = power_limit.limit_brightness(&self.frame, brightness, correction);
+        self.estimated_milliamps = Some(power_limit.estimate(&self.frame, brightness, correction));
+        brightness
+    }
+
+    /// Starts the clock on a new transition, and ends a completed transition.
+    fn update_transition(&mut self, time_in_ms: u64) {
+        let Some(transition) = &mut self.transition else {
+            return;
+        };
+        if transition.start_in_ms.is_none() {
+            transition.start_in_ms = Some(time_in_ms);
+        }
+        if transition.alpha(time_in_ms) >= 1. {
+            self.transition = None;
+        }
+    }
+
+    /// Starts the clock on a new fade, and ends a completed fade in.
+    fn update_fade(&mut self, time_in_ms: u64) {
+        let Some(fade) = &mut self.fade else {
+            return;
+        };
+        if fade.start_in_ms.is_none() {
+            fade.start_in_ms = Some(time_in_ms);
+        }
+        if !fade.is_out && fade.level(time_in_ms) >= 1. {
+            self.fade = None;
+        }
+    }
+
+    /// Returns the brightness at a given time, with any fade applied.
+    fn faded_brightness(&self, time_in_ms: u64) -> f32 {
+        let brightness = self.brightness_curve.apply(self.brightness);
+        match &self.fade {
+            Some(fade) => brightness * fade.level(time_in_ms),
+            None => brightness,
+        }
+    }
+}
+
+/// A crossfade from an outgoing pattern to the current pattern.
+#[derive(Debug)]
+struct Transition<Pattern> {
+    /// The outgoing pattern
+    from: Pattern,
+    /// Duration of the crossfade, in milliseconds
+    duration_in_ms: u64,
+    /// The time at which the crossfade started, once ticked
+    start_in_ms: Option<u64>,
+}
+
+impl<Pattern> Transition<Pattern> {
+    /// Returns the blend alpha at a given time: 0.0 at the start, 1.0 at the end.
+    fn alpha(&self, time_in_ms: u64) -> f32 {
+        let start_in_ms = self.start_in_ms.unwrap_or(time_in_ms);
+        if self.duration_in_ms == 0 {
+            return 1.;
+        }
+        let elapsed_in_ms = time_in_ms.saturating_sub(start_in_ms);
+        (elapsed_in_ms as f32 / self.duration_in_ms as f32).min(1.)
+    }
+
+    /// Ticks the outgoing and incoming patterns, blending their colors.
+    fn tick<'a, Dim, Layout>(
+        &'a self,
+        to: &'a Pattern,
+        frame: FrameTime,
+    ) -> impl Iterator<Item = LinearSrgb> + 'a
+    where
+        Dim: 'a,
+        Layout: LayoutForDim<Dim> + 'a,
+        Pattern: PatternTrait<Dim, Layout>,
+        LinearSrgb: FromColor<Pattern::Color>,
+    {
+        let alpha = self.alpha(frame.time_in_ms);
+        self.from
+            .tick_frame(frame)
+            .zip(to.tick_frame(frame))
+            .map(move |(from, to)| {
+                lerp(
+                    LinearSrgb::from_color(from),
+                    LinearSrgb::from_color(to),
+                    alpha,
+                )
+            })
+    }
+}
+
+/// How a [`Control`] handles an error from its driver, on each tick.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum ErrorPolicy {
+    /// Return the error from the tick
+    #[default]
+    Propagate,
+    /// Show the frame again, and return the error if it fails again
+    RetryOnce,
+    /// Return `Ok(())`, so the LEDs hold the last frame written (with a warning logged,
+    /// with the `defmt` feature)
+    Ignore,
+}
+
+impl ErrorPolicy {
+    /// Returns the result of a tick, by the policy.
+    fn handle<Error>(self, result: Result<(), Error>) -> Result<(), Error> {
+        match result {
+            Err(_) if self == ErrorPolicy::Ignore => {
+                // Not in tests, which have no defmt logger to link.
+                #[cfg(all(feature = "defmt", not(test)))]
+                defmt::warn!("blinksy: ignored an error from the driver");
+                Ok(())
+            }
+            result => result,
+        }
+    }
+}
+
+/// How a [`Control`] maps its brightness level to a multiplier of the output.
+///
+/// The output of LEDs is proportional to their multiplier, but our eyes are more sensitive
+/// to changes in dim light than in bright light: a linear brightness of 0.5 looks much
+/// brighter than half.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum BrightnessCurve {
+    /// The multiplier is the brightness level
+    #[default]
+    Linear,
+    /// The brightness level is the perceived lightness (CIE 1976 L*, from 0.0 to 1.0), and
+    /// the multiplier is its relative luminance (CIE Y)
+    ///
+    /// So a brightness level of 0.5 looks about half as bright as 1.0, with a multiplier
+    /// of about 0.18.
+    Perceptual,
+}
+
+impl BrightnessCurve {
+    /// Maps a brightness level (0.0 to 1.0) to a multiplier of the output (0.0 to 1.0).
+    pub fn apply(self, brightness: f32) -> f32 {
+        match self {
+            BrightnessCurve::Linear => brightness,
+            BrightnessCurve::Perceptual => {
+                // CIE constant kappa, (29/3)^3.
+                const KAPPA: f32 = 903.3;
+                let lightness = brightness.clamp(0., 1.) * 100.;
+                if lightness <= 8. {
+                    lightness / KAPPA
+                } else {
+                    let cube_root = (lightness + 16.) / 116.;
+                    cube_root * cube_root * cube_root
+                }
+            }
+        }
+    }
+}
+
+/// A fade of the brightness in from black, or out to black.
+#[derive(Debug, Clone, Copy)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+struct Fade {
+    /// Whether fading out to black, rather than in from black
+    is_out: bool,
+    /// Duration of the fade, in milliseconds
+    duration_in_ms: u64,
+    /// The time at which the fade started, once ticked
+    start_in_ms: Option<u64>,
+}
+
+impl Fade {
+    /// Returns the brightness multiplier at a given time, from 0.0 to 1.0.
+    fn level(&self, time_in_ms: u64) -> f32 {
+        let start_in_ms = self.start_in_ms.unwrap_or(time_in_ms);
+        let progress = if self.duration_in_ms == 0 {
+            1.
+        } else {
+            let elapsed_in_ms = time_in_ms.saturating_sub(start_in_ms);
+            (elapsed_in_ms as f32 / self.duration_in_ms as f32).min(1.)
+        };
+        if self.is_out {
+            1. - progress
+        } else {
+            progress
+        }
+    }
+}
+
+/// A limit on the estimated current drawn by the LEDs.
+#[derive(Debug, Clone, Copy)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+struct PowerLimit {
+    /// Maximum current for all LEDs, in milliamps
+    max_milliamps: u32,
+    /// Current of a single channel at full power, in milliamps
+    milliamps_per_channel: f32,
+}
+
+impl PowerLimit {
+    /// Estimates the current drawn by a frame, in milliamps.
+    fn estimate(&self, pixels: &[LinearSrgb], brightness: f32, correction: ColorCorrection) -> f32 {
+        let channel = |value: f32| (value * brightness).clamp(0., 1.);
+        let power: f32 = pixels
+            .iter()
+            .map(|pixel| {
+                let (red, green, blue) = correction.apply(pixel.red, pixel.green, pixel.blue);
+                channel(red) + channel(green) + channel(blue)
+            })
+            .sum();
+        power * self.milliamps_per_channel
+    }
+
+    /// Returns the brightness, scaled down if needed to keep a frame within the limit.
+    fn limit_brightness(
+        &self,
+        pixels: &[LinearSrgb],
+        brightness: f32,
+        correction: ColorCorrection,
+    ) -> f32 {
+        let max_milliamps = self.max_milliamps as f32;
+        if self.estimate(pixels, brightness, correction) <= max_milliamps {
+            return brightness;
+        }
+        // Scale by the unclamped estimate, which is never less than the clamped estimate,
+        // so the scaled frame is always within the limit.
+        let full_milliamps: f32 = pixels
+            .iter()
+            .map(|pixel| {
+                let (red, green, blue) = correction.apply(pixel.red, pixel.green, pixel.blue);
+                red + green + blue
+            })
+            .sum::<f32>()
+            * self.milliamps_per_channel;
+        brightness.min(max_milliamps / full_milliamps)
+    }
+}
+
+/// A captured state of a [`Control`] system, such as for a preset.
+///
+/// Use [`Control::snapshot`] to capture and [`Control::restore`] to restore.
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct ControlState<const PIXEL_COUNT: usize, Pattern> {
+    /// Overall brightness level
+    pub brightness: f32,
+    /// Curve mapping the brightness level to the output
+    pub brightness_curve: BrightnessCurve,
+    /// Color correction
+    pub correction: ColorCorrection,
+    /// White point, in kelvin
+    pub white_point: Option<f32>,
+    /// White mode, for LEDs with a white channel
+    pub white_mode: Option<WhiteMode>,
+    /// Maximum frame rate, in frames per second
+    pub max_fps: Option<u32>,
+    /// Policy for driver errors
+    pub error_policy: ErrorPolicy,
+    /// Limit on the estimated current
+    power_limit: Option<PowerLimit>,
+    /// Per-pixel brightness mask
+    pub mask: Option<[f32; PIXEL_COUNT]>,
+    /// Amount of blur, from 0.0 to 1.0
+    pub blur: f32,
+    /// Constant test frame
+    pub test_frame: Option<LinearSrgb>,
+    /// Static frame
+    pub static_frame: Option<Vec<LinearSrgb, PIXEL_COUNT>>,
+    /// Fade in or out
+    fade: Option<Fade>,
+    /// Pattern, including its parameters
+    pub pattern: Pattern,
+}
+
+impl<
+        const PIXEL_COUNT: usize,
+        const FRAME_BUFFER_SIZE: usize,
+        Dim,
+        Exec,
+        Layout,
+        Pattern,
+        Driver,
+        Time,
+    > Control<PIXEL_COUNT, FRAME_BUFFER_SIZE, Dim, Exec, Layout, Pattern, Driver, Time>
+where
+    Layout: LayoutForDim<Dim>,
+    Pattern: PatternTrait<Dim, Layout>,
+    LinearSrgb: FromColor<Pattern::Color>,
+{
+    /// Renders a frame into a new buffer.
+    fn render(&self, time_in_ms: u64) -> Vec<LinearSrgb, PIXEL_COUNT> {
+        let mut pixels = match self.is_static {
+            true => self.frame.clone(),
+            false => Vec::new(),
+        };
+        Self::render_frame(
+            &self.pattern,
+            self.transition.as_ref(),
+            self.blur,
+            self.mask.as_ref(),
+            self.test_frame,
+            self.is_static,
+            self.next_frame_time(time_in_ms),
+            &mut pixels,
+        );
+        pixels
+    }
+
+    /// Renders a frame into a buffer in place, blending any transition and applying any
+    /// blur and mask.
+    ///
+    /// If a test frame is set, renders that frame instead. If a static frame is set, the
+    /// buffer already holds it, so is left as is. At most one of the two is set.
+    ///
+    /// Takes each part of the [`Control`] it needs, rather than `&self`, so the buffer can
+    /// be owned by the [`Control`].
+    #[allow(clippy::too_many_arguments)]
+    fn render_frame(
+        pattern: &Pattern,
+        transition: Option<&Transition<Pattern>>,
+        blur: f32,
+        mask: Option<&[f32; PIXEL_COUNT]>,
+        test_frame: Option<LinearSrgb>,
+        is_static: bool,
+        frame: FrameTime,
+        pixels: &mut Vec<LinearSrgb, PIXEL_COUNT>,
+    ) {
+        if is_static {
+            return;
+        }
+        pixels.clear();
+        if let Some(color) = test_frame {
+            pixels.extend(core::iter::repeat_n(color, PIXEL_COUNT));
+            return;
+        }
+        match transition {
+            None => pixels.extend(
+                pattern
+                    .tick_frame(frame)
+                    .map(LinearSrgb::from_color)
+                    .take(PIXEL_COUNT),
+            ),
+            Some(transition) => pixels.extend(transition.tick(pattern, frame).take(PIXEL_COUNT)),
+        }
+        if blur > 0. {
+            Self::apply_blur(pixels, blur);
+        }
+        if let Some(mask) = mask {
+            for (pixel, value) in pixels.iter_mut().zip(mask) {
+                *pixel = *pixel * *value;
+            }
+        }
+    }
+
+    /// Blends each pixel toward the average of its adjacent pixels.
+    fn apply_blur(pixels: &mut Vec<LinearSrgb, PIXEL_COUNT>, amount: f32) {
+        let original = pixels.clone();
+        for (index, pixel) in pixels.iter_mut().enumerate() {
+            let mut count = 0;
+            let mut sum = LinearSrgb::new(0., 0., 0.);
+            for neighbor in Layout::adjacent(index).filter_map(|other| original.get(other)) {
+                sum = LinearSrgb::new(
+                    sum.red + neighbor.red,
+                    sum.green + neighbor.green,
+                    sum.blue + neighbor.blue,
+                );
+                count += 1;
+            }
+            if count == 0 {
+                continue;
+            }
+            let blend = |value: f32, sum: f32| value + (sum / count as f32 - value) * amount;
+            *pixel = LinearSrgb::new(
+                blend(pixel.red, sum.red),
+                blend(pixel.green, sum.green),
+                blend(pixel.blue, sum.blue),
+            );
+        }
+    }
+
+    /// Renders the colors of a frame into a buffer, without driving the LEDs.
+    ///
+    /// The colors are as the driver would receive them: with any transition, blur, mask,
+    /// and test frame, then with color correction and brightness (within any power limit)
+    /// applied. This is useful for debugging, recording, compositing, and tests.
+    ///
+    /// Unlike [`Control::tick`], this doesn't limit the frame rate, advance a transition,
+    /// or update the power estimate.
+    ///
+    /// # Arguments
+    ///
+    /// - `time_in_ms` - Current time in milliseconds
+    /// - `buf` - The buffer to render into, one color per LED
+    ///
+    /// # Panics
+    ///
+    /// Panics if `buf.len()` is not equal to `PIXEL_COUNT`.
+    pub fn render_into(&self, time_in_ms: u64, buf: &mut [LinearSrgb]) {
+        assert_eq!(
+            buf.len(),
+            PIXEL_COUNT,
+            "buffer length must equal the pixel count"
+        );
+        let pixels = self.render(time_in_ms);
+        let brightness = self.faded_brightness(time_in_ms);
+        let correction = self.output_correction();
+        let brightness = match &self.power_limit {
+            Some(power_limit) => power_limit.limit_brightness(&pixels, brightness, correction),
+            None => brightness,
+        };
+        for (output, pixel) in buf.iter_mut().zip(pixels) {
+            let (red, green, blue) = correction.apply(pixel.red, pixel.green, pixel.blue);
+            *output = LinearSrgb::new(red * brightness, green * brightness, blue * brightness);
+        }
+    }
+}
+
+impl<
+        const PIXEL_COUNT: usize,
+        const FRAME_BUFFER_SIZE: usize,
+        Dim,
+        Layout,
+        Pattern,
+        Driver,
+        Time,
+    > Control<PIXEL_COUNT, FRAME_BUFFER_SIZE, Dim, Blocking, Layout, Pattern, Driver, Time>
+where
+    Layout: LayoutForDim<Dim>,
+    Pattern: PatternTrait<Dim, Layout>,
+    Driver: DriverTrait,
+    Driver::Color: FromColor<Pattern::Color> + FromColor<LinearSrgb>,
+    LinearSrgb: FromColor<Pattern::Color>,
+{
+    /// Updates the LED state based on the current time.
+    ///
+    /// If the frame rate is limited (see [`Control::set_max_fps`]) and the next frame is
+    /// not yet due, this does nothing.
+    ///
+    /// This method:
+    /// 1. Calls the pattern to generate colors (or uses the test or static frame, if set),
+    ///    into a frame buffer owned by the [`Control`], with
+    ///    [`Pattern::tick_frame`](crate::pattern::Pattern::tick_frame)
+    ///    - If transitioning, blends the colors of the outgoing and incoming patterns
+    ///    - If blurred, blends each pixel toward its neighbors
+    ///    - If masked, multiplies each pixel by the mask
+    ///    - If fading, scales the brightness by the fade
+    ///    - If power limited, scales down the brightness to within the limit
+    /// 2. Passes the colors and brightness to the driver
+    ///
+    /// # Arguments
+    ///
+    /// - `time_in_ms` - Current time in milliseconds
+    ///
+    /// # Returns
+    ///
+    /// Result indicating success or an error from the driver
+    pub fn tick(&mut self, time_in_ms: u64) -> Result<(), Driver::Error> {
+        let frame = self.next_frame_time(time_in_ms);
+        if !self.update_frame_due(time_in_ms) {
+            return Ok(());
+        }
+        self.update_transition(time_in_ms);
+        self.update_fade(time_in_ms);
+        Self::render_frame(
+            &self.pattern,
+            self.transition.as_ref(),
+            self.blur,
+            self.mask.as_ref(),
+            self.test_frame,
+            self.is_static,
+            frame,
+            &mut self.frame,
+        );
+        self.frame_count += 1;
+        let brightness = self.limit_power(self.faded_brightness(time_in_ms));
+        let correction = self.output_correction();
+        let mut result = self.show_frame(brightness, correction);
+        if result.is_err() && self.error_policy == ErrorPolicy::RetryOnce {
+            result = self.show_frame(brightness, correction);
+        }
+        self.error_policy.handle(result)
+    }
+
+    /// Updates the LED state based on the current time from the time source.
+    ///
+    /// Same as [`Control::tick`], but the time is pulled from the [`TimeSource`] set with
+    /// [`Control::with_time_source`].
+    ///
+    /// # Returns
+    ///
+    /// Result indicating success or an error from the driver
+    pub fn tick_auto(&mut self) -> Result<(), Driver::Error>
+    where
+        Time: TimeSource,
+    {
+        let time_in_ms = self.time_source.now_in_ms();
+        self.tick(time_in_ms)
+    }
+
+    /// Turns off the LEDs immediately, by writing a frame of black to the driver.
+    ///
+    /// This doesn't change the pattern or any other state, so the next tick shows the
+    /// pattern again. To keep the LEDs off, such as on shutdown, stop ticking.
+    ///
+    /// # Returns
+    ///
+    /// Result indicating success or an error from the driver
+    pub fn clear(&mut self) -> Result<(), Driver::Error> {
+        self.driver.clear::<PIXEL_COUNT, FRAME_BUFFER_SIZE>()
+    }
+
+    /// Shows the frame buffer on the driver.
+    fn show_frame(
+        &mut self,
+        brightness: f32,
+        correction: ColorCorrection,
+    ) -> Result<(), Driver::Error> {
+        self.driver.show::<PIXEL_COUNT, FRAME_BUFFER_SIZE, _, _>(
+            self.frame.iter().copied(),
+            brightness,
+            correction,
+        )
+    }
+}
+
+#[cfg(feature = "async")]
+impl<
+        const PIXEL_COUNT: usize,
+        const FRAME_BUFFER_SIZE: usize,
+        Dim,
+        Layout,
+        Pattern,
+        Driver,
+        Time,
+    > Control<PIXEL_COUNT, FRAME_BUFFER_SIZE, Dim, Async, Layout, Pattern, Driver, Time>
+where
+    Layout: LayoutForDim<Dim>,
+    Pattern: PatternTrait<Dim, Layout>,
+    Driver: DriverAsyncTrait,
+    Driver::Color: FromColor<Pattern::Color> + FromColor<LinearSrgb>,
+    LinearSrgb: FromColor<Pattern::Color>,
+{
+    /// Updates the LED state based on the current time, asynchronously.
+    ///
+    /// If the frame rate is limited (see [`Control::set_max_fps`]) and the next frame is
+    /// not yet due, this does nothing.
+    ///
+    /// This method:
+    /// 1. Calls the pattern to generate colors (or uses the test or static frame, if set),
+    ///    into a frame buffer owned by the [`Control`], with
+    ///    [`Pattern::tick_frame`](crate::pattern::Pattern::tick_frame)
+    ///    - If transitioning, blends the colors of the outgoing and incoming patterns
+    ///    - If blurred, blends each pixel toward its neighbors
+    ///    - If masked, multiplies each pixel by the mask
+    ///    - If fading, scales the brightness by the fade
+    ///    - If power limited, scales down the brightness to within the limit
+    /// 2. Passes the colors and brightness to the driver
+    ///
+    /// # Arguments
+    ///
+    /// - `time_in_ms` - Current time in milliseconds
+    ///
+    /// # Returns
+    ///
+    /// Result indicating success or an error from the driver
+    pub async fn tick(&mut self, time_in_ms: u64) -> Result<(), Driver::Error> {
+        let frame = self.next_frame_time(time_in_ms);
+        if !self.update_frame_due(time_in_ms) {
+            return Ok(());
+        }
+        self.update_transition(time_in_ms);
+        self.update_fade(time_in_ms);
+        Self::render_frame(
+            &self.pattern,
+            self.transition.as_ref(),
+            self.blur,
+            self.mask.as_ref(),
+            self.test_frame,
+            self.is_static,
+            frame,
+            &mut self.frame,
+        );
+        self.frame_count += 1;
+        let brightness = self.limit_power(self.faded_brightness(time_in_ms));
+        let correction = self.output_correction();
+        let mut result = self.show_frame(brightness, correction).await;
+        if result.is_err() && self.error_policy == ErrorPolicy::RetryOnce {
+            result = self.show_frame(brightness, correction).await;
+        }
+        self.error_policy.handle(result)
+    }
+
+    /// Updates the LED state based on the current time from the time source.
+    ///
+    /// Same as [`Control::tick`], but the time is pulled from the [`TimeSource`] set with
+    /// [`Control::with_time_source`].
+    ///
+    /// # Returns
+    ///
+    /// Result indicating success or an error from the driver
+    pub async fn tick_auto(&mut self) -> Result<(), Driver::Error>
+    where
+        Time: TimeSource,
+    {
+        let time_in_ms = self.time_source.now_in_ms();
+        self.tick(time_in_ms).await
+    }
+
+    /// Turns off the LEDs immediately, by writing a frame of black to the driver,
+    /// asynchronously.
+    ///
+    /// This doesn't change the pattern or any other state, so the next tick shows the
+    /// pattern again. To keep the LEDs off, such as on shutdown, stop ticking.
+    ///
+    /// # Returns
+    ///
+    /// Result indicating success or an error from the driver
+    pub async fn clear(&mut self) -> Result<(), Driver::Error> {
+        self.driver.clear::<PIXEL_COUNT, FRAME_BUFFER_SIZE>().await
+    }
+
+    /// Shows the frame buffer on the driver, asynchronously.
+    async fn show_frame(
+        &mut self,
+        brightness: f32,
+        correction: ColorCorrection,
+    ) -> Result<(), Driver::Error> {
+        self.driver
+            .show::<PIXEL_COUNT, FRAME_BUFFER_SIZE, _, _>(
+                self.frame.iter().copied(),
+                brightness,
+                correction,
+            )
+            .await
+    }
+}
+
+/// The builder allows your to build up your [`Control`] system one-by-one
+/// and handles the combination of generic types and constraints that
+/// [`Control`] expects.
+pub struct ControlBuilder<
+    const PIXEL_COUNT: usize,
+    const FRAME_BUFFER_SIZE: usize,
+    Dim,
+    Exec,
+    Layout,
+    Pattern,
+    Driver,
+    IsFrameBufferSet,
+> {
+    dim: PhantomData<Dim>,
+    exec: PhantomData<Exec>,
+    layout: PhantomData<Layout>,
+    pattern: Pattern,
+    driver: Driver,
+    is_frame_buffer_set: PhantomData<IsFrameBufferSet>,
+}
+
+impl ControlBuilder<0, 0, (), (), (), (), (), Unset> {
+    /// Starts building a one-dimensional blocking control system.
+    ///
+    /// # Returns
+    ///
+    /// A builder initialized for 1D, blocking
+    pub fn new_1d() -> ControlBuilder<0, 0, Dim1d, Blocking, (), (), (), Unset> {
+        ControlBuilder {
+            dim: PhantomData,
+            exec: PhantomData,
+            layout: PhantomData,
+            pattern: (),
+            driver: (),
+            is_frame_buffer_set: PhantomData,
+        }
+    }
+}
+
+#[cfg(feature = "async")]
+impl ControlBuilder<0, 0, (), (), (), (), (), Unset> {
+    /// Starts building a one-dimensional asynchronous control system.
+    ///
+    /// # Returns
+    ///
+    /// A builder initialized for 1D, async
+    pub fn new_1d_async() -> ControlBuilder<0, 0, Dim1d, Async, (), (), (), Unset> {
+        ControlBuilder {
+            dim: PhantomData,
+            exec: PhantomData,
+            layout: PhantomData,
+            pattern: (),
+            driver: (),
+            is_frame_buffer_set: PhantomData,
+        }
+    }
+}
+
+impl ControlBuilder<0, 0, (), (), (), (), (), Unset> {
+    /// Starts building a two-dimensional blocking control system.
+    ///
+    /// # Returns
+    ///
+    /// A builder initialized for 2D, blocking
+    pub fn new_2d() -> ControlBuilder<0, 0, Dim2d, Blocking, (), (), (), Unset> {
+        ControlBuilder {
+            dim: PhantomData,
+            exec: PhantomData,
+            layout: PhantomData,
+            pattern: (),
+            driver: (),
+            is_frame_buffer_set: PhantomData,
+        }
+    }
+}
+
+#[cfg(feature = "async")]
+impl ControlBuilder<0, 0, (), (), (), (), (), Unset> {
+    /// Starts building a two-dimensional asynchronous control system.
+    ///
+    /// # Returns
+    ///
+    /// A builder initialized for 2D, async
+    pub fn new_2d_async() -> ControlBuilder<0, 0, Dim2d, Async, (), (), (), Unset> {
+        ControlBuilder {
+            dim: PhantomData,
+            exec: PhantomData,
+            layout: PhantomData,
+            pattern: (),
+            driver: (),
+            is_frame_buffer_set: PhantomData,
+        }
+    }
+}
+
+impl ControlBuilder<0, 0, (), (), (), (), (), Unset> {
+    /// Starts building a three-dimensional blocking control system.
+    ///
+    /// # Returns
+    ///
+    /// A builder initialized for 3D, blocking
+    pub fn new_3d() -> ControlBuilder<0, 0, Dim3d, Blocking, (), (), (), Unset> {
+        ControlBuilder {
+            dim: PhantomData,
+            exec: PhantomData,
+            layout: PhantomData,
+            pattern: (),
+            driver: (),
+            is_frame_buffer_set: PhantomData,
+        }
+    }
+}
+
+#[cfg(feature = "async")]
+impl ControlBuilder<0, 0, (), (), (), (), (), Unset> {
+    /// Starts building a three-dimensional asynchronous control system.
+    ///
+    /// # Returns
+    ///
+    /// A builder initialized for 3D, async
+    pub fn new_3d_async() -> ControlBuilder<0, 0, Dim3d, Async, (), (), (), Unset> {
+        ControlBuilder {
+            dim: PhantomData,
+            exec: PhantomData,
+            layout: PhantomData,
+            pattern: (),
+            driver: (),
+            is_frame_buffer_set: PhantomData,
+        }
+    }
+}
+
+impl<const FRAME_BUFFER_SIZE: usize, Dim, Exec, Pattern, Driver, IsFrameBufferSet>
+    ControlBuilder<0, FRAME_BUFFER_SIZE, Dim, Exec, (), Pattern, Driver, IsFrameBufferSet>
+{
+    /// Specifies the layout type for the control system.
+    ///
+    /// # Type Parameters
+    ///
+    /// - `Layout` - The layout type implementing Layout that corresponds to Dim
+    /// - `PIXEL_COUNT` - A constant for the number of pixels (`Layout::PIXEL_COUNT`)
+    ///
+    /// Until  [the `generic_const_exprs` feature](https://doc.rust-lang.org/beta/unstable-book/language-features/generic-const-exprs.html) is stable,
+    /// the user must explicitly provide `PIXEL_COUNT` as `Layout::PIXEL_COUNT`.
+    ///
+    /// # Returns
+    ///
+    /// Builder with layout type specified
+    pub fn with_layout<Layout, const PIXEL_COUNT: usize>(
+        self,
+    ) -> ControlBuilder<
+        PIXEL_COUNT,
+        FRAME_BUFFER_SIZE,
+        Dim,
+        Exec,
+        Layout,
+        Pattern,
+        Driver,
+        IsFrameBufferSet,
+    >
+    where
+        Layout: LayoutForDim<Dim>,
+    {
+        ControlBuilder {
+            dim: self.dim,
+            exec: self.exec,
+            layout: PhantomData,
+            pattern: self.pattern,
+            driver: self.driver,
+            is_frame_buffer_set: self.is_frame_buffer_set,
+        }
+    }
+}
+
+impl<
+        const PIXEL_COUNT: usize,
+        const FRAME_BUFFER_SIZE: usize,
+        Dim,
+        Exec,
+        Layout,
+        Driver,
+        IsFrameBufferSet,
+    >
+    ControlBuilder<PIXEL_COUNT, FRAME_BUFFER_SIZE, Dim, Exec, Layout, (), Driver, IsFrameBufferSet>
+where
+    Layout: LayoutForDim<Dim>,
+{
+    /// Specifies the pattern and its parameters.
+    ///
+    /// # Type Parameters
+    ///
+    /// - `Pattern` - The pattern type implementing Pattern<Dim, Layout>
+    ///
+    /// # Arguments
+    ///
+    /// - `params` - The pattern parameters
+    ///
+    /// # Returns
+    ///
+    /// Builder with pattern specified
+    pub fn with_pattern<Pattern>(
+        self,
+        params: Pattern::Params,
+    ) -> ControlBuilder<
+        PIXEL_COUNT,
+        FRAME_BUFFER_SIZE,
+        Dim,
+        Exec,
+        Layout,
+        Pattern,
+        Driver,
+        IsFrameBufferSet,
+    >
+    where
+        Pattern: PatternTrait<Dim, Layout>,
+    {
+        let pattern = Pattern::new(params);
+        ControlBuilder {
+            dim: self.dim,
+            exec: self.exec,
+            layout: self.layout,
+            pattern,
+            driver: self.driver,
+            is_frame_buffer_set: self.is_frame_buffer_set,
+        }
+    }
+
+    /// Specifies two patterns to layer, and how to combine them.
+    ///
+    /// See [`Blend`].
+    ///
+    /// # Type Parameters
+    ///
+    /// - `A` - The bottom pattern type implementing Pattern<Dim, Layout>
+    /// - `B` - The top pattern type implementing Pattern<Dim, Layout>
+    ///
+    /// # Arguments
+    ///
+    /// - `a_params` - The bottom pattern parameters
+    /// - `b_params` - The top pattern parameters
+    /// - `mode` - How to combine the patterns
+    ///
+    /// # Returns
+    ///
+    /// Builder with the blended pattern specified
+    pub fn with_blend<A, B>(
+        self,
+        a_params: A::Params,
+        b_params: B::Params,
+        mode: BlendMode,
+    ) -> ControlBuilder<
+        PIXEL_COUNT,
+        FRAME_BUFFER_SIZE,
+        Dim,
+        Exec,
+        Layout,
+        Blend<A, B>,
+        Driver,
+        IsFrameBufferSet,
+    >
+    where
+        A: PatternTrait<Dim, Layout>,
+        B: PatternTrait<Dim, Layout>,
+        LinearSrgb: FromColor<A::Color> + FromColor<B::Color>,
+    {
+        self.with_pattern::<Blend<A, B>>(BlendParams {
+            a: a_params,
+            b: b_params,
+            mode,
+        })
+    }
+
+    /// Specifies a pattern to scale by an envelope over time.
+    ///
+    /// See [`Envelope`].
+    ///
+    /// # Type Parameters
+    ///
+    /// - `Pattern` - The pattern type implementing Pattern<Dim, Layout>
+    ///
+    /// # Arguments
+    ///
+    /// - `params` - The pattern parameters
+    /// - `shape` - The shape of the envelope
+    ///
+    /// # Returns
+    ///
+    /// Builder with the enveloped pattern specified
+    pub fn with_envelope<Pattern>(
+        self,
+        params: Pattern::Params,
+        shape: EnvelopeShape,
+    ) -> ControlBuilder<
+        PIXEL_COUNT,
+        FRAME_BUFFER_SIZE,
+        Dim,
+        Exec,
+        Layout,
+        Envelope<Pattern>,
+        Driver,
+        IsFrameBufferSet,
+    >
+    where
+        Pattern: PatternTrait<Dim, Layout>,
+        LinearSrgb: FromColor<Pattern::Color>,
+    {
+        self.with_pattern::<Envelope<Pattern>>(EnvelopeParams {
+            pattern: params,
+            shape,
+        })
+    }
+}
+
+impl<const PIXEL_COUNT: usize, const FRAME_BUFFER_SIZE: usize, Dim, Layout, Pattern>
+    ControlBuilder<PIXEL_COUNT, FRAME_BUFFER_SIZE, Dim, Blocking, Layout, Pattern, (), Unset>
+{
+    /// Specifies the LED driver for the control system (blocking).
+    ///
+    /// # Type Parameters
+    ///
+    /// - `Driver` - The blocking driver type
+    ///
+    /// # Arguments
+    ///
+    /// - `driver` - The LED driver instance (blocking)
+    ///
+    /// # Returns
+    ///
+    /// Builder with driver and frame buffer size specified
+    pub fn with_driver<Driver>(
+        self,
+        driver: Driver,
+    ) -> ControlBuilder<PIXEL_COUNT, FRAME_BUFFER_SIZE, Dim, Blocking, Layout, Pattern, Driver, Unset>
+    where
+        Driver: DriverTrait,
+    {
+        ControlBuilder {
+            dim: self.dim,
+            exec: self.exec,
+            layout: self.layout,
+            pattern: self.pattern,
+            driver,
+            is_frame_buffer_set: self.is_frame_buffer_set,
+        }
+    }
+}
+
+#[cfg(feature = "async")]
+impl<const PIXEL_COUNT: usize, const FRAME_BUFFER_SIZE: usize, Dim, Layout, Pattern>
+    ControlBuilder<PIXEL_COUNT, FRAME_BUFFER_SIZE, Dim, Async, Layout, Pattern, (), Unset>
+{
+    /// Specifies the LED driver for the control system (blocking).
+    ///
+    /// # Type Parameters
+    ///
+    /// - `Driver` - The blocking driver type
+    ///
+    /// # Arguments
+    ///
+    /// - `driver` - The LED driver instance (blocking)
+    ///
+    /// # Returns
+    ///
+    /// Builder with driver and frame buffer size specified
+    pub fn with_driver<Driver>(
+        self,
+        driver: Driver,
+    ) -> ControlBuilder<PIXEL_COUNT, FRAME_BUFFER_SIZE, Dim, Async, Layout, Pattern, Driver, Unset>
+    where
+        Driver: DriverAsyncTrait,
+    {
+        ControlBuilder {
+            dim: self.dim,
+            exec: self.exec,
+            layout: self.layout,
+            pattern: self.pattern,
+            driver,
+            is_frame_buffer_set: self.is_frame_buffer_set,
+        }
+    }
+}
+
+impl<const PIXEL_COUNT: usize, Dim, Layout, Pattern, Driver>
+    ControlBuilder<PIXEL_COUNT, 0, Dim, Blocking, Layout, Pattern, Driver, Unset>
+{
+    /// Specifies the frame buffer size for the control system (blocking).
+    ///
+    /// # Type Parameters
+    ///
+    /// - `FRAME_BUFFER_SIZE` - The per-call frame buffer size
+    ///
+    /// Until  [the `generic_const_exprs` feature](https://doc.rust-lang.org/beta/unstable-book/language-features/generic-const-exprs.html) is stable,
+    /// the user must explicitly provide the correct `FRAME_BUFFER_SIZE`. Typically this should be
+    /// calculated using the [LED](crate::leds) `frame_buffer_size` constant function, e.g. (`{ Ws2812::frame_buffer_size(Layout::PIXEL_COUNT) }`).
+    ///
+    /// # Returns
+    ///
+    /// Builder with frame buffer size specified
+    pub fn with_frame_buffer_size<const FRAME_BUFFER_SIZE: usize>(
+        self,
+    ) -> ControlBuilder<PIXEL_COUNT, FRAME_BUFFER_SIZE, Dim, Blocking, Layout, Pattern, Driver, Set>
+    where
+        Driver: DriverTrait,
+    {
+        ControlBuilder {
+            dim: self.dim,
+            exec: self.exec,
+            layout: self.layout,
+            pattern: self.pattern,
+            driver: self.driver,
+            is_frame_buffer_set: PhantomData,
+        }
+    }
+}
+
+#[cfg(feature = "async")]
+impl<const PIXEL_COUNT: usize, Dim, Layout, Pattern, Driver>
+    ControlBuilder<PIXEL_COUNT, 0, Dim, Async, Layout, Pattern, Driver, Unset>
+{
+    /// Specifies the frame buffer size for the control system (async).
+    ///
+    /// # Type Parameters
+    ///
+    /// - `FRAME_BUFFER_SIZE` - The per-call frame buffer size
+    ///
+    /// # Returns
+    ///
+    /// Builder with frame buffer size specified
+    pub fn with_frame_buffer_size<const FRAME_BUFFER_SIZE: usize>(
+        self,
+    ) -> ControlBuilder<PIXEL_COUNT, FRAME_BUFFER_SIZE, Dim, Async, Layout, Pattern, Driver, Set>
+    where
+        Driver: DriverAsyncTrait,
+    {
+        ControlBuilder {
+            dim: self.dim,
+            exec: self.exec,
+            layout: self.layout,
+            pattern: self.pattern,
+            driver: self.driver,
+            is_frame_buffer_set: PhantomData,
+        }
+    }
+}
+
+impl<const PIXEL_COUNT: usize, const FRAME_BUFFER_SIZE: usize, Dim, Layout, Pattern, Driver>
+    ControlBuilder<PIXEL_COUNT, FRAME_BUFFER_SIZE, Dim, Blocking, Layout, Pattern, Driver, Set>
+where
+    Layout: LayoutForDim<Dim>,
+    Pattern: PatternTrait<Dim, Layout>,
+    Driver: DriverTrait,
+    Driver::Color: FromColor<Pattern::Color>,
+{
+    /// Builds the final [`Control`] struct.
+    ///
+    /// # Returns
+    ///
+    /// A fully configured Control instance
+    pub fn build(
+        self,
+    ) -> Control<PIXEL_COUNT, FRAME_BUFFER_SIZE, Dim, Blocking, Layout, Pattern, Driver> {
+        Control::new(self.pattern, self.driver)
+    }
+}
+
+#[cfg(feature = "async")]
+impl<const PIXEL_COUNT: usize, const FRAME_BUFFER_SIZE: usize, Dim, Layout, Pattern, Driver>
+    ControlBuilder<PIXEL_COUNT, FRAME_BUFFER_SIZE, Dim, Async, Layout, Pattern, Driver, Set>
+where
+    Layout: LayoutForDim<Dim>,
+    Pattern: PatternTrait<Dim, Layout>,
+    Driver: DriverAsyncTrait,
+    Driver::Color: FromColor<Pattern::Color>,
+{
+    /// Builds the final [`Control`] struct.
+    ///
+    /// # Returns
+    ///
+    /// A fully configured Control instance
+    pub fn build(
+        self,
+    ) -> Control<PIXEL_COUNT, FRAME_BUFFER_SIZE, Dim, Async, Layout, Pattern, Driver> {
+        Control::new(self.pattern, self.driver)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        color::LinearSrgb,
+        layout::{Layout1d, Layout2d, Shape2d, Vec2},
+        layout1d, layout2d,
+        patterns::{
+            rainbow::{Rainbow, RainbowParams},
+            switch::{BuiltinSwitch1d, BuiltinSwitchParams},
+        },
+    };
+
+    layout1d!(Layout, 4);
+
+    /// Driver which keeps the last frame shown.
+    #[derive(Default)]
+    struct FrameDriver {
+        frame: Vec<LinearSrgb, 4>,
+        writes: usize,
+        /// The number of writes left to fail
+        failures: usize,
+    }
+
+    impl DriverTrait for FrameDriver {
+        type Error = ();
+        type Color = LinearSrgb;
+        type Word = LinearSrgb;
+
+        fn encode<const PIXEL_COUNT: usize, const FRAME_BUFFER_SIZE: usize, Pixels, Color>(
+            &mut self,
+            pixels: Pixels,
+            brightness: f32,
+            correction: ColorCorrection,
+        ) -> Vec<Self::Word, FRAME_BUFFER_SIZE>
+        where
+            Pixels: IntoIterator<Item = Color>,
+            Self::Color: FromColor<Color>,
+        {
+            pixels
+                .into_iter()
+                .map(|color| {
+                    let color = LinearSrgb::from_color(color);
+                    let (red, green, blue) = correction.apply(color.red, color.green, color.blue);
+                    LinearSrgb::new(red * brightness, green * brightness, blue * brightness)
+                })
+                .collect()
+        }
+
+        fn write<const FRAME_BUFFER_SIZE: usize>(
+            &mut self,
+            frame: Vec<Self::Word, FRAME_BUFFER_SIZE>,
+            _brightness: f32,
+            _correction: ColorCorrection,
+        ) -> Result<(), Self::Error> {
+            if self.failures > 0 {
+                self.failures -= 1;
+                return Err(());
+            }
+            self.frame = frame.into_iter().collect();
+            self.writes += 1;
+            Ok(())
+        }
+    }
+
+    fn build() -> Control<4, 4, Dim1d, Blocking, Layout, Rainbow, FrameDriver> {
+        ControlBuilder::new_1d()
+            .with_layout::<Layout, { Layout::PIXEL_COUNT }>()
+            .with_pattern::<Rainbow>(RainbowParams::default())
+            .with_driver(FrameDriver::default())
+            .with_frame_buffer_size::<4>()
+            .build()
+    }
+
+    #[test]
+    fn test_snapshot_restore() {
+        let mut control = build();
+        control.set_brightness(0.5);
+        control.set_brightness_curve(BrightnessCurve::Perceptual);
+        control.set_color_correction(ColorCorrection::new(1.0, 0.8, 0.6));
+        control.set_white_point(4000.);
+        control.set_mask(&[1., 0.5, 0.25, 0.]);
+        control.set_blur(0.5);
+        let state = control.snapshot();
+
+        control.tick(1000).unwrap();
+        let expected = control.driver.frame.clone();
+
+        control.set_brightness(1.0);
+        control.set_brightness_curve(BrightnessCurve::Linear);
+        control.set_color_correction(ColorCorrection::default());
+        control.clear_white_point();
+        control.set_white_mode(WhiteMode::Off);
+        control.set_error_policy(ErrorPolicy::Ignore);
+        control.set_power_limit(100, 20.);
+        control.clear_mask();
+        control.set_blur(0.);
+        control.play_static(Some(Vec::from_iter([LinearSrgb::new(1., 0., 0.)])));
+        control.fade_out(100);
+        *control.pattern_mut() = PatternTrait::<Dim1d, Layout>::new(RainbowParams {
+            time_scalar: 1.,
+            position_scalar: 2.,
+            ..Default::default()
+        });
+        control.tick(1000).unwrap();
+        assert_ne!(control.driver.frame, expected);
+        control.set_max_fps(30);
+        // Ends the static frame, as they share a buffer.
+        control.set_test_frame(Some(LinearSrgb::new(1., 1., 1.)));
+
+        control.restore(state);
+        assert_eq!(control.brightness_curve, BrightnessCurve::Perceptual);
+        assert_eq!(control.white_point, Some(4000.));
+        assert_eq!(control.white_mode, None);
+        assert_eq!(control.max_fps, None);
+        assert_eq!(control.error_policy, ErrorPolicy::Propagate);
+        assert!(control.power_limit.is_none());
+        assert_eq!(control.mask, Some([1., 0.5, 0.25, 0.]));
+        assert_eq!(control.blur, 0.5);
+        assert_eq!(control.test_frame, None);
+        assert!(!control.is_static);
+        assert!(control.fade.is_none());
+
+        control.tick(1000).unwrap();
+        assert_eq!(control.driver.frame, expected);
+    }
+
+    /// Pattern which shows the frame number and the time since the last frame.
+    struct FrameCounter;
+
+    impl PatternTrait<Dim1d, Layout> for FrameCounter {
+        type Params = ();
+        type Color = LinearSrgb;
+
+        fn new(_params: Self::Params) -> Self {
+            Self
+        }
+
+        fn tick(&self, time_in_ms: u64) -> impl Iterator<Item = Self::Color> {
+            self.tick_frame(FrameTime::first(time_in_ms))
+        }
+
+        fn tick_frame(&self, frame: FrameTime) -> impl Iterator<Item = Self::Color> {
+            let color = LinearSrgb::new(
+                frame.frame as f32 / 10.,
+                frame.delta_in_ms as f32 / 100.,
+                0.,
+            );
+            core::iter::repeat_n(color, Layout::PIXEL_COUNT)
+        }
+    }
+
+    #[test]
+    fn test_frame_time() {
+        let mut control = ControlBuilder::new_1d()
+            .with_layout::<Layout, { Layout::PIXEL_COUNT }>()
+            .with_pattern::<FrameCounter>(())
+            .with_driver(FrameDriver::default())
+            .with_frame_buffer_size::<4>()
+            .build();
+
+        control.tick(100).unwrap();
+        assert_eq!(control.driver.frame[0], LinearSrgb::new(0., 0., 0.));
+        control.tick(116).unwrap();
+        assert_eq!(control.driver.frame[0], LinearSrgb::new(0.1, 0.16, 0.));
+        control.tick(140).unwrap();
+        assert_eq!(control.driver.frame[0], LinearSrgb::new(0.2, 0.24, 0.));
+
+        // Frames skipped by the frame rate limit aren't counted.
+        control.set_max_fps(10);
+        control.tick(200).unwrap();
+        control.tick(250).unwrap();
+        assert_eq!(control.driver.frame[0], LinearSrgb::new(0.3, 0.6, 0.));
+    }
+
+    #[test]
+    fn test_error_policy() {
+        let mut control = build();
+        control.tick(0).unwrap();
+        let last_frame = control.driver.frame.clone();
+
+        // Propagate: the first failure is returned.
+        control.driver.failures = 1;
+        assert_eq!(control.tick(1000), Err(()));
+        assert_eq!(control.driver.writes, 1);
+
+        // Retry once: one failure is retried, two failures are returned.
+        control.set_error_policy(ErrorPolicy::RetryOnce);
+        control.driver.failures = 1;
+        assert_eq!(control.tick(1000), Ok(()));
+        assert_eq!(control.driver.writes, 2);
+        control.driver.failures = 2;
+        assert_eq!(control.tick(2000), Err(()));
+        assert_eq!(control.driver.writes, 2);
+
+        // Ignore: the failure is ignored, and the driver holds the last frame written.
+        control.set_error_policy(ErrorPolicy::Ignore);
+        control.tick(0).unwrap();
+        let last_frame_written = control.driver.frame.clone();
+        assert_eq!(last_frame_written, last_frame);
+        control.driver.failures = 1;
+        assert_eq!(control.tick(3000), Ok(()));
+        assert_eq!(control.driver.frame, last_frame_written);
+        assert_eq!(control.driver.writes, 3);
+    }
+
+    #[test]
+    fn test_clear() {
+        let mut control = build();
+        control.set_test_frame(Some(LinearSrgb::new(1., 1., 1.)));
+        control.tick(0).unwrap();
+        assert_eq!(control.driver.frame[0], LinearSrgb::new(1., 1., 1.));
+
+        control.clear().unwrap();
+        assert_eq!(control.driver.frame.len(), 4);
+        assert!(control
+            .driver
+            .frame
+            .iter()
+            .all(|color| *color == LinearSrgb::new(0., 0., 0.)));
+    }
+
+    #[test]
+    fn test_brightness_curve() {
+        assert_eq!(BrightnessCurve::Linear.apply(0.5), 0.5);
+        assert_eq!(BrightnessCurve::Perceptual.apply(0.), 0.);
+        assert!((BrightnessCurve::Perceptual.apply(1.) - 1.).abs() < 1e-6);
+        let half = BrightnessCurve::Perceptual.apply(0.5);
+        assert!((half - 0.184).abs() < 1e-3, "{half}");
+
+        let mut control = build();
+        control.set_test_frame(Some(LinearSrgb::new(1., 1., 1.)));
+        control.set_brightness(0.5);
+        control.tick(0).unwrap();
+        assert_eq!(control.driver.frame[0].red, 0.5);
+
+        control.set_brightness_curve(BrightnessCurve::Perceptual);
+        control.tick(0).unwrap();
+        assert!(control.driver.frame[0].red < 0.5);
+        assert_eq!(control.driver.frame[0].red, half);
+    }
+
+    #[test]
+    fn test_white_point() {
+        let mut control = build();
+        control.set_test_frame(Some(LinearSrgb::new(1., 1., 1.)));
+        control.set_white_point(crate::color::temperature::CANDLE);
+        control.set_color_correction(ColorCorrection::new(1.0, 0.5, 1.0));
+        control.tick(0).unwrap();
+
+        let candle = crate::color::temperature::from_kelvin(crate::color::temperature::CANDLE);
+        let expected = LinearSrgb::new(candle.red, candle.green * 0.5, candle.blue);
+        assert_eq!(control.driver.frame[0], expected);
+
+        control.clear_white_point();
+        control.tick(0).unwrap();
+        assert_eq!(control.driver.frame[0], LinearSrgb::new(1., 0.5, 1.));
+    }
+
+    #[test]
+    fn test_transition_alpha() {
+        let transition = Transition {
+            from: (),
+            duration_in_ms: 100,
+            start_in_ms: Some(1000),
+        };
+        assert_eq!(transition.alpha(1000), 0.);
+        assert_eq!(transition.alpha(1050), 0.5);
+        assert_eq!(transition.alpha(1100), 1.);
+        assert_eq!(transition.alpha(2000), 1.);
+    }
+
+    #[test]
+    fn test_transition_to_params() {
+        let params = || RainbowParams {
+            time_scalar: 0.,
+            position_scalar: 2.,
+            ..Default::default()
+        };
+
+        let mut from = build();
+        from.tick(1000).unwrap();
+        let mut to = build();
+        *to.pattern_mut() = PatternTrait::<Dim1d, Layout>::new(params());
+        to.tick(1000).unwrap();
+
+        let mut control = build();
+        control.transition_to_params(params(), 100);
+
+        // Starts from the outgoing pattern
+        control.tick(1000).unwrap();
+        assert_eq!(control.driver.frame, from.driver.frame);
+
+        // Blends in between
+        control.tick(1050).unwrap();
+        assert_ne!(control.driver.frame, from.driver.frame);
+        assert_ne!(control.driver.frame, to.driver.frame);
+
+        // Ends with the incoming pattern
+        control.tick(1100).unwrap();
+        assert_eq!(control.driver.frame, to.driver.frame);
+        assert!(control.transition.is_none());
+    }
+
+    #[test]
+    fn test_fade_level() {
+        let fade = |is_out| Fade {
+            is_out,
+            duration_in_ms: 100,
+            start_in_ms: Some(1000),
+        };
+        assert_eq!(fade(false).level(1000), 0.);
+        assert_eq!(fade(false).level(1050), 0.5);
+        assert_eq!(fade(false).level(1100), 1.);
+        assert_eq!(fade(true).level(1000), 1.);
+        assert_eq!(fade(true).level(1050), 0.5);
+        assert_eq!(fade(true).level(2000), 0.);
+    }
+
+    #[test]
+    fn test_fade_in_out() {
+        let mut control = build();
+        *control.pattern_mut() = PatternTrait::<Dim1d, Layout>::new(RainbowParams {
+            time_scalar: 0.,
+            position_scalar: 0.,
+            ..Default::default()
+        });
+
+        // The fade starts from the next tick.
+        control.fade_in(100);
+        control.tick(1000).unwrap();
+        assert_eq!(control.driver.frame[0].red, 0.);
+        control.tick(1050).unwrap();
+        assert_eq!(control.driver.frame[0].red, 0.5);
+        control.tick(1100).unwrap();
+        assert_eq!(control.driver.frame[0].red, 1.);
+
+        control.fade_out(100);
+        control.tick(2000).unwrap();
+        assert!(!control.is_faded_out());
+        control.tick(2050).unwrap();
+        assert_eq!(control.driver.frame[0].red, 0.5);
+        assert!(!control.is_faded_out());
+        control.tick(2100).unwrap();
+        assert_eq!(control.driver.frame[0].red, 0.);
+        assert!(control.is_faded_out());
+
+        // Stays faded out.
+        control.tick(3000).unwrap();
+        assert_eq!(control.driver.frame[0].red, 0.);
+    }
+
+    #[test]
+    fn test_max_fps() {
+        let mut control = build();
+        control.set_max_fps(10);
+
+        for time_in_ms in 0..1000 {
+            control.tick(time_in_ms).unwrap();
+        }
+        assert_eq!(control.driver.writes, 10);
+
+        // Skipped frames are not caught up.
+        control.tick(5000).unwrap();
+        control.tick(5050).unwrap();
+        assert_eq!(control.driver.writes, 11);
+
+        control.set_max_fps(0);
+        control.tick(5051).unwrap();
+        assert_eq!(control.driver.writes, 12);
+    }
+
+    #[test]
+    fn test_max_fps_after_clock_reset() {
+        let mut control = build();
+        control.set_max_fps(10);
+
+        control.tick(5000).unwrap();
+        assert_eq!(control.driver.writes, 1);
+
+        // The clock goes back, so the fixed timestep starts again from the new time.
+        control.tick(0).unwrap();
+        assert_eq!(control.driver.writes, 2);
+        for time_in_ms in 1..1000 {
+            control.tick(time_in_ms).unwrap();
+        }
+        assert_eq!(control.driver.writes, 11);
+    }
+
+    #[test]
+    fn test_power_limit() {
+        let mut control = build();
+        *control.pattern_mut() = PatternTrait::<Dim1d, Layout>::new(RainbowParams {
+            time_scalar: 0.,
+            position_scalar: 0.,
+            ..Default::default()
+        });
+
+        // Unlimited, a red rainbow at full brightness draws a single channel per LED.
+        control.set_power_limit(1000, 20.);
+        control.tick(0).unwrap();
+        assert_eq!(control.estimated_milliamps(), Some(80.));
+        assert_eq!(control.driver.frame[0].red, 1.);
+
+        control.set_power_limit(40, 20.);
+        control.tick(1).unwrap();
+        assert_eq!(control.estimated_milliamps(), Some(40.));
+        assert_eq!(control.driver.frame[0].red, 0.5);
+
+        control.set_power_limit(0, 20.);
+        control.tick(2).unwrap();
+        assert_eq!(control.estimated_milliamps(), None);
+        assert_eq!(control.driver.frame[0].red, 1.);
+    }
+
+    #[test]
+    fn test_mask() {
+        let mut control = build();
+        *control.pattern_mut() = PatternTrait::<Dim1d, Layout>::new(RainbowParams {
+            time_scalar: 0.,
+            position_scalar: 0.,
+            ..Default::default()
+        });
+
+        // A shorter mask leaves the remaining pixels unchanged.
+        control.set_mask(&[0., 0.5, 1.]);
+        control.tick(0).unwrap();
+        let reds: [f32; 4] = core::array::from_fn(|index| control.driver.frame[index].red);
+        assert_eq!(reds, [0., 0.5, 1., 1.]);
+
+        control.clear_mask();
+        control.tick(1).unwrap();
+        let reds: [f32; 4] = core::array::from_fn(|index| control.driver.frame[index].red);
+        assert_eq!(reds, [1.; 4]);
+    }
+
+    #[test]
+    fn test_test_frame() {
+        let mut control = build();
+        control.tick(0).unwrap();
+        let pattern_frame = control.driver.frame.clone();
+
+        let white = LinearSrgb::new(1., 1., 1.);
+        control.set_brightness(0.5);
+        control.set_test_frame(Some(white));
+        control.tick(1).unwrap();
+        assert!(control
+            .driver
+            .frame
+            .iter()
+            .all(|pixel| *pixel == LinearSrgb::new(0.5, 0.5, 0.5)));
+
+        control.set_brightness(1.);
+        control.set_test_frame(None);
+        control.tick(0).unwrap();
+        assert_eq!(control.driver.frame, pattern_frame);
+    }
+
+    #[test]
+    fn test_play_static() {
+        let mut control = build();
+        control.tick(0).unwrap();
+        let frame = control.snapshot_frame();
+        let shown = control.driver.frame.clone();
+
+        // The snapshot is before brightness is applied.
+        control.set_brightness(0.5);
+        control.tick(0).unwrap();
+        assert_eq!(control.snapshot_frame(), frame);
+        assert_ne!(control.driver.frame, shown);
+
+        control.set_brightness(1.);
+        control.play_static(Some(frame.clone()));
+        control.tick(5000).unwrap();
+        assert_eq!(control.driver.frame, shown);
+        assert_eq!(control.snapshot().static_frame, Some(frame.clone()));
+
+        control.play_static(None);
+        control.tick(5000).unwrap();
+        assert_ne!(control.driver.frame, shown);
+
+        // A test frame ends the static frame.
+        control.play_static(Some(frame));
+        control.set_test_frame(Some(LinearSrgb::new(1., 1., 1.)));
+        control.tick(5000).unwrap();
+        control.set_test_frame(None);
+        control.tick(5000).unwrap();
+        assert_ne!(control.driver.frame, shown);
+    }
+
+    #[test]
+    fn test_tick_auto() {
+        let mut next_time_in_ms = 0;
+        let mut control = build().with_time_source(move || {
+            let time_in_ms = next_time_in_ms;
+            next_time_in_ms += 50;
+            time_in_ms
+        });
+        control.set_max_fps(10);
+
+        // Frames are due every 100 ms, so every second tick is skipped.
+        for _ in 0..10 {
+            control.tick_auto().unwrap();
+        }
+        assert_eq!(control.driver.writes, 5);
+
+        // Explicit time still works with a time source.
+        control.tick(10_000).unwrap();
+        assert_eq!(control.driver.writes, 6);
+    }
+
+    #[test]
+    fn test_render_into() {
+        let mut control = build();
+        control.set_brightness(0.5);
+        control.set_mask(&[0., 1., 1., 1.]);
+
+        let mut buf = [LinearSrgb::new(0., 0., 0.); 4];
+        control.render_into(0, &mut buf);
+        assert_eq!(control.driver.writes, 0);
+
+        control.tick(0).unwrap();
+        assert_eq!(control.driver.frame.as_slice(), &buf);
+        assert_eq!(buf[0], LinearSrgb::new(0., 0., 0.));
+    }
+
+    #[test]
+    fn test_blur() {
+        layout2d!(
+            Grid,
+            [Shape2d::Grid {
+                start: Vec2::new(-1., -1.),
+                horizontal_end: Vec2::new(1., -1.),
+                vertical_end: Vec2::new(-1., 1.),
+                horizontal_pixel_count: 3,
+                vertical_pixel_count: 3,
+                serpentine: true,
+            }]
+        );
+
+        /// Pattern which lights only the pixel at the center.
+        struct Center;
+
+        impl PatternTrait<Dim2d, Grid> for Center {
+            type Params = ();
+            type Color = LinearSrgb;
+
+            fn new(_params: Self::Params) -> Self {
+                Self
+            }
+
+            fn tick(&self, _time_in_ms: u64) -> impl Iterator<Item = Self::Color> {
+                Grid::points().map(|point| {
+                    let value = if point == Vec2::ZERO { 1. } else { 0. };
+                    LinearSrgb::new(value, value, value)
+                })
+            }
+        }
+
+        let mut control = ControlBuilder::new_2d()
+            .with_layout::<Grid, { Grid::PIXEL_COUNT }>()
+            .with_pattern::<Center>(())
+            .with_driver(FrameDriver::default())
+            .with_frame_buffer_size::<9>()
+            .build();
+        control.set_blur(0.5);
+
+        let mut buf = [LinearSrgb::new(0., 0., 0.); 9];
+        control.render_into(0, &mut buf);
+        let reds: [f32; 9] = core::array::from_fn(|index| buf[index].red);
+
+        // The center keeps half its light, each edge gets half of the average of its 5
+        // neighbors, and each corner gets half of the average of its 3 neighbors.
+        let (center, edge, corner) = (0.5, 0.1, 0.5 / 3.);
+        assert_eq!(
+            reds,
+            [corner, edge, corner, edge, center, edge, corner, edge, corner]
+        );
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_render_into_wrong_length() {
+        let control = build();
+        control.render_into(0, &mut [LinearSrgb::new(0., 0., 0.); 3]);
+    }
+
+    #[test]
+    fn test_next_pattern() {
+        let mut control = ControlBuilder::new_1d()
+            .with_layout::<Layout, { Layout::PIXEL_COUNT }>()
+            .with_pattern::<BuiltinSwitch1d>(BuiltinSwitchParams::default())
+            .with_driver(FrameDriver::default())
+            .with_frame_buffer_size::<4>()
+            .build();
+
+        control.next_pattern();
+        assert_eq!(control.pattern().pattern_index(), 1);
+        control.next_pattern();
+        assert_eq!(control.pattern().pattern_index(), 0);
+        control.select_pattern(3);
+        assert_eq!(control.pattern().pattern_index(), 1);
+    }
+}
@@ -0,0 +1,300 @@
+use core::{fmt, marker::PhantomData};
+
+use crate::{
+    color::{FromColor, LinearSrgb},
+    layout::LayoutForDim,
+    pattern::{FrameTime, Pattern as PatternTrait},
+};
+
+/// A pattern running on a segment of the LEDs, such as one of several physical strips.
+///
+/// The segment starts at a pixel index of the whole layout, and spans as many pixels as
+/// its own layout.
+///
+/// # Type Parameters
+///
+/// - `Layout` - The layout of the segment
+/// - `Pattern` - The pattern of the segment
+pub struct Segment<Layout, Pattern> {
+    /// Index of the first pixel of the segment, in the whole layout
+    start: usize,
+    /// Pattern of the segment
+    pattern: Pattern,
+    /// Marker for the layout of the segment
+    layout: PhantomData<Layout>,
+}
+
+impl<Layout, Pattern> Segment<Layout, Pattern> {
+    /// Creates a new segment.
+    ///
+    /// # Arguments
+    ///
+    /// - `start` - Index of the first pixel of the segment, in the whole layout
+    /// - `params` - The parameters of the segment's pattern
+    pub fn new<Dim>(start: usize, params: Pattern::Params) -> Self
+    where
+        Layout: LayoutForDim<Dim>,
+        Pattern: PatternTrait<Dim, Layout>,
+    {
+        Self {
+            start,
+            pattern: Pattern::new(params),
+            layout: PhantomData,
+        }
+    }
+
+    /// Returns a reference to the pattern of the segment.
+    pub fn pattern(&self) -> &Pattern {
+        &self.pattern
+    }
+
+    /// Returns a mutable reference to the pattern of the segment.
+    pub fn pattern_mut(&mut self) -> &mut Pattern {
+        &mut self.pattern
+    }
+}
+
+impl<Layout, Pattern: Clone> Clone for Segment<Layout, Pattern> {
+    fn clone(&self) -> Self {
+        Self {
+            start: self.start,
+            pattern: self.pattern.clone(),
+            layout: PhantomData,
+        }
+    }
+}
+
+impl<Layout, Pattern: fmt::Debug> fmt::Debug for Segment<Layout, Pattern> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Segment")
+            .field("start", &self.start)
+            .field("pattern", &self.pattern)
+            .finish()
+    }
+}
+
+/// Trait for a list of [`Segment`]s: a single segment, or a tuple of up to 8 segments.
+pub trait Segments<Dim> {
+    /// Ticks the pattern of each segment for a frame, writing its colors into the pixels.
+    ///
+    /// # Arguments
+    ///
+    /// - `frame` - The time of the frame
+    /// - `pixels` - The pixels of the whole layout
+    fn tick(&self, frame: FrameTime, pixels: &mut [LinearSrgb]);
+}
+
+impl<Dim, Layout, Pattern> Segments<Dim> for Segment<Layout, Pattern>
+where
+    Layout: LayoutForDim<Dim>,
+    Pattern: PatternTrait<Dim, Layout>,
+    LinearSrgb: FromColor<Pattern::Color>,
+{
+    fn tick(&self, frame: FrameTime, pixels: &mut [LinearSrgb]) {
+        let Some(pixels) = pixels.get_mut(self.start..) else {
+            return;
+        };
+        for (pixel, color) in pixels.iter_mut().zip(self.pattern.tick_frame(frame)) {
+            *pixel = LinearSrgb::from_color(color);
+        }
+    }
+}
+
+macro_rules! impl_segments_for_tuple {
+    ($($segment:ident),+) => {
+        impl<Dim, $($segment),+> Segments<Dim> for ($($segment,)+)
+        where
+            $($segment: Segments<Dim>,)+
+        {
+            #[allow(non_snake_case)]
+            fn tick(&self, frame: FrameTime, pixels: &mut [LinearSrgb]) {
+                let ($($segment,)+) = self;
+                $($segment.tick(frame, pixels);)+
+            }
+        }
+    };
+}
+
+impl_segments_for_tuple!(A);
+impl_segments_for_tuple!(A, B);
+impl_segments_for_tuple!(A, B, C);
+impl_segments_for_tuple!(A, B, C, D);
+impl_segments_for_tuple!(A, B, C, D, E);
+impl_segments_for_tuple!(A, B, C, D, E, F);
+impl_segments_for_tuple!(A, B, C, D, E, F, G);
+impl_segments_for_tuple!(A, B, C, D, E, F, G, H);
+
+/// A pattern composed of independent patterns, each on a segment of the LEDs.
+///
+/// This lets you run different patterns on several physical strips (or regions of a
+/// layout), driven through one [`Control`](crate::control::Control).
+///
+/// Each frame, the segments are ticked in order, each writing its colors into its range
+/// of pixels. If ranges overlap, the last segment wins. Pixels outside of every range are
+/// off. Segments which extend past the end of the layout are truncated.
+///
+/// # Type Parameters
+///
+/// - `PIXEL_COUNT` - The number of LEDs in the whole layout
+/// - `Segments` - The [`Segments`]: a single [`Segment`], or a tuple of segments
+///
+/// # Example
+///
+/// ```rust,ignore
+/// use blinksy::{
+///     ControlBuilder,
+///     control::{MultiSegment, Segment},
+///     layout::Layout1d,
+///     layout1d,
+///     patterns::{
+///         noise::{noise_fns, Noise1d, NoiseParams},
+///         rainbow::{Rainbow, RainbowParams},
+///     },
+/// };
+///
+/// // Two physical strips, chained on one data line
+/// layout1d!(StripA, 30);
+/// layout1d!(StripB, 60);
+/// layout1d!(Layout, { StripA::PIXEL_COUNT + StripB::PIXEL_COUNT });
+///
+/// type Segments = (
+///     Segment<StripA, Rainbow>,
+///     Segment<StripB, Noise1d<noise_fns::Perlin>>,
+/// );
+///
+/// let mut control = ControlBuilder::new_1d()
+///     .with_layout::<Layout, { Layout::PIXEL_COUNT }>()
+///     .with_pattern::<MultiSegment<{ Layout::PIXEL_COUNT }, Segments>>((
+///         Segment::new(0, RainbowParams::default()),
+///         Segment::new(StripA::PIXEL_COUNT, NoiseParams::default()),
+///     ))
+///     .with_driver(/* Your driver */)
+///     .with_frame_buffer_size::</* Length of frame buffer */>()
+///     .build();
+/// ```
+#[derive(Debug, Clone)]
+pub struct MultiSegment<const PIXEL_COUNT: usize, Segments> {
+    /// The segments
+    segments: Segments,
+}
+
+impl<const PIXEL_COUNT: usize, Segments> MultiSegment<PIXEL_COUNT, Segments> {
+    /// Returns a reference to the segments.
+    pub fn segments(&self) -> &Segments {
+        &self.segments
+    }
+
+    /// Returns a mutable reference to the segments.
+    pub fn segments_mut(&mut self) -> &mut Segments {
+        &mut self.segments
+    }
+}
+
+impl<const PIXEL_COUNT: usize, Dim, Layout, S> PatternTrait<Dim, Layout>
+    for MultiSegment<PIXEL_COUNT, S>
+where
+    Layout: LayoutForDim<Dim>,
+    S: Segments<Dim>,
+{
+    type Params = S;
+    type Color = LinearSrgb;
+
+    /// Creates a new multi-segment pattern from its segments.
+    fn new(segments: Self::Params) -> Self {
+        Self { segments }
+    }
+
+    /// Generates colors for the whole layout, composed from each segment.
+    fn tick(&self, time_in_ms: u64) -> impl Iterator<Item = Self::Color> {
+        PatternTrait::<Dim, Layout>::tick_frame(self, FrameTime::first(time_in_ms))
+    }
+
+    /// Generates colors for the whole layout for a frame, composed from each segment.
+    fn tick_frame(&self, frame: FrameTime) -> impl Iterator<Item = Self::Color> {
+        let mut pixels = [LinearSrgb::new(0., 0., 0.); PIXEL_COUNT];
+        self.segments.tick(frame, &mut pixels);
+        pixels.into_iter()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        color::Srgb, control::test_patterns::Fill, layout::Layout1d, layout1d, markers::Dim1d,
+    };
+
+    layout1d!(Layout, 6);
+    layout1d!(SegmentLayout, 3);
+
+    /// Pattern which shows the frame number.
+    #[derive(Debug, Clone)]
+    struct FrameNumber;
+
+    impl<Layout: Layout1d> PatternTrait<Dim1d, Layout> for FrameNumber {
+        type Params = ();
+        type Color = LinearSrgb;
+
+        fn new(_params: Self::Params) -> Self {
+            Self
+        }
+
+        fn tick(&self, time_in_ms: u64) -> impl Iterator<Item = Self::Color> {
+            PatternTrait::<Dim1d, Layout>::tick_frame(self, FrameTime::first(time_in_ms))
+        }
+
+        fn tick_frame(&self, frame: FrameTime) -> impl Iterator<Item = Self::Color> {
+            Layout::points().map(move |_| LinearSrgb::new(frame.frame as f32 / 10., 0., 0.))
+        }
+    }
+
+    #[test]
+    fn test_segments_forward_frame_time() {
+        type Segments = (
+            Segment<SegmentLayout, FrameNumber>,
+            Segment<SegmentLayout, FrameNumber>,
+        );
+        let pattern: MultiSegment<6, Segments> =
+            PatternTrait::<Dim1d, Layout>::new((Segment::new(0, ()), Segment::new(3, ())));
+
+        let frame = FrameTime {
+            time_in_ms: 1000,
+            frame: 5,
+            delta_in_ms: 16,
+        };
+        assert!(PatternTrait::<Dim1d, Layout>::tick_frame(&pattern, frame)
+            .all(|color| color.red == 0.5));
+    }
+
+    #[test]
+    fn test_segments_compose_last_wins() {
+        let red = Srgb::new(1., 0., 0.);
+        let green = Srgb::new(0., 1., 0.);
+
+        type Segments = (
+            Segment<SegmentLayout, Fill<Srgb>>,
+            Segment<SegmentLayout, Fill<Srgb>>,
+            Segment<SegmentLayout, Fill<Srgb>>,
+        );
+        let pattern: MultiSegment<6, Segments> = PatternTrait::<Dim1d, Layout>::new((
+            Segment::new(0, red),
+            Segment::new(2, green),
+            // Truncated past the end of the layout
+            Segment::new(5, red),
+        ));
+
+        let mut reds = [0.; 6];
+        let mut greens = [0.; 6];
+        for ((red, green), color) in
+            reds.iter_mut()
+                .zip(greens.iter_mut())
+                .zip(PatternTrait::<Dim1d, Layout>::tick(&pattern, 0))
+        {
+            *red = color.red;
+            *green = color.green;
+        }
+
+        assert_eq!(reds, [1., 1., 0., 0., 0., 1.]);
+        assert_eq!(greens, [0., 0., 1., 1., 1., 0.]);
+    }
+}
@@ -0,0 +1,22 @@
+//! Patterns shared by the tests of the pattern combinators.
+
+use crate::{
+    color::LinearSrgb, layout::Layout1d, markers::Dim1d, pattern::Pattern as PatternTrait,
+};
+
+/// Pattern which fills its layout with a single color.
+#[derive(Debug, Clone)]
+pub(crate) struct Fill<C = LinearSrgb>(C);
+
+impl<Layout: Layout1d, C: Copy> PatternTrait<Dim1d, Layout> for Fill<C> {
+    type Params = C;
+    type Color = C;
+
+    fn new(params: Self::Params) -> Self {
+        Self(params)
+    }
+
+    fn tick(&self, _time_in_ms: u64) -> impl Iterator<Item = Self::Color> {
+        Layout::points().map(|_| self.0)
+    }
+}
@@ -7,9 +7,9 @@ use crate::{
     util::bits::{word_to_bits_msb, Word as WordTrait},
 };
 
-use super::ClockedWriter;
 #[cfg(feature = "async")]
 use super::ClockedWriterAsync;
+use super::{ClockedLed, ClockedWriter};
 
 /// Builder for [`ClockedDelay`].
 pub struct ClockedDelayBuilder<Data, Clock, Delay, DataRate> {
@@ -90,9 +90,31 @@ where
     Data: OutputPin,
     Clock: OutputPin,
 {
+    /// Builds the [`ClockedDelay`], without checking the data rate.
+    ///
+    /// Prefer [`Self::try_build`], to catch a data rate faster than the LEDs support.
+    #[deprecated(note = "doesn't check the data rate, use `try_build` instead")]
     pub fn build(self) -> ClockedDelay<Data, Clock, Delay> {
         ClockedDelay::new(self.data, self.clock, self.delay, self.data_rate)
     }
+
+    /// Builds the [`ClockedDelay`], checking the data rate against the LED chipset's
+    /// [`ClockedLed::MAX_DATA_RATE`], if any.
+    pub fn try_build<Led: ClockedLed>(
+        self,
+    ) -> Result<ClockedDelay<Data, Clock, Delay>, DataRateError> {
+        ClockedDelay::try_new::<Led>(self.data, self.clock, self.delay, self.data_rate)
+    }
+}
+
+/// Error for a data rate faster than a LED chipset supports.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct DataRateError {
+    /// The requested data rate
+    pub data_rate: Megahertz,
+    /// The fastest data rate the LED chipset supports
+    pub max_data_rate: Megahertz,
 }
 
 /// Writer for clocked LEDs using GPIO bit-banging with a delay timer.
@@ -131,7 +153,9 @@ where
 ///             .with_clock(clock_pin)
 ///             .with_delay(delay)
 ///             .with_data_rate(Megahertz::MHz(2))
-///             .build()
+///             .try_build::<Apa102>()
+///             // 2 MHz is within the 24 MHz an APA102 supports
+///             .unwrap()
 ///         )
 /// }
 /// ```
@@ -160,7 +184,9 @@ where
     Data: OutputPin,
     Clock: OutputPin,
 {
-    /// Creates a new ClockedDelay.
+    /// Creates a new ClockedDelay, without checking the data rate.
+    ///
+    /// Prefer [`Self::try_new`], to catch a data rate faster than the LEDs support.
     ///
     /// # Arguments
     ///
@@ -184,6 +210,35 @@ where
             t_half_cycle_ns,
         }
     }
+
+    /// Creates a new ClockedDelay, checking the data rate against the LED chipset's
+    /// [`ClockedLed::MAX_DATA_RATE`], if any.
+    ///
+    /// # Arguments
+    ///
+    /// - `data` - The GPIO pin for data output
+    /// - `clock` - The GPIO pin for clock output
+    /// - `delay` - The delay provider for timing control
+    /// - `data_rate` - The clock frequency in MHz
+    ///
+    /// # Returns
+    ///
+    /// A new ClockedDelay instance, or an error if the data rate is faster than the LEDs
+    /// support
+    pub fn try_new<Led: ClockedLed>(
+        data: Data,
+        clock: Clock,
+        delay: Delay,
+        data_rate: Megahertz,
+    ) -> Result<Self, DataRateError> {
+        match Led::MAX_DATA_RATE {
+            Some(max_data_rate) if data_rate > max_data_rate => Err(DataRateError {
+                data_rate,
+                max_data_rate,
+            }),
+            _ => Ok(Self::new(data, clock, delay, data_rate)),
+        }
+    }
 }
 
 /// Error type for the ClockedDelay.
@@ -299,3 +354,104 @@ where
         Ok(())
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use core::convert::Infallible;
+
+    use embedded_hal::digital::ErrorType;
+
+    use super::*;
+    use crate::{color::ColorCorrection, leds::Apa102};
+
+    /// Pin which ignores every change.
+    #[derive(Debug)]
+    struct NullPin;
+
+    impl ErrorType for NullPin {
+        type Error = Infallible;
+    }
+
+    impl OutputPin for NullPin {
+        fn set_low(&mut self) -> Result<(), Self::Error> {
+            Ok(())
+        }
+
+        fn set_high(&mut self) -> Result<(), Self::Error> {
+            Ok(())
+        }
+    }
+
+    /// Delay which returns immediately.
+    #[derive(Debug)]
+    struct NullDelay;
+
+    impl DelayNs for NullDelay {
+        fn delay_ns(&mut self, _ns: u32) {}
+    }
+
+    /// LED chipset with no known maximum data rate.
+    struct UnknownLed;
+
+    impl ClockedLed for UnknownLed {
+        type Word = u8;
+        type Color = ();
+
+        fn start() -> impl IntoIterator<Item = Self::Word> {
+            []
+        }
+
+        fn led(
+            _color: Self::Color,
+            _brightness: f32,
+            _correction: ColorCorrection,
+        ) -> impl IntoIterator<Item = Self::Word> {
+            []
+        }
+
+        fn end(_pixel_count: usize) -> impl IntoIterator<Item = Self::Word> {
+            []
+        }
+    }
+
+    fn try_new<Led: ClockedLed>(
+        data_rate: Megahertz,
+    ) -> Result<ClockedDelay<NullPin, NullPin, NullDelay>, DataRateError> {
+        ClockedDelay::try_new::<Led>(NullPin, NullPin, NullDelay, data_rate)
+    }
+
+    #[test]
+    fn test_try_new_checks_data_rate() {
+        assert!(try_new::<Apa102>(Megahertz::MHz(24)).is_ok());
+        assert_eq!(
+            try_new::<Apa102>(Megahertz::MHz(30)).unwrap_err(),
+            DataRateError {
+                data_rate: Megahertz::MHz(30),
+                max_data_rate: Megahertz::MHz(24),
+            }
+        );
+    }
+
+    #[test]
+    fn test_try_new_without_max_data_rate() {
+        assert!(try_new::<UnknownLed>(Megahertz::MHz(100)).is_ok());
+    }
+
+    #[test]
+    fn test_try_build_checks_data_rate() {
+        let builder = || {
+            ClockedDelayBuilder::default()
+                .with_data(NullPin)
+                .with_clock(NullPin)
+                .with_delay(NullDelay)
+        };
+        assert!(builder()
+            .with_data_rate(Megahertz::MHz(2))
+            .try_build::<Apa102>()
+            .is_ok());
+        assert!(builder()
+            .with_data_rate(Megahertz::MHz(25))
+            .try_build::<Apa102>()
+            .is_err());
+    }
+}
@@ -35,6 +35,7 @@
 //! use blinksy::{
 //!     color::{ColorCorrection, FromColor, LinearSrgb, RgbChannels},
 //!     driver::ClockedLed,
+//!     time::Megahertz,
 //!     util::component::Component,
 //! };
 //!
@@ -45,6 +46,9 @@
 //!     type Word = u8;
 //!     type Color = LinearSrgb;
 //!
+//!     // Fastest clock rate the chipset supports
+//!     const MAX_DATA_RATE: Option<Megahertz> = Some(Megahertz::MHz(20));
+//!
 //!     fn start() -> impl IntoIterator<Item = Self::Word> {
 //!         // Start frame
 //!         [0x00, 0x00, 0x00, 0x00]
@@ -57,10 +61,8 @@
 //!     ) -> impl IntoIterator<Item = Self::Word> {
 //!         // Color data for one LED
 //!         let linear = LinearSrgb::from_color(color);
-//!         let (mut r, mut g, mut b) = (linear.red, linear.green, linear.blue);
-//!         r = r * brightness * correction.red;
-//!         g = g * brightness * correction.green;
-//!         b = b * brightness * correction.blue;
+//!         let (r, g, b) = correction.apply(linear.red, linear.green, linear.blue);
+//!         let (r, g, b) = (r * brightness, g * brightness, b * brightness);
 //!         let (r_u8, g_u8, b_u8) = (
 //!             Component::from_normalized_f32(r),
 //!             Component::from_normalized_f32(g),
@@ -86,6 +88,7 @@ use crate::color::{ColorCorrection, FromColor};
 use crate::driver::Driver;
 #[cfg(feature = "async")]
 use crate::driver::DriverAsync;
+use crate::time::Megahertz;
 
 mod delay;
 mod spi;
@@ -108,6 +111,15 @@ pub trait ClockedLed {
     /// The color representation type.
     type Color;
 
+    /// The fastest data rate (clock frequency) the chipset supports, if known.
+    ///
+    /// Over-clocking a chipset doesn't fail loudly, the LEDs just flicker or show the wrong
+    /// colors. [`ClockedDelay::try_new`] checks its data rate against this. For an SPI bus,
+    /// configure the bus frequency at or below this.
+    ///
+    /// By default, `None`: any data rate is allowed.
+    const MAX_DATA_RATE: Option<Megahertz> = None;
+
     /// A start frame to begin a transmission.
     ///
     /// # Returns
@@ -271,6 +283,16 @@ impl<Led> ClockedDriver<Led, ()> {
     }
 }
 
+impl<Led, Writer> ClockedDriver<Led, Writer> {
+    /// Changes the LED protocol of the driver, keeping its writer.
+    pub(crate) fn map_led<NewLed>(self) -> ClockedDriver<NewLed, Writer> {
+        ClockedDriver {
+            led: PhantomData,
+            writer: self.writer,
+        }
+    }
+}
+
 impl<Led, Writer> Driver for ClockedDriver<Led, Writer>
 where
     Led: ClockedLed,
@@ -15,6 +15,10 @@ use super::ClockedWriterAsync;
 ///   - Better timing precision
 /// - Parameters defined by a ClockedLed implementation
 ///
+/// The data rate is the SPI bus frequency, which is configured with your HAL. Keep it at
+/// or below the chipset's [`ClockedLed::MAX_DATA_RATE`](super::ClockedLed::MAX_DATA_RATE),
+/// e.g. `Apa102::MAX_DATA_RATE`, if known.
+///
 /// ## Usage
 ///
 /// ```rust
@@ -0,0 +1,368 @@
+use crate::{
+    layout::{Layout2d, Shape2d},
+    util::component::{Component, QuantizeMode},
+};
+
+/// Trait for how a clockless driver quantizes normalized channel values into words.
+pub trait Dither {
+    /// Quantizes a normalized channel value, at an index of the frame buffer, into a word.
+    ///
+    /// # Arguments
+    ///
+    /// - `index` - The index of the word in the frame buffer
+    /// - `value` - The normalized channel value (0.0 to 1.0)
+    /// - `mode` - How to quantize the value, where it isn't otherwise dithered
+    ///
+    /// # Returns
+    ///
+    /// The word to write
+    fn quantize<Word: Component>(&mut self, index: usize, value: f32, mode: QuantizeMode) -> Word;
+}
+
+/// No dithering: each value is quantized independently, with the mode.
+impl Dither for () {
+    #[inline]
+    fn quantize<Word: Component>(&mut self, _index: usize, value: f32, mode: QuantizeMode) -> Word {
+        mode.quantize(value, &mut 0.)
+    }
+}
+
+/// Temporal dithering.
+///
+/// At low brightness, quantizing to 8-bit words causes visible banding. With temporal
+/// dithering, the error lost when quantizing each word (the sub-LSB remainder) is carried
+/// forward into the same word of the next frame. Over many frames, the average output
+/// matches the full-precision color.
+///
+/// Keeps one `f32` error accumulator per word of the frame buffer, for
+/// [`QuantizeMode::Dither`]. With [`QuantizeMode::Round`] or [`QuantizeMode::Floor`],
+/// dithering is disabled, and each error is cleared as its word is quantized.
+///
+/// Tip: Use [`ClocklessDriver::with_temporal_dithering`](super::ClocklessDriver::with_temporal_dithering)
+/// to add temporal dithering to your driver.
+///
+/// # Type Parameters
+///
+/// - `FRAME_BUFFER_SIZE` - Length of the frame buffer, in words
+#[derive(Debug)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct TemporalDither<const FRAME_BUFFER_SIZE: usize> {
+    /// Error carried forward for each word, in normalized units
+    errors: [f32; FRAME_BUFFER_SIZE],
+}
+
+impl<const FRAME_BUFFER_SIZE: usize> Default for TemporalDither<FRAME_BUFFER_SIZE> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<const FRAME_BUFFER_SIZE: usize> TemporalDither<FRAME_BUFFER_SIZE> {
+    /// Creates a new temporal dither, with no error carried forward.
+    pub fn new() -> Self {
+        Self {
+            errors: [0.; FRAME_BUFFER_SIZE],
+        }
+    }
+}
+
+impl<const FRAME_BUFFER_SIZE: usize> Dither for TemporalDither<FRAME_BUFFER_SIZE> {
+    fn quantize<Word: Component>(&mut self, index: usize, value: f32, mode: QuantizeMode) -> Word {
+        match self.errors.get_mut(index) {
+            Some(error) => mode.quantize(value, error),
+            // Beyond the frame buffer, nothing is carried forward.
+            None => mode.quantize(value, &mut 0.),
+        }
+    }
+}
+
+/// The 4x4 Bayer matrix, of thresholds from 0 to 15.
+const BAYER_4X4: [[u8; 4]; 4] = [[0, 8, 2, 10], [12, 4, 14, 6], [3, 11, 1, 9], [15, 7, 13, 5]];
+
+/// Ordered (Bayer) dithering, for 2D grids.
+///
+/// At low brightness, quantizing to words causes visible banding. With ordered
+/// dithering, each pixel of the grid has a threshold, from a 4x4 Bayer matrix tiled
+/// across the grid, which decides whether the pixel rounds up or down. Over each 4x4
+/// block, the average output matches the full-precision color. Unlike
+/// [`TemporalDither`], the output of each frame is the same, so doesn't flicker.
+///
+/// Needs the position of each pixel in the grid, so is made from the grid of a layout.
+/// Pixels before or beyond the grid, or with dithering disabled, are quantized with the
+/// [`QuantizeMode`] of the driver.
+///
+/// The thresholds are scaled to one level of the word, so any integer word is dithered
+/// (for a `u16` word, between adjacent 16-bit levels).
+///
+/// Tip: Use [`ClocklessDriver::with_bayer_dithering`](super::ClocklessDriver::with_bayer_dithering)
+/// to add ordered dithering to your driver.
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct BayerDither {
+    /// Whether dithering is enabled
+    is_enabled: bool,
+    /// Number of words per pixel
+    channel_count: usize,
+    /// Number of pixels along each horizontal row of the grid
+    horizontal_pixel_count: usize,
+    /// Number of pixels along each vertical column of the grid
+    vertical_pixel_count: usize,
+    /// Whether horizontal rows of pixels are wired in a zigzag pattern
+    serpentine: bool,
+    /// Number of pixels before the first pixel of the grid
+    pixel_offset: usize,
+}
+
+impl BayerDither {
+    /// Creates a new ordered dither for a grid.
+    ///
+    /// # Arguments
+    ///
+    /// - `channel_count` - Number of words per pixel, such as 3 for RGB
+    /// - `horizontal_pixel_count` - Number of pixels along each horizontal row
+    /// - `vertical_pixel_count` - Number of pixels along each vertical column
+    /// - `serpentine` - Whether horizontal rows of pixels are wired in a zigzag pattern
+    pub fn new(
+        channel_count: usize,
+        horizontal_pixel_count: usize,
+        vertical_pixel_count: usize,
+        serpentine: bool,
+    ) -> Self {
+        Self {
+            is_enabled: true,
+            channel_count,
+            horizontal_pixel_count,
+            vertical_pixel_count,
+            serpentine,
+            pixel_offset: 0,
+        }
+    }
+
+    /// Returns the dither with the grid starting after a number of pixels, such as after
+    /// the pixels of other shapes in a layout.
+    ///
+    /// # Arguments
+    ///
+    /// - `pixel_offset` - Number of pixels before the first pixel of the grid
+    pub fn with_pixel_offset(self, pixel_offset: usize) -> Self {
+        Self {
+            pixel_offset,
+            ..self
+        }
+    }
+
+    /// Creates a new ordered dither for the first grid of a 2D layout.
+    ///
+    /// The grid starts after the pixels of any shapes before it. If the layout has no grid,
+    /// nothing is dithered.
+    ///
+    /// # Type Parameters
+    ///
+    /// - `Layout` - The 2D layout
+    ///
+    /// # Arguments
+    ///
+    /// - `channel_count` - Number of words per pixel, such as 3 for RGB
+    pub fn from_layout<Layout: Layout2d>(channel_count: usize) -> Self {
+        let mut pixel_offset = 0;
+        for shape in Layout::shapes() {
+            if let Shape2d::Grid {
+                horizontal_pixel_count,
+                vertical_pixel_count,
+                serpentine,
+                ..
+            } = shape
+            {
+                return Self::new(
+                    channel_count,
+                    horizontal_pixel_count,
+                    vertical_pixel_count,
+                    serpentine,
+                )
+                .with_pixel_offset(pixel_offset);
+            }
+            pixel_offset += shape.pixel_count();
+        }
+        Self::new(channel_count, 0, 0, false)
+    }
+
+    /// Enables or disables dithering.
+    pub fn set_enabled(&mut self, is_enabled: bool) {
+        self.is_enabled = is_enabled;
+    }
+
+    /// Returns whether dithering is enabled.
+    pub fn is_enabled(&self) -> bool {
+        self.is_enabled
+    }
+
+    /// Returns the threshold for a word, from 0.0 to 1.0, or `None` if beyond the grid.
+    fn threshold(&self, index: usize) -> Option<f32> {
+        if self.channel_count == 0 || self.horizontal_pixel_count == 0 {
+            return None;
+        }
+        let pixel = (index / self.channel_count).checked_sub(self.pixel_offset)?;
+        let row = pixel / self.horizontal_pixel_count;
+        if row >= self.vertical_pixel_count {
+            return None;
+        }
+        let column = pixel % self.horizontal_pixel_count;
+        // In a serpentine grid, every odd row runs backwards.
+        let column = if self.serpentine && row % 2 == 1 {
+            self.horizontal_pixel_count - 1 - column
+        } else {
+            column
+        };
+        let threshold = BAYER_4X4[row % 4][column % 4];
+        Some((threshold as f32 + 0.5) / 16.)
+    }
+}
+
+impl Dither for BayerDither {
+    fn quantize<Word: Component>(&mut self, index: usize, value: f32, mode: QuantizeMode) -> Word {
+        if !self.is_enabled {
+            return mode.quantize(value, &mut 0.);
+        }
+        let Some(threshold) = self.threshold(index) else {
+            return mode.quantize(value, &mut 0.);
+        };
+        if Word::STEP == 0. {
+            // A continuous word has no levels to dither between.
+            return Word::from_normalized_f32(value);
+        }
+        Word::from_normalized_f32_floor(value.clamp(0., 1.) + threshold * Word::STEP)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_average_approaches_target() {
+        const FRAMES: usize = 1000;
+        let target = 10.3 / 255.;
+
+        let mut dither = TemporalDither::<1>::default();
+        let sum: u32 = (0..FRAMES)
+            .map(|_| dither.quantize::<u8>(0, target, QuantizeMode::Dither) as u32)
+            .sum();
+        let average = sum as f32 / FRAMES as f32 / 255.;
+        assert!((average - target).abs() < 0.01 / 255.);
+
+        // Without dithering, the remainder is always lost.
+        assert_eq!(dither.quantize::<u8>(0, target, QuantizeMode::Round), 10);
+        assert_eq!(
+            dither.quantize::<u8>(0, 10.6 / 255., QuantizeMode::Round),
+            11
+        );
+        assert_eq!(
+            dither.quantize::<u8>(0, 10.6 / 255., QuantizeMode::Floor),
+            10
+        );
+        // And the error carried forward was cleared.
+        assert_eq!(dither.quantize::<u8>(0, target, QuantizeMode::Dither), 10);
+    }
+
+    #[test]
+    fn test_bayer_dark_gradient() {
+        const WIDTH: usize = 16;
+        const HEIGHT: usize = 4;
+
+        // A dark gradient, from level 10.0 to 11.0 across the grid.
+        let target = |column: usize| (10. + column as f32 / WIDTH as f32) / 255.;
+        let levels = |dither: &mut dyn FnMut(usize, f32) -> u8| -> [[u8; WIDTH]; HEIGHT] {
+            core::array::from_fn(|row| {
+                core::array::from_fn(|column| dither(row * WIDTH + column, target(column)))
+            })
+        };
+
+        // The error of the average of each 4x4 block, in levels.
+        let block_errors = |levels: [[u8; WIDTH]; HEIGHT]| -> [f32; WIDTH / 4] {
+            core::array::from_fn(|block| {
+                let columns = block * 4..block * 4 + 4;
+                let sum: u32 = levels
+                    .iter()
+                    .flat_map(|row| row[columns.clone()].iter())
+                    .map(|&level| level as u32)
+                    .sum();
+                let target: f32 = columns.map(|column| target(column) * 255.).sum::<f32>() * 4.;
+                (sum as f32 - target).abs() / 16.
+            })
+        };
+
+        let mut bayer = BayerDither::new(1, WIDTH, HEIGHT, false);
+        let dithered =
+            levels(&mut |index, value| bayer.quantize(index, value, QuantizeMode::Round));
+        let undithered = levels(&mut |index, value| ().quantize(index, value, QuantizeMode::Floor));
+
+        // Without dithering, rounded down, every pixel bands to level 10.
+        assert!(undithered.iter().flatten().all(|&level| level == 10));
+        assert!(block_errors(undithered)[3] > 0.8);
+
+        // With dithering, each block averages to its target.
+        assert!(dithered.iter().flatten().any(|&level| level == 11));
+        assert!(block_errors(dithered).iter().all(|&error| error < 0.1));
+    }
+
+    #[test]
+    fn test_bayer_16_bit_words() {
+        const LEVEL: f32 = 1. / u16::MAX as f32;
+
+        let mut bayer = BayerDither::new(1, 4, 4, false);
+        // A quarter of a 16-bit level above level 1000.
+        let value = 1000.25 * LEVEL;
+        let levels: [u16; 16] =
+            core::array::from_fn(|index| bayer.quantize(index, value, QuantizeMode::Round));
+
+        assert!(levels.iter().all(|&level| level == 1000 || level == 1001));
+        let sum: u32 = levels.iter().map(|&level| level as u32).sum();
+        assert_eq!(sum, 1000 * 16 + 4);
+    }
+
+    #[test]
+    fn test_bayer_from_layout_after_other_shapes() {
+        use crate::{
+            layout::{Shape2d, Vec2},
+            layout2d,
+        };
+
+        layout2d!(
+            Layout,
+            [
+                Shape2d::Point(Vec2::new(0., 0.)),
+                Shape2d::Point(Vec2::new(0., 0.5)),
+                Shape2d::Grid {
+                    start: Vec2::new(-1., -1.),
+                    horizontal_end: Vec2::new(1., -1.),
+                    vertical_end: Vec2::new(-1., 1.),
+                    horizontal_pixel_count: 4,
+                    vertical_pixel_count: 4,
+                    serpentine: false,
+                }
+            ]
+        );
+
+        let bayer = BayerDither::from_layout::<Layout>(3);
+        // The 2 points before the grid aren't dithered.
+        assert_eq!(bayer.threshold(0), None);
+        assert_eq!(bayer.threshold(5), None);
+        // The grid starts at the third pixel, with the first threshold of the matrix.
+        assert_eq!(bayer.threshold(6), Some(0.5 / 16.));
+        assert_eq!(bayer.threshold(9), Some(8.5 / 16.));
+    }
+
+    #[test]
+    fn test_bayer_beyond_grid() {
+        let mut bayer = BayerDither::new(3, 2, 2, true);
+        let value = 10.9 / 255.;
+        // 2x2 pixels of 3 words each, then beyond the grid, which is quantized with the mode.
+        assert_eq!(bayer.quantize::<u8>(0, value, QuantizeMode::Round), 10);
+        assert_eq!(bayer.quantize::<u8>(12, value, QuantizeMode::Round), 11);
+        assert_eq!(bayer.quantize::<u8>(12, value, QuantizeMode::Floor), 10);
+
+        bayer.set_enabled(false);
+        assert_eq!(bayer.quantize::<u8>(3, value, QuantizeMode::Round), 11);
+        assert_eq!(bayer.quantize::<u8>(3, value, QuantizeMode::Floor), 10);
+    }
+}
@@ -24,6 +24,23 @@
 //!
 //! - [`ClocklessDriver`]: Generic driver for clockless LEDs and writers.
 //!
+//! ## Dithering
+//!
+//! - [`TemporalDither`]: Carries quantization error between frames, for smooth low
+//!   brightness.
+//! - [`BayerDither`]: Applies an ordered dither across a 2D grid, for smooth low
+//!   brightness without flicker.
+//!
+//! Without dithering, each channel is rounded to the nearest word. To round down instead,
+//! see [`ClocklessDriver::with_quantize_mode`] and [`QuantizeMode`].
+//!
+//! ## Gamma Lookup
+//!
+//! To apply a gamma to the output without a `powf` per channel, such as instead of a gamma
+//! in the [`ColorCorrection`], add a [`GammaLut256`] with
+//! [`ClocklessDriver::with_gamma_lut`]. Each channel is mapped through the table before it
+//! is quantized, so any dithering still smooths the darks.
+//!
 //! ## Writers
 //!
 //! - ~~[`ClocklessDelay`]: Writer using GPIO bit-banging with a delay timer~~
@@ -63,18 +80,23 @@
 use core::marker::PhantomData;
 use heapless::Vec;
 
+#[cfg(feature = "fixed-point")]
+use crate::color::fixed::LinearSrgbFixed;
 #[cfg(feature = "async")]
 use crate::driver::DriverAsync;
 use crate::{
-    color::{ColorCorrection, FromColor, LedChannels, LedColor, LinearSrgb},
+    color::{ColorCorrection, FromColor, GammaLut256, LedChannels, LedColor, LinearSrgb},
     driver::Driver,
+    layout::Layout2d,
     time::Nanoseconds,
-    util::component::Component,
+    util::component::{Component, QuantizeMode},
 };
 
 mod delay;
+mod dither;
 
 pub use self::delay::*;
+pub use self::dither::*;
 
 /// Trait that defines the timing parameters and protocol specifics for a clockless LED chipset.
 ///
@@ -165,13 +187,120 @@ pub trait ClocklessLed {
         I: IntoIterator<Item = C>,
         LinearSrgb: FromColor<C>,
     {
-        Vec::from_iter(pixels.into_iter().flat_map(|pixel| {
-            let linear_srgb = LinearSrgb::from_color(pixel);
-            let data: LedColor<Self::Word> =
-                linear_srgb.to_led(Self::LED_CHANNELS, brightness, correction);
-            data.into_iter()
-        }))
+        Self::encode_with_dither::<PIXEL_COUNT, BUFFER_SIZE, _, _, _>(
+            pixels,
+            brightness,
+            correction,
+            &mut (),
+            QuantizeMode::Round,
+        )
     }
+
+    /// Encodes a buffer to represent the next frame update, with [`Dither`].
+    ///
+    /// The same as [`ClocklessLed::encode`], except each channel is quantized into a word
+    /// by the dither, with a [`QuantizeMode`].
+    ///
+    /// # Type Arguments
+    ///
+    /// - `PIXEL_COUNT`: Number of pixels
+    /// - `BUFFER_SIZE`: Size of the frame buffer
+    ///
+    /// # Arguments
+    ///
+    /// - `pixels` - Iterator over colors
+    /// - `brightness` - Global brightness scaling factor (0.0 to 1.0)
+    /// - `correction` - Color correction factors
+    /// - `dither` - How to dither each channel into a word
+    /// - `quantize_mode` - How to quantize each channel, where it isn't otherwise dithered
+    fn encode_with_dither<const PIXEL_COUNT: usize, const BUFFER_SIZE: usize, I, C, D>(
+        pixels: I,
+        brightness: f32,
+        correction: ColorCorrection,
+        dither: &mut D,
+        quantize_mode: QuantizeMode,
+    ) -> Vec<Self::Word, BUFFER_SIZE>
+    where
+        I: IntoIterator<Item = C>,
+        LinearSrgb: FromColor<C>,
+        D: Dither,
+    {
+        Self::encode_with_channels::<PIXEL_COUNT, BUFFER_SIZE, _, _, _>(
+            pixels,
+            Self::LED_CHANNELS,
+            brightness,
+            correction,
+            dither,
+            quantize_mode,
+            None,
+        )
+    }
+
+    /// Encodes a buffer to represent the next frame update, with [`Dither`], in a color
+    /// channel order other than [`ClocklessLed::LED_CHANNELS`], and with an optional
+    /// [`GammaLut256`].
+    ///
+    /// The same as [`ClocklessLed::encode_with_dither`], except the color channels are
+    /// ordered by `channels`, such as for a clone of an LED chipset with another order, and
+    /// each channel is mapped through the gamma lookup table, if any, before it is quantized.
+    ///
+    /// # Type Arguments
+    ///
+    /// - `PIXEL_COUNT`: Number of pixels
+    /// - `BUFFER_SIZE`: Size of the frame buffer
+    ///
+    /// # Arguments
+    ///
+    /// - `pixels` - Iterator over colors
+    /// - `channels` - The color channel order and format
+    /// - `brightness` - Global brightness scaling factor (0.0 to 1.0)
+    /// - `correction` - Color correction factors
+    /// - `dither` - How to dither each channel into a word
+    /// - `quantize_mode` - How to quantize each channel, where it isn't otherwise dithered
+    /// - `gamma_lut` - The gamma lookup table to map each channel through, if any
+    fn encode_with_channels<const PIXEL_COUNT: usize, const BUFFER_SIZE: usize, I, C, D>(
+        pixels: I,
+        channels: LedChannels,
+        brightness: f32,
+        correction: ColorCorrection,
+        dither: &mut D,
+        quantize_mode: QuantizeMode,
+        gamma_lut: Option<&GammaLut256>,
+    ) -> Vec<Self::Word, BUFFER_SIZE>
+    where
+        I: IntoIterator<Item = C>,
+        LinearSrgb: FromColor<C>,
+        D: Dither,
+    {
+        Vec::from_iter(
+            pixels
+                .into_iter()
+                .flat_map(|pixel| {
+                    let linear_srgb = LinearSrgb::from_color(pixel);
+                    let data: LedColor<f32> = linear_srgb.to_led(channels, brightness, correction);
+                    data.into_iter()
+                })
+                .enumerate()
+                .map(|(index, value)| encode_word(dither, quantize_mode, gamma_lut, index, value)),
+        )
+    }
+}
+
+/// Maps a channel through a gamma lookup table, if any, then quantizes it into a word with
+/// a dither and a mode.
+#[inline]
+pub(crate) fn encode_word<Word: Component, D: Dither>(
+    dither: &mut D,
+    quantize_mode: QuantizeMode,
+    gamma_lut: Option<&GammaLut256>,
+    index: usize,
+    value: f32,
+) -> Word {
+    let value = match gamma_lut {
+        Some(gamma_lut) => gamma_lut.apply_normalized(value),
+        None => value,
+    };
+    dither.quantize(index, value, quantize_mode)
 }
 
 /// Trait for types that can write data words to a clockless protocol.
@@ -206,13 +335,22 @@ pub trait ClocklessWriterAsync<Led: ClocklessLed> {
 ///
 /// - `Led` - The LED protocol implementation (must implement ClocklessLed)
 /// - `Writer` - The clocked writer
+/// - `Dither` - The [`Dither`], by default none
 #[derive(Debug)]
 #[cfg_attr(feature = "defmt", derive(defmt::Format))]
-pub struct ClocklessDriver<Led, Writer> {
+pub struct ClocklessDriver<Led, Writer, Dither = ()> {
     /// Marker for the LED protocol type
     led: PhantomData<Led>,
     /// Writer implementation for the clocked protocol
     writer: Writer,
+    /// How to quantize each channel into a word
+    dither: Dither,
+    /// Color channel order, if overriding the order of the LED protocol
+    channels: Option<LedChannels>,
+    /// How to quantize each channel, where it isn't otherwise dithered
+    quantize_mode: QuantizeMode,
+    /// Gamma lookup table to map each word through, if any
+    gamma_lut: Option<GammaLut256>,
 }
 
 impl Default for ClocklessDriver<(), ()> {
@@ -220,6 +358,10 @@ impl Default for ClocklessDriver<(), ()> {
         ClocklessDriver {
             led: PhantomData,
             writer: (),
+            dither: (),
+            channels: None,
+            quantize_mode: QuantizeMode::Round,
+            gamma_lut: None,
         }
     }
 }
@@ -229,6 +371,10 @@ impl<Writer> ClocklessDriver<(), Writer> {
         ClocklessDriver {
             led: PhantomData,
             writer: self.writer,
+            dither: self.dither,
+            channels: self.channels,
+            quantize_mode: self.quantize_mode,
+            gamma_lut: self.gamma_lut,
         }
     }
 }
@@ -238,14 +384,242 @@ impl<Led> ClocklessDriver<Led, ()> {
         ClocklessDriver {
             led: self.led,
             writer,
+            dither: self.dither,
+            channels: self.channels,
+            quantize_mode: self.quantize_mode,
+            gamma_lut: self.gamma_lut,
+        }
+    }
+}
+
+impl<Led, Writer> ClocklessDriver<Led, Writer> {
+    /// Adds [`TemporalDither`] to the driver, and sets the [`QuantizeMode::Dither`] mode.
+    ///
+    /// # Type Parameters
+    ///
+    /// - `FRAME_BUFFER_SIZE` - Length of the frame buffer, in words
+    pub fn with_temporal_dithering<const FRAME_BUFFER_SIZE: usize>(
+        self,
+    ) -> ClocklessDriver<Led, Writer, TemporalDither<FRAME_BUFFER_SIZE>> {
+        ClocklessDriver {
+            led: self.led,
+            writer: self.writer,
+            dither: TemporalDither::default(),
+            channels: self.channels,
+            quantize_mode: QuantizeMode::Dither,
+            gamma_lut: self.gamma_lut,
+        }
+    }
+
+    /// Adds [`BayerDither`] to the driver, for the first grid of a 2D layout.
+    ///
+    /// # Type Parameters
+    ///
+    /// - `Layout` - The 2D layout
+    pub fn with_bayer_dithering<Layout: Layout2d>(self) -> ClocklessDriver<Led, Writer, BayerDither>
+    where
+        Led: ClocklessLed,
+    {
+        let channel_count = self.led_channels().channel_count();
+        ClocklessDriver {
+            led: self.led,
+            writer: self.writer,
+            dither: BayerDither::from_layout::<Layout>(channel_count),
+            channels: self.channels,
+            quantize_mode: self.quantize_mode,
+            gamma_lut: self.gamma_lut,
         }
     }
 }
 
-impl<Led, Writer> Driver for ClocklessDriver<Led, Writer>
+impl<Led, Writer, Dither> ClocklessDriver<Led, Writer, Dither> {
+    /// Overrides the color channel order of the LED protocol.
+    ///
+    /// For LEDs which use the timing of a chipset, but another channel order, such as
+    /// WS2812 clones with RGB or BGR order, rather than the datasheet's GRB.
+    ///
+    /// Note: If the number of channels differs from the LED protocol (such as RGBW rather
+    /// than RGB), size the frame buffer for the new number of channels.
+    ///
+    /// # Arguments
+    ///
+    /// - `channels` - The color channel order and format of the LEDs
+    ///
+    /// # Example
+    ///
+    /// ```rust,ignore
+    /// use blinksy::{
+    ///     color::{LedChannels, RgbChannels},
+    ///     driver::ClocklessDriver,
+    ///     leds::Ws2812,
+    /// };
+    ///
+    /// let driver = ClocklessDriver::default()
+    ///     .with_led::<Ws2812>()
+    ///     .with_channels(LedChannels::Rgb(RgbChannels::RGB))
+    ///     .with_writer(/* Your writer */);
+    /// ```
+    pub fn with_channels(self, channels: LedChannels) -> Self {
+        Self {
+            channels: Some(channels),
+            ..self
+        }
+    }
+
+    /// Adds a [`GammaLut256`] to the encode step of the driver.
+    ///
+    /// Before each channel is quantized into a word (with any dithering), the channel is
+    /// mapped through the table, interpolating between entries. Use this instead of a gamma
+    /// in the [`ColorCorrection`], which costs a `powf` per channel.
+    ///
+    /// # Arguments
+    ///
+    /// - `lut` - The gamma lookup table to map each channel through
+    ///
+    /// # Example
+    ///
+    /// ```rust,ignore
+    /// use blinksy::{color::GammaLut256, driver::ClocklessDriver, leds::Ws2812};
+    ///
+    /// let driver = ClocklessDriver::default()
+    ///     .with_led::<Ws2812>()
+    ///     .with_writer(/* Your writer */)
+    ///     .with_temporal_dithering::</* Length of frame buffer */>()
+    ///     .with_gamma_lut(GammaLut256::new(2.2));
+    /// ```
+    pub fn with_gamma_lut(self, lut: GammaLut256) -> Self {
+        Self {
+            gamma_lut: Some(lut),
+            ..self
+        }
+    }
+
+    /// Sets how the driver quantizes each channel into a word, where it isn't otherwise
+    /// dithered.
+    ///
+    /// By default, the driver rounds each channel to the nearest word. This keeps any
+    /// dither: with [`BayerDither`], the mode applies beyond the grid, or with dithering
+    /// disabled. [`QuantizeMode::Dither`] needs the error buffer of a [`TemporalDither`],
+    /// so use [`ClocklessDriver::with_temporal_dithering`] for it.
+    ///
+    /// # Arguments
+    ///
+    /// - `mode` - How to quantize each channel
+    ///
+    /// # Example
+    ///
+    /// ```rust,ignore
+    /// use blinksy::{
+    ///     driver::{ClocklessDriver, QuantizeMode},
+    ///     leds::Ws2812,
+    /// };
+    ///
+    /// let driver = ClocklessDriver::default()
+    ///     .with_led::<Ws2812>()
+    ///     .with_writer(/* Your writer */)
+    ///     .with_quantize_mode(QuantizeMode::Floor);
+    /// ```
+    pub fn with_quantize_mode(self, mode: QuantizeMode) -> Self {
+        Self {
+            quantize_mode: mode,
+            ..self
+        }
+    }
+
+    /// Sets how the driver quantizes each channel into a word, where it isn't otherwise
+    /// dithered.
+    pub fn set_quantize_mode(&mut self, mode: QuantizeMode) {
+        self.quantize_mode = mode;
+    }
+
+    /// Returns how the driver quantizes each channel into a word.
+    pub fn quantize_mode(&self) -> QuantizeMode {
+        self.quantize_mode
+    }
+
+    /// Returns the color channel order of the LEDs: the override, if any, or else the order
+    /// of the LED protocol.
+    pub fn led_channels(&self) -> LedChannels
+    where
+        Led: ClocklessLed,
+    {
+        self.channels.unwrap_or(Led::LED_CHANNELS)
+    }
+}
+
+#[cfg(feature = "fixed-point")]
+impl<Led, Writer, Dither> ClocklessDriver<Led, Writer, Dither>
+where
+    Led: ClocklessLed<Word = u8>,
+{
+    /// Encodes a frame of fixed-point colors in integer math, for LEDs with 8-bit words.
+    ///
+    /// Each color is scaled by the brightness and mapped through the gamma lookup table, if
+    /// any (see [`ClocklessDriver::with_gamma_lut`]), without `f32` math, such as for
+    /// microcontrollers without a floating point unit. Color correction and dithering are
+    /// skipped. Write the frame with [`Driver::write`].
+    ///
+    /// Requires the `fixed-point` feature.
+    ///
+    /// # Type Parameters
+    ///
+    /// - `FRAME_BUFFER_SIZE` - Length of the frame buffer, in words
+    ///
+    /// # Arguments
+    ///
+    /// - `pixels` - Iterator over colors
+    /// - `brightness` - Brightness (0 to `u16::MAX`)
+    ///
+    /// # Example
+    ///
+    /// ```rust,ignore
+    /// use blinksy::{color::fixed::LinearSrgbFixed, driver::Driver};
+    ///
+    /// let pixels = [LinearSrgbFixed::new(u16::MAX, 0, 0); 16];
+    /// let frame = driver.encode_fixed::<48>(pixels, u16::MAX / 2);
+    /// Driver::write(&mut driver, frame, 1., ColorCorrection::default())?;
+    /// ```
+    pub fn encode_fixed<const FRAME_BUFFER_SIZE: usize>(
+        &self,
+        pixels: impl IntoIterator<Item = LinearSrgbFixed>,
+        brightness: u16,
+    ) -> Vec<u8, FRAME_BUFFER_SIZE> {
+        let channels = self.led_channels();
+        let gamma_lut = self.gamma_lut.as_ref();
+        Vec::from_iter(
+            pixels
+                .into_iter()
+                .flat_map(|pixel| pixel.to_led(channels, brightness, gamma_lut)),
+        )
+    }
+}
+
+impl<Led, Writer, const FRAME_BUFFER_SIZE: usize>
+    ClocklessDriver<Led, Writer, TemporalDither<FRAME_BUFFER_SIZE>>
+{
+    /// Enables or disables temporal dithering: sets the mode to [`QuantizeMode::Dither`] or
+    /// [`QuantizeMode::Round`].
+    pub fn set_dithering(&mut self, is_enabled: bool) {
+        self.set_quantize_mode(if is_enabled {
+            QuantizeMode::Dither
+        } else {
+            QuantizeMode::Round
+        });
+    }
+}
+
+impl<Led, Writer> ClocklessDriver<Led, Writer, BayerDither> {
+    /// Enables or disables ordered dithering.
+    pub fn set_dithering(&mut self, is_enabled: bool) {
+        self.dither.set_enabled(is_enabled);
+    }
+}
+
+impl<Led, Writer, D> Driver for ClocklessDriver<Led, Writer, D>
 where
     Led: ClocklessLed,
     Writer: ClocklessWriter<Led>,
+    D: Dither,
 {
     type Error = Writer::Error;
     type Color = LinearSrgb;
@@ -261,7 +635,16 @@ where
         I: IntoIterator<Item = C>,
         Self::Color: FromColor<C>,
     {
-        Led::encode::<PIXEL_COUNT, FRAME_BUFFER_SIZE, _, _>(pixels, brightness, correction)
+        let channels = self.led_channels();
+        Led::encode_with_channels::<PIXEL_COUNT, FRAME_BUFFER_SIZE, _, _, _>(
+            pixels,
+            channels,
+            brightness,
+            correction,
+            &mut self.dither,
+            self.quantize_mode,
+            self.gamma_lut.as_ref(),
+        )
     }
 
     fn write<const FRAME_BUFFER_SIZE: usize>(
@@ -275,10 +658,11 @@ where
 }
 
 #[cfg(feature = "async")]
-impl<Led, Writer> DriverAsync for ClocklessDriver<Led, Writer>
+impl<Led, Writer, D> DriverAsync for ClocklessDriver<Led, Writer, D>
 where
     Led: ClocklessLed,
     Writer: ClocklessWriterAsync<Led>,
+    D: Dither,
 {
     type Error = Writer::Error;
     type Color = LinearSrgb;
@@ -294,7 +678,16 @@ where
         I: IntoIterator<Item = C>,
         Self::Color: FromColor<C>,
     {
-        Led::encode::<PIXEL_COUNT, FRAME_BUFFER_SIZE, _, _>(pixels, brightness, correction)
+        let channels = self.led_channels();
+        Led::encode_with_channels::<PIXEL_COUNT, FRAME_BUFFER_SIZE, _, _, _>(
+            pixels,
+            channels,
+            brightness,
+            correction,
+            &mut self.dither,
+            self.quantize_mode,
+            self.gamma_lut.as_ref(),
+        )
     }
 
     async fn write<const FRAME_BUFFER_SIZE: usize>(
@@ -304,3 +697,170 @@ where
         self.writer.write(frame).await
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use core::convert::Infallible;
+
+    use super::*;
+    use crate::{
+        color::{RgbChannels, Srgb},
+        leds::Ws2812,
+    };
+
+    /// Writer which discards each frame.
+    struct NullWriter;
+
+    impl ClocklessWriter<Ws2812> for NullWriter {
+        type Error = Infallible;
+
+        fn write<const FRAME_BUFFER_SIZE: usize>(
+            &mut self,
+            _frame: Vec<u8, FRAME_BUFFER_SIZE>,
+        ) -> Result<(), Self::Error> {
+            Ok(())
+        }
+    }
+
+    fn encode_red<D: Dither>(driver: &mut ClocklessDriver<Ws2812, NullWriter, D>) -> Vec<u8, 3> {
+        Driver::encode::<1, 3, _, _>(
+            driver,
+            [Srgb::new(1., 0., 0.)],
+            1.,
+            ColorCorrection::default(),
+        )
+    }
+
+    #[test]
+    fn test_red_pixel_in_datasheet_grb_order() {
+        let mut driver = ClocklessDriver::default()
+            .with_led::<Ws2812>()
+            .with_writer(NullWriter);
+        assert_eq!(encode_red(&mut driver).as_slice(), [0, 255, 0]);
+    }
+
+    #[test]
+    fn test_gamma_lut_before_dither() {
+        let mut driver = ClocklessDriver::default()
+            .with_led::<Ws2812>()
+            .with_writer(NullWriter)
+            .with_temporal_dithering::<3>()
+            .with_gamma_lut(GammaLut256::new(2.));
+        let mut encode = |value: f32| -> u8 {
+            Driver::encode::<1, 3, _, _>(
+                &mut driver,
+                [LinearSrgb::new(0., value, 0.)],
+                1.,
+                ColorCorrection::default(),
+            )[0]
+        };
+        assert_eq!(encode(1.), 255);
+        // Through the table to about 65.0, then dithered.
+        let levels = [encode(128.75 / 255.), encode(128.75 / 255.)];
+        assert_eq!(levels, [65, 65]);
+    }
+
+    #[cfg(feature = "fixed-point")]
+    #[test]
+    fn test_encode_fixed_matches_float() {
+        use crate::color::fixed::{HsvFixed, LinearSrgbFixed};
+
+        let mut driver = ClocklessDriver::default()
+            .with_led::<Ws2812>()
+            .with_writer(NullWriter)
+            .with_gamma_lut(GammaLut256::new(2.2));
+        let pixels = [
+            HsvFixed::new(0, u16::MAX, u16::MAX),
+            HsvFixed::new(0x5555, 0x8000, 0xC000),
+            HsvFixed::new(0xAAAA, u16::MAX, 0x4000),
+        ]
+        .map(LinearSrgbFixed::from_color);
+
+        let fixed = driver.encode_fixed::<9>(pixels, 0xC000);
+        let float = Driver::encode::<3, 9, _, _>(
+            &mut driver,
+            pixels,
+            0xC000 as f32 / u16::MAX as f32,
+            ColorCorrection::default(),
+        );
+        // Within a few 8-bit steps, as the fixed-point words are rounded down before the
+        // gamma lookup, rather than interpolated.
+        for (fixed, float) in fixed.iter().zip(&float) {
+            assert!(fixed.abs_diff(*float) <= 3, "{fixed} != {float}");
+        }
+    }
+
+    #[test]
+    fn test_gamma_lut_keeps_dither_in_darks() {
+        let mut driver = ClocklessDriver::default()
+            .with_led::<Ws2812>()
+            .with_writer(NullWriter)
+            .with_temporal_dithering::<3>()
+            .with_gamma_lut(GammaLut256::new(2.2));
+        // Through the gamma, 0.1 is about 1.6 of a word, between words which the table
+        // alone would both map to 2.
+        let frames = 100;
+        let sum: u32 = (0..frames)
+            .map(|_| {
+                Driver::encode::<1, 3, _, _>(
+                    &mut driver,
+                    [LinearSrgb::new(0., 0.1, 0.)],
+                    1.,
+                    ColorCorrection::default(),
+                )[0] as u32
+            })
+            .sum();
+        let average = sum as f32 / frames as f32;
+        let expected = 0.1f32.powf(2.2) * 255.;
+        assert!((average - expected).abs() < 0.05, "{average} != {expected}");
+    }
+
+    #[test]
+    fn test_quantize_mode_keeps_bayer_dither() {
+        use crate::{
+            layout::{Shape2d, Vec2},
+            layout2d,
+        };
+
+        layout2d!(
+            Layout,
+            [
+                Shape2d::Grid {
+                    start: Vec2::new(-1., -1.),
+                    horizontal_end: Vec2::new(1., -1.),
+                    vertical_end: Vec2::new(-1., 1.),
+                    horizontal_pixel_count: 2,
+                    vertical_pixel_count: 1,
+                    serpentine: false,
+                },
+                Shape2d::Point(Vec2::new(0., 0.))
+            ]
+        );
+
+        let mut driver = ClocklessDriver::default()
+            .with_led::<Ws2812>()
+            .with_writer(NullWriter)
+            .with_bayer_dithering::<Layout>()
+            .with_quantize_mode(QuantizeMode::Floor);
+        assert_eq!(driver.quantize_mode(), QuantizeMode::Floor);
+
+        let value = 10.9 / 255.;
+        let frame = Driver::encode::<3, 9, _, _>(
+            &mut driver,
+            [LinearSrgb::new(value, value, value); 3],
+            1.,
+            ColorCorrection::default(),
+        );
+        // The grid pixels are dithered, and the pixel beyond the grid is rounded down.
+        assert_eq!(frame.as_slice(), [10, 10, 10, 11, 11, 11, 10, 10, 10]);
+    }
+
+    #[test]
+    fn test_red_pixel_in_overridden_rgb_order() {
+        let mut driver = ClocklessDriver::default()
+            .with_led::<Ws2812>()
+            .with_channels(LedChannels::Rgb(RgbChannels::RGB))
+            .with_writer(NullWriter);
+        assert_eq!(encode_red(&mut driver).as_slice(), [255, 0, 0]);
+    }
+}
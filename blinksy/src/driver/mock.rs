@@ -0,0 +1,220 @@
+//! # Mock Driver
+//!
+//! This module provides a driver which records each frame, rather than writing to any LED
+//! hardware, so patterns can be tested end-to-end with [`Control`](crate::control::Control).
+//!
+//! Enabled in the crate's own tests, or with the `mock` feature.
+//!
+//! ## Example
+//!
+//! ```rust,ignore
+//! use blinksy::{
+//!     driver::MockDriver,
+//!     layout::Layout1d,
+//!     layout1d,
+//!     patterns::rainbow::{Rainbow, RainbowParams},
+//!     ControlBuilder,
+//! };
+//!
+//! layout1d!(Layout, 4);
+//!
+//! let mut control = ControlBuilder::new_1d()
+//!     .with_layout::<Layout, { Layout::PIXEL_COUNT }>()
+//!     .with_pattern::<Rainbow>(RainbowParams::default())
+//!     .with_driver(MockDriver::<{ Layout::PIXEL_COUNT }>::new())
+//!     .with_frame_buffer_size::<{ Layout::PIXEL_COUNT }>()
+//!     .build();
+//!
+//! control.tick(0).unwrap();
+//! ```
+
+use core::convert::Infallible;
+use heapless::Vec;
+
+#[cfg(feature = "async")]
+use crate::driver::DriverAsync;
+use crate::{
+    color::{ColorCorrection, FromColor, LinearSrgb},
+    driver::Driver,
+};
+
+/// A driver which records the last frame written, with its brightness and color correction.
+///
+/// The frame is the colors given to the driver, before brightness and color correction.
+///
+/// For details, see [mock module](crate::driver::mock).
+///
+/// # Type Parameters
+///
+/// - `PIXEL_COUNT` - The number of pixels in each frame
+#[derive(Debug, Clone)]
+pub struct MockDriver<const PIXEL_COUNT: usize> {
+    /// The last frame written
+    frame: Vec<LinearSrgb, PIXEL_COUNT>,
+    /// The brightness of the last frame written
+    brightness: f32,
+    /// The color correction of the last frame written
+    correction: ColorCorrection,
+    /// The number of frames written
+    writes: usize,
+}
+
+impl<const PIXEL_COUNT: usize> MockDriver<PIXEL_COUNT> {
+    /// Creates a new mock driver, with no frame written.
+    pub fn new() -> Self {
+        Self {
+            frame: Vec::new(),
+            brightness: 0.,
+            correction: ColorCorrection::default(),
+            writes: 0,
+        }
+    }
+
+    /// Returns the last frame written, or an empty frame if none.
+    pub fn frame(&self) -> &[LinearSrgb] {
+        &self.frame
+    }
+
+    /// Returns the brightness of the last frame written.
+    pub fn brightness(&self) -> f32 {
+        self.brightness
+    }
+
+    /// Returns the color correction of the last frame written.
+    pub fn correction(&self) -> ColorCorrection {
+        self.correction
+    }
+
+    /// Returns the number of frames written.
+    pub fn writes(&self) -> usize {
+        self.writes
+    }
+
+    /// Returns the last frame written, with brightness and color correction applied.
+    pub fn output(&self) -> impl Iterator<Item = LinearSrgb> + '_ {
+        self.frame.iter().map(|color| {
+            let (red, green, blue) = self.correction.apply(color.red, color.green, color.blue);
+            LinearSrgb::new(
+                red * self.brightness,
+                green * self.brightness,
+                blue * self.brightness,
+            )
+        })
+    }
+
+    fn record<const FRAME_BUFFER_SIZE: usize>(
+        &mut self,
+        frame: Vec<LinearSrgb, FRAME_BUFFER_SIZE>,
+        brightness: f32,
+        correction: ColorCorrection,
+    ) {
+        self.frame = frame.into_iter().collect();
+        self.brightness = brightness;
+        self.correction = correction;
+        self.writes += 1;
+    }
+}
+
+impl<const PIXEL_COUNT: usize> Default for MockDriver<PIXEL_COUNT> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<const PIXEL_COUNT: usize> Driver for MockDriver<PIXEL_COUNT> {
+    type Error = Infallible;
+    type Color = LinearSrgb;
+    type Word = LinearSrgb;
+
+    fn encode<const PIXELS: usize, const FRAME_BUFFER_SIZE: usize, Pixels, C>(
+        &mut self,
+        pixels: Pixels,
+        _brightness: f32,
+        _correction: ColorCorrection,
+    ) -> Vec<Self::Word, FRAME_BUFFER_SIZE>
+    where
+        Pixels: IntoIterator<Item = C>,
+        Self::Color: FromColor<C>,
+    {
+        pixels.into_iter().map(LinearSrgb::from_color).collect()
+    }
+
+    fn write<const FRAME_BUFFER_SIZE: usize>(
+        &mut self,
+        frame: Vec<Self::Word, FRAME_BUFFER_SIZE>,
+        brightness: f32,
+        correction: ColorCorrection,
+    ) -> Result<(), Self::Error> {
+        self.record(frame, brightness, correction);
+        Ok(())
+    }
+}
+
+#[cfg(feature = "async")]
+impl<const PIXEL_COUNT: usize> DriverAsync for MockDriver<PIXEL_COUNT> {
+    type Error = Infallible;
+    type Color = LinearSrgb;
+    type Word = LinearSrgb;
+
+    fn encode<const PIXELS: usize, const FRAME_BUFFER_SIZE: usize, Pixels, C>(
+        &mut self,
+        pixels: Pixels,
+        _brightness: f32,
+        _correction: ColorCorrection,
+    ) -> Vec<Self::Word, FRAME_BUFFER_SIZE>
+    where
+        Pixels: IntoIterator<Item = C>,
+        Self::Color: FromColor<C>,
+    {
+        pixels.into_iter().map(LinearSrgb::from_color).collect()
+    }
+
+    async fn write<const FRAME_BUFFER_SIZE: usize>(
+        &mut self,
+        frame: Vec<Self::Word, FRAME_BUFFER_SIZE>,
+    ) -> Result<(), Self::Error> {
+        // The async driver interface doesn't give the brightness or color correction.
+        self.record(frame, 1., ColorCorrection::default());
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        control::ControlBuilder,
+        layout::Layout1d,
+        layout1d,
+        markers::Dim1d,
+        pattern::Pattern,
+        patterns::rainbow::{Rainbow, RainbowParams},
+    };
+
+    layout1d!(Layout, 4);
+
+    #[test]
+    fn test_rainbow_through_control() {
+        let mut control = ControlBuilder::new_1d()
+            .with_layout::<Layout, { Layout::PIXEL_COUNT }>()
+            .with_pattern::<Rainbow>(RainbowParams::default())
+            .with_driver(MockDriver::<{ Layout::PIXEL_COUNT }>::new())
+            .with_frame_buffer_size::<{ Layout::PIXEL_COUNT }>()
+            .build();
+        control.set_brightness(0.5);
+        control.set_color_correction(ColorCorrection::new(1.0, 0.8, 0.6));
+        control.tick(1000).unwrap();
+
+        let rainbow: Rainbow = Pattern::<Dim1d, Layout>::new(RainbowParams::default());
+        let expected: Vec<LinearSrgb, 4> = Pattern::<Dim1d, Layout>::tick(&rainbow, 1000)
+            .map(LinearSrgb::from_color)
+            .collect();
+
+        let driver = control.driver();
+        assert_eq!(driver.writes(), 1);
+        assert_eq!(driver.frame(), expected.as_slice());
+        assert_eq!(driver.brightness(), 0.5);
+        assert_eq!(driver.correction(), ColorCorrection::new(1.0, 0.8, 0.6));
+        assert_eq!(driver.output().next().unwrap().red, expected[0].red * 0.5);
+    }
+}
@@ -11,16 +11,34 @@
 //!
 //! - [`clocked`]: For clocked (two-wire) protocols (like [`APA102`](crate::leds::Apa102))
 //! - [`clockless`]: For clockless (one-wire) protocols (like [`WS2812`](crate::leds::Ws2812))
+//!
+//! ## Drivers
+//!
+//! - [`seesaw`]: For NeoPixels through an Adafruit SeeSaw co-processor, over I2C
+//! - [`smart_leds`]: For any [smart-leds](https://github.com/smart-leds-rs/smart-leds) driver
+//! - `mock`: For recording frames in tests (with the `mock` feature)
+
+use core::iter::repeat_n;
 
 use heapless::Vec;
 
-use crate::color::{ColorCorrection, FromColor};
+use crate::color::{ColorCorrection, FromColor, LinearSrgb};
 
 pub mod clocked;
 pub mod clockless;
+#[cfg(any(test, feature = "mock"))]
+pub mod mock;
+pub mod seesaw;
+pub mod smart_leds;
 
 pub use clocked::*;
 pub use clockless::*;
+#[cfg(any(test, feature = "mock"))]
+pub use mock::*;
+pub use seesaw::*;
+pub use smart_leds::*;
+
+pub use crate::util::component::QuantizeMode;
 
 /// Core trait for all blocking LED drivers.
 ///
@@ -170,6 +188,29 @@ pub trait Driver {
             self.encode::<PIXEL_COUNT, FRAME_BUFFER_SIZE, _, _>(pixels, brightness, correction);
         self.write(frame_buffer, brightness, correction)
     }
+
+    /// Turns off the LEDs: shows a frame of black.
+    ///
+    /// # Type Parameters
+    ///
+    /// - `PIXEL_COUNT` - Number of pixels to turn off
+    /// - `FRAME_BUFFER_SIZE` - Length of encoded frame buffer, in words.
+    ///
+    /// # Returns
+    ///
+    /// Result indicating success or an error
+    fn clear<const PIXEL_COUNT: usize, const FRAME_BUFFER_SIZE: usize>(
+        &mut self,
+    ) -> Result<(), Self::Error>
+    where
+        Self::Color: FromColor<LinearSrgb>,
+    {
+        self.show::<PIXEL_COUNT, FRAME_BUFFER_SIZE, _, _>(
+            repeat_n(LinearSrgb::new(0., 0., 0.), PIXEL_COUNT),
+            0.,
+            ColorCorrection::default(),
+        )
+    }
 }
 
 /// Core trait for all async LED drivers.
@@ -317,4 +358,29 @@ pub trait DriverAsync {
             self.encode::<PIXEL_COUNT, FRAME_BUFFER_SIZE, _, _>(pixels, brightness, correction);
         self.write(frame_buffer).await
     }
+
+    #[allow(async_fn_in_trait)]
+    /// Turns off the LEDs: shows a frame of black, asynchronously.
+    ///
+    /// # Type Parameters
+    ///
+    /// - `PIXEL_COUNT` - Number of pixels to turn off
+    /// - `FRAME_BUFFER_SIZE` - Length of encoded frame buffer, in words.
+    ///
+    /// # Returns
+    ///
+    /// Future that resolves to a Result indicating success or an error
+    async fn clear<const PIXEL_COUNT: usize, const FRAME_BUFFER_SIZE: usize>(
+        &mut self,
+    ) -> Result<(), Self::Error>
+    where
+        Self::Color: FromColor<LinearSrgb>,
+    {
+        self.show::<PIXEL_COUNT, FRAME_BUFFER_SIZE, _, _>(
+            repeat_n(LinearSrgb::new(0., 0., 0.), PIXEL_COUNT),
+            0.,
+            ColorCorrection::default(),
+        )
+        .await
+    }
 }
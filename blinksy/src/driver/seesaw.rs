@@ -0,0 +1,390 @@
+//! # SeeSaw NeoPixel Driver
+//!
+//! This module provides a driver for NeoPixel (WS2812) LEDs connected to an
+//! [Adafruit SeeSaw](https://learn.adafruit.com/adafruit-seesaw-atsamd09-breakout) co-processor,
+//! such as the [Adafruit NeoDriver](https://www.adafruit.com/product/5766).
+//!
+//! The main microcontroller sends colors over I2C, and the SeeSaw generates the precise
+//! clockless timing. This is useful when the main microcontroller has no free peripheral
+//! (RMT, PIO, SPI) to drive clockless LEDs.
+//!
+//! ## Protocol
+//!
+//! Every command is an I2C write to the NeoPixel module (base `0x0E`), followed by a
+//! function register and its data:
+//!
+//! 1. `PIN` (`0x01`): The SeeSaw pin the LEDs are connected to
+//! 2. `SPEED` (`0x02`): `0x01` for 800 KHz
+//! 3. `BUF_LENGTH` (`0x03`): The length of the pixel buffer in bytes (big-endian u16)
+//! 4. `BUF` (`0x04`): A big-endian u16 byte offset, then pixel data. The SeeSaw can only
+//!    receive a small I2C transfer, so the pixel buffer is written in chunks of at most
+//!    [`SEESAW_NEOPIXEL_CHUNK_SIZE`] bytes.
+//! 5. `SHOW` (`0x05`): Latch the pixel buffer to the LEDs
+//!
+//! The setup commands (1 to 3) are sent before the first frame, and again if the length of
+//! the frame changes. Since the length is a u16, a frame of more than 65535 bytes is an
+//! error, [`SeeSawError::FrameTooLong`].
+//!
+//! (References: [Adafruit_seesaw](https://github.com/adafruit/Adafruit_Seesaw),
+//! [Adafruit_CircuitPython_seesaw](https://github.com/adafruit/Adafruit_CircuitPython_seesaw))
+//!
+//! ## Example
+//!
+//! ```rust
+//! use embedded_hal::i2c::I2c;
+//! use blinksy::{
+//!     color::{LedChannels, RgbChannels},
+//!     driver::SeeSawNeoPixelDriver,
+//! };
+//!
+//! fn setup_leds<I>(i2c: I) -> SeeSawNeoPixelDriver<I>
+//! where
+//!     I: I2c,
+//! {
+//!     // NeoDriver default address, with NeoPixel output on SeeSaw pin 15
+//!     SeeSawNeoPixelDriver::new(i2c, 0x60, 15)
+//!         .with_channels(LedChannels::Rgb(RgbChannels::GRB))
+//! }
+//! ```
+
+use embedded_hal::i2c::I2c;
+#[cfg(feature = "async")]
+use embedded_hal_async::i2c::I2c as I2cAsync;
+use heapless::Vec;
+
+#[cfg(feature = "async")]
+use crate::driver::DriverAsync;
+use crate::{
+    color::{ColorCorrection, FromColor, LedChannels, LedColor, LinearSrgb, RgbChannels},
+    driver::Driver,
+};
+
+/// SeeSaw module base register for NeoPixels.
+const NEOPIXEL_BASE: u8 = 0x0E;
+
+/// NeoPixel function register: output pin.
+const NEOPIXEL_PIN: u8 = 0x01;
+
+/// NeoPixel function register: output speed.
+const NEOPIXEL_SPEED: u8 = 0x02;
+
+/// NeoPixel function register: pixel buffer length, in bytes.
+const NEOPIXEL_BUF_LENGTH: u8 = 0x03;
+
+/// NeoPixel function register: pixel buffer data.
+const NEOPIXEL_BUF: u8 = 0x04;
+
+/// NeoPixel function register: show the pixel buffer.
+const NEOPIXEL_SHOW: u8 = 0x05;
+
+/// NeoPixel speed value for 800 KHz (WS2812).
+const NEOPIXEL_SPEED_800KHZ: u8 = 0x01;
+
+/// Maximum number of pixel data bytes written in a single `BUF` command.
+pub const SEESAW_NEOPIXEL_CHUNK_SIZE: usize = 24;
+
+/// Length of a `BUF` command: base, function, offset, and pixel data.
+const BUF_COMMAND_SIZE: usize = 4 + SEESAW_NEOPIXEL_CHUNK_SIZE;
+
+/// A driver for NeoPixel LEDs through an Adafruit SeeSaw co-processor over I2C.
+///
+/// For details, see [seesaw module](crate::driver::seesaw).
+///
+/// # Type Parameters
+///
+/// - `I2c` - The I2C bus
+#[derive(Debug)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct SeeSawNeoPixelDriver<I2c> {
+    /// I2C bus
+    i2c: I2c,
+    /// I2C address of the SeeSaw
+    address: u8,
+    /// SeeSaw pin the LEDs are connected to
+    pin: u8,
+    /// Color channel order of the LEDs
+    channels: LedChannels,
+    /// Pixel buffer length the SeeSaw was set up with, if any
+    buffer_length: Option<u16>,
+}
+
+impl<I2c> SeeSawNeoPixelDriver<I2c> {
+    /// Creates a new SeeSaw NeoPixel driver, for LEDs with GRB channel order.
+    ///
+    /// # Arguments
+    ///
+    /// - `i2c` - The I2C bus
+    /// - `address` - The I2C address of the SeeSaw
+    /// - `pin` - The SeeSaw pin the LEDs are connected to
+    pub fn new(i2c: I2c, address: u8, pin: u8) -> Self {
+        Self {
+            i2c,
+            address,
+            pin,
+            channels: LedChannels::Rgb(RgbChannels::GRB),
+            buffer_length: None,
+        }
+    }
+
+    /// Sets the color channel order of the LEDs.
+    pub fn with_channels(self, channels: LedChannels) -> Self {
+        Self { channels, ..self }
+    }
+
+    /// Releases the I2C bus.
+    pub fn release(self) -> I2c {
+        self.i2c
+    }
+
+    /// A compile-time function to get a `FRAME_BUFFER_SIZE`, given a `PIXEL_COUNT`.
+    pub const fn frame_buffer_size(pixel_count: usize, channels: LedChannels) -> usize {
+        pixel_count * channels.channel_count()
+    }
+
+    /// Encodes pixels into bytes, in the channel order of the LEDs.
+    fn encode_pixels<const FRAME_BUFFER_SIZE: usize, I, C>(
+        &self,
+        pixels: I,
+        brightness: f32,
+        correction: ColorCorrection,
+    ) -> Vec<u8, FRAME_BUFFER_SIZE>
+    where
+        I: IntoIterator<Item = C>,
+        LinearSrgb: FromColor<C>,
+    {
+        let channels = self.channels;
+        Vec::from_iter(pixels.into_iter().flat_map(move |pixel| {
+            let linear_srgb = LinearSrgb::from_color(pixel);
+            let data: LedColor<u8> = linear_srgb.to_led(channels, brightness, correction);
+            data.into_iter()
+        }))
+    }
+
+    /// Returns the `PIN`, `SPEED`, and `BUF_LENGTH` commands to set up a frame length.
+    fn setup_commands(&self, frame_length: u16) -> ([u8; 3], [u8; 3], [u8; 4]) {
+        let [length_high, length_low] = frame_length.to_be_bytes();
+        (
+            [NEOPIXEL_BASE, NEOPIXEL_PIN, self.pin],
+            [NEOPIXEL_BASE, NEOPIXEL_SPEED, NEOPIXEL_SPEED_800KHZ],
+            [NEOPIXEL_BASE, NEOPIXEL_BUF_LENGTH, length_high, length_low],
+        )
+    }
+}
+
+/// Errors from the [`SeeSawNeoPixelDriver`].
+///
+/// # Type Parameters
+///
+/// - `E` - The error of the I2C bus
+#[derive(Debug)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub enum SeeSawError<E> {
+    /// Error from the I2C bus
+    I2c(E),
+    /// The frame is longer than the 65535 bytes of a `BUF_LENGTH` command
+    FrameTooLong,
+}
+
+/// Returns the length of a frame, for the `BUF_LENGTH` command.
+fn frame_length<E>(frame: &[u8]) -> Result<u16, SeeSawError<E>> {
+    u16::try_from(frame.len()).map_err(|_| SeeSawError::FrameTooLong)
+}
+
+/// Returns the `BUF` commands to write a frame, in chunks.
+///
+/// The frame is at most 65535 bytes (see [`frame_length`]), so each offset fits a u16.
+fn buf_commands(frame: &[u8]) -> impl Iterator<Item = Vec<u8, BUF_COMMAND_SIZE>> + '_ {
+    frame
+        .chunks(SEESAW_NEOPIXEL_CHUNK_SIZE)
+        .enumerate()
+        .map(|(index, chunk)| {
+            let [offset_high, offset_low] =
+                ((index * SEESAW_NEOPIXEL_CHUNK_SIZE) as u16).to_be_bytes();
+            let mut command = Vec::new();
+            // The capacity fits a full chunk, so these can't fail.
+            let _ =
+                command.extend_from_slice(&[NEOPIXEL_BASE, NEOPIXEL_BUF, offset_high, offset_low]);
+            let _ = command.extend_from_slice(chunk);
+            command
+        })
+}
+
+impl<I> Driver for SeeSawNeoPixelDriver<I>
+where
+    I: I2c,
+{
+    type Error = SeeSawError<I::Error>;
+    type Color = LinearSrgb;
+    type Word = u8;
+
+    fn encode<const PIXEL_COUNT: usize, const FRAME_BUFFER_SIZE: usize, Pixels, C>(
+        &mut self,
+        pixels: Pixels,
+        brightness: f32,
+        correction: ColorCorrection,
+    ) -> Vec<Self::Word, FRAME_BUFFER_SIZE>
+    where
+        Pixels: IntoIterator<Item = C>,
+        Self::Color: FromColor<C>,
+    {
+        self.encode_pixels(pixels, brightness, correction)
+    }
+
+    fn write<const FRAME_BUFFER_SIZE: usize>(
+        &mut self,
+        frame: Vec<Self::Word, FRAME_BUFFER_SIZE>,
+        _brightness: f32,
+        _correction: ColorCorrection,
+    ) -> Result<(), Self::Error> {
+        let frame_length = frame_length(&frame)?;
+        if self.buffer_length != Some(frame_length) {
+            let (pin, speed, buffer_length) = self.setup_commands(frame_length);
+            for command in [&pin[..], &speed, &buffer_length] {
+                self.i2c
+                    .write(self.address, command)
+                    .map_err(SeeSawError::I2c)?;
+            }
+            self.buffer_length = Some(frame_length);
+        }
+        for command in buf_commands(&frame) {
+            self.i2c
+                .write(self.address, &command)
+                .map_err(SeeSawError::I2c)?;
+        }
+        self.i2c
+            .write(self.address, &[NEOPIXEL_BASE, NEOPIXEL_SHOW])
+            .map_err(SeeSawError::I2c)
+    }
+}
+
+#[cfg(feature = "async")]
+impl<I> DriverAsync for SeeSawNeoPixelDriver<I>
+where
+    I: I2cAsync,
+{
+    type Error = SeeSawError<I::Error>;
+    type Color = LinearSrgb;
+    type Word = u8;
+
+    fn encode<const PIXEL_COUNT: usize, const FRAME_BUFFER_SIZE: usize, Pixels, C>(
+        &mut self,
+        pixels: Pixels,
+        brightness: f32,
+        correction: ColorCorrection,
+    ) -> Vec<Self::Word, FRAME_BUFFER_SIZE>
+    where
+        Pixels: IntoIterator<Item = C>,
+        Self::Color: FromColor<C>,
+    {
+        self.encode_pixels(pixels, brightness, correction)
+    }
+
+    async fn write<const FRAME_BUFFER_SIZE: usize>(
+        &mut self,
+        frame: Vec<Self::Word, FRAME_BUFFER_SIZE>,
+    ) -> Result<(), Self::Error> {
+        let frame_length = frame_length(&frame)?;
+        if self.buffer_length != Some(frame_length) {
+            let (pin, speed, buffer_length) = self.setup_commands(frame_length);
+            for command in [&pin[..], &speed, &buffer_length] {
+                self.i2c
+                    .write(self.address, command)
+                    .await
+                    .map_err(SeeSawError::I2c)?;
+            }
+            self.buffer_length = Some(frame_length);
+        }
+        for command in buf_commands(&frame) {
+            self.i2c
+                .write(self.address, &command)
+                .await
+                .map_err(SeeSawError::I2c)?;
+        }
+        self.i2c
+            .write(self.address, &[NEOPIXEL_BASE, NEOPIXEL_SHOW])
+            .await
+            .map_err(SeeSawError::I2c)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use core::convert::Infallible;
+
+    use embedded_hal::i2c::{ErrorType, Operation};
+
+    use super::*;
+
+    /// I2C bus which records every write.
+    #[derive(Default)]
+    struct MockI2c {
+        writes: Vec<(u8, Vec<u8, 32>), 16>,
+    }
+
+    impl ErrorType for MockI2c {
+        type Error = Infallible;
+    }
+
+    impl I2c for MockI2c {
+        fn transaction(
+            &mut self,
+            address: u8,
+            operations: &mut [Operation<'_>],
+        ) -> Result<(), Self::Error> {
+            for operation in operations {
+                if let Operation::Write(bytes) = operation {
+                    let bytes = Vec::from_slice(bytes).unwrap();
+                    self.writes.push((address, bytes)).unwrap();
+                }
+            }
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn test_register_writes() {
+        let mut driver = SeeSawNeoPixelDriver::new(MockI2c::default(), 0x60, 15)
+            .with_channels(LedChannels::Rgb(RgbChannels::RGB));
+
+        // 10 pixels, so 30 bytes: one full chunk and one partial chunk.
+        let pixels = [LinearSrgb::new(1., 0., 0.); 10];
+        driver
+            .show::<10, 30, _, _>(pixels, 1., ColorCorrection::default())
+            .unwrap();
+
+        let mut chunk = [0; 28];
+        chunk[..4].copy_from_slice(&[0x0E, 0x04, 0x00, 0x00]);
+        for pixel in chunk[4..].chunks_mut(3) {
+            pixel[0] = 0xFF;
+        }
+
+        let writes = &driver.i2c.writes;
+        assert_eq!(writes.len(), 6);
+        assert!(writes.iter().all(|(address, _)| *address == 0x60));
+        assert_eq!(writes[0].1, [0x0E, 0x01, 15]);
+        assert_eq!(writes[1].1, [0x0E, 0x02, 0x01]);
+        assert_eq!(writes[2].1, [0x0E, 0x03, 0x00, 30]);
+        assert_eq!(writes[3].1, chunk);
+        assert_eq!(writes[4].1, [0x0E, 0x04, 0x00, 24, 0xFF, 0, 0, 0xFF, 0, 0]);
+        assert_eq!(writes[5].1, [0x0E, 0x05]);
+
+        // Setup is only sent once.
+        driver
+            .show::<10, 30, _, _>(pixels, 1., ColorCorrection::default())
+            .unwrap();
+        assert_eq!(driver.i2c.writes.len(), 9);
+    }
+
+    #[test]
+    fn test_frame_too_long() {
+        let mut driver = SeeSawNeoPixelDriver::new(MockI2c::default(), 0x60, 15);
+
+        // One byte more than a u16 length.
+        let mut frame: Vec<u8, 65536> = Vec::new();
+        frame.resize(65536, 0).unwrap();
+        let result = Driver::write(&mut driver, frame, 1., ColorCorrection::default());
+
+        assert!(matches!(result, Err(SeeSawError::FrameTooLong)));
+        assert!(driver.i2c.writes.is_empty());
+    }
+}
@@ -0,0 +1,198 @@
+//! # Smart Leds Bridge
+//!
+//! This module provides a bridge from any [smart-leds] driver (an implementation of
+//! [`SmartLedsWrite`]) to a Blinksy [`Driver`], so existing smart-leds drivers can be used
+//! with [`Control`](crate::control::Control).
+//!
+//! ## Brightness
+//!
+//! The brightness is applied to the linear color ([`LinearSrgb`]), as for every other
+//! Blinksy driver, before each channel is encoded as an 8-bit value for the smart-leds
+//! driver. Scaling in linear space halves the light output at 50% brightness.
+//!
+//! (Scaling a gamma-encoded color instead, such as a [`GammaSrgb`](crate::color::GammaSrgb),
+//! isn't physically correct: at 50% brightness, a full red would be about 21% of the
+//! light output, rather than 50%.)
+//!
+//! [smart-leds]: https://github.com/smart-leds-rs/smart-leds
+//!
+//! ## Example
+//!
+//! ```rust
+//! use blinksy::driver::SmartLedsDriver;
+//! use smart_leds_trait::{SmartLedsWrite, RGB8};
+//!
+//! fn setup_leds<W>(writer: W) -> SmartLedsDriver<W>
+//! where
+//!     W: SmartLedsWrite<Color = RGB8>,
+//! {
+//!     SmartLedsDriver::new(writer)
+//! }
+//! ```
+
+use heapless::Vec;
+#[cfg(feature = "async")]
+use smart_leds_trait::SmartLedsWriteAsync;
+use smart_leds_trait::{SmartLedsWrite, RGB8};
+
+#[cfg(feature = "async")]
+use crate::driver::DriverAsync;
+use crate::{
+    color::{ColorCorrection, FromColor, LedRgb, LinearSrgb},
+    driver::Driver,
+};
+
+/// A driver which writes through a [smart-leds](https://github.com/smart-leds-rs/smart-leds)
+/// driver.
+///
+/// For details, see [smart_leds module](crate::driver::smart_leds).
+///
+/// # Type Parameters
+///
+/// - `Writer` - The smart-leds driver
+#[derive(Debug)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct SmartLedsDriver<Writer> {
+    /// The smart-leds driver
+    writer: Writer,
+}
+
+impl<Writer> SmartLedsDriver<Writer> {
+    /// Creates a new bridge to a smart-leds driver.
+    ///
+    /// # Arguments
+    ///
+    /// - `writer` - The smart-leds driver
+    pub fn new(writer: Writer) -> Self {
+        Self { writer }
+    }
+
+    /// Releases the smart-leds driver.
+    pub fn release(self) -> Writer {
+        self.writer
+    }
+}
+
+/// Encodes pixels into 8-bit RGB colors, scaled by the brightness in linear space.
+fn encode_pixels<const FRAME_BUFFER_SIZE: usize, I, C>(
+    pixels: I,
+    brightness: f32,
+    correction: ColorCorrection,
+) -> Vec<RGB8, FRAME_BUFFER_SIZE>
+where
+    I: IntoIterator<Item = C>,
+    LinearSrgb: FromColor<C>,
+{
+    Vec::from_iter(pixels.into_iter().map(|pixel| {
+        let linear_srgb = LinearSrgb::from_color(pixel);
+        let rgb: LedRgb<u8> = LedRgb::from_linear_srgb(linear_srgb, brightness, correction);
+        RGB8::new(rgb[0], rgb[1], rgb[2])
+    }))
+}
+
+impl<Writer> Driver for SmartLedsDriver<Writer>
+where
+    Writer: SmartLedsWrite,
+    RGB8: Into<Writer::Color>,
+{
+    type Error = Writer::Error;
+    type Color = LinearSrgb;
+    type Word = RGB8;
+
+    fn encode<const PIXEL_COUNT: usize, const FRAME_BUFFER_SIZE: usize, Pixels, C>(
+        &mut self,
+        pixels: Pixels,
+        brightness: f32,
+        correction: ColorCorrection,
+    ) -> Vec<Self::Word, FRAME_BUFFER_SIZE>
+    where
+        Pixels: IntoIterator<Item = C>,
+        Self::Color: FromColor<C>,
+    {
+        encode_pixels(pixels, brightness, correction)
+    }
+
+    fn write<const FRAME_BUFFER_SIZE: usize>(
+        &mut self,
+        frame: Vec<Self::Word, FRAME_BUFFER_SIZE>,
+        _brightness: f32,
+        _correction: ColorCorrection,
+    ) -> Result<(), Self::Error> {
+        self.writer.write(frame)
+    }
+}
+
+#[cfg(feature = "async")]
+impl<Writer> DriverAsync for SmartLedsDriver<Writer>
+where
+    Writer: SmartLedsWriteAsync,
+    RGB8: Into<Writer::Color>,
+{
+    type Error = Writer::Error;
+    type Color = LinearSrgb;
+    type Word = RGB8;
+
+    fn encode<const PIXEL_COUNT: usize, const FRAME_BUFFER_SIZE: usize, Pixels, C>(
+        &mut self,
+        pixels: Pixels,
+        brightness: f32,
+        correction: ColorCorrection,
+    ) -> Vec<Self::Word, FRAME_BUFFER_SIZE>
+    where
+        Pixels: IntoIterator<Item = C>,
+        Self::Color: FromColor<C>,
+    {
+        encode_pixels(pixels, brightness, correction)
+    }
+
+    async fn write<const FRAME_BUFFER_SIZE: usize>(
+        &mut self,
+        frame: Vec<Self::Word, FRAME_BUFFER_SIZE>,
+    ) -> Result<(), Self::Error> {
+        self.writer.write(frame).await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use core::convert::Infallible;
+
+    use super::*;
+    use crate::color::Srgb;
+
+    /// Smart-leds driver which records the colors written.
+    struct Recorder(Vec<RGB8, 4>);
+
+    impl SmartLedsWrite for Recorder {
+        type Error = Infallible;
+        type Color = RGB8;
+
+        fn write<T, I>(&mut self, iterator: T) -> Result<(), Self::Error>
+        where
+            T: IntoIterator<Item = I>,
+            I: Into<Self::Color>,
+        {
+            self.0 = iterator.into_iter().map(Into::into).collect();
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn test_half_brightness_red_is_linear_scaled() {
+        let mut driver = SmartLedsDriver::new(Recorder(Vec::new()));
+        Driver::show::<1, 1, _, _>(
+            &mut driver,
+            [Srgb::new(1., 0., 0.)],
+            0.5,
+            ColorCorrection::default(),
+        )
+        .unwrap();
+
+        let expected: LedRgb<u8> =
+            LedRgb::from_linear_srgb(LinearSrgb::new(0.5, 0., 0.), 1., ColorCorrection::default());
+        let red = driver.release().0[0];
+        assert_eq!(red, RGB8::new(expected[0], 0, 0));
+        // Half the light output, not the gamma-encoded half (about 21%).
+        assert!((127..=128).contains(&red.r));
+    }
+}
@@ -0,0 +1,239 @@
+/// Creates an LED layout by chaining several layouts, in wiring order, onto one data line.
+///
+/// For physical setups of several sub-strips (or panels) of different lengths, joined into
+/// one data line. The chained layout has every LED of each layout, one after the other, and
+/// records the index range of each layout, as a segment.
+///
+/// - For 1D layouts, the chain is a [`Layout1d`](crate::layout::Layout1d) of the total length.
+///   The points of each layout are mapped from -1.0 to 1.0 onto the span of its segment, so a
+///   transformed layout, such as a [`Reversed`](crate::layout::Reversed) sub-strip, keeps its
+///   transform.
+/// - For 2D layouts, the chain is a [`Layout2d`](crate::layout::Layout2d) with the shapes and
+///   the points of each layout.
+///
+/// Besides the layout trait, the chained type has:
+///
+/// - `SEGMENT_COUNT`: The number of chained layouts
+/// - `segments()`: The index range of each chained layout, in order
+/// - `SEGMENT_NAMES`: The name of each chained layout (its type), in order
+///
+/// A segment's range can position a [`Segment`](crate::control::Segment) of a
+/// [`MultiSegment`](crate::control::MultiSegment), or select pixels for a
+/// [mask](crate::control::Control::set_mask).
+///
+/// # Arguments
+///
+/// - `#[$attr]` - Optional attributes to apply to the struct (e.g., `#[derive(Debug)]`)
+/// - `$vis` - Optional visibility modifier (e.g., `pub`)
+/// - `$name` - The name of the layout type to create
+/// - `Layout1d` or `Layout2d` - The dimension of the layouts
+/// - `[$($layout:ty),*]` - A list of layout types to chain, in wiring order
+///
+/// # Output
+///
+/// Macro output will be a type definition that implements [`Layout1d`](crate::layout::Layout1d)
+/// or [`Layout2d`](crate::layout::Layout2d).
+///
+/// # Example
+///
+/// ```rust
+/// use blinksy::{layout::Layout1d, layout1d, layout_chain};
+///
+/// layout1d!(Desk, 30);
+/// layout1d!(Shelf, 45);
+/// layout1d!(Window, 12);
+///
+/// // Three sub-strips, joined into one data line
+/// layout_chain!(Layout, Layout1d, [Desk, Shelf, Window]);
+///
+/// assert_eq!(Layout::PIXEL_COUNT, 87);
+/// assert_eq!(Layout::SEGMENT_COUNT, 3);
+/// assert_eq!(Layout::segments(), [0..30, 30..75, 75..87]);
+/// assert_eq!(Layout::SEGMENT_NAMES, ["Desk", "Shelf", "Window"]);
+///
+/// // Light only the shelf
+/// let shelf = &Layout::segments()[1];
+/// let mask: [f32; Layout::PIXEL_COUNT] =
+///     core::array::from_fn(|index| if shelf.contains(&index) { 1. } else { 0. });
+/// ```
+#[macro_export]
+macro_rules! layout_chain {
+    ($(#[$attr:meta])* $vis:vis $name:ident, Layout1d, [$($layout:ty),* $(,)?]) => {
+        $(#[$attr])*
+        $vis struct $name;
+
+        impl $crate::layout::Layout1d for $name {
+            const PIXEL_COUNT: usize =
+                0 $(+ <$layout as $crate::layout::Layout1d>::PIXEL_COUNT)*;
+
+            fn points() -> impl Iterator<Item = f32> {
+                #[allow(unused_variables)]
+                let spacing = <Self as $crate::layout::Layout1d>::spacing();
+                #[allow(unused_mut, unused_variables)]
+                let mut segments = Self::segments().into_iter();
+                ::core::iter::empty()
+                    $(.chain({
+                        // Map the points of the layout from -1.0 to 1.0 onto its segment.
+                        let segment = segments.next().unwrap_or(0..0);
+                        let first = -1. + segment.start as f32 * spacing;
+                        let last = -1. + segment.end.saturating_sub(1) as f32 * spacing;
+                        <$layout as $crate::layout::Layout1d>::points()
+                            .map(move |x| first + (x + 1.) / 2. * (last - first))
+                    }))*
+            }
+        }
+
+        $crate::layout_chain!(@segments $name, Layout1d, [$($layout),*]);
+    };
+    ($(#[$attr:meta])* $vis:vis $name:ident, Layout2d, [$($layout:ty),* $(,)?]) => {
+        $(#[$attr])*
+        $vis struct $name;
+
+        impl $crate::layout::Layout2d for $name {
+            const PIXEL_COUNT: usize =
+                0 $(+ <$layout as $crate::layout::Layout2d>::PIXEL_COUNT)*;
+
+            fn shapes() -> impl Iterator<Item = $crate::layout::Shape2d> {
+                ::core::iter::empty()
+                    $(.chain(<$layout as $crate::layout::Layout2d>::shapes()))*
+            }
+
+            fn points() -> impl Iterator<Item = $crate::layout::Vec2> {
+                ::core::iter::empty()
+                    $(.chain(<$layout as $crate::layout::Layout2d>::points()))*
+            }
+        }
+
+        $crate::layout_chain!(@segments $name, Layout2d, [$($layout),*]);
+    };
+    (@segments $name:ident, $trait:ident, [$($layout:ty),*]) => {
+        impl $name {
+            /// The number of chained layouts.
+            pub const SEGMENT_COUNT: usize = [$(::core::stringify!($layout)),*].len();
+
+            /// The name of each chained layout, its type, in wiring order.
+            pub const SEGMENT_NAMES: [&'static str; Self::SEGMENT_COUNT] =
+                [$(::core::stringify!($layout)),*];
+
+            /// Returns the index range of each chained layout, in wiring order.
+            pub const fn segments() -> [::core::ops::Range<usize>; Self::SEGMENT_COUNT] {
+                let mut end = 0;
+                [$({
+                    let start = end;
+                    end += <$layout as $crate::layout::$trait>::PIXEL_COUNT;
+                    start..end
+                }),*]
+            }
+        }
+    };
+}
+
+#[cfg(test)]
+mod tests {
+    use heapless::Vec;
+
+    use crate::{
+        layout::{Layout1d, Layout2d, Reversed, Shape2d, Vec2},
+        layout1d, layout2d,
+    };
+
+    layout1d!(StripA, 3);
+    layout1d!(StripB, 5);
+    layout1d!(StripC, 2);
+
+    layout_chain!(Strips, Layout1d, [StripA, StripB, StripC]);
+
+    layout2d!(
+        Line,
+        [Shape2d::Line {
+            start: Vec2::new(-1., -1.),
+            end: Vec2::new(1., -1.),
+            pixel_count: 4,
+        }]
+    );
+
+    layout2d!(
+        Grid,
+        [Shape2d::Grid {
+            start: Vec2::new(-1., 0.),
+            horizontal_end: Vec2::new(1., 0.),
+            vertical_end: Vec2::new(-1., 1.),
+            horizontal_pixel_count: 2,
+            vertical_pixel_count: 3,
+            serpentine: true,
+        }]
+    );
+
+    layout_chain!(Panels, Layout2d, [Line, Grid]);
+
+    layout_chain!(ReversedStrips, Layout1d, [StripA, Reversed<StripB>]);
+
+    layout_chain!(ReversedPanels, Layout2d, [Line, Reversed<Grid>]);
+
+    #[test]
+    fn test_1d_segments_sum_to_pixel_count() {
+        assert_eq!(Strips::PIXEL_COUNT, 10);
+        assert_eq!(Strips::SEGMENT_COUNT, 3);
+
+        let segments = Strips::segments();
+        assert_eq!(segments, [0..3, 3..8, 8..10]);
+        assert_eq!(Strips::SEGMENT_NAMES, ["StripA", "StripB", "StripC"]);
+        let total: usize = segments.iter().map(|segment| segment.len()).sum();
+        assert_eq!(total, Strips::PIXEL_COUNT);
+        assert_eq!(segments.last().unwrap().end, Strips::PIXEL_COUNT);
+    }
+
+    #[test]
+    fn test_2d_segments_sum_to_pixel_count() {
+        assert_eq!(Panels::PIXEL_COUNT, 10);
+        assert_eq!(Panels::shapes().count(), 2);
+        assert_eq!(Panels::points().count(), Panels::PIXEL_COUNT);
+
+        let segments = Panels::segments();
+        assert_eq!(segments, [0..4, 4..10]);
+        assert_eq!(Panels::SEGMENT_NAMES, ["Line", "Grid"]);
+        let total: usize = segments.iter().map(|segment| segment.len()).sum();
+        assert_eq!(total, Panels::PIXEL_COUNT);
+    }
+
+    #[test]
+    fn test_1d_points_span_segments() {
+        // Untransformed layouts chain into evenly spaced points.
+        let points: Vec<f32, 10> = Strips::points().collect();
+        let expected: Vec<f32, 10> = (0..10).map(|index| -1. + index as f32 * 2. / 9.).collect();
+        for (point, expected) in points.iter().zip(&expected) {
+            assert!((point - expected).abs() < 1e-6, "{point} != {expected}");
+        }
+    }
+
+    #[test]
+    fn test_1d_reversed_sub_layout() {
+        assert_eq!(ReversedStrips::PIXEL_COUNT, 8);
+        assert_eq!(ReversedStrips::segments(), [0..3, 3..8]);
+        assert_eq!(
+            ReversedStrips::SEGMENT_NAMES,
+            ["StripA", "Reversed<StripB>"]
+        );
+        let points: Vec<f32, 8> = ReversedStrips::points().collect();
+        let spacing = 2. / 7.;
+        let expected = [0., 1., 2., 7., 6., 5., 4., 3.].map(|index: f32| -1. + index * spacing);
+        assert_eq!(points.len(), expected.len());
+        for (point, expected) in points.iter().zip(&expected) {
+            assert!((point - expected).abs() < 1e-6, "{point} != {expected}");
+        }
+    }
+
+    #[test]
+    fn test_2d_reversed_sub_layout() {
+        assert_eq!(ReversedPanels::SEGMENT_COUNT, 2);
+        assert_eq!(ReversedPanels::SEGMENT_NAMES, ["Line", "Reversed<Grid>"]);
+        assert_eq!(ReversedPanels::segments(), [0..4, 4..10]);
+
+        let mut grid: Vec<Vec2, 6> = Grid::points().collect();
+        grid.reverse();
+        let points: Vec<Vec2, 10> = ReversedPanels::points().collect();
+        let line: Vec<Vec2, 4> = Line::points().collect();
+        assert_eq!(points[..4], line[..]);
+        assert_eq!(points[4..], grid[..]);
+    }
+}
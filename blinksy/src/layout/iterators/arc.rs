@@ -60,6 +60,29 @@ impl<Item> ArcStepIterator<Item> {
             length,
         }
     }
+
+    /// Create a new iterator around a full ring, evenly spaced from angle 0.
+    ///
+    /// The last sample is one step before angle 2π, so isn't on top of the first.
+    ///
+    /// - `origin`: center of the ring
+    /// - `cos_scalar`, `sin_scalar`: basis vectors used in the parametric form
+    /// - `length`: number of samples; 0 yields no points
+    pub const fn new_ring(origin: Item, cos_scalar: Item, sin_scalar: Item, length: usize) -> Self {
+        let end_angle_in_radians = if length > 0 {
+            core::f32::consts::TAU * (length - 1) as f32 / length as f32
+        } else {
+            0.
+        };
+        Self::new(
+            origin,
+            cos_scalar,
+            sin_scalar,
+            0.,
+            end_angle_in_radians,
+            length,
+        )
+    }
 }
 
 impl<Item> Iterator for ArcStepIterator<Item>
@@ -90,4 +113,49 @@ where
 
         Some(point)
     }
+
+    fn nth(&mut self, n: usize) -> Option<Self::Item> {
+        self.index = self.index.saturating_add(n);
+        self.next()
+    }
+}
+
+/// Returns the indices of the pixels within `radius` steps of a pixel around a ring,
+/// excluding the pixel itself, wrapping around from the last pixel to the first.
+///
+/// Each neighbor is returned once, even if `radius` reaches around the whole ring.
+pub(crate) fn ring_neighbors(
+    pixel_count: usize,
+    index: usize,
+    radius: usize,
+) -> impl Iterator<Item = usize> {
+    let (backward, forward) = if index < pixel_count {
+        let forward = radius.min(pixel_count - 1);
+        (radius.min(pixel_count - 1 - forward), forward)
+    } else {
+        (0, 0)
+    };
+    // Offsets from the pixel, shifted by the pixel count to stay unsigned.
+    (pixel_count - backward..=pixel_count + forward)
+        .filter(move |&offset| offset != pixel_count)
+        .map(move |offset| (index + offset) % pixel_count)
+}
+
+#[cfg(test)]
+mod tests {
+    use heapless::Vec;
+
+    use super::*;
+
+    #[test]
+    fn test_ring_neighbors_wrap_around() {
+        let neighbors =
+            |index, radius| -> Vec<usize, 8> { ring_neighbors(8, index, radius).collect() };
+        assert_eq!(neighbors(0, 1), [7, 1]);
+        assert_eq!(neighbors(7, 2), [5, 6, 0, 1]);
+        // Around the whole ring, each other pixel once.
+        assert_eq!(neighbors(3, 5), [1, 2, 4, 5, 6, 7, 0]);
+        assert!(neighbors(8, 1).is_empty());
+        assert_eq!(ring_neighbors(0, 0, 1).count(), 0);
+    }
 }
@@ -79,4 +79,111 @@ where
         }
         Some(point)
     }
+
+    fn nth(&mut self, n: usize) -> Option<Self::Item> {
+        if self.horizontal_pixel_count == 0 {
+            self.vertical_index = self.vertical_pixel_count;
+            return None;
+        }
+        let index = (self.vertical_index * self.horizontal_pixel_count + self.horizontal_index)
+            .saturating_add(n);
+        self.vertical_index = index / self.horizontal_pixel_count;
+        self.horizontal_index = index % self.horizontal_pixel_count;
+        self.next()
+    }
+}
+
+/// Returns the indices of the pixels within `radius` rows and columns of a pixel in a grid,
+/// excluding the pixel itself.
+///
+/// The neighbors are returned row by row, in order of column, not in order of index.
+pub(crate) fn grid_neighbors(
+    horizontal_pixel_count: usize,
+    vertical_pixel_count: usize,
+    serpentine: bool,
+    index: usize,
+    radius: usize,
+) -> impl Iterator<Item = usize> {
+    // In a serpentine grid, every odd row runs backwards.
+    let flip = move |row: usize, column: usize| {
+        if serpentine && row % 2 == 1 {
+            horizontal_pixel_count - 1 - column
+        } else {
+            column
+        }
+    };
+
+    let (rows, columns, row, column) = if index < horizontal_pixel_count * vertical_pixel_count {
+        let row = index / horizontal_pixel_count;
+        let column = flip(row, index % horizontal_pixel_count);
+        (
+            row.saturating_sub(radius)..=(row + radius).min(vertical_pixel_count - 1),
+            column.saturating_sub(radius)..=(column + radius).min(horizontal_pixel_count - 1),
+            row,
+            column,
+        )
+    } else {
+        #[allow(clippy::reversed_empty_ranges)]
+        (1..=0, 1..=0, 0, 0)
+    };
+
+    rows.flat_map(move |other_row| {
+        columns
+            .clone()
+            .filter(move |&other_column| (other_row, other_column) != (row, column))
+            .map(move |other_column| {
+                other_row * horizontal_pixel_count + flip(other_row, other_column)
+            })
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use heapless::Vec;
+
+    use super::*;
+    use crate::{
+        layout::{Layout1d, Layout2d, Shape2d, Vec2},
+        layout1d, layout2d,
+    };
+
+    #[test]
+    fn test_grid_neighbors() {
+        // 0  1  2  3
+        // 7  6  5  4
+        // 8  9 10 11
+        let neighbors: Vec<usize, 8> = grid_neighbors(4, 3, true, 5, 1).collect();
+        assert_eq!(neighbors, [1, 2, 3, 6, 4, 9, 10, 11]);
+
+        // Corners are clamped to the grid.
+        let neighbors: Vec<usize, 8> = grid_neighbors(4, 3, true, 0, 1).collect();
+        assert_eq!(neighbors, [1, 7, 6]);
+
+        assert_eq!(grid_neighbors(4, 3, true, 12, 1).count(), 0);
+    }
+
+    #[test]
+    fn test_layout_neighbors() {
+        layout1d!(Strip, 5);
+
+        let neighbors: Vec<usize, 4> = Strip::neighbors(1, 2).collect();
+        assert_eq!(neighbors, [0, 2, 3]);
+        assert_eq!(Strip::neighbors(5, 2).count(), 0);
+
+        layout2d!(
+            Panel,
+            [Shape2d::Grid {
+                start: Vec2::new(-1., -1.),
+                horizontal_end: Vec2::new(1., -1.),
+                vertical_end: Vec2::new(-1., 1.),
+                horizontal_pixel_count: 3,
+                vertical_pixel_count: 3,
+                serpentine: true,
+            }]
+        );
+
+        // The grid has a spacing of 1.0, so only direct neighbors are within 1.0.
+        let neighbors: Vec<usize, 8> = Panel::neighbors(4, 1.).collect();
+        assert_eq!(neighbors, [1, 3, 5, 7]);
+    }
 }
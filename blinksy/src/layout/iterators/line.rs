@@ -51,4 +51,9 @@ where
         self.index += 1;
         Some(next)
     }
+
+    fn nth(&mut self, n: usize) -> Option<Self::Item> {
+        self.index = self.index.saturating_add(n);
+        self.next()
+    }
 }
@@ -1,7 +1,9 @@
 mod arc;
 mod grid;
 mod line;
+mod spiral;
 
 pub use self::arc::*;
 pub use self::grid::*;
 pub use self::line::*;
+pub use self::spiral::*;
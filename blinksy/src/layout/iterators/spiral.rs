@@ -0,0 +1,100 @@
+use core::ops::{Add, Mul};
+
+#[allow(unused_imports)]
+use num_traits::Float;
+
+/// Iterator for points along an Archimedean spiral in 2D or 3D.
+///
+/// Parametric form, for `t` from 0.0 (first point) to 1.0 (last point):
+///
+/// ```text
+/// radius(t) = start_radius + t * (end_radius - start_radius)
+/// theta(t) = t * angle_in_radians
+/// point(t) = origin + radius(t) * (cos(theta(t)) * cos_axis + sin(theta(t)) * sin_axis)
+/// ```
+///
+/// - 2D spiral, counter-clockwise from the right of the origin:
+///   - `cos_axis = (1, 0)`
+///   - `sin_axis = (0, 1)`
+///
+/// The radius grows linearly with the angle, so turns of the spiral are evenly spaced. If
+/// the angle is 0, the points are along a line, from `start_radius` to `end_radius`.
+#[derive(Debug, Clone)]
+pub struct SpiralStepIterator<Item> {
+    origin: Item,
+    cos_axis: Item,
+    sin_axis: Item,
+    start_radius: f32,
+    end_radius: f32,
+    angle_in_radians: f32,
+    index: usize,
+    length: usize,
+}
+
+impl<Item> SpiralStepIterator<Item> {
+    /// Create a new spiral iterator.
+    ///
+    /// - `origin`: center of the spiral
+    /// - `cos_axis`, `sin_axis`: unit basis vectors used in the parametric form
+    /// - `start_radius`: radius of the first point
+    /// - `end_radius`: radius of the last point
+    /// - `angle_in_radians`: angle swept from the first point to the last point
+    /// - `length`: number of samples; 0 yields no points
+    pub const fn new(
+        origin: Item,
+        cos_axis: Item,
+        sin_axis: Item,
+        start_radius: f32,
+        end_radius: f32,
+        angle_in_radians: f32,
+        length: usize,
+    ) -> Self {
+        Self {
+            origin,
+            cos_axis,
+            sin_axis,
+            start_radius,
+            end_radius,
+            angle_in_radians,
+            index: 0,
+            length,
+        }
+    }
+}
+
+impl<Item> Iterator for SpiralStepIterator<Item>
+where
+    Item: Add<Output = Item> + Copy,
+    f32: Mul<Item, Output = Item> + Mul<f32, Output = f32>,
+{
+    type Item = Item;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.index >= self.length {
+            return None;
+        }
+
+        let denom = if self.length > 1 {
+            (self.length - 1) as f32
+        } else {
+            1.0
+        };
+
+        let t: f32 = (self.index as f32) / denom;
+        let radius: f32 = self.start_radius + t * (self.end_radius - self.start_radius);
+        let theta: f32 = t * self.angle_in_radians;
+
+        let point = self.origin
+            + (radius * theta.cos()) * self.cos_axis
+            + (radius * theta.sin()) * self.sin_axis;
+
+        self.index += 1;
+
+        Some(point)
+    }
+
+    fn nth(&mut self, n: usize) -> Option<Self::Item> {
+        self.index = self.index.saturating_add(n);
+        self.next()
+    }
+}
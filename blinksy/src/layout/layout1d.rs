@@ -1,3 +1,8 @@
+use super::{
+    position::{positions, Position},
+    Vec3,
+};
+
 /// Trait for one-dimensional LED layouts.
 ///
 /// Implementors of this trait represent a linear arrangement of LEDs.
@@ -11,12 +16,49 @@ pub trait Layout1d {
 
     /// Returns an iterator over all points (LED positions) in this layout.
     fn points() -> impl Iterator<Item = f32> {
-        let spacing = if Self::PIXEL_COUNT > 1 {
+        let spacing = Self::spacing();
+        (0..Self::PIXEL_COUNT).map(move |index| -1.0 + (index as f32 * spacing))
+    }
+
+    /// Returns the distance between adjacent points, or 0.0 if fewer than 2 LEDs.
+    ///
+    /// The LEDs span 2.0 units, so the more LEDs, the closer the points. To move at a speed
+    /// in units per second, whatever the number of LEDs, move `speed / spacing()` LEDs per
+    /// second.
+    fn spacing() -> f32 {
+        if Self::PIXEL_COUNT > 1 {
             2.0 / (Self::PIXEL_COUNT as f32 - 1.0)
         } else {
             0.0
+        }
+    }
+
+    /// Returns an iterator over the positions of all LEDs in this layout: the index, the
+    /// point (along the x axis), and the normalized index of each LED.
+    fn positions() -> impl Iterator<Item = Position> {
+        positions(
+            Self::points().map(|x| Vec3::new(x, 0., 0.)),
+            Self::PIXEL_COUNT,
+        )
+    }
+
+    /// Returns the indices of the LEDs within `radius` LEDs of an LED, excluding the LED
+    /// itself.
+    ///
+    /// This takes O(radius) steps, however many LEDs are in the layout.
+    ///
+    /// # Arguments
+    ///
+    /// - `index` - The index of the LED
+    /// - `radius` - The number of LEDs to reach in each direction
+    fn neighbors(index: usize, radius: usize) -> impl Iterator<Item = usize> {
+        #[allow(clippy::reversed_empty_ranges)]
+        let others = if index < Self::PIXEL_COUNT {
+            index.saturating_sub(radius)..=(index + radius).min(Self::PIXEL_COUNT - 1)
+        } else {
+            1..=0
         };
-        (0..Self::PIXEL_COUNT).map(move |index| -1.0 + (index as f32 * spacing))
+        others.filter(move |&other_index| other_index != index)
     }
 }
 
@@ -2,14 +2,18 @@ use core::iter::{once, Once};
 
 use crate::layout::ArcStepIterator;
 
-use super::iterators::{GridStepIterator, LineStepIterator};
+use super::iterators::{
+    grid_neighbors, ring_neighbors, GridStepIterator, LineStepIterator, SpiralStepIterator,
+};
+use super::position::{positions, Position};
 
 pub use glam::Vec2;
 
 /// Enumeration of two-dimensional shape primitives.
 ///
 /// Each variant represents a different type of 2D arrangement of LEDs.
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum Shape2d {
     /// A single point at the specified location.
     Point(Vec2),
@@ -57,6 +61,10 @@ pub enum Shape2d {
     /// - Positive angles are counter-clockwise.
     /// - To make a full ellipse, set end = start + [`TAU`].
     ///
+    /// For circles, use [`Shape2d::Ring`] (or [`Shape2d::circle`]), or
+    /// [`Shape2d::circular_arc`] to choose the axes and angles from a radius, a start angle,
+    /// and a direction.
+    ///
     /// How to choose `axis_u` / `axis_v`:
     ///
     /// - Axis-aligned circle with with radius `r`:
@@ -92,6 +100,122 @@ pub enum Shape2d {
         /// Number of LEDs
         pixel_count: usize,
     },
+
+    /// A ring of LEDs, evenly spaced around a full circle, such as an LED ring or halo.
+    ///
+    /// The first LED is at angle 0 (to the right of the center), and the LEDs go
+    /// counter-clockwise. The last LED is one LED's spacing before the first, not on top of
+    /// it.
+    Ring {
+        /// Center of the circle
+        center: Vec2,
+        /// Radius of the circle
+        radius: f32,
+        /// Number of LEDs
+        pixel_count: usize,
+    },
+
+    /// A spiral of LEDs, for spiral LED art.
+    ///
+    /// An Archimedean spiral: the first LED is at `start_radius` at angle 0 (to the right of
+    /// the center), and the LEDs go counter-clockwise for `turns` full turns, with the
+    /// radius growing (or shrinking) evenly to `end_radius` at the last LED.
+    ///
+    /// With 0 turns, the LEDs are along a line, out from the center to the right. Negative
+    /// turns go clockwise.
+    Spiral {
+        /// Center of the spiral
+        center: Vec2,
+        /// Radius of the first LED
+        start_radius: f32,
+        /// Radius of the last LED
+        end_radius: f32,
+        /// Number of full turns from the first LED to the last LED
+        turns: f32,
+        /// Number of LEDs
+        pixel_count: usize,
+    },
+}
+
+#[cfg(feature = "defmt")]
+impl defmt::Format for Shape2d {
+    fn format(&self, f: defmt::Formatter) {
+        // Vec2 doesn't implement defmt::Format, so format its components.
+        match self {
+            Shape2d::Point(point) => defmt::write!(f, "Point(({=f32}, {=f32}))", point.x, point.y),
+            Shape2d::Line {
+                start,
+                end,
+                pixel_count,
+            } => defmt::write!(
+                f,
+                "Line {{ start: ({=f32}, {=f32}), end: ({=f32}, {=f32}), pixel_count: {=usize} }}",
+                start.x, start.y,
+                end.x, end.y,
+                pixel_count
+            ),
+            Shape2d::Grid {
+                start,
+                horizontal_end,
+                vertical_end,
+                horizontal_pixel_count,
+                vertical_pixel_count,
+                serpentine,
+            } => defmt::write!(
+                f,
+                "Grid {{ start: ({=f32}, {=f32}), horizontal_end: ({=f32}, {=f32}), vertical_end: ({=f32}, {=f32}), horizontal_pixel_count: {=usize}, vertical_pixel_count: {=usize}, serpentine: {=bool} }}",
+                start.x, start.y,
+                horizontal_end.x, horizontal_end.y,
+                vertical_end.x, vertical_end.y,
+                horizontal_pixel_count,
+                vertical_pixel_count,
+                serpentine
+            ),
+            Shape2d::Arc {
+                center,
+                axis_u,
+                axis_v,
+                start_angle_in_radians,
+                end_angle_in_radians,
+                pixel_count,
+            } => defmt::write!(
+                f,
+                "Arc {{ center: ({=f32}, {=f32}), axis_u: ({=f32}, {=f32}), axis_v: ({=f32}, {=f32}), start_angle_in_radians: {=f32}, end_angle_in_radians: {=f32}, pixel_count: {=usize} }}",
+                center.x, center.y,
+                axis_u.x, axis_u.y,
+                axis_v.x, axis_v.y,
+                start_angle_in_radians,
+                end_angle_in_radians,
+                pixel_count
+            ),
+            Shape2d::Ring {
+                center,
+                radius,
+                pixel_count,
+            } => defmt::write!(
+                f,
+                "Ring {{ center: ({=f32}, {=f32}), radius: {=f32}, pixel_count: {=usize} }}",
+                center.x, center.y,
+                radius,
+                pixel_count
+            ),
+            Shape2d::Spiral {
+                center,
+                start_radius,
+                end_radius,
+                turns,
+                pixel_count,
+            } => defmt::write!(
+                f,
+                "Spiral {{ center: ({=f32}, {=f32}), start_radius: {=f32}, end_radius: {=f32}, turns: {=f32}, pixel_count: {=usize} }}",
+                center.x, center.y,
+                start_radius,
+                end_radius,
+                turns,
+                pixel_count
+            ),
+        }
+    }
 }
 
 /// Iterator over points in a 2D shape.
@@ -105,6 +229,8 @@ pub enum Shape2dPointsIterator {
     Grid(GridStepIterator<Vec2, f32>),
     /// Iterator for points along an arc
     Arc(ArcStepIterator<Vec2>),
+    /// Iterator for points along a spiral
+    Spiral(SpiralStepIterator<Vec2>),
 }
 
 impl Iterator for Shape2dPointsIterator {
@@ -116,6 +242,17 @@ impl Iterator for Shape2dPointsIterator {
             Shape2dPointsIterator::Line(iter) => iter.next(),
             Shape2dPointsIterator::Grid(iter) => iter.next(),
             Shape2dPointsIterator::Arc(iter) => iter.next(),
+            Shape2dPointsIterator::Spiral(iter) => iter.next(),
+        }
+    }
+
+    fn nth(&mut self, n: usize) -> Option<Self::Item> {
+        match self {
+            Shape2dPointsIterator::Point(iter) => iter.nth(n),
+            Shape2dPointsIterator::Line(iter) => iter.nth(n),
+            Shape2dPointsIterator::Grid(iter) => iter.nth(n),
+            Shape2dPointsIterator::Arc(iter) => iter.nth(n),
+            Shape2dPointsIterator::Spiral(iter) => iter.nth(n),
         }
     }
 }
@@ -144,7 +281,79 @@ impl From<ArcStepIterator<Vec2>> for Shape2dPointsIterator {
     }
 }
 
+impl From<SpiralStepIterator<Vec2>> for Shape2dPointsIterator {
+    fn from(value: SpiralStepIterator<Vec2>) -> Self {
+        Shape2dPointsIterator::Spiral(value)
+    }
+}
+
 impl Shape2d {
+    /// Creates a circular [`Shape2d::Arc`], from a start angle, around an angle in a
+    /// direction.
+    ///
+    /// The first LED is at the start angle, and the last LED is at the end of the arc.
+    ///
+    /// # Arguments
+    ///
+    /// - `center` - Center of the circle
+    /// - `radius` - Radius of the circle
+    /// - `start_angle_in_radians` - Angle of the first LED, counter-clockwise from the right
+    ///   of the center
+    /// - `angle_in_radians` - Angle from the first LED to the last LED
+    /// - `clockwise` - Whether the LEDs go clockwise, rather than counter-clockwise
+    /// - `pixel_count` - Number of LEDs
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use core::f32::consts::{FRAC_PI_2, PI};
+    /// use blinksy::{layout2d, layout::{Shape2d, Vec2}};
+    ///
+    /// // The left half of a circle, from the top to the bottom
+    /// layout2d!(
+    ///     Layout,
+    ///     [Shape2d::circular_arc(Vec2::ZERO, 1., FRAC_PI_2, PI, false, 16)]
+    /// );
+    /// ```
+    pub const fn circular_arc(
+        center: Vec2,
+        radius: f32,
+        start_angle_in_radians: f32,
+        angle_in_radians: f32,
+        clockwise: bool,
+        pixel_count: usize,
+    ) -> Self {
+        let angle_in_radians = if clockwise {
+            -angle_in_radians
+        } else {
+            angle_in_radians
+        };
+        Shape2d::Arc {
+            center,
+            axis_u: Vec2::new(radius, 0.),
+            axis_v: Vec2::new(0., radius),
+            start_angle_in_radians,
+            end_angle_in_radians: start_angle_in_radians + angle_in_radians,
+            pixel_count,
+        }
+    }
+
+    /// Creates a full circle of LEDs, as a [`Shape2d::Ring`], evenly spaced
+    /// counter-clockwise from the right of the center.
+    ///
+    /// # Arguments
+    ///
+    /// - `center` - Center of the circle
+    /// - `radius` - Radius of the circle
+    /// - `pixel_count` - Number of LEDs
+    pub const fn circle(center: Vec2, radius: f32, pixel_count: usize) -> Self {
+        Shape2d::Ring {
+            center,
+            radius,
+            pixel_count,
+        }
+    }
+
     /// Returns the total number of pixels (LEDs) in this shape.
     pub const fn pixel_count(&self) -> usize {
         match *self {
@@ -156,6 +365,8 @@ impl Shape2d {
                 ..
             } => horizontal_pixel_count * vertical_pixel_count,
             Shape2d::Arc { pixel_count, .. } => pixel_count,
+            Shape2d::Ring { pixel_count, .. } => pixel_count,
+            Shape2d::Spiral { pixel_count, .. } => pixel_count,
         }
     }
 
@@ -210,8 +421,77 @@ impl Shape2d {
                 pixel_count,
             )
             .into(),
+            Shape2d::Ring {
+                center,
+                radius,
+                pixel_count,
+            } => ArcStepIterator::new_ring(
+                center,
+                Vec2::new(radius, 0.),
+                Vec2::new(0., radius),
+                pixel_count,
+            )
+            .into(),
+            Shape2d::Spiral {
+                center,
+                start_radius,
+                end_radius,
+                turns,
+                pixel_count,
+            } => SpiralStepIterator::new(
+                center,
+                Vec2::X,
+                Vec2::Y,
+                start_radius,
+                end_radius,
+                turns * core::f32::consts::TAU,
+                pixel_count,
+            )
+            .into(),
         }
     }
+
+    /// Returns the indices of the pixels near a pixel in this shape, excluding the pixel
+    /// itself.
+    ///
+    /// For a grid, the neighbors are the pixels within `radius` rows and columns. For a
+    /// line, an arc, a ring, or a spiral, the neighbors are the pixels within `radius` steps
+    /// along it. Around a ring, the steps wrap from the last pixel to the first. Indices are
+    /// relative to the start of this shape.
+    ///
+    /// This takes O(radius²) steps for a grid, and O(radius) steps otherwise, however many
+    /// pixels are in the shape.
+    ///
+    /// # Arguments
+    ///
+    /// - `index` - The index of the pixel in this shape
+    /// - `radius` - The number of pixels to reach in each direction
+    pub fn neighbors(&self, index: usize, radius: usize) -> impl Iterator<Item = usize> {
+        // A ring is a line which wraps around, so each shape has either grid or ring
+        // neighbors, and the other is empty.
+        let ((horizontal_pixel_count, vertical_pixel_count, serpentine), ring_pixel_count) =
+            match *self {
+                Shape2d::Grid {
+                    horizontal_pixel_count,
+                    vertical_pixel_count,
+                    serpentine,
+                    ..
+                } => (
+                    (horizontal_pixel_count, vertical_pixel_count, serpentine),
+                    0,
+                ),
+                Shape2d::Ring { pixel_count, .. } => ((0, 0, false), pixel_count),
+                _ => ((self.pixel_count(), 1, false), 0),
+            };
+        grid_neighbors(
+            horizontal_pixel_count,
+            vertical_pixel_count,
+            serpentine,
+            index,
+            radius,
+        )
+        .chain(ring_neighbors(ring_pixel_count, index, radius))
+    }
 }
 
 /// Trait for two-dimensional LED layouts.
@@ -237,6 +517,35 @@ pub trait Layout2d {
     fn points() -> impl Iterator<Item = Vec2> {
         Self::shapes().flat_map(|s| s.points())
     }
+
+    /// Returns an iterator over the positions of all LEDs in this layout: the index, the
+    /// point (where z is 0.0), and the normalized index of each LED.
+    fn positions() -> impl Iterator<Item = Position> {
+        positions(
+            Self::points().map(|point| point.extend(0.)),
+            Self::PIXEL_COUNT,
+        )
+    }
+
+    /// Returns the indices of the LEDs within a distance of an LED, excluding the LED
+    /// itself.
+    ///
+    /// This scans every point in the layout, so takes O(n) steps per LED, or O(n²) steps for
+    /// every LED. For a grid, prefer [`Shape2d::neighbors`], which takes O(radius²) steps.
+    ///
+    /// # Arguments
+    ///
+    /// - `index` - The index of the LED
+    /// - `radius` - The maximum distance to a neighbor, in layout space
+    fn neighbors(index: usize, radius: f32) -> impl Iterator<Item = usize> {
+        let center = Self::points().nth(index);
+        Self::points()
+            .enumerate()
+            .filter_map(move |(other_index, point)| {
+                let center = center?;
+                (other_index != index && point.distance(center) <= radius).then_some(other_index)
+            })
+    }
 }
 
 /// Creates a two-dimensional LED layout from a collection of shapes.
@@ -284,3 +593,137 @@ macro_rules! layout2d {
         }
     };
 }
+
+#[cfg(test)]
+mod tests {
+    use core::f32::consts::{FRAC_PI_2, PI};
+
+    use super::*;
+
+    fn assert_points_near(shape: Shape2d, expected: &[Vec2]) {
+        assert_eq!(shape.pixel_count(), expected.len());
+        for (point, expected) in shape.points().zip(expected) {
+            assert!(point.abs_diff_eq(*expected, 1e-6), "{point} != {expected}");
+        }
+    }
+
+    #[test]
+    fn test_half_circle_from_top() {
+        let top = Vec2::new(0., 1.);
+        let bottom = Vec2::new(0., -1.);
+        assert_points_near(
+            Shape2d::circular_arc(Vec2::ZERO, 1., FRAC_PI_2, PI, false, 3),
+            &[top, Vec2::new(-1., 0.), bottom],
+        );
+        assert_points_near(
+            Shape2d::circular_arc(Vec2::ZERO, 1., FRAC_PI_2, PI, true, 3),
+            &[top, Vec2::new(1., 0.), bottom],
+        );
+    }
+
+    #[test]
+    fn test_ring_even_spacing() {
+        for pixel_count in [8, 12] {
+            let center = Vec2::new(0.5, -0.5);
+            let ring = Shape2d::Ring {
+                center,
+                radius: 0.4,
+                pixel_count,
+            };
+            assert_eq!(ring.pixel_count(), pixel_count);
+
+            let points: heapless::Vec<Vec2, 12> = ring.points().collect();
+            assert_eq!(points.len(), pixel_count);
+            assert!(points[0].abs_diff_eq(center + Vec2::new(0.4, 0.), 1e-6));
+
+            // Every LED is on the circle, and the same distance from the next, including
+            // from the last LED back around to the first.
+            let spacing = 2. * 0.4 * (core::f32::consts::PI / pixel_count as f32).sin();
+            for (index, point) in points.iter().enumerate() {
+                let next = points[(index + 1) % pixel_count];
+                assert!((point.distance(center) - 0.4).abs() < 1e-6);
+                assert!((point.distance(next) - spacing).abs() < 1e-5);
+            }
+        }
+    }
+
+    #[test]
+    fn test_circle() {
+        assert_points_near(
+            Shape2d::circle(Vec2::new(1., 1.), 0.5, 4),
+            &[
+                Vec2::new(1.5, 1.),
+                Vec2::new(1., 1.5),
+                Vec2::new(0.5, 1.),
+                Vec2::new(1., 0.5),
+            ],
+        );
+    }
+
+    #[test]
+    fn test_ring_neighbors_wrap_around() {
+        let ring = Shape2d::circle(Vec2::ZERO, 1., 6);
+        let neighbors: heapless::Vec<usize, 6> = ring.neighbors(0, 1).collect();
+        assert_eq!(neighbors, [5, 1]);
+        let neighbors: heapless::Vec<usize, 6> = ring.neighbors(5, 1).collect();
+        assert_eq!(neighbors, [4, 0]);
+
+        // An arc around the same circle doesn't wrap.
+        let arc = Shape2d::circular_arc(Vec2::ZERO, 1., 0., PI, false, 6);
+        let neighbors: heapless::Vec<usize, 6> = arc.neighbors(0, 1).collect();
+        assert_eq!(neighbors, [1]);
+    }
+
+    #[test]
+    fn test_spiral_radii() {
+        let center = Vec2::new(0.1, 0.2);
+        let spiral = |turns: f32| Shape2d::Spiral {
+            center,
+            start_radius: 0.2,
+            end_radius: 0.9,
+            turns,
+            pixel_count: 50,
+        };
+
+        for turns in [0., 1., 2.5, -3.] {
+            let points: heapless::Vec<Vec2, 50> = spiral(turns).points().collect();
+            assert_eq!(points.len(), 50);
+            // The first LED is at the start radius, at angle 0.
+            assert!(points[0].abs_diff_eq(center + Vec2::new(0.2, 0.), 1e-6));
+            // The last LED is at the end radius.
+            assert!((points[49].distance(center) - 0.9).abs() < 1e-5);
+            // The radius grows with each LED.
+            assert!(points
+                .windows(2)
+                .all(|pair| pair[1].distance(center) > pair[0].distance(center)));
+        }
+
+        // With no turns, the spiral is a line out to the right.
+        assert_points_near(
+            Shape2d::Spiral {
+                center: Vec2::ZERO,
+                start_radius: 0.,
+                end_radius: 1.,
+                turns: 0.,
+                pixel_count: 3,
+            },
+            &[Vec2::ZERO, Vec2::new(0.5, 0.), Vec2::new(1., 0.)],
+        );
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn test_grid_serde_round_trip() {
+        let grid = Shape2d::Grid {
+            start: Vec2::new(-1., -1.),
+            horizontal_end: Vec2::new(1., -1.),
+            vertical_end: Vec2::new(-1., 1.),
+            horizontal_pixel_count: 16,
+            vertical_pixel_count: 8,
+            serpentine: true,
+        };
+        let json = serde_json::to_string(&grid).unwrap();
+        let parsed: Shape2d = serde_json::from_str(&json).unwrap();
+        assert_eq!(parsed, grid);
+    }
+}
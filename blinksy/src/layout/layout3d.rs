@@ -2,14 +2,16 @@ use core::iter::{once, Once};
 
 use crate::layout::ArcStepIterator;
 
-use super::iterators::{GridStepIterator, LineStepIterator};
+use super::iterators::{grid_neighbors, ring_neighbors, GridStepIterator, LineStepIterator};
+use super::position::{positions, Position};
 
 pub use glam::Vec3;
 
 /// Enumeration of three-dimensional shape primitives.
 ///
 /// Each variant represents a different type of 3D arrangement of LEDs.
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum Shape3d {
     /// A single point at the specified location.
     Point(Vec3),
@@ -91,6 +93,23 @@ pub enum Shape3d {
         /// Number of LEDs
         pixel_count: usize,
     },
+
+    /// A ring of LEDs, evenly spaced around a full circle, such as an LED ring or halo.
+    ///
+    /// The circle lies in the plane of `axis_u` and `axis_v`, which should be perpendicular
+    /// and the length of the radius, as for a circular [`Shape3d::Arc`]. The first LED is at
+    /// angle 0 (`center + axis_u`), and the LEDs go towards `axis_v`. The last LED is one
+    /// LED's spacing before the first, not on top of it.
+    Ring {
+        /// Center of the circle
+        center: Vec3,
+        /// Cosine-axis vector, from the center to the first LED
+        axis_u: Vec3,
+        /// Sine-axis vector
+        axis_v: Vec3,
+        /// Number of LEDs
+        pixel_count: usize,
+    },
 }
 
 impl Shape3d {
@@ -105,6 +124,7 @@ impl Shape3d {
                 ..
             } => horizontal_pixel_count * vertical_pixel_count,
             Shape3d::Arc { pixel_count, .. } => pixel_count,
+            Shape3d::Ring { pixel_count, .. } => pixel_count,
         }
     }
 
@@ -158,8 +178,56 @@ impl Shape3d {
                 pixel_count,
             )
             .into(),
+            Shape3d::Ring {
+                center,
+                axis_u,
+                axis_v,
+                pixel_count,
+            } => ArcStepIterator::new_ring(center, axis_u, axis_v, pixel_count).into(),
         }
     }
+
+    /// Returns the indices of the pixels near a pixel in this shape, excluding the pixel
+    /// itself.
+    ///
+    /// For a grid, the neighbors are the pixels within `radius` rows and columns. For a
+    /// line, an arc, or a ring, the neighbors are the pixels within `radius` steps along it.
+    /// Around a ring, the steps wrap from the last pixel to the first. Indices are relative to
+    /// the start of this shape.
+    ///
+    /// This takes O(radius²) steps for a grid, and O(radius) steps otherwise, however many
+    /// pixels are in the shape.
+    ///
+    /// # Arguments
+    ///
+    /// - `index` - The index of the pixel in this shape
+    /// - `radius` - The number of pixels to reach in each direction
+    pub fn neighbors(&self, index: usize, radius: usize) -> impl Iterator<Item = usize> {
+        // A ring is a line which wraps around, so each shape has either grid or ring
+        // neighbors, and the other is empty.
+        let ((horizontal_pixel_count, vertical_pixel_count, serpentine), ring_pixel_count) =
+            match *self {
+                Shape3d::Grid {
+                    horizontal_pixel_count,
+                    vertical_pixel_count,
+                    serpentine,
+                    ..
+                } => (
+                    (horizontal_pixel_count, vertical_pixel_count, serpentine),
+                    0,
+                ),
+                Shape3d::Ring { pixel_count, .. } => ((0, 0, false), pixel_count),
+                _ => ((self.pixel_count(), 1, false), 0),
+            };
+        grid_neighbors(
+            horizontal_pixel_count,
+            vertical_pixel_count,
+            serpentine,
+            index,
+            radius,
+        )
+        .chain(ring_neighbors(ring_pixel_count, index, radius))
+    }
 }
 
 /// Trait for three-dimensional LED layouts.
@@ -189,6 +257,100 @@ pub trait Layout3d {
     fn points() -> impl Iterator<Item = Vec3> {
         Self::shapes().flat_map(|s| s.points())
     }
+
+    /// Returns an iterator over the positions of all LEDs in this layout: the index, the
+    /// point, and the normalized index of each LED.
+    fn positions() -> impl Iterator<Item = Position> {
+        positions(Self::points(), Self::PIXEL_COUNT)
+    }
+
+    /// Returns the indices of the LEDs within a distance of an LED, excluding the LED
+    /// itself.
+    ///
+    /// This scans every point in the layout, so takes O(n) steps per LED, or O(n²) steps for
+    /// every LED. For a grid, prefer [`Shape3d::neighbors`], which takes O(radius²) steps.
+    ///
+    /// # Arguments
+    ///
+    /// - `index` - The index of the LED
+    /// - `radius` - The maximum distance to a neighbor, in layout space
+    fn neighbors(index: usize, radius: f32) -> impl Iterator<Item = usize> {
+        let center = Self::points().nth(index);
+        Self::points()
+            .enumerate()
+            .filter_map(move |(other_index, point)| {
+                let center = center?;
+                (other_index != index && point.distance(center) <= radius).then_some(other_index)
+            })
+    }
+}
+
+#[cfg(feature = "defmt")]
+impl defmt::Format for Shape3d {
+    fn format(&self, f: defmt::Formatter) {
+        // Vec3 doesn't implement defmt::Format, so format its components.
+        match self {
+            Shape3d::Point(point) => defmt::write!(f, "Point(({=f32}, {=f32}, {=f32}))", point.x, point.y, point.z),
+            Shape3d::Line {
+                start,
+                end,
+                pixel_count,
+            } => defmt::write!(
+                f,
+                "Line {{ start: ({=f32}, {=f32}, {=f32}), end: ({=f32}, {=f32}, {=f32}), pixel_count: {=usize} }}",
+                start.x, start.y, start.z,
+                end.x, end.y, end.z,
+                pixel_count
+            ),
+            Shape3d::Grid {
+                start,
+                horizontal_end,
+                vertical_end,
+                horizontal_pixel_count,
+                vertical_pixel_count,
+                serpentine,
+            } => defmt::write!(
+                f,
+                "Grid {{ start: ({=f32}, {=f32}, {=f32}), horizontal_end: ({=f32}, {=f32}, {=f32}), vertical_end: ({=f32}, {=f32}, {=f32}), horizontal_pixel_count: {=usize}, vertical_pixel_count: {=usize}, serpentine: {=bool} }}",
+                start.x, start.y, start.z,
+                horizontal_end.x, horizontal_end.y, horizontal_end.z,
+                vertical_end.x, vertical_end.y, vertical_end.z,
+                horizontal_pixel_count,
+                vertical_pixel_count,
+                serpentine
+            ),
+            Shape3d::Arc {
+                center,
+                axis_u,
+                axis_v,
+                start_angle_in_radians,
+                end_angle_in_radians,
+                pixel_count,
+            } => defmt::write!(
+                f,
+                "Arc {{ center: ({=f32}, {=f32}, {=f32}), axis_u: ({=f32}, {=f32}, {=f32}), axis_v: ({=f32}, {=f32}, {=f32}), start_angle_in_radians: {=f32}, end_angle_in_radians: {=f32}, pixel_count: {=usize} }}",
+                center.x, center.y, center.z,
+                axis_u.x, axis_u.y, axis_u.z,
+                axis_v.x, axis_v.y, axis_v.z,
+                start_angle_in_radians,
+                end_angle_in_radians,
+                pixel_count
+            ),
+            Shape3d::Ring {
+                center,
+                axis_u,
+                axis_v,
+                pixel_count,
+            } => defmt::write!(
+                f,
+                "Ring {{ center: ({=f32}, {=f32}, {=f32}), axis_u: ({=f32}, {=f32}, {=f32}), axis_v: ({=f32}, {=f32}, {=f32}), pixel_count: {=usize} }}",
+                center.x, center.y, center.z,
+                axis_u.x, axis_u.y, axis_u.z,
+                axis_v.x, axis_v.y, axis_v.z,
+                pixel_count
+            ),
+        }
+    }
 }
 
 /// Iterator over points in a 3D shape.
@@ -215,6 +377,15 @@ impl Iterator for Shape3dPointsIterator {
             Shape3dPointsIterator::Arc(iter) => iter.next(),
         }
     }
+
+    fn nth(&mut self, n: usize) -> Option<Self::Item> {
+        match self {
+            Shape3dPointsIterator::Point(iter) => iter.nth(n),
+            Shape3dPointsIterator::Line(iter) => iter.nth(n),
+            Shape3dPointsIterator::Grid(iter) => iter.nth(n),
+            Shape3dPointsIterator::Arc(iter) => iter.nth(n),
+        }
+    }
 }
 
 impl From<Once<Vec3>> for Shape3dPointsIterator {
@@ -256,3 +427,41 @@ macro_rules! layout3d {
         }
     };
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_ring_in_plane() {
+        // A ring of radius 0.5 in the XZ plane, from the front.
+        let ring = Shape3d::Ring {
+            center: Vec3::ZERO,
+            axis_u: Vec3::new(0., 0., 0.5),
+            axis_v: Vec3::new(0.5, 0., 0.),
+            pixel_count: 4,
+        };
+        let expected = [
+            Vec3::new(0., 0., 0.5),
+            Vec3::new(0.5, 0., 0.),
+            Vec3::new(0., 0., -0.5),
+            Vec3::new(-0.5, 0., 0.),
+        ];
+        assert_eq!(ring.pixel_count(), 4);
+        for (point, expected) in ring.points().zip(expected) {
+            assert!(point.abs_diff_eq(expected, 1e-6), "{point} != {expected}");
+        }
+    }
+
+    #[test]
+    fn test_ring_neighbors_wrap_around() {
+        let ring = Shape3d::Ring {
+            center: Vec3::ZERO,
+            axis_u: Vec3::X,
+            axis_v: Vec3::Y,
+            pixel_count: 5,
+        };
+        let neighbors: heapless::Vec<usize, 5> = ring.neighbors(4, 2).collect();
+        assert_eq!(neighbors, [2, 3, 0, 1]);
+    }
+}
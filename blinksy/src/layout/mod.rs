@@ -11,6 +11,19 @@
 //! `.points()`. method, which maps each LED pixel into a 1D, 2D, or 3D space between -1.0 and
 //! 1.0.
 //!
+//! For patterns that need the index of each LED as well as its point (e.g. an index-based
+//! chase on a 2D layout), `.positions()` yields a [`Position`] per LED, with the index, the
+//! point as a [`Vec3`], and the normalized index (0.0 to 1.0). A [`Position`] converts back
+//! into the point of its dimension, so code written for `.points()` works with
+//! `.positions()` too. For any dimension, see [`LayoutForDim::layout_positions`].
+//!
+//! ## Spacing
+//!
+//! The same space holds any number of LEDs, so the distance between LEDs depends on the
+//! layout. For patterns which move at a speed in units per second, use
+//! [`Layout1d::spacing`], or [`LayoutForDim::average_spacing`] for any layout, to convert
+//! the speed into LEDs per second, so the pattern looks the same on dense and sparse strips.
+//!
 //! ## 1D Layouts
 //!
 //! For simple linear arrangements, use the [`layout1d!`] macro:
@@ -47,33 +60,286 @@
 //!
 //! For 3D layouts, use the [`layout3d!`] macro with one or more [`Shape3d`] definitions.
 //!
+//! ## Chained Layouts
+//!
+//! For several sub-strips or panels joined into one data line, use the [`layout_chain!`] macro
+//! to chain their layouts, in wiring order. The chained layout records the index range of each
+//! layout, from `segments()`, to target each physical sub-strip with a
+//! [`Segment`](crate::control::Segment) or a mask.
+//!
+//! ```rust
+//! use blinksy::{layout::Layout1d, layout1d, layout_chain};
+//!
+//! layout1d!(Desk, 30);
+//! layout1d!(Shelf, 45);
+//!
+//! layout_chain!(Layout, Layout1d, [Desk, Shelf]);
+//!
+//! assert_eq!(Layout::segments(), [0..30, 30..75]);
+//! ```
+//!
+//! ## Layout Transforms
+//!
+//! To change a layout without editing it, wrap it in a transform:
+//!
+//! - [`Reversed`]: Reverses the order of the LEDs, such as for a strip wired from the other
+//!   end
+//! - [`Mirrored`]: Mirrors the left half onto the right half
+//! - [`Oversampled`]: Replaces each LED of a 1D layout with several virtual LEDs, to render
+//!   at a higher resolution
+//!
+//! ```rust,ignore
+//! let mut control = ControlBuilder::new_1d()
+//!     .with_layout::<Reversed<Layout>, { Layout::PIXEL_COUNT }>()
+//!     // ...
+//!     .build();
+//! ```
+//!
 //! [`layout1d!`]: crate::layout1d!
 //! [`layout2d!`]: crate::layout2d!
 //! [`layout3d!`]: crate::layout3d!
+//! [`layout_chain!`]: crate::layout_chain!
 
 use crate::markers::{Dim1d, Dim2d, Dim3d};
 
+mod chain;
 mod iterators;
 mod layout1d;
 mod layout2d;
 mod layout3d;
+mod position;
+mod transform;
 
 pub use iterators::*;
 pub use layout1d::*;
 pub use layout2d::*;
 pub use layout3d::*;
+pub use position::Position;
+pub use transform::*;
 
 /// Trait for associating layout types with dimension markers.
 ///
 /// This trait creates the relationship between a layout type and its dimensionality,
 /// which helps enforce correct combinations at compile time.
-pub trait LayoutForDim<Dim> {}
+pub trait LayoutForDim<Dim> {
+    /// The point of an LED: `f32` for 1D, [`Vec2`] for 2D, or [`Vec3`] for 3D layouts.
+    type Point: Copy;
+
+    /// Returns the number of LEDs, the `PIXEL_COUNT` of the layout.
+    fn pixel_count() -> usize;
+
+    /// Returns an iterator over all points (LED positions), the `points()` of the layout.
+    fn layout_points() -> impl Iterator<Item = Self::Point>;
+
+    /// Returns an iterator over the [`Position`] of each LED, the `positions()` of the
+    /// layout.
+    fn layout_positions() -> impl Iterator<Item = Position>;
+
+    /// Returns the indices of the LEDs adjacent to an LED, excluding the LED itself.
+    ///
+    /// - For 1D layouts, the LEDs before and after it.
+    /// - For 2D and 3D layouts, the LEDs next to it within the same shape, including
+    ///   diagonally in a grid. LEDs in different shapes are never adjacent.
+    ///
+    /// # Arguments
+    ///
+    /// - `index` - The index of the LED
+    fn adjacent(index: usize) -> impl Iterator<Item = usize>;
+
+    /// Returns the distance between two points.
+    fn distance(a: Self::Point, b: Self::Point) -> f32;
+
+    /// Returns the average distance between adjacent LEDs, or 0.0 if no LEDs are adjacent.
+    ///
+    /// Averages over each LED and the next LED, where they're [adjacent](Self::adjacent).
+    /// For 1D layouts, this is [`Layout1d::spacing`].
+    fn average_spacing() -> f32 {
+        let mut total = 0.;
+        let mut count = 0;
+        let pairs = Self::layout_points().zip(Self::layout_points().skip(1));
+        for (index, (point, next_point)) in pairs.enumerate() {
+            if Self::adjacent(index).any(|other_index| other_index == index + 1) {
+                total += Self::distance(point, next_point);
+                count += 1;
+            }
+        }
+        if count == 0 {
+            0.
+        } else {
+            total / count as f32
+        }
+    }
+}
 
 /// All types implementing Layout1d are compatible with Dim1d.
-impl<T> LayoutForDim<Dim1d> for T where T: Layout1d {}
+impl<T> LayoutForDim<Dim1d> for T
+where
+    T: Layout1d,
+{
+    type Point = f32;
+
+    fn pixel_count() -> usize {
+        T::PIXEL_COUNT
+    }
+
+    fn layout_points() -> impl Iterator<Item = f32> {
+        T::points()
+    }
+
+    fn layout_positions() -> impl Iterator<Item = Position> {
+        T::positions()
+    }
+
+    fn adjacent(index: usize) -> impl Iterator<Item = usize> {
+        T::neighbors(index, 1)
+    }
+
+    fn distance(a: f32, b: f32) -> f32 {
+        (b - a).abs()
+    }
+
+    fn average_spacing() -> f32 {
+        T::spacing()
+    }
+}
 
 /// All types implementing Layout2d are compatible with Dim2d.
-impl<T> LayoutForDim<Dim2d> for T where T: Layout2d {}
+impl<T> LayoutForDim<Dim2d> for T
+where
+    T: Layout2d,
+{
+    type Point = Vec2;
+
+    fn pixel_count() -> usize {
+        T::PIXEL_COUNT
+    }
+
+    fn layout_points() -> impl Iterator<Item = Vec2> {
+        T::points()
+    }
+
+    fn layout_positions() -> impl Iterator<Item = Position> {
+        T::positions()
+    }
+
+    fn distance(a: Vec2, b: Vec2) -> f32 {
+        a.distance(b)
+    }
+
+    fn adjacent(index: usize) -> impl Iterator<Item = usize> {
+        let mut offset = 0;
+        T::shapes()
+            .find_map(|shape| {
+                let pixel_count = shape.pixel_count();
+                if index < offset + pixel_count {
+                    Some((shape, offset))
+                } else {
+                    offset += pixel_count;
+                    None
+                }
+            })
+            .into_iter()
+            .flat_map(move |(shape, offset)| {
+                shape
+                    .neighbors(index - offset, 1)
+                    .map(move |other_index| other_index + offset)
+            })
+    }
+}
 
 /// All types implementing Layout3d are compatible with Dim3d.
-impl<T> LayoutForDim<Dim3d> for T where T: Layout3d {}
+impl<T> LayoutForDim<Dim3d> for T
+where
+    T: Layout3d,
+{
+    type Point = Vec3;
+
+    fn pixel_count() -> usize {
+        T::PIXEL_COUNT
+    }
+
+    fn layout_points() -> impl Iterator<Item = Vec3> {
+        T::points()
+    }
+
+    fn layout_positions() -> impl Iterator<Item = Position> {
+        T::positions()
+    }
+
+    fn distance(a: Vec3, b: Vec3) -> f32 {
+        a.distance(b)
+    }
+
+    fn adjacent(index: usize) -> impl Iterator<Item = usize> {
+        let mut offset = 0;
+        T::shapes()
+            .find_map(|shape| {
+                let pixel_count = shape.pixel_count();
+                if index < offset + pixel_count {
+                    Some((shape, offset))
+                } else {
+                    offset += pixel_count;
+                    None
+                }
+            })
+            .into_iter()
+            .flat_map(move |(shape, offset)| {
+                shape
+                    .neighbors(index - offset, 1)
+                    .map(move |other_index| other_index + offset)
+            })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{layout1d, layout2d};
+
+    layout1d!(Strip, 5);
+    layout1d!(DenseStrip, 9);
+
+    layout2d!(
+        Line,
+        [Shape2d::Line {
+            start: Vec2::new(-1., 0.),
+            end: Vec2::new(1., 0.),
+            pixel_count: 5,
+        }]
+    );
+
+    layout2d!(
+        Grid,
+        [Shape2d::Grid {
+            start: Vec2::new(-1., -1.),
+            horizontal_end: Vec2::new(1., -1.),
+            vertical_end: Vec2::new(-1., 1.),
+            horizontal_pixel_count: 3,
+            vertical_pixel_count: 5,
+            serpentine: true,
+        }]
+    );
+
+    #[test]
+    fn test_1d_spacing() {
+        assert_eq!(Strip::spacing(), 0.5);
+        assert_eq!(DenseStrip::spacing(), 0.25);
+        assert_eq!(<Strip as LayoutForDim<Dim1d>>::average_spacing(), 0.5);
+
+        // A speed of 1 unit per second is twice as many LEDs per second on the dense strip.
+        assert_eq!(1. / DenseStrip::spacing(), 2. / Strip::spacing());
+    }
+
+    #[test]
+    fn test_average_spacing_on_line() {
+        assert_eq!(<Line as LayoutForDim<Dim2d>>::average_spacing(), 0.5);
+    }
+
+    #[test]
+    fn test_average_spacing_on_grid() {
+        // Columns are 1.0 apart and rows are 0.5 apart: 2 of each per row, then up a row.
+        let spacing = <Grid as LayoutForDim<Dim2d>>::average_spacing();
+        let expected = (5. * 2. * 1. + 4. * 0.5) / 14.;
+        assert!((spacing - expected).abs() < 1e-6);
+    }
+}
@@ -0,0 +1,110 @@
+use super::{Vec2, Vec3};
+
+/// The position of a LED in a layout.
+///
+/// Bundles the LED's index with its point in space, for patterns that need both. The same
+/// type is used for every dimension: a 1D or 2D point is on the plane where the missing
+/// axes are 0.0.
+///
+/// A position converts into the point of its dimension, with [`From`]: an `f32` for 1D, a
+/// [`Vec2`] for 2D, or a [`Vec3`] for 3D. So code written for points works with positions
+/// too.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Position {
+    /// The index of the LED, in wiring order
+    pub index: usize,
+    /// The point of the LED, from -1.0 to 1.0 along each axis
+    pub position: Vec3,
+    /// The index of the LED, from 0.0 for the first LED to 1.0 for the last LED
+    pub normalized: f32,
+}
+
+impl Position {
+    /// Creates the position of a LED.
+    ///
+    /// # Arguments
+    ///
+    /// - `index` - The index of the LED
+    /// - `position` - The point of the LED
+    /// - `pixel_count` - The number of LEDs in the layout
+    pub fn new(index: usize, position: Vec3, pixel_count: usize) -> Self {
+        let normalized = if pixel_count > 1 {
+            index as f32 / (pixel_count - 1) as f32
+        } else {
+            0.
+        };
+        Self {
+            index,
+            position,
+            normalized,
+        }
+    }
+}
+
+impl From<Position> for f32 {
+    fn from(position: Position) -> Self {
+        position.position.x
+    }
+}
+
+impl From<Position> for Vec2 {
+    fn from(position: Position) -> Self {
+        position.position.truncate()
+    }
+}
+
+impl From<Position> for Vec3 {
+    fn from(position: Position) -> Self {
+        position.position
+    }
+}
+
+/// Maps points, in wiring order, to positions.
+///
+/// # Arguments
+///
+/// - `points` - The points of the LEDs
+/// - `pixel_count` - The number of LEDs in the layout
+pub(super) fn positions(
+    points: impl Iterator<Item = Vec3>,
+    pixel_count: usize,
+) -> impl Iterator<Item = Position> {
+    points
+        .enumerate()
+        .map(move |(index, point)| Position::new(index, point, pixel_count))
+}
+
+#[cfg(test)]
+mod tests {
+    use heapless::Vec;
+
+    use super::*;
+    use crate::{
+        layout::{Layout1d, Layout2d, Reversed, Shape2d},
+        layout1d, layout2d,
+    };
+
+    layout1d!(Strip, 3);
+
+    layout2d!(
+        Line,
+        [Shape2d::Line {
+            start: Vec2::new(-1., 0.5),
+            end: Vec2::new(1., 0.5),
+            pixel_count: 2,
+        }]
+    );
+
+    #[test]
+    fn test_positions() {
+        let positions: Vec<Position, 3> = Reversed::<Strip>::positions().collect();
+        assert_eq!(positions[0], Position::new(0, Vec3::new(1., 0., 0.), 3));
+        assert_eq!(positions[1].normalized, 0.5);
+        assert_eq!(f32::from(positions[2]), -1.);
+        assert_eq!(positions[2].index, 2);
+
+        let positions: Vec<Position, 2> = Line::positions().collect();
+        assert_eq!(positions[1].position, Vec3::new(1., 0.5, 0.));
+        assert_eq!(Vec2::from(positions[1]), Vec2::new(1., 0.5));
+    }
+}
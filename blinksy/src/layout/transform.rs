@@ -0,0 +1,301 @@
+use core::marker::PhantomData;
+
+use heapless::Vec;
+#[allow(unused_imports)]
+use num_traits::Float;
+
+use super::{Layout1d, Layout2d, Layout3d, Shape2d, Shape3d, Vec2, Vec3};
+
+/// A layout with the order of its LEDs reversed.
+///
+/// The first LED gets the position of the last LED in `Layout`, and so on, so every
+/// pattern runs in the opposite direction, such as for a strip wired from the other end.
+///
+/// [`shapes()`](Layout2d::shapes) returns the shapes of `Layout` in reverse order, so each
+/// shape covers the same LEDs as in [`points()`](Layout2d::points). Within each shape, the
+/// order of the points is reversed by `points()`, but not by the shape itself.
+///
+/// Works with [`Layout1d`], [`Layout2d`], and [`Layout3d`].
+///
+/// For 2D and 3D layouts, a frame takes O(n) steps, as each shape steps straight to each of
+/// its points. For a 1D layout, the points of `Layout` can only be stepped through from the
+/// first, so are reversed in chunks of [`REVERSED_CHUNK_LENGTH`], for O(n² / 64) steps.
+///
+/// # Example
+///
+/// ```rust
+/// use blinksy::{layout::{Layout1d, Reversed}, layout1d};
+///
+/// layout1d!(Layout, 60);
+///
+/// assert_eq!(<Reversed<Layout> as Layout1d>::PIXEL_COUNT, 60);
+/// assert_eq!(<Reversed<Layout> as Layout1d>::points().next(), Some(1.));
+/// ```
+#[derive(Debug)]
+pub struct Reversed<Layout>(PhantomData<Layout>);
+
+/// The number of points a 1D [`Reversed`] layout buffers at a time.
+pub const REVERSED_CHUNK_LENGTH: usize = 64;
+
+/// A layout with its right half mirrored from its left half.
+///
+/// Each point with a positive `x` is reflected across `x = 0`, so the right half of every
+/// pattern is a mirror image of the left half. The order of the LEDs is unchanged.
+///
+/// [`shapes()`](Layout2d::shapes) returns the shapes of `Layout` unchanged, only the
+/// positions of [`points()`](Layout2d::points) are mirrored.
+///
+/// Works with [`Layout1d`], [`Layout2d`], and [`Layout3d`].
+///
+/// # Example
+///
+/// ```rust
+/// use blinksy::{layout::{Layout1d, Mirrored}, layout1d};
+///
+/// layout1d!(Layout, 3);
+///
+/// let points: [f32; 3] = core::array::from_fn(|index| {
+///     <Mirrored<Layout> as Layout1d>::points().nth(index).unwrap()
+/// });
+/// assert_eq!(points, [-1., 0., -1.]);
+/// ```
+#[derive(Debug)]
+pub struct Mirrored<Layout>(PhantomData<Layout>);
+
+/// A 1D layout with `FACTOR` virtual LEDs in place of each LED.
+///
+/// The virtual LEDs of each LED are evenly spread across the LED's share of the strip,
+/// centered on its point, so a pattern can be rendered at a higher resolution, then averaged
+/// back down, such as with [`Downsample`](crate::control::Downsample).
+///
+/// Assumes the LEDs of `Layout` are evenly spaced from -1.0 to 1.0, as with [`layout1d!`].
+/// The outer virtual LEDs of the first and last LEDs would be beyond the ends of the
+/// strip, so are clamped to -1.0 and 1.0.
+///
+/// [`layout1d!`]: crate::layout1d!
+///
+/// # Example
+///
+/// ```rust
+/// use blinksy::{layout::{Layout1d, Oversampled}, layout1d};
+///
+/// layout1d!(Layout, 3);
+///
+/// assert_eq!(<Oversampled<Layout, 2> as Layout1d>::PIXEL_COUNT, 6);
+/// let points: [f32; 6] = core::array::from_fn(|index| {
+///     <Oversampled<Layout, 2> as Layout1d>::points().nth(index).unwrap()
+/// });
+/// assert_eq!(points, [-1., -0.75, -0.25, 0.25, 0.75, 1.]);
+/// ```
+#[derive(Debug)]
+pub struct Oversampled<Layout, const FACTOR: usize>(PhantomData<Layout>);
+
+impl<Layout: Layout1d> Layout1d for Reversed<Layout> {
+    const PIXEL_COUNT: usize = Layout::PIXEL_COUNT;
+
+    fn points() -> impl Iterator<Item = f32> {
+        let chunk_count = Self::PIXEL_COUNT.div_ceil(REVERSED_CHUNK_LENGTH);
+        (0..chunk_count).rev().flat_map(|chunk| {
+            let mut points: Vec<f32, REVERSED_CHUNK_LENGTH> = Layout::points()
+                .skip(chunk * REVERSED_CHUNK_LENGTH)
+                .take(REVERSED_CHUNK_LENGTH)
+                .collect();
+            points.reverse();
+            points.into_iter()
+        })
+    }
+}
+
+impl<Layout: Layout2d> Layout2d for Reversed<Layout> {
+    const PIXEL_COUNT: usize = Layout::PIXEL_COUNT;
+
+    fn shapes() -> impl Iterator<Item = Shape2d> {
+        let shape_count = Layout::shapes().count();
+        (0..shape_count)
+            .rev()
+            .filter_map(|index| Layout::shapes().nth(index))
+    }
+
+    fn points() -> impl Iterator<Item = Vec2> {
+        // Each shape steps straight to a point, so reverse the points of each shape.
+        Self::shapes().flat_map(|shape| {
+            (0..shape.pixel_count())
+                .rev()
+                .filter_map(move |index| shape.points().nth(index))
+        })
+    }
+}
+
+impl<Layout: Layout3d> Layout3d for Reversed<Layout> {
+    const PIXEL_COUNT: usize = Layout::PIXEL_COUNT;
+
+    fn shapes() -> impl Iterator<Item = Shape3d> {
+        let shape_count = Layout::shapes().count();
+        (0..shape_count)
+            .rev()
+            .filter_map(|index| Layout::shapes().nth(index))
+    }
+
+    fn points() -> impl Iterator<Item = Vec3> {
+        // Each shape steps straight to a point, so reverse the points of each shape.
+        Self::shapes().flat_map(|shape| {
+            (0..shape.pixel_count())
+                .rev()
+                .filter_map(move |index| shape.points().nth(index))
+        })
+    }
+}
+
+impl<Layout: Layout1d> Layout1d for Mirrored<Layout> {
+    const PIXEL_COUNT: usize = Layout::PIXEL_COUNT;
+
+    fn points() -> impl Iterator<Item = f32> {
+        Layout::points().map(|x| -x.abs())
+    }
+}
+
+impl<Layout: Layout1d, const FACTOR: usize> Layout1d for Oversampled<Layout, FACTOR> {
+    const PIXEL_COUNT: usize = Layout::PIXEL_COUNT * FACTOR;
+
+    fn points() -> impl Iterator<Item = f32> {
+        let spacing = if Layout::PIXEL_COUNT > 1 {
+            2.0 / (Layout::PIXEL_COUNT as f32 - 1.0)
+        } else {
+            0.0
+        };
+        Layout::points().flat_map(move |x| {
+            (0..FACTOR).map(move |sample| {
+                let offset = spacing * ((sample as f32 + 0.5) / FACTOR as f32 - 0.5);
+                (x + offset).clamp(-1., 1.)
+            })
+        })
+    }
+}
+
+impl<Layout: Layout2d> Layout2d for Mirrored<Layout> {
+    const PIXEL_COUNT: usize = Layout::PIXEL_COUNT;
+
+    fn shapes() -> impl Iterator<Item = Shape2d> {
+        Layout::shapes()
+    }
+
+    fn points() -> impl Iterator<Item = Vec2> {
+        Layout::points().map(|point| Vec2::new(-point.x.abs(), point.y))
+    }
+}
+
+impl<Layout: Layout3d> Layout3d for Mirrored<Layout> {
+    const PIXEL_COUNT: usize = Layout::PIXEL_COUNT;
+
+    fn shapes() -> impl Iterator<Item = Shape3d> {
+        Layout::shapes()
+    }
+
+    fn points() -> impl Iterator<Item = Vec3> {
+        Layout::points().map(|point| Vec3::new(-point.x.abs(), point.y, point.z))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{layout1d, layout2d, layout3d};
+
+    layout1d!(Strip, 5);
+
+    layout1d!(LongStrip, 150);
+
+    layout3d!(
+        Cube,
+        [
+            Shape3d::Line {
+                start: Vec3::new(-1., -1., -1.),
+                end: Vec3::new(1., -1., -1.),
+                pixel_count: 3,
+            },
+            Shape3d::Grid {
+                start: Vec3::new(-1., -1., 1.),
+                horizontal_end: Vec3::new(1., -1., 1.),
+                vertical_end: Vec3::new(-1., 1., 1.),
+                horizontal_pixel_count: 2,
+                vertical_pixel_count: 2,
+                serpentine: false,
+            },
+        ]
+    );
+
+    layout2d!(
+        Panel,
+        [
+            Shape2d::Point(Vec2::new(0., 0.)),
+            Shape2d::Grid {
+                start: Vec2::new(-1., -1.),
+                horizontal_end: Vec2::new(1., -1.),
+                vertical_end: Vec2::new(-1., 1.),
+                horizontal_pixel_count: 3,
+                vertical_pixel_count: 3,
+                serpentine: true,
+            },
+            Shape2d::Line {
+                start: Vec2::new(-1., 1.),
+                end: Vec2::new(1., 1.),
+                pixel_count: 4,
+            },
+        ]
+    );
+
+    #[test]
+    fn test_reversed_1d() {
+        assert_eq!(<Reversed<Strip> as Layout1d>::PIXEL_COUNT, 5);
+        let points: Vec<f32, 5> = <Reversed<Strip> as Layout1d>::points().collect();
+        assert_eq!(points, [1., 0.5, 0., -0.5, -1.]);
+    }
+
+    #[test]
+    fn test_reversed_1d_across_chunks() {
+        let mut expected: Vec<f32, 150> = LongStrip::points().collect();
+        expected.reverse();
+        let points: Vec<f32, 150> = <Reversed<LongStrip> as Layout1d>::points().collect();
+        assert_eq!(points, expected);
+    }
+
+    #[test]
+    fn test_reversed_2d() {
+        assert_eq!(<Reversed<Panel> as Layout2d>::PIXEL_COUNT, 14);
+        let mut expected: Vec<Vec2, 14> = Panel::points().collect();
+        expected.reverse();
+        let points: Vec<Vec2, 14> = <Reversed<Panel> as Layout2d>::points().collect();
+        assert_eq!(points, expected);
+
+        let mut expected: Vec<Shape2d, 3> = Panel::shapes().collect();
+        expected.reverse();
+        let shapes: Vec<Shape2d, 3> = <Reversed<Panel> as Layout2d>::shapes().collect();
+        assert_eq!(shapes, expected);
+    }
+
+    #[test]
+    fn test_reversed_3d() {
+        assert_eq!(<Reversed<Cube> as Layout3d>::PIXEL_COUNT, 7);
+        let mut expected: Vec<Vec3, 7> = Cube::points().collect();
+        expected.reverse();
+        let points: Vec<Vec3, 7> = <Reversed<Cube> as Layout3d>::points().collect();
+        assert_eq!(points, expected);
+
+        let mut expected: Vec<Shape3d, 2> = Cube::shapes().collect();
+        expected.reverse();
+        let shapes: Vec<Shape3d, 2> = <Reversed<Cube> as Layout3d>::shapes().collect();
+        assert_eq!(shapes, expected);
+    }
+
+    #[test]
+    fn test_mirrored() {
+        assert_eq!(<Mirrored<Strip> as Layout1d>::PIXEL_COUNT, 5);
+        let points: Vec<f32, 5> = <Mirrored<Strip> as Layout1d>::points().collect();
+        assert_eq!(points, [-1., -0.5, 0., -0.5, -1.]);
+
+        assert_eq!(<Mirrored<Panel> as Layout2d>::PIXEL_COUNT, 14);
+        for (point, mirrored) in Panel::points().zip(<Mirrored<Panel> as Layout2d>::points()) {
+            assert_eq!(mirrored, Vec2::new(-point.x.abs(), point.y));
+        }
+    }
+}
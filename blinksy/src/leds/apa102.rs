@@ -1,8 +1,12 @@
-use core::iter::repeat_n;
+use core::{iter::repeat_n, marker::PhantomData};
 
 use crate::{
-    color::{ColorCorrection, LinearSrgb, RgbChannels},
-    driver::clocked::ClockedLed,
+    color::{
+        rgb_order::{Bgr, RgbOrder},
+        ColorCorrection, LinearSrgb,
+    },
+    driver::{clocked::ClockedLed, ClockedDriver},
+    time::Megahertz,
     util::component::Component,
 };
 
@@ -22,11 +26,20 @@ use crate::{
 /// - 5-bit global brightness control (0-31)
 /// - Supports high update rates (Bring-your-own clock rate)
 ///
+/// The data rate is set by your writer: the clock frequency of your SPI bus, or the
+/// data rate of a [`ClockedDelay`](crate::driver::ClockedDelay).
+///
 /// This implementation includes the "High Definition" color handling from FastLED, which
 /// optimizes the use of the 5-bit brightness and 8-bit per-channel values.
+///
+/// # Type Parameters
+///
+/// - `Order` - The [RGB channel order](crate::color::rgb_order), BGR by default. Some
+///   clones use a different order, such as `Apa102<rgb_order::Rgb>`, also set with
+///   [`ClockedDriver::with_rgb_order`].
 #[derive(Debug)]
 #[cfg_attr(feature = "defmt", derive(defmt::Format))]
-pub struct Apa102;
+pub struct Apa102<Order = Bgr>(PhantomData<Order>);
 
 impl Apa102 {
     /// A compile-time function to get a `FRAME_BUFFER_SIZE`, given a `PIXEL_COUNT`.
@@ -40,7 +53,39 @@ impl Apa102 {
     ///   .build();
     /// ```
     pub const fn frame_buffer_size(pixel_count: usize) -> usize {
-        4 + pixel_count * 4 + (pixel_count - 1).div_ceil(16)
+        4 + pixel_count * 4 + Self::end_frame_size(pixel_count)
+    }
+
+    /// Returns the length in bytes of the end frame.
+    ///
+    /// Each LED delays the clock by half a cycle as it passes data along, so the end frame
+    /// must clock at least `pixel_count / 2` more bits for the data to reach the last LED.
+    const fn end_frame_size(pixel_count: usize) -> usize {
+        pixel_count.div_ceil(2).div_ceil(8)
+    }
+}
+
+impl<Order, Writer> ClockedDriver<Apa102<Order>, Writer> {
+    /// Sets the RGB channel order of the LEDs, such as for an APA102 clone.
+    ///
+    /// The same as `.with_led::<Apa102<NewOrder>>()`.
+    ///
+    /// # Type Parameters
+    ///
+    /// - `NewOrder` - The [RGB channel order](crate::color::rgb_order)
+    ///
+    /// # Example
+    ///
+    /// ```rust,ignore
+    /// use blinksy::{color::rgb_order, driver::ClockedDriver, leds::Apa102};
+    ///
+    /// let driver = ClockedDriver::default()
+    ///     .with_led::<Apa102>()
+    ///     .with_rgb_order::<rgb_order::Rgb>()
+    ///     .with_writer(/* Your writer */);
+    /// ```
+    pub fn with_rgb_order<NewOrder: RgbOrder>(self) -> ClockedDriver<Apa102<NewOrder>, Writer> {
+        self.map_led()
     }
 }
 
@@ -50,13 +95,17 @@ impl Apa102 {
 ///
 /// 1. Start frame: 32 bits of zeros
 /// 2. LED frames: Each LED gets 32 bits (8-bit brightness, 8-bit blue, 8-bit green, 8-bit red)
-/// 3. End frame: (n/2) bits of zeros where n is the number of LEDs
+/// 3. End frame: at least (n/2) bits of zeros where n is the number of LEDs, rounded up to
+///    whole bytes
 ///
 /// (References: [Hackaday](https://hackaday.com/2014/12/09/digging-into-the-apa102-serial-led-protocol/), [Pololu](https://www.pololu.com/product/2554))
-impl ClockedLed for Apa102 {
+impl<Order: RgbOrder> ClockedLed for Apa102<Order> {
     type Word = u8;
     type Color = LinearSrgb;
 
+    /// Fastest clock rate (~24 MHz, as in FastLED)
+    const MAX_DATA_RATE: Option<Megahertz> = Some(Megahertz::MHz(24));
+
     fn start() -> impl IntoIterator<Item = Self::Word> {
         [0x00, 0x00, 0x00, 0x00]
     }
@@ -69,9 +118,7 @@ impl ClockedLed for Apa102 {
         let (red, green, blue) = (linear_rgb.red, linear_rgb.green, linear_rgb.blue);
 
         // Color correct
-        let red = red * correction.red;
-        let green = green * correction.green;
-        let blue = blue * correction.blue;
+        let (red, green, blue) = correction.apply(red, green, blue);
 
         // Convert color components to u16's
         let (red_u16, green_u16, blue_u16) = (
@@ -86,14 +133,13 @@ impl ClockedLed for Apa102 {
             five_bit_bitshift(red_u16, green_u16, blue_u16, brightness);
 
         let brightness_byte = 0b11100000 | (brightness & 0b00011111);
-        let led_bytes = RgbChannels::BGR.reorder([red_u8, green_u8, blue_u8]);
+        let led_bytes = Order::RGB_CHANNELS.reorder([red_u8, green_u8, blue_u8]);
 
         [brightness_byte, led_bytes[0], led_bytes[1], led_bytes[2]]
     }
 
     fn end(pixel_count: usize) -> impl IntoIterator<Item = Self::Word> {
-        let num_bytes = (pixel_count - 1).div_ceil(16);
-        repeat_n(0u8, num_bytes)
+        repeat_n(0u8, Apa102::end_frame_size(pixel_count))
     }
 }
 
@@ -253,3 +299,75 @@ fn map16_to_8(x: u16) -> u8 {
 fn max3(a: u16, b: u16, c: u16) -> u16 {
     a.max(b).max(c)
 }
+
+#[cfg(test)]
+mod tests {
+    use core::convert::Infallible;
+
+    use super::*;
+    use crate::{
+        color::rgb_order::Rgb,
+        driver::{ClockedWriter, Driver},
+    };
+
+    fn end_len(pixel_count: usize) -> usize {
+        Apa102::<Bgr>::end(pixel_count).into_iter().count()
+    }
+
+    #[test]
+    fn test_end_frame_length() {
+        assert_eq!(end_len(0), 0);
+        assert_eq!(end_len(1), 1);
+        // 17 LEDs need 9 bits, which is more than one byte.
+        assert_eq!(end_len(17), 2);
+        assert_eq!(end_len(64), 4);
+        assert_eq!(end_len(300), 19);
+        assert_eq!(Apa102::frame_buffer_size(300), 4 + 300 * 4 + 19);
+    }
+
+    #[test]
+    fn test_rgb_order() {
+        let red = LinearSrgb::new(1., 0., 0.);
+        let correction = ColorCorrection::default();
+        let bgr: [u8; 4] = collect(Apa102::<Bgr>::led(red, 1., correction));
+        let rgb: [u8; 4] = collect(Apa102::<Rgb>::led(red, 1., correction));
+        assert_eq!(bgr[1..], [0, 0, 0xff]);
+        assert_eq!(rgb[1..], [0xff, 0, 0]);
+    }
+
+    #[test]
+    fn test_driver_with_rgb_order() {
+        struct NullWriter;
+
+        impl ClockedWriter<u8> for NullWriter {
+            type Error = Infallible;
+
+            fn write<Words>(&mut self, _words: Words) -> Result<(), Self::Error>
+            where
+                Words: AsRef<[u8]>,
+            {
+                Ok(())
+            }
+        }
+
+        let mut driver = ClockedDriver::default()
+            .with_led::<Apa102>()
+            .with_rgb_order::<Rgb>()
+            .with_writer(NullWriter);
+        let frame = Driver::encode::<1, { Apa102::frame_buffer_size(1) }, _, _>(
+            &mut driver,
+            [LinearSrgb::new(1., 0., 0.)],
+            1.,
+            ColorCorrection::default(),
+        );
+        assert_eq!(frame[5..8], [0xff, 0, 0]);
+    }
+
+    fn collect(words: impl IntoIterator<Item = u8>) -> [u8; 4] {
+        let mut bytes = [0; 4];
+        for (byte, word) in bytes.iter_mut().zip(words) {
+            *byte = word;
+        }
+        bytes
+    }
+}
@@ -0,0 +1,129 @@
+use core::{iter::repeat_n, marker::PhantomData};
+
+use crate::{
+    color::{
+        rgb_order::{Grb, RgbOrder},
+        ColorCorrection, LinearSrgb,
+    },
+    driver::clocked::ClockedLed,
+    time::Megahertz,
+    util::component::Component,
+};
+
+/// # LPD8806 LEDs
+///
+/// This type describes the LPD8806 LEDs, an older chipset common in early addressable
+/// strips.
+///
+/// ## Driver
+///
+/// - [`ClockedDriver`](crate::driver::ClockedDriver)
+///
+/// ## Key Features
+///
+/// - Two-wire [clocked protocol](crate::driver::clocked) (data and clock)
+/// - 21-bit color (7 bits per channel)
+/// - Supports up to 20 MHz clock rate
+///
+/// # Type Parameters
+///
+/// - `Order` - The [RGB channel order](crate::color::rgb_order), GRB by default.
+#[derive(Debug)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct Lpd8806<Order = Grb>(PhantomData<Order>);
+
+impl Lpd8806 {
+    /// A compile-time function to get a `FRAME_BUFFER_SIZE`, given a `PIXEL_COUNT`.
+    ///
+    /// ```rust,ignore
+    /// layout1d!(Layout, 60);
+    ///
+    /// let mut control = ControlBuilder::new_1d()
+    ///   // ...
+    ///   .with_frame_buffer_size::<{ Lpd8806::frame_buffer_size(Layout::PIXEL_COUNT) }>()
+    ///   .build();
+    /// ```
+    pub const fn frame_buffer_size(pixel_count: usize) -> usize {
+        pixel_count * 3 + Self::latch_size(pixel_count)
+    }
+
+    /// Returns the length in bytes of the latch (end frame).
+    ///
+    /// Each zero byte resets the data of 32 LEDs, so the latch needs one byte for every 32
+    /// LEDs, rounded up.
+    const fn latch_size(pixel_count: usize) -> usize {
+        pixel_count.div_ceil(32)
+    }
+}
+
+/// ## Protocol Details
+///
+/// The LPD8806 protocol consists of:
+///
+/// 1. LED frames: Each LED gets 3 bytes (green, red, blue), each with the high bit set
+///    and the 7-bit channel value in the low bits
+/// 2. Latch: One zero byte per 32 LEDs, rounded up
+///
+/// (Reference: [Adafruit](https://github.com/adafruit/LPD8806))
+impl<Order: RgbOrder> ClockedLed for Lpd8806<Order> {
+    type Word = u8;
+    type Color = LinearSrgb;
+
+    /// Fastest clock rate (20 MHz)
+    const MAX_DATA_RATE: Option<Megahertz> = Some(Megahertz::MHz(20));
+
+    fn start() -> impl IntoIterator<Item = Self::Word> {
+        []
+    }
+
+    fn led(
+        linear_rgb: LinearSrgb,
+        brightness: f32,
+        correction: ColorCorrection,
+    ) -> impl IntoIterator<Item = Self::Word> {
+        let (red, green, blue) =
+            correction.apply(linear_rgb.red, linear_rgb.green, linear_rgb.blue);
+
+        let to_byte = |value: f32| {
+            let value: u8 = Component::from_normalized_f32(value * brightness);
+            0b1000_0000 | (value >> 1)
+        };
+
+        Order::RGB_CHANNELS.reorder([to_byte(red), to_byte(green), to_byte(blue)])
+    }
+
+    fn end(pixel_count: usize) -> impl IntoIterator<Item = Self::Word> {
+        repeat_n(0u8, Lpd8806::latch_size(pixel_count))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use heapless::Vec;
+
+    use super::*;
+
+    fn end_len(pixel_count: usize) -> usize {
+        Lpd8806::<Grb>::end(pixel_count).into_iter().count()
+    }
+
+    #[test]
+    fn test_latch_length() {
+        assert_eq!(end_len(0), 0);
+        assert_eq!(end_len(1), 1);
+        assert_eq!(end_len(32), 1);
+        assert_eq!(end_len(33), 2);
+        assert_eq!(Lpd8806::frame_buffer_size(1), 3 + 1);
+        assert_eq!(Lpd8806::frame_buffer_size(32), 96 + 1);
+        assert_eq!(Lpd8806::frame_buffer_size(33), 99 + 2);
+    }
+
+    #[test]
+    fn test_led() {
+        let red = LinearSrgb::new(1., 0., 0.);
+        let bytes: Vec<u8, 3> = Lpd8806::<Grb>::led(red, 1., ColorCorrection::default())
+            .into_iter()
+            .collect();
+        assert_eq!(bytes, [0x80, 0xff, 0x80]);
+    }
+}
@@ -1,17 +1,32 @@
 //! # LED Chipsets
 //!
 //! - [`Apa102`]: APA102 (DotStar) LEDs
+//! - [`Lpd8806`]: LPD8806 LEDs
+//! - [`P9813`]: P9813 (Grove Chainable RGB) LEDs
+//! - [`Ws2801`]: WS2801 LEDs
 //! - [`Ws2812`]: WS2812 (NeoPixel) LEDs
 //! - [`Sk6812`]: SK6812 LEDs
+//! - [`Tm1814`]: TM1814 LEDs
+//! - [`Ucs1903`]: UCS1903 LEDs
 //!
 //! If you want help to support a new chipset, [make an issue](https://github.com/ahdinosaur/blinksy/issues)!
 
 mod apa102;
+mod lpd8806;
+mod p9813;
 mod sk6812;
+mod tm1814;
+mod ucs1903;
+mod ws2801;
 mod ws2812;
 
 pub use apa102::Apa102;
+pub use lpd8806::Lpd8806;
+pub use p9813::P9813;
 pub use sk6812::Sk6812;
+pub use tm1814::{Tm1814, Tm1814Current};
+pub use ucs1903::Ucs1903;
+pub use ws2801::Ws2801;
 pub use ws2812::Ws2812;
 
 use crate::driver::ClocklessLed;
@@ -0,0 +1,119 @@
+use crate::{
+    color::{ColorCorrection, LinearSrgb},
+    driver::clocked::ClockedLed,
+    time::Megahertz,
+    util::component::Component,
+};
+
+/// # P9813 LEDs
+///
+/// This type describes the P9813 LED driver, as used in Seeed's Grove Chainable RGB LEDs.
+///
+/// ## Driver
+///
+/// - [`ClockedDriver`](crate::driver::ClockedDriver)
+///
+/// ## Key Features
+///
+/// - Two-wire [clocked protocol](crate::driver::clocked) (data and clock)
+/// - 24-bit color (8 bits per channel)
+/// - A checksum (flag) byte per LED
+#[derive(Debug)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct P9813;
+
+impl P9813 {
+    /// A compile-time function to get a `FRAME_BUFFER_SIZE`, given a `PIXEL_COUNT`.
+    ///
+    /// ```rust,ignore
+    /// layout1d!(Layout, 10);
+    ///
+    /// let mut control = ControlBuilder::new_1d()
+    ///   // ...
+    ///   .with_frame_buffer_size::<{ P9813::frame_buffer_size(Layout::PIXEL_COUNT) }>()
+    ///   .build();
+    /// ```
+    pub const fn frame_buffer_size(pixel_count: usize) -> usize {
+        4 + pixel_count * 4 + 4
+    }
+}
+
+/// ## Protocol Details
+///
+/// The P9813 protocol consists of:
+///
+/// 1. Start frame: 32 bits of zeros
+/// 2. LED frames: Each LED gets 32 bits (8-bit flag, 8-bit blue, 8-bit green, 8-bit red)
+/// 3. End frame: 32 bits of zeros
+///
+/// The flag byte starts with two 1 bits, followed by the inverted two highest bits of
+/// blue, green, and red:
+///
+/// ```text
+/// 1 1 !B7 !B6 !G7 !G6 !R7 !R6
+/// ```
+///
+/// (Reference: [Datasheet](https://files.seeedstudio.com/wiki/Grove-Chainable_RGB_LED/res/P9813_datasheet.pdf))
+impl ClockedLed for P9813 {
+    type Word = u8;
+    type Color = LinearSrgb;
+
+    /// Fastest clock rate (~15 MHz)
+    const MAX_DATA_RATE: Option<Megahertz> = Some(Megahertz::MHz(15));
+
+    fn start() -> impl IntoIterator<Item = Self::Word> {
+        [0x00, 0x00, 0x00, 0x00]
+    }
+
+    fn led(
+        linear_rgb: LinearSrgb,
+        brightness: f32,
+        correction: ColorCorrection,
+    ) -> impl IntoIterator<Item = Self::Word> {
+        let (red, green, blue) =
+            correction.apply(linear_rgb.red, linear_rgb.green, linear_rgb.blue);
+
+        let to_byte = |value: f32| -> u8 { Component::from_normalized_f32(value * brightness) };
+        let (red, green, blue) = (to_byte(red), to_byte(green), to_byte(blue));
+
+        [flag(red, green, blue), blue, green, red]
+    }
+
+    fn end(_pixel_count: usize) -> impl IntoIterator<Item = Self::Word> {
+        [0x00, 0x00, 0x00, 0x00]
+    }
+}
+
+/// Returns the flag (checksum) byte for a color.
+fn flag(red: u8, green: u8, blue: u8) -> u8 {
+    let inverted_high_bits = |value: u8| (!value >> 6) & 0b11;
+    0b1100_0000
+        | (inverted_high_bits(blue) << 4)
+        | (inverted_high_bits(green) << 2)
+        | inverted_high_bits(red)
+}
+
+#[cfg(test)]
+mod tests {
+    use heapless::Vec;
+
+    use super::*;
+
+    #[test]
+    fn test_flag() {
+        // Black, white, red, and a mix of high bits
+        assert_eq!(flag(0x00, 0x00, 0x00), 0xFF);
+        assert_eq!(flag(0xFF, 0xFF, 0xFF), 0xC0);
+        assert_eq!(flag(0xFF, 0x00, 0x00), 0xFC);
+        assert_eq!(flag(0x40, 0x80, 0xC0), 0xC6);
+    }
+
+    #[test]
+    fn test_led() {
+        let red = LinearSrgb::new(1., 0., 0.);
+        let bytes: Vec<u8, 4> = P9813::led(red, 1., ColorCorrection::default())
+            .into_iter()
+            .collect();
+        assert_eq!(bytes, [0xFC, 0x00, 0x00, 0xFF]);
+    }
+}
@@ -14,6 +14,8 @@ use crate::{color::LedChannels, driver::ClocklessLed};
 ///
 /// - Single-wire [clockless protocol](crate::driver::clockless) (data only, no clock)
 /// - 32-bit color (8 bits per channel, 4 channels)
+#[derive(Debug)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
 pub struct Sk6812;
 
 impl Sk6812 {
@@ -0,0 +1,241 @@
+use fugit::NanosDurationU32 as Nanoseconds;
+use heapless::Vec;
+
+use crate::{
+    color::{
+        ColorCorrection, FromColor, GammaLut256, LedChannels, LedColor, LinearSrgb, RgbwChannels,
+    },
+    driver::{clockless::encode_word, ClocklessLed, Dither, QuantizeMode},
+};
+
+/// # TM1814 LEDs
+///
+/// This type describes the TM1814 LEDs, constant-current RGBW LEDs used on high-power strips.
+///
+/// # Driver
+///
+/// - [`ClocklessDriver`](crate::driver::ClocklessDriver)
+///
+/// ## Key Features
+///
+/// - Single-wire [clockless protocol](crate::driver::clockless) (data only, no clock)
+/// - 32-bit color (8 bits per channel, 4 channels)
+/// - Constant current output, set per channel at the start of each frame
+///
+/// ## Current
+///
+/// The output current of each channel is set by `CURRENT`, the bits of a [`Tm1814Current`],
+/// which is sent before the pixel data of each frame:
+///
+/// ```rust,ignore
+/// use blinksy::{
+///     driver::ClocklessDriver,
+///     leds::{Tm1814, Tm1814Current},
+/// };
+///
+/// type Led = Tm1814<{ Tm1814Current::new(32, 32, 32, 63).into_bits() }>;
+///
+/// let driver = ClocklessDriver::default()
+///     .with_led::<Led>()
+///     .with_writer(/* Your writer */);
+/// ```
+///
+/// ## Inverted Signal
+///
+/// The TM1814 data line idles high, so the signal is inverted compared to [`super::Ws2812`].
+/// Invert the output of the writer, such as with an inverting level shifter.
+#[derive(Debug)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct Tm1814<const CURRENT: u32 = { Tm1814Current::DEFAULT.into_bits() }>;
+
+impl<const CURRENT: u32> Tm1814<CURRENT> {
+    /// The number of words in the current configuration sent before the pixel data.
+    pub const PREAMBLE_SIZE: usize = 8;
+
+    /// A compile-time function to get a `FRAME_BUFFER_SIZE`, given a `PIXEL_COUNT`.
+    ///
+    /// Includes the current configuration sent before the pixel data.
+    ///
+    /// ```rust,ignore
+    /// layout1d!(Layout, 60);
+    ///
+    /// let mut control = ControlBuilder::new_1d()
+    ///   // ...
+    ///   .with_frame_buffer_size::<{ Tm1814::frame_buffer_size(Layout::PIXEL_COUNT) }>()
+    ///   .build();
+    /// ```
+    pub const fn frame_buffer_size(pixel_count: usize) -> usize {
+        Self::PREAMBLE_SIZE + super::clockless_frame_buffer_size::<Self>(pixel_count)
+    }
+
+    /// Returns the current configuration sent before the pixel data: the current setting of
+    /// each channel (white, red, green, blue), then the bitwise complement of each.
+    pub const fn preamble() -> [u8; 8] {
+        let [white, red, green, blue] = Tm1814Current::from_bits(CURRENT).to_words();
+        [white, red, green, blue, !white, !red, !green, !blue]
+    }
+}
+
+/// The output current of each channel of [`Tm1814`] LEDs.
+///
+/// Each setting is from 0 to 63, for a current of 6.5 mA + 0.5 mA × setting: from 6.5 mA to
+/// 38 mA.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct Tm1814Current {
+    /// Red channel current setting
+    pub red: u8,
+    /// Green channel current setting
+    pub green: u8,
+    /// Blue channel current setting
+    pub blue: u8,
+    /// White channel current setting
+    pub white: u8,
+}
+
+impl Tm1814Current {
+    /// The maximum current setting, for 38 mA.
+    pub const MAX: u8 = 63;
+
+    /// The default current of each channel, 22.5 mA.
+    pub const DEFAULT: Self = Self::new(32, 32, 32, 32);
+
+    /// Creates a current configuration, clamping each setting to [`Tm1814Current::MAX`].
+    ///
+    /// # Arguments
+    ///
+    /// - `red` - Red channel current setting (0 to 63)
+    /// - `green` - Green channel current setting (0 to 63)
+    /// - `blue` - Blue channel current setting (0 to 63)
+    /// - `white` - White channel current setting (0 to 63)
+    pub const fn new(red: u8, green: u8, blue: u8, white: u8) -> Self {
+        const fn clamp(setting: u8) -> u8 {
+            if setting > Tm1814Current::MAX {
+                Tm1814Current::MAX
+            } else {
+                setting
+            }
+        }
+        Self {
+            red: clamp(red),
+            green: clamp(green),
+            blue: clamp(blue),
+            white: clamp(white),
+        }
+    }
+
+    /// Packs the configuration into bits, for the `CURRENT` of [`Tm1814`].
+    pub const fn into_bits(self) -> u32 {
+        u32::from_be_bytes(self.to_words())
+    }
+
+    /// Unpacks the configuration from bits, as from [`Tm1814Current::into_bits`].
+    pub const fn from_bits(bits: u32) -> Self {
+        let [white, red, green, blue] = bits.to_be_bytes();
+        Self::new(red, green, blue, white)
+    }
+
+    /// Returns the current settings in protocol order: white, red, green, blue.
+    const fn to_words(self) -> [u8; 4] {
+        [self.white, self.red, self.green, self.blue]
+    }
+}
+
+impl Default for Tm1814Current {
+    fn default() -> Self {
+        Self::DEFAULT
+    }
+}
+
+/// ## Protocol Details
+///
+/// The TM1814 protocol uses precise timing of pulses on a single (inverted) data line:
+///
+/// - A '0' bit is represented by a short pulse (~360ns) followed by a long pause (~890ns)
+/// - A '1' bit is represented by a long pulse (~720ns) followed by a short pause (~530ns)
+/// - After sending all bits, a reset of at least 200µs is required
+///
+/// (References: TM1814 datasheet, from Titan Micro Electronics)
+///
+/// Each frame begins with the current configuration (see [`Tm1814::preamble`]), then each LED
+/// receives 32 bits (WRGB) and passes subsequent data to the next LED in the chain.
+impl<const CURRENT: u32> ClocklessLed for Tm1814<CURRENT> {
+    type Word = u8;
+
+    /// Duration of pulse for '0' bit (~360ns)
+    const T_0H: Nanoseconds = Nanoseconds::nanos(360);
+
+    /// Duration of pause for '0' bit (~890ns)
+    const T_0L: Nanoseconds = Nanoseconds::nanos(890);
+
+    /// Duration of pulse for '1' bit (~720ns)
+    const T_1H: Nanoseconds = Nanoseconds::nanos(720);
+
+    /// Duration of pause for '1' bit (~530ns)
+    const T_1L: Nanoseconds = Nanoseconds::nanos(530);
+
+    /// Reset period (>200µs) - signals the end of a data stream
+    const T_RESET: Nanoseconds = Nanoseconds::micros(200);
+
+    /// LED channel specification - TM1814 uses WRGB ordering
+    const LED_CHANNELS: LedChannels = LedChannels::Rgbw(RgbwChannels::WRGB);
+
+    /// Encodes the current configuration, then the pixels.
+    fn encode_with_channels<const PIXEL_COUNT: usize, const BUFFER_SIZE: usize, I, C, D>(
+        pixels: I,
+        channels: LedChannels,
+        brightness: f32,
+        correction: ColorCorrection,
+        dither: &mut D,
+        quantize_mode: QuantizeMode,
+        gamma_lut: Option<&GammaLut256>,
+    ) -> Vec<Self::Word, BUFFER_SIZE>
+    where
+        I: IntoIterator<Item = C>,
+        LinearSrgb: FromColor<C>,
+        D: Dither,
+    {
+        let pixels = pixels
+            .into_iter()
+            .flat_map(|pixel| {
+                let linear_srgb = LinearSrgb::from_color(pixel);
+                let data: LedColor<f32> = linear_srgb.to_led(channels, brightness, correction);
+                data.into_iter()
+            })
+            .enumerate()
+            .map(|(index, value)| encode_word(dither, quantize_mode, gamma_lut, index, value));
+        Vec::from_iter(Self::preamble().into_iter().chain(pixels))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::color::Srgb;
+
+    #[test]
+    fn test_current_preamble_before_pixel_data() {
+        type Led = Tm1814<{ Tm1814Current::new(10, 20, 30, 40).into_bits() }>;
+
+        let frame: Vec<u8, { Led::frame_buffer_size(1) }> =
+            Led::encode_with_dither::<1, { Led::frame_buffer_size(1) }, _, _, _>(
+                [Srgb::new(1., 0., 0.)],
+                1.,
+                ColorCorrection::default(),
+                &mut (),
+                QuantizeMode::Round,
+            );
+
+        assert_eq!(
+            frame.as_slice(),
+            [40, 10, 20, 30, !40, !10, !20, !30, 0, 255, 0, 0]
+        );
+    }
+
+    #[test]
+    fn test_current_settings_are_clamped() {
+        let current = Tm1814Current::new(64, 255, 0, 63);
+        assert_eq!(current, Tm1814Current::new(63, 63, 0, 63));
+        assert_eq!(Tm1814Current::from_bits(current.into_bits()), current);
+    }
+}
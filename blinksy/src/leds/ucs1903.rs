@@ -0,0 +1,102 @@
+use fugit::NanosDurationU32 as Nanoseconds;
+
+use crate::{
+    color::{LedChannels, RgbChannels},
+    driver::ClocklessLed,
+};
+
+/// # UCS1903 LEDs
+///
+/// This type describes the UCS1903 LEDs, an older WS2811-like chipset still sold widely.
+///
+/// # Driver
+///
+/// - [`ClocklessDriver`](crate::driver::ClocklessDriver)
+///
+/// ## Key Features
+///
+/// - Single-wire [clockless protocol](crate::driver::clockless) (data only, no clock)
+/// - 24-bit color (8 bits per channel, 3 channels)
+/// - Slow data rate (400 kHz): 60μs per pixel
+///
+/// ## Refresh Rate
+///
+/// At half the data rate of [`super::Ws2812`], the refresh rate of a strip is half as fast:
+/// 15 ms per frame for 250 LEDs, so at most about 65 frames per second.
+#[derive(Debug)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct Ucs1903;
+
+impl Ucs1903 {
+    /// A compile-time function to get a `FRAME_BUFFER_SIZE`, given a `PIXEL_COUNT`.
+    ///
+    /// ```rust,ignore
+    /// layout1d!(Layout, 60);
+    ///
+    /// let mut control = ControlBuilder::new_1d()
+    ///   // ...
+    ///   .with_frame_buffer_size::<{ Ucs1903::frame_buffer_size(Layout::PIXEL_COUNT) }>()
+    ///   .build();
+    /// ```
+    pub const fn frame_buffer_size(pixel_count: usize) -> usize {
+        super::clockless_frame_buffer_size::<Self>(pixel_count)
+    }
+}
+
+/// ## Protocol Details
+///
+/// The UCS1903 protocol uses precise timing of pulses on a single data line:
+///
+/// - A '0' bit is represented by a short high pulse (~500ns) followed by a long low pulse (~2000ns)
+/// - A '1' bit is represented by a long high pulse (~2000ns) followed by a short low pulse (~500ns)
+/// - After sending all bits, a reset pulse of at least 24µs is required
+///
+/// (References: UCS1903 datasheet, from UCS Semiconductor)
+///
+/// Each LED receives 24 bits (RGB) and then passes subsequent data to the next LED in the chain.
+impl ClocklessLed for Ucs1903 {
+    type Word = u8;
+
+    /// Duration of high signal for '0' bit (~500ns)
+    const T_0H: Nanoseconds = Nanoseconds::nanos(500);
+
+    /// Duration of low signal for '0' bit (~2000ns)
+    const T_0L: Nanoseconds = Nanoseconds::nanos(2000);
+
+    /// Duration of high signal for '1' bit (~2000ns)
+    const T_1H: Nanoseconds = Nanoseconds::nanos(2000);
+
+    /// Duration of low signal for '1' bit (~500ns)
+    const T_1L: Nanoseconds = Nanoseconds::nanos(500);
+
+    /// Reset period (>24µs) - signals the end of a data stream
+    const T_RESET: Nanoseconds = Nanoseconds::micros(24);
+
+    /// LED channel specification - UCS1903 uses RGB ordering
+    const LED_CHANNELS: LedChannels = LedChannels::Rgb(RgbChannels::RGB);
+}
+
+#[cfg(test)]
+mod tests {
+    use heapless::Vec;
+
+    use super::*;
+    use crate::color::{ColorCorrection, Srgb};
+
+    #[test]
+    fn test_timings_match_datasheet() {
+        // 400 kHz data rate
+        assert_eq!(Ucs1903::t_cycle(), Nanoseconds::nanos(2500));
+        assert_eq!(Ucs1903::T_RESET, Nanoseconds::micros(24));
+    }
+
+    #[test]
+    fn test_encode_rgb_order() {
+        let frame: Vec<u8, 6> = Ucs1903::encode::<2, 6, _, _>(
+            [Srgb::new(1., 0., 0.), Srgb::new(0., 0., 1.)],
+            1.,
+            ColorCorrection::default(),
+        );
+        assert_eq!(frame.as_slice(), [255, 0, 0, 0, 0, 255]);
+    }
+}
@@ -0,0 +1,117 @@
+use core::marker::PhantomData;
+
+use crate::{
+    color::{
+        rgb_order::{Rgb, RgbOrder},
+        ColorCorrection, LinearSrgb,
+    },
+    driver::clocked::ClockedLed,
+    time::Megahertz,
+    util::component::Component,
+};
+
+/// # WS2801 LEDs
+///
+/// This type describes the WS2801 LEDs, a simple chipset still common in older
+/// installations.
+///
+/// ## Driver
+///
+/// - [`ClockedDriver`](crate::driver::ClockedDriver)
+///
+/// ## Key Features
+///
+/// - Two-wire [clocked protocol](crate::driver::clocked) (data and clock)
+/// - 24-bit color (8 bits per channel)
+/// - Supports up to ~1 MHz clock rate in practice (25 MHz per the datasheet, but long
+///   wires between pixels don't keep up)
+///
+/// # Type Parameters
+///
+/// - `Order` - The [RGB channel order](crate::color::rgb_order), RGB by default.
+#[derive(Debug)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct Ws2801<Order = Rgb>(PhantomData<Order>);
+
+impl Ws2801 {
+    /// A compile-time function to get a `FRAME_BUFFER_SIZE`, given a `PIXEL_COUNT`.
+    ///
+    /// ```rust,ignore
+    /// layout1d!(Layout, 60);
+    ///
+    /// let mut control = ControlBuilder::new_1d()
+    ///   // ...
+    ///   .with_frame_buffer_size::<{ Ws2801::frame_buffer_size(Layout::PIXEL_COUNT) }>()
+    ///   .build();
+    /// ```
+    pub const fn frame_buffer_size(pixel_count: usize) -> usize {
+        pixel_count * 3
+    }
+}
+
+/// ## Protocol Details
+///
+/// The WS2801 protocol consists of:
+///
+/// 1. LED frames: Each LED gets 3 bytes (8-bit red, 8-bit green, 8-bit blue)
+///
+/// There are no start or end frames. Instead, the LEDs latch when the clock is held low
+/// for 500μs, which happens between frames.
+///
+/// (Reference: [Datasheet](https://cdn-shop.adafruit.com/datasheets/WS2801.pdf))
+impl<Order: RgbOrder> ClockedLed for Ws2801<Order> {
+    type Word = u8;
+    type Color = LinearSrgb;
+
+    /// Fastest clock rate (25 MHz per the datasheet, though long wires may need slower)
+    const MAX_DATA_RATE: Option<Megahertz> = Some(Megahertz::MHz(25));
+
+    fn start() -> impl IntoIterator<Item = Self::Word> {
+        []
+    }
+
+    fn led(
+        linear_rgb: LinearSrgb,
+        brightness: f32,
+        correction: ColorCorrection,
+    ) -> impl IntoIterator<Item = Self::Word> {
+        let (red, green, blue) =
+            correction.apply(linear_rgb.red, linear_rgb.green, linear_rgb.blue);
+
+        let to_byte = |value: f32| -> u8 { Component::from_normalized_f32(value * brightness) };
+
+        Order::RGB_CHANNELS.reorder([to_byte(red), to_byte(green), to_byte(blue)])
+    }
+
+    fn end(_pixel_count: usize) -> impl IntoIterator<Item = Self::Word> {
+        []
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use heapless::Vec;
+
+    use super::*;
+    use crate::color::rgb_order::Bgr;
+
+    fn encode<Order: RgbOrder>(color: LinearSrgb, brightness: f32) -> Vec<u8, 8> {
+        Ws2801::<Order>::update([color, color], brightness, ColorCorrection::default(), 2)
+            .into_iter()
+            .collect()
+    }
+
+    #[test]
+    fn test_encode() {
+        let orange = LinearSrgb::new(1., 0.5, 0.);
+        assert_eq!(
+            encode::<Rgb>(orange, 1.),
+            [0xff, 0x80, 0x00, 0xff, 0x80, 0x00]
+        );
+        assert_eq!(
+            encode::<Bgr>(orange, 1.),
+            [0x00, 0x80, 0xff, 0x00, 0x80, 0xff]
+        );
+        assert_eq!(encode::<Rgb>(orange, 0.), [0x00; 6]);
+    }
+}
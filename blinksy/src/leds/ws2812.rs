@@ -19,6 +19,13 @@ use crate::{
 /// - Single-wire [clockless protocol](crate::driver::clockless) (data only, no clock)
 /// - 24-bit color (8 bits per channel, 3 channels)
 /// - Fixed update rate: 30μs per pixel
+///
+/// ## Color Order
+///
+/// The datasheet order is GRB. For clones with another order, such as RGB or BGR, use
+/// [`ClocklessDriver::with_channels`](crate::driver::ClocklessDriver::with_channels).
+#[derive(Debug)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
 pub struct Ws2812;
 
 impl Ws2812 {
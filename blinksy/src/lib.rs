@@ -21,6 +21,12 @@
 //! - **Global settings**: Control overall brightness and color correction.
 //! - **Desktop simulation**: Simulate your LEDs on your desktop to play with ideas.
 //! - **RGB+W support**: Supports RGB + White color channels
+//! - **Serde support**: Load layouts, pattern params, and color correction from config (with
+//!   the `serde` feature).
+//! - **Defmt support**: Log errors, colors, and config on embedded targets (with the `defmt`
+//!   feature).
+//! - **Fixed-point colors**: Color types stored and converted with integer math (with the
+//!   `fixed-point` feature).
 //!
 //! ### LED Support
 //!
@@ -28,26 +34,81 @@
 //!
 //! - **[WS2812B]**: Affordable RGB LED, aka NeoPixel
 //! - **[SK6812]**: RGBW LED
+//! - **[TM1814]**: Constant-current RGBW LED
+//! - **[UCS1903]**: Slower (400 kHz) RGB LED
 //!
 //! #### [Clocked](crate::driver::clocked): Two-wire (data and clock)
 //!
 //! - **[APA102]**: High-FPS RGB LED, aka DotStar
+//! - **[LPD8806]**: Older 7-bit RGB LED
+//! - **[P9813]**: RGB LED driver, aka Grove Chainable RGB LED
+//! - **[WS2801]**: Older RGB LED, without frames
+//!
+//! #### [I2C co-processor](crate::driver::seesaw)
+//!
+//! - **[SeeSaw NeoPixel]**: WS2812B through an Adafruit SeeSaw, aka NeoDriver
 //!
 //! If you want help to support a new LED chipset, [make an issue](https://github.com/ahdinosaur/blinksy/issues)!
 //!
 //! [WS2812B]: leds::Ws2812
 //! [SK6812]: leds::Sk6812
+//! [TM1814]: leds::Tm1814
+//! [UCS1903]: leds::Ucs1903
 //! [APA102]: leds::Apa102
+//! [LPD8806]: leds::Lpd8806
+//! [P9813]: leds::P9813
+//! [WS2801]: leds::Ws2801
+//! [SeeSaw NeoPixel]: driver::SeeSawNeoPixelDriver
 //!
 //! ### Pattern (Effect) Library:
 //!
 //! - **[Rainbow]**: A basic scrolling rainbow
 //! - **[Noise]**: A flow through random noise functions
+//! - **[Radar]**: A sweep rotating around the center of a 2D layout
+//! - **[Spectrum]**: Audio frequency bars across a 2D grid
+//! - **[ScrollingText]**: A message scrolling across a 2D grid
+//! - **[TouchZones]**: Discrete input zones that light up and fade
+//! - **[VuMeter]**: An audio level meter with a decaying peak dot
+//! - **[ColorWipe]**: A color filling the LEDs one at a time
+//! - **[TheaterChase]**: Every Nth LED lit and chasing along, like a marquee
+//! - **[Meteor]**: A meteor streaking across the LEDs, with a sparkling trail
+//! - **[Strobe]**: Every LED flashing on and off, like a strobe light
+//! - **[MatrixRain]**: Digital rain falling down the columns of a 2D grid
+//! - **[Starfield]**: Stars flying toward the viewer through a 3D layout
+//! - **[Gradient]**: A gradient of any number of color stops, optionally scrolling
+//! - **[Wave]**: A sine wave of brightness moving along a strip
+//! - **[Juggle]**: Colored dots swinging back and forth at different speeds
+//! - **[Shader]**: Any closure of each LED's position and the time, for quick experiments
+//! - **[LavaLamp]**: Slow, organic blobs of warm color drifting upward
+//! - **[Raster]**: An external image, such as live video, shown on a 2D layout
+//! - **[Clock]**: The time of day as a progress bar, color-coded by the hour
+//! - **[Lightning]**: Random strikes of lightning flashing against a dim sky
+//! - **[Alternate]**: Segments taking turns to light up, like police lights
 //!
 //! If you want help to port a pattern from FastLED / WLED to Rust, [make an issue](https://github.com/ahdinosaur/blinksy/issues)!
 //!
 //! [Rainbow]: patterns::rainbow
 //! [Noise]: patterns::noise
+//! [Radar]: patterns::radar
+//! [Spectrum]: patterns::spectrum
+//! [ScrollingText]: patterns::text
+//! [TouchZones]: patterns::touch
+//! [VuMeter]: patterns::vu
+//! [ColorWipe]: patterns::wipe
+//! [TheaterChase]: patterns::theater
+//! [Meteor]: patterns::meteor
+//! [Strobe]: patterns::strobe
+//! [MatrixRain]: patterns::matrix_rain
+//! [Starfield]: patterns::starfield
+//! [Gradient]: patterns::gradient
+//! [Wave]: patterns::wave
+//! [Juggle]: patterns::juggle
+//! [Shader]: patterns::shader
+//! [LavaLamp]: patterns::lava
+//! [Raster]: patterns::raster
+//! [Clock]: patterns::clock
+//! [Lightning]: patterns::lightning
+//! [Alternate]: patterns::alternate
 //!
 //! ### Microcontroller Family Support
 //!
@@ -67,9 +128,9 @@
 //! |----------------|-------------|---------------|------------------------|---------------|
 //! | ALL            | [embedded-hal] | [blinksy]  | -                      | TODO [Spi #12][clockless-spi] |
 //! | ESP32          | [esp-hal]   | [blinksy-esp] | [Rmt][rmt]             | - |
-//! | RP (2040/2350) | [rp-hal]    | TODO          | TODO [#36][rp-issue]   | - |
+//! | RP2040         | [rp-hal]    | [blinksy-rp]  | [Pio][pio]             | - |
 //! | STM32          | [stm32-hal] | TODO          | TODO [#78][stm32-issue] | - |
-//! | nRF            | [nrf-hal]   | TODO          | TODO [#77][nrf-issue]  | - |
+//! | nRF52          | [nrf-hal]   | [blinksy-nrf] | [Pwm][pwm]             | - |
 //! | atsamd         | [atsamd]    | TODO          | TODO [#67][atsamd-issue] | - |
 //! | AVR (Arduino)  | [avr-hal]   | TODO          | TODO [#79][avr-issue]  | - |
 //! | CH32           | [ch32-hal]  | TODO          | TODO [#80][ch32-issue] | - |
@@ -80,11 +141,13 @@
 //! [blinksy-esp]: https://docs.rs/blinksy-esp/0.11/
 //! [rmt]: https://docs.espressif.com/projects/rust/esp-hal/latest/
 //! [rp-hal]: https://github.com/rp-rs/rp-hal/
-//! [rp-issue]: https://github.com/ahdinosaur/blinksy/issues/36
+//! [blinksy-rp]: https://docs.rs/blinksy-rp/0.11/
+//! [pio]: https://docs.rs/blinksy-rp/0.11/blinksy_rp/pio/
 //! [stm32-hal]: https://github.com/David-OConnor/stm32-hal
 //! [stm32-issue]: https://github.com/ahdinosaur/blinksy/issues/78
 //! [nrf-hal]: https://github.com/nrf-rs/nrf-hal
-//! [nrf-issue]: https://github.com/ahdinosaur/blinksy/issues/77
+//! [blinksy-nrf]: https://docs.rs/blinksy-nrf/0.11/
+//! [pwm]: https://docs.rs/blinksy-nrf/0.11/blinksy_nrf/pwm/
 //! [atsamd]: https://github.com/atsamd-rs/atsamd
 //! [atsamd-issue]: https://github.com/ahdinosaur/blinksy/issues/67
 //! [avr-hal]: https://github.com/Rahix/avr-hal
@@ -397,6 +460,7 @@
 //! ```
 //!
 
+pub mod audio;
 pub mod color;
 pub mod control;
 pub mod driver;
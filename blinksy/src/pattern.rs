@@ -7,24 +7,72 @@
 //!
 //! - The layout of the LEDs (through its type parameters)
 //! - Configuration parameters during initialization
-//! - The current time during each update cycle
+//! - The current time during each update cycle (and, if wanted, the frame number and the
+//!   time since the last frame, as a [`FrameTime`])
 //!
 //! And produces:
 //!
 //! - A sequence of colors for each LED in the layout
 //!
+//! For simple patterns, where the color of each LED depends only on its position and the
+//! time, the [`pattern!`](crate::pattern!) macro writes the [`Pattern`] impl for you.
+//!
 //! For the library of built-in patterns, see [patterns](crate::patterns).
 //!
 //! [a WLED effect]: https://kno.wled.ge/features/effects/
 
 use crate::layout::LayoutForDim;
 
+/// The time of a frame, as given to [`Pattern::tick_frame`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct FrameTime {
+    /// The current time in milliseconds
+    pub time_in_ms: u64,
+    /// The number of frames rendered before this frame, so 0 for the first frame
+    pub frame: u64,
+    /// The time since the last frame, in milliseconds, or 0 for the first frame
+    pub delta_in_ms: u64,
+}
+
+impl FrameTime {
+    /// Creates the time of a first frame: frame 0, with no time since the last frame.
+    ///
+    /// # Arguments
+    ///
+    /// - `time_in_ms` - The current time in milliseconds
+    pub fn first(time_in_ms: u64) -> Self {
+        Self {
+            time_in_ms,
+            frame: 0,
+            delta_in_ms: 0,
+        }
+    }
+}
+
 /// Trait for creating visual effects on LED layouts.
 ///
 /// Patterns generate colors for each LED in a layout based on time and position.
 /// They are generic over both the dimension they operate in and the specific
 /// layout type.
 ///
+/// To iterate over the LEDs, use the layout's `points()` for the point of each LED, or
+/// `positions()` for a [`Position`](crate::layout::Position) of each LED, with the index and
+/// normalized index as well as the point. A pattern for any dimension can use
+/// [`LayoutForDim::layout_positions`](crate::layout::LayoutForDim::layout_positions), and
+/// convert each position into the point of its dimension with [`From`].
+///
+/// ## Time or Frames
+///
+/// Prefer [`Pattern::tick`], which depends only on the current time: the colors are then
+/// the same at the same time, however often the pattern is ticked, so the pattern works
+/// the same at any frame rate, in a crossfade, or rendered out of order.
+///
+/// For effects which are easier per frame, such as decaying a little on every frame,
+/// override [`Pattern::tick_frame`], which also receives the frame number and the time
+/// since the last frame. [`Control`](crate::control::Control) always calls
+/// [`Pattern::tick_frame`], which by default calls [`Pattern::tick`].
+///
 /// # Type Parameters
 ///
 /// - `Dim` - The dimension marker (Dim1d, Dim2d, or Dim3d)
@@ -94,4 +142,285 @@ where
     ///
     /// An iterator yielding one color per LED in the layout
     fn tick(&self, time_in_ms: u64) -> impl Iterator<Item = Self::Color>;
+
+    /// Generates colors for all LEDs in the layout for a frame.
+    ///
+    /// By default, calls [`Pattern::tick`] with the current time. Override this for effects
+    /// which depend on the frame number or the time since the last frame.
+    ///
+    /// # Arguments
+    ///
+    /// - `frame` - The time of the frame
+    ///
+    /// # Returns
+    ///
+    /// An iterator yielding one color per LED in the layout
+    fn tick_frame(&self, frame: FrameTime) -> impl Iterator<Item = Self::Color> {
+        self.tick(frame.time_in_ms)
+    }
+}
+
+/// Define a pattern from a function of each LED's position and the time.
+///
+/// This macro creates a pattern struct holding its params, and implements [`Pattern`] for
+/// any layout of the dimension, by mapping each point of the layout to a color. The
+/// generated code doesn't allocate.
+///
+/// The position of each LED is:
+///
+/// - `Dim1d`: an `f32` from -1.0 to 1.0
+/// - `Dim2d`: a [`Vec2`](crate::layout::Vec2)
+/// - `Dim3d`: a [`Vec3`](crate::layout::Vec3)
+///
+/// Or, if the position is annotated as `position: Position`, a
+/// [`Position`](crate::layout::Position), with the index and normalized index of the LED
+/// as well as its point.
+///
+/// The pixel function receives a reference to the params, the position, and the time in
+/// milliseconds (`u64`).
+///
+/// # Arguments
+///
+/// - Optional attributes (e.g., doc comments, derives) and visibility
+/// - The name of the pattern, with its dimension marker: `Dim1d`, `Dim2d`, or `Dim3d`
+/// - `params`: The params type
+/// - `color`: The color type
+/// - `pixel`: A closure from params, position, and time to color
+///
+/// # Example
+///
+/// ```rust
+/// use blinksy::{
+///     color::Okhsv,
+///     layout::{Layout1d, Layout2d, Shape2d, Vec2},
+///     layout1d, layout2d,
+///     markers::{Dim1d, Dim2d},
+///     pattern,
+///     pattern::Pattern,
+/// };
+///
+/// #[derive(Debug, Clone)]
+/// pub struct GradientParams {
+///     pub start_hue: f32,
+///     pub end_hue: f32,
+/// }
+///
+/// pattern!(
+///     /// A gradient of hues along a strip.
+///     #[derive(Debug, Clone)]
+///     pub Gradient<Dim1d> {
+///         params: GradientParams,
+///         color: Okhsv,
+///         pixel: |params, x, _time_in_ms| {
+///             let progress = (x + 1.) / 2.;
+///             let hue = params.start_hue + (params.end_hue - params.start_hue) * progress;
+///             Okhsv::new(hue, 1., 1.)
+///         },
+///     }
+/// );
+///
+/// pattern!(
+///     /// A diagonal band of light sweeping across a grid.
+///     pub Sweep<Dim2d> {
+///         params: f32,
+///         color: Okhsv,
+///         pixel: |speed, point, time_in_ms| {
+///             let position = (point.x + point.y) / 4. + 0.5;
+///             let phase = (time_in_ms as f32 * speed).fract();
+///             let brightness = 1. - ((position - phase).abs() * 4.).min(1.);
+///             Okhsv::new(0.6, 1., brightness)
+///         },
+///     }
+/// );
+///
+/// pattern!(
+///     /// A dot chasing along the wiring of a grid, one LED at a time.
+///     pub Chase<Dim2d> {
+///         params: u64,
+///         color: Okhsv,
+///         pixel: |ms_per_led, position: Position, time_in_ms| {
+///             let index = (time_in_ms / ms_per_led) as usize % 64;
+///             Okhsv::new(0., 0., if position.index == index { 1. } else { 0. })
+///         },
+///     }
+/// );
+///
+/// layout1d!(Strip, 3);
+///
+/// let gradient: Gradient = Pattern::<Dim1d, Strip>::new(GradientParams {
+///     start_hue: 0.,
+///     end_hue: 0.5,
+/// });
+/// let hues: Vec<f32> = Pattern::<Dim1d, Strip>::tick(&gradient, 0)
+///     .map(|color| color.h)
+///     .collect();
+/// assert_eq!(hues, [0., 0.25, 0.5]);
+///
+/// layout2d!(
+///     Grid,
+///     [Shape2d::Grid {
+///         start: Vec2::new(-1., -1.),
+///         horizontal_end: Vec2::new(1., -1.),
+///         vertical_end: Vec2::new(-1., 1.),
+///         horizontal_pixel_count: 8,
+///         vertical_pixel_count: 8,
+///         serpentine: true,
+///     }]
+/// );
+///
+/// let sweep: Sweep = Pattern::<Dim2d, Grid>::new(0.001);
+/// assert_eq!(Pattern::<Dim2d, Grid>::tick(&sweep, 0).count(), Grid::PIXEL_COUNT);
+///
+/// let chase: Chase = Pattern::<Dim2d, Grid>::new(100);
+/// let lit = Pattern::<Dim2d, Grid>::tick(&chase, 250).position(|color| color.v == 1.);
+/// assert_eq!(lit, Some(2));
+/// ```
+#[macro_export]
+macro_rules! pattern {
+    (
+        $(#[$attr:meta])* $vis:vis $name:ident<Dim1d> {
+            params: $params_ty:ty,
+            color: $color_ty:ty,
+            pixel: |$params:pat_param, $position:ident: Position, $time:pat_param| $body:expr $(,)?
+        }
+    ) => {
+        $crate::pattern!(
+            @impl
+            $(#[$attr])* $vis $name,
+            $crate::markers::Dim1d,
+            $crate::layout::Layout1d,
+            $crate::layout::Position,
+            $params_ty,
+            $color_ty,
+            |$params, $position, $time| $body
+        );
+    };
+    (
+        $(#[$attr:meta])* $vis:vis $name:ident<Dim2d> {
+            params: $params_ty:ty,
+            color: $color_ty:ty,
+            pixel: |$params:pat_param, $position:ident: Position, $time:pat_param| $body:expr $(,)?
+        }
+    ) => {
+        $crate::pattern!(
+            @impl
+            $(#[$attr])* $vis $name,
+            $crate::markers::Dim2d,
+            $crate::layout::Layout2d,
+            $crate::layout::Position,
+            $params_ty,
+            $color_ty,
+            |$params, $position, $time| $body
+        );
+    };
+    (
+        $(#[$attr:meta])* $vis:vis $name:ident<Dim3d> {
+            params: $params_ty:ty,
+            color: $color_ty:ty,
+            pixel: |$params:pat_param, $position:ident: Position, $time:pat_param| $body:expr $(,)?
+        }
+    ) => {
+        $crate::pattern!(
+            @impl
+            $(#[$attr])* $vis $name,
+            $crate::markers::Dim3d,
+            $crate::layout::Layout3d,
+            $crate::layout::Position,
+            $params_ty,
+            $color_ty,
+            |$params, $position, $time| $body
+        );
+    };
+    (
+        $(#[$attr:meta])* $vis:vis $name:ident<Dim1d> {
+            params: $params_ty:ty,
+            color: $color_ty:ty,
+            pixel: |$params:pat_param, $position:pat_param, $time:pat_param| $body:expr $(,)?
+        }
+    ) => {
+        $crate::pattern!(
+            @impl
+            $(#[$attr])* $vis $name,
+            $crate::markers::Dim1d,
+            $crate::layout::Layout1d,
+            f32,
+            $params_ty,
+            $color_ty,
+            |$params, $position, $time| $body
+        );
+    };
+    (
+        $(#[$attr:meta])* $vis:vis $name:ident<Dim2d> {
+            params: $params_ty:ty,
+            color: $color_ty:ty,
+            pixel: |$params:pat_param, $position:pat_param, $time:pat_param| $body:expr $(,)?
+        }
+    ) => {
+        $crate::pattern!(
+            @impl
+            $(#[$attr])* $vis $name,
+            $crate::markers::Dim2d,
+            $crate::layout::Layout2d,
+            $crate::layout::Vec2,
+            $params_ty,
+            $color_ty,
+            |$params, $position, $time| $body
+        );
+    };
+    (
+        $(#[$attr:meta])* $vis:vis $name:ident<Dim3d> {
+            params: $params_ty:ty,
+            color: $color_ty:ty,
+            pixel: |$params:pat_param, $position:pat_param, $time:pat_param| $body:expr $(,)?
+        }
+    ) => {
+        $crate::pattern!(
+            @impl
+            $(#[$attr])* $vis $name,
+            $crate::markers::Dim3d,
+            $crate::layout::Layout3d,
+            $crate::layout::Vec3,
+            $params_ty,
+            $color_ty,
+            |$params, $position, $time| $body
+        );
+    };
+    (
+        @impl
+        $(#[$attr:meta])* $vis:vis $name:ident,
+        $dim:ty,
+        $layout_trait:path,
+        $position_ty:ty,
+        $params_ty:ty,
+        $color_ty:ty,
+        |$params:pat_param, $position:pat_param, $time:pat_param| $body:expr
+    ) => {
+        $(#[$attr])*
+        $vis struct $name {
+            params: $params_ty,
+        }
+
+        impl<Layout> $crate::pattern::Pattern<$dim, Layout> for $name
+        where
+            Layout: $layout_trait,
+        {
+            type Params = $params_ty;
+            type Color = $color_ty;
+
+            fn new(params: Self::Params) -> Self {
+                Self { params }
+            }
+
+            fn tick(&self, time_in_ms: u64) -> impl Iterator<Item = Self::Color> {
+                let params = &self.params;
+                let pixel = |$params: &$params_ty,
+                             $position: $position_ty,
+                             $time: u64|
+                 -> $color_ty { $body };
+                Layout::positions().map(move |position| {
+                    pixel(params, ::core::convert::From::from(position), time_in_ms)
+                })
+            }
+        }
+    };
 }
@@ -0,0 +1,268 @@
+//! # Alternate Pattern
+//!
+//! The alternate pattern splits the strip into segments, and toggles which segments are
+//! lit, like the red and blue halves of police lights.
+//!
+//! Each segment is assigned a color from a list, in turn. The segments take turns: first
+//! the even segments are lit, then the odd segments, and so on, at a configurable rate.
+//! Optionally, the lit segments flicker: flash a few times in each turn.
+//!
+//! The segments are split by LED index, so 2D and 3D layouts are split in the order of
+//! their shapes.
+//!
+//! ## Example
+//!
+//! ```rust,ignore
+//! use blinksy::{
+//!     ControlBuilder,
+//!     color::Hsv,
+//!     layout::Layout1d,
+//!     layout1d,
+//!     patterns::alternate::{Alternate, AlternateParams}
+//! };
+//! use heapless::Vec;
+//!
+//! // Define a 1D layout
+//! layout1d!(Layout, 60);
+//!
+//! // Police lights: red and blue halves, flashing 3 times in each turn
+//! let control = ControlBuilder::new_1d()
+//!     .with_layout::<Layout, { Layout::PIXEL_COUNT }>()
+//!     .with_pattern::<Alternate<2>>(AlternateParams {
+//!         colors: Vec::from_slice(&[Hsv::new(0., 1., 1.), Hsv::new(0.66, 1., 1.)]).unwrap(),
+//!         segments: 2,
+//!         rate: 2.,
+//!         flicker: 3,
+//!     })
+//!     .with_driver(/* Your driver */)
+//!     .with_frame_buffer_size::</* Length of frame buffer */>()
+//!     .build();
+//! ```
+
+use heapless::Vec;
+
+use crate::{
+    color::Hsv,
+    layout::{Layout1d, Layout2d, Layout3d},
+    markers::{Dim1d, Dim2d, Dim3d},
+    pattern::Pattern,
+};
+
+/// Configuration parameters for the Alternate pattern.
+///
+/// # Type Parameters
+///
+/// - `N` - The maximum number of colors
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct AlternateParams<const N: usize> {
+    /// The colors of the segments, assigned to each segment in turn
+    pub colors: Vec<Hsv, N>,
+    /// The number of segments to split the LEDs into
+    pub segments: usize,
+    /// How many times to toggle the lit segments per second
+    pub rate: f32,
+    /// How many times the lit segments flash in each turn (0 for solid)
+    pub flicker: u32,
+}
+
+impl<const N: usize> Default for AlternateParams<N> {
+    fn default() -> Self {
+        Self {
+            colors: Vec::from_iter(
+                [Hsv::new(0., 1., 1.), Hsv::new(0.66, 1., 1.)]
+                    .into_iter()
+                    .take(N),
+            ),
+            segments: 2,
+            rate: 2.,
+            flicker: 0,
+        }
+    }
+}
+
+/// Alternate pattern implementation.
+///
+/// Toggles between lighting the even and odd segments.
+///
+/// # Type Parameters
+///
+/// - `N` - The maximum number of colors
+#[derive(Debug, Clone)]
+pub struct Alternate<const N: usize> {
+    /// Configuration parameters
+    params: AlternateParams<N>,
+}
+
+impl<const N: usize> Alternate<N> {
+    /// Returns the segment of an LED, by index.
+    fn segment(&self, index: usize, pixel_count: usize) -> usize {
+        let segments = self.params.segments.max(1);
+        index * segments / pixel_count.max(1)
+    }
+
+    /// Returns the color of a segment, when lit.
+    fn segment_color(&self, segment: usize) -> Hsv {
+        let colors = &self.params.colors;
+        if colors.is_empty() {
+            return Hsv::new(0., 0., 0.);
+        }
+        colors[segment % colors.len()]
+    }
+
+    /// Returns which turn it is (0 for the even segments, 1 for the odd segments), and
+    /// whether the lit segments are on (rather than off, between flickers).
+    fn phase(&self, time_in_ms: u64) -> (usize, bool) {
+        const MILLISECONDS_PER_SECOND: f64 = 1e3;
+        // In f64, so the phase stays precise for long running times.
+        let turns = time_in_ms as f64 * self.params.rate as f64 / MILLISECONDS_PER_SECOND;
+        let turn = (turns as u64 % 2) as usize;
+        let is_on = match self.params.flicker {
+            0 => true,
+            flicker => {
+                let within_turn = turns - (turns as u64) as f64;
+                // Each flash is on for the first half of its period.
+                ((within_turn * 2. * flicker as f64) as u64).is_multiple_of(2)
+            }
+        };
+        (turn, is_on)
+    }
+
+    /// Generates the colors for `pixel_count` LEDs.
+    fn colors(&self, time_in_ms: u64, pixel_count: usize) -> impl Iterator<Item = Hsv> + '_ {
+        let (turn, is_on) = self.phase(time_in_ms);
+        (0..pixel_count).map(move |index| {
+            let segment = self.segment(index, pixel_count);
+            let color = self.segment_color(segment);
+            if is_on && segment % 2 == turn {
+                color
+            } else {
+                Hsv::from_hue(color.hue, color.saturation, 0.)
+            }
+        })
+    }
+}
+
+impl<const N: usize, Layout> Pattern<Dim1d, Layout> for Alternate<N>
+where
+    Layout: Layout1d,
+{
+    type Params = AlternateParams<N>;
+    type Color = Hsv;
+
+    /// Creates a new Alternate pattern with the specified parameters.
+    fn new(params: Self::Params) -> Self {
+        Self { params }
+    }
+
+    /// Generates colors for a 1D layout.
+    fn tick(&self, time_in_ms: u64) -> impl Iterator<Item = Self::Color> {
+        self.colors(time_in_ms, Layout::PIXEL_COUNT)
+    }
+}
+
+impl<const N: usize, Layout> Pattern<Dim2d, Layout> for Alternate<N>
+where
+    Layout: Layout2d,
+{
+    type Params = AlternateParams<N>;
+    type Color = Hsv;
+
+    /// Creates a new Alternate pattern with the specified parameters.
+    fn new(params: Self::Params) -> Self {
+        Self { params }
+    }
+
+    /// Generates colors for a 2D layout.
+    fn tick(&self, time_in_ms: u64) -> impl Iterator<Item = Self::Color> {
+        self.colors(time_in_ms, Layout::PIXEL_COUNT)
+    }
+}
+
+impl<const N: usize, Layout> Pattern<Dim3d, Layout> for Alternate<N>
+where
+    Layout: Layout3d,
+{
+    type Params = AlternateParams<N>;
+    type Color = Hsv;
+
+    /// Creates a new Alternate pattern with the specified parameters.
+    fn new(params: Self::Params) -> Self {
+        Self { params }
+    }
+
+    /// Generates colors for a 3D layout.
+    fn tick(&self, time_in_ms: u64) -> impl Iterator<Item = Self::Color> {
+        self.colors(time_in_ms, Layout::PIXEL_COUNT)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::layout1d;
+
+    layout1d!(Layout, 6);
+
+    /// Returns the value of each LED at a time.
+    fn values(pattern: &Alternate<3>, time_in_ms: u64) -> [f32; 6] {
+        let mut values = [0.; 6];
+        for (value, color) in values
+            .iter_mut()
+            .zip(Pattern::<Dim1d, Layout>::tick(pattern, time_in_ms))
+        {
+            *value = color.value;
+        }
+        values
+    }
+
+    fn params(flicker: u32) -> AlternateParams<3> {
+        AlternateParams {
+            colors: Vec::from_slice(&[
+                Hsv::new(0., 1., 1.),
+                Hsv::new(0.33, 1., 1.),
+                Hsv::new(0.66, 1., 1.),
+            ])
+            .unwrap(),
+            segments: 3,
+            // A turn every 500 ms.
+            rate: 2.,
+            flicker,
+        }
+    }
+
+    #[test]
+    fn test_segment_colors() {
+        let pattern: Alternate<3> = Pattern::<Dim1d, Layout>::new(params(0));
+        let segments: [usize; 6] = core::array::from_fn(|index| pattern.segment(index, 6));
+        assert_eq!(segments, [0, 0, 1, 1, 2, 2]);
+
+        let hues: [f32; 6] = core::array::from_fn(|index| {
+            Pattern::<Dim1d, Layout>::tick(&pattern, 0)
+                .nth(index)
+                .unwrap()
+                .hue
+                .inner()
+        });
+        assert_eq!(hues, [0., 0., 0.33, 0.33, 0.66, 0.66]);
+    }
+
+    #[test]
+    fn test_phase_toggles() {
+        let pattern: Alternate<3> = Pattern::<Dim1d, Layout>::new(params(0));
+        // Even segments are lit first, then odd segments.
+        assert_eq!(values(&pattern, 0), [1., 1., 0., 0., 1., 1.]);
+        assert_eq!(values(&pattern, 600), [0., 0., 1., 1., 0., 0.]);
+        assert_eq!(values(&pattern, 1100), [1., 1., 0., 0., 1., 1.]);
+    }
+
+    #[test]
+    fn test_flicker() {
+        // 2 flashes per turn: on for the first and third 125 ms of each 500 ms turn.
+        let pattern: Alternate<3> = Pattern::<Dim1d, Layout>::new(params(2));
+        assert_eq!(values(&pattern, 50), [1., 1., 0., 0., 1., 1.]);
+        assert_eq!(values(&pattern, 150), [0.; 6]);
+        assert_eq!(values(&pattern, 300), [1., 1., 0., 0., 1., 1.]);
+        assert_eq!(values(&pattern, 450), [0.; 6]);
+    }
+}
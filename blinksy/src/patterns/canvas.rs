@@ -0,0 +1,211 @@
+//! # Canvas Pattern
+//!
+//! The canvas pattern bridges Blinksy to the [`embedded-graphics`] ecosystem, so you can
+//! draw text, shapes, and sprites onto a 2D LED matrix.
+//!
+//! [`Canvas2d`] implements [`DrawTarget`] with [`Rgb888`] colors. Anything drawn onto
+//! the canvas is shown on the LEDs on the next tick.
+//!
+//! Canvas pixel coordinates start at `(0, 0)` in the left top corner, as is usual for
+//! `embedded-graphics`. Each LED in the layout is mapped to the canvas pixel nearest its
+//! position, so the order of LEDs (including serpentine wiring) is handled by the layout.
+//!
+//! Requires the `embedded-graphics` feature.
+//!
+//! ## Example
+//!
+//! ```rust,ignore
+//! use blinksy::{
+//!     ControlBuilder,
+//!     layout2d,
+//!     layout::{Layout2d, Shape2d, Vec2},
+//!     patterns::canvas::Canvas2d,
+//! };
+//! use embedded_graphics::{
+//!     pixelcolor::Rgb888,
+//!     prelude::*,
+//!     primitives::{Circle, PrimitiveStyle},
+//! };
+//!
+//! // Define a 2D layout
+//! layout2d!(
+//!     Layout,
+//!     [Shape2d::Grid {
+//!         start: Vec2::new(-1., -1.),
+//!         horizontal_end: Vec2::new(1., -1.),
+//!         vertical_end: Vec2::new(-1., 1.),
+//!         horizontal_pixel_count: 16,
+//!         vertical_pixel_count: 16,
+//!         serpentine: true,
+//!     }]
+//! );
+//!
+//! let mut control = ControlBuilder::new_2d()
+//!     .with_layout::<Layout, { Layout::PIXEL_COUNT }>()
+//!     .with_pattern::<Canvas2d<16, 16>>(())
+//!     .with_driver(/* Your driver */)
+//!     .with_frame_buffer_size::</* Length of frame buffer */>()
+//!     .build();
+//!
+//! Circle::new(Point::new(4, 4), 8)
+//!     .into_styled(PrimitiveStyle::with_fill(Rgb888::RED))
+//!     .draw(control.pattern_mut())
+//!     .unwrap();
+//!
+//! loop {
+//!     control.tick(/* current time in milliseconds */).unwrap();
+//! }
+//! ```
+//!
+//! [`embedded-graphics`]: https://docs.rs/embedded-graphics
+
+use core::convert::Infallible;
+
+use embedded_graphics_core::{
+    draw_target::DrawTarget,
+    geometry::{OriginDimensions, Size},
+    pixelcolor::{Rgb888, RgbColor},
+    Pixel,
+};
+
+use crate::{
+    color::Srgb, layout::Layout2d, markers::Dim2d, pattern::Pattern, patterns::util::nearest,
+};
+
+/// Canvas pattern implementation.
+///
+/// A `WIDTH` x `HEIGHT` buffer of pixels, which can be drawn on as an
+/// `embedded-graphics` [`DrawTarget`].
+///
+/// # Type Parameters
+///
+/// - `WIDTH` - Number of canvas pixels along each horizontal row
+/// - `HEIGHT` - Number of canvas pixels along each vertical column
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct Canvas2d<const WIDTH: usize, const HEIGHT: usize> {
+    /// Canvas pixels, by row from the top
+    pixels: [[Srgb; WIDTH]; HEIGHT],
+}
+
+impl<const WIDTH: usize, const HEIGHT: usize> Canvas2d<WIDTH, HEIGHT> {
+    /// Returns the canvas pixel at the given column and row, if within bounds.
+    pub fn pixel(&self, column: usize, row: usize) -> Option<Srgb> {
+        self.pixels.get(row)?.get(column).copied()
+    }
+
+    /// Returns the canvas pixel nearest to a 2D layout position.
+    fn sample(&self, x: f32, y: f32) -> Srgb {
+        let column = nearest(x, WIDTH);
+        // Layout y increases towards the top, canvas rows increase towards the bottom.
+        let row = nearest(-y, HEIGHT);
+        self.pixels[row][column]
+    }
+}
+
+impl<const WIDTH: usize, const HEIGHT: usize> OriginDimensions for Canvas2d<WIDTH, HEIGHT> {
+    fn size(&self) -> Size {
+        Size::new(WIDTH as u32, HEIGHT as u32)
+    }
+}
+
+impl<const WIDTH: usize, const HEIGHT: usize> DrawTarget for Canvas2d<WIDTH, HEIGHT> {
+    type Color = Rgb888;
+    type Error = Infallible;
+
+    fn draw_iter<I>(&mut self, pixels: I) -> Result<(), Self::Error>
+    where
+        I: IntoIterator<Item = Pixel<Self::Color>>,
+    {
+        for Pixel(point, color) in pixels {
+            let (Ok(column), Ok(row)) = (usize::try_from(point.x), usize::try_from(point.y)) else {
+                continue;
+            };
+            if let Some(pixel) = self
+                .pixels
+                .get_mut(row)
+                .and_then(|pixels| pixels.get_mut(column))
+            {
+                *pixel = Srgb::new(
+                    color.r() as f32 / 255.,
+                    color.g() as f32 / 255.,
+                    color.b() as f32 / 255.,
+                );
+            }
+        }
+        Ok(())
+    }
+}
+
+impl<const WIDTH: usize, const HEIGHT: usize, Layout> Pattern<Dim2d, Layout>
+    for Canvas2d<WIDTH, HEIGHT>
+where
+    Layout: Layout2d,
+{
+    type Params = ();
+    type Color = Srgb;
+
+    /// Creates a new, blank canvas.
+    fn new(_params: Self::Params) -> Self {
+        Self {
+            pixels: [[Srgb::new(0., 0., 0.); WIDTH]; HEIGHT],
+        }
+    }
+
+    /// Generates colors for a 2D layout.
+    ///
+    /// Each LED takes the color of the canvas pixel nearest its position.
+    fn tick(&self, _time_in_ms: u64) -> impl Iterator<Item = Self::Color> {
+        Layout::points().map(move |point| self.sample(point.x, point.y))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        layout::{Shape2d, Vec2},
+        layout2d,
+    };
+    use embedded_graphics_core::geometry::Point;
+
+    layout2d!(
+        Layout,
+        [Shape2d::Grid {
+            start: Vec2::new(-1., -1.),
+            horizontal_end: Vec2::new(1., -1.),
+            vertical_end: Vec2::new(-1., 1.),
+            horizontal_pixel_count: 3,
+            vertical_pixel_count: 2,
+            serpentine: true,
+        }]
+    );
+
+    #[test]
+    fn test_draw_respects_serpentine() {
+        let mut canvas: Canvas2d<3, 2> = Pattern::<Dim2d, Layout>::new(());
+
+        // Left top canvas pixel is the last LED of the serpentine second row.
+        canvas
+            .draw_iter([Pixel(Point::new(0, 0), Rgb888::WHITE)])
+            .unwrap();
+        // Left bottom canvas pixel is the first LED.
+        canvas
+            .draw_iter([Pixel(Point::new(0, 1), Rgb888::RED)])
+            .unwrap();
+
+        let mut reds = [0.; 6];
+        let mut greens = [0.; 6];
+        for ((red, green), color) in
+            reds.iter_mut()
+                .zip(greens.iter_mut())
+                .zip(Pattern::<Dim2d, Layout>::tick(&canvas, 0))
+        {
+            *red = color.red;
+            *green = color.green;
+        }
+
+        assert_eq!(reds, [1., 0., 0., 0., 0., 1.]);
+        assert_eq!(greens, [0., 0., 0., 0., 0., 1.]);
+    }
+}
@@ -0,0 +1,204 @@
+//! # Clock Pattern
+//!
+//! The clock pattern shows the time of day as a progress bar along a strip, for ambient
+//! displays.
+//!
+//! The strip fills from the first LED over the day (or over each half day), so at noon a
+//! 24 hour clock is half lit. The bar is color-coded by the hour, sampled from a palette,
+//! so the time can be read from the color as well as the length.
+//!
+//! ## Wall-clock Time
+//!
+//! Patterns are given the time in milliseconds since some start, not the time of day. To
+//! show the time of day, either:
+//!
+//! - Give [`Control`](crate::control::Control) a [`TimeSource`](crate::time::TimeSource)
+//!   which returns the milliseconds since midnight, or
+//! - Set [`ClockParams::offset_in_ms`] to the milliseconds since midnight at time zero.
+//!
+//! ## Example
+//!
+//! ```rust,ignore
+//! use blinksy::{
+//!     ControlBuilder,
+//!     layout::Layout1d,
+//!     layout1d,
+//!     patterns::clock::{ClockHours, ClockParams, WordClockless}
+//! };
+//!
+//! // Define a 1D layout
+//! layout1d!(Layout, 60);
+//!
+//! // Started at 7:30am
+//! let control = ControlBuilder::new_1d()
+//!     .with_layout::<Layout, { Layout::PIXEL_COUNT }>()
+//!     .with_pattern::<WordClockless>(ClockParams {
+//!         offset_in_ms: (7 * 60 + 30) * 60 * 1000,
+//!         hours: ClockHours::Twelve,
+//!         ..Default::default()
+//!     })
+//!     .with_driver(/* Your driver */)
+//!     .with_frame_buffer_size::</* Length of frame buffer */>()
+//!     .build();
+//! ```
+
+use crate::{
+    color::{palette::GradientPalette, LinearSrgb},
+    layout::Layout1d,
+    markers::Dim1d,
+    pattern::Pattern,
+};
+
+/// Milliseconds in an hour.
+const MILLISECONDS_PER_HOUR: u64 = 60 * 60 * 1000;
+
+/// A palette around the color wheel, from red, through green and blue, back to red.
+pub const HOUR_PALETTE: GradientPalette<'static> = GradientPalette::from_fastled(&[
+    (0, 255, 0, 0),
+    (43, 255, 255, 0),
+    (85, 0, 255, 0),
+    (128, 0, 255, 255),
+    (170, 0, 0, 255),
+    (213, 255, 0, 255),
+    (255, 255, 0, 0),
+]);
+
+/// How many hours the clock shows before starting again.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum ClockHours {
+    /// The strip fills from midnight to noon, then again from noon to midnight
+    Twelve,
+    /// The strip fills from midnight to midnight
+    #[default]
+    TwentyFour,
+}
+
+impl ClockHours {
+    /// Returns the number of hours.
+    pub fn count(self) -> u64 {
+        match self {
+            ClockHours::Twelve => 12,
+            ClockHours::TwentyFour => 24,
+        }
+    }
+}
+
+/// Configuration parameters for the clock pattern.
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct ClockParams {
+    /// Milliseconds since midnight at time zero
+    pub offset_in_ms: u64,
+    /// Whether the strip fills over 12 or 24 hours
+    pub hours: ClockHours,
+    /// The palette to color-code the hours, from midnight (index 0) to the end of the
+    /// clock (index 255)
+    pub palette: GradientPalette<'static>,
+}
+
+impl Default for ClockParams {
+    fn default() -> Self {
+        Self {
+            offset_in_ms: 0,
+            hours: ClockHours::default(),
+            palette: HOUR_PALETTE,
+        }
+    }
+}
+
+/// Clock pattern implementation.
+///
+/// Lights a fraction of the strip by the time of day, color-coded by the hour.
+#[derive(Debug, Clone)]
+pub struct WordClockless {
+    /// Configuration parameters
+    params: ClockParams,
+}
+
+impl WordClockless {
+    /// Returns the fraction of the clock (0.0 to 1.0) passed at a time.
+    fn fraction(&self, time_in_ms: u64) -> f32 {
+        let period_in_ms = self.params.hours.count() * MILLISECONDS_PER_HOUR;
+        let time_of_clock = time_in_ms.wrapping_add(self.params.offset_in_ms) % period_in_ms;
+        time_of_clock as f32 / period_in_ms as f32
+    }
+
+    /// Generates the colors for `pixel_count` LEDs.
+    fn colors(&self, time_in_ms: u64, pixel_count: usize) -> impl Iterator<Item = LinearSrgb> {
+        let fraction = self.fraction(time_in_ms);
+        let hours = self.params.hours.count() as f32;
+        // The color of the current hour.
+        let hour = (fraction * hours) as u32 as f32 / hours;
+        let color = self.params.palette.sample((hour * 255.) as u8);
+        let front = fraction * pixel_count as f32;
+
+        (0..pixel_count).map(move |index| {
+            // LEDs before the front are lit, and the LED at the front is partly lit.
+            let level = (front - index as f32).clamp(0., 1.);
+            color * level
+        })
+    }
+}
+
+impl<Layout> Pattern<Dim1d, Layout> for WordClockless
+where
+    Layout: Layout1d,
+{
+    type Params = ClockParams;
+    type Color = LinearSrgb;
+
+    /// Creates a new clock pattern with the specified parameters.
+    fn new(params: Self::Params) -> Self {
+        Self { params }
+    }
+
+    /// Generates colors for a 1D layout.
+    fn tick(&self, time_in_ms: u64) -> impl Iterator<Item = Self::Color> {
+        self.colors(time_in_ms, Layout::PIXEL_COUNT)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::layout1d;
+
+    layout1d!(Strip, 10);
+
+    fn lit_count(hours: ClockHours, time_in_ms: u64) -> usize {
+        let pattern: WordClockless = Pattern::<Dim1d, Strip>::new(ClockParams {
+            hours,
+            ..Default::default()
+        });
+        Pattern::<Dim1d, Strip>::tick(&pattern, time_in_ms)
+            .filter(|color| color.red + color.green + color.blue > 0.)
+            .count()
+    }
+
+    #[test]
+    fn test_lit_fraction_at_noon_and_midnight() {
+        let noon = 12 * MILLISECONDS_PER_HOUR;
+        let midnight = 24 * MILLISECONDS_PER_HOUR;
+
+        assert_eq!(lit_count(ClockHours::TwentyFour, noon), 5);
+        assert_eq!(lit_count(ClockHours::TwentyFour, midnight), 0);
+        // A 12 hour clock starts again at noon.
+        assert_eq!(lit_count(ClockHours::Twelve, noon), 0);
+        assert_eq!(lit_count(ClockHours::Twelve, noon - 1), 10);
+    }
+
+    #[test]
+    fn test_offset_maps_wall_clock() {
+        let pattern: WordClockless = Pattern::<Dim1d, Strip>::new(ClockParams {
+            offset_in_ms: 6 * MILLISECONDS_PER_HOUR,
+            ..Default::default()
+        });
+        // Started at 6am, so 6 hours later is noon.
+        let lit = Pattern::<Dim1d, Strip>::tick(&pattern, 6 * MILLISECONDS_PER_HOUR)
+            .filter(|color| color.red + color.green + color.blue > 0.)
+            .count();
+        assert_eq!(lit, 5);
+    }
+}
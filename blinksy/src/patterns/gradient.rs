@@ -0,0 +1,179 @@
+//! # Gradient Pattern
+//!
+//! The gradient pattern spreads a gradient of color stops across the LEDs, optionally
+//! scrolling over time.
+//!
+//! - In 1D, the gradient runs along the strip, from start to end.
+//! - In 2D and 3D, the gradient runs along the X axis, from left to right.
+//!
+//! The gradient is sampled from 0.0 at the start to 1.0 at the end, see
+//! [`ColorStops::sample`]. While scrolling, the gradient wraps around, so for a seamless
+//! loop, make the first and last stops the same color.
+//!
+//! ## Example
+//!
+//! ```rust,ignore
+//! use blinksy::{
+//!     ControlBuilder,
+//!     color::{gradient::ColorStops, LinearSrgb},
+//!     layout::Layout1d,
+//!     layout1d,
+//!     patterns::gradient::{Gradient, GradientParams}
+//! };
+//!
+//! // Define a 1D layout
+//! layout1d!(Layout, 60);
+//!
+//! // Create a sunset Gradient pattern
+//! let control = ControlBuilder::new_1d()
+//!     .with_layout::<Layout, { Layout::PIXEL_COUNT }>()
+//!     .with_pattern::<Gradient<3>>(GradientParams {
+//!         stops: ColorStops::from([
+//!             (0., LinearSrgb::new(1., 0.2, 0.)),
+//!             (0.5, LinearSrgb::new(0.8, 0., 0.3)),
+//!             (1., LinearSrgb::new(0.1, 0., 0.4)),
+//!         ]),
+//!         speed: 0.,
+//!     })
+//!     .with_driver(/* Your driver */)
+//!     .with_frame_buffer_size::</* Length of frame buffer */>()
+//!     .build();
+//! ```
+
+use num_traits::Euclid;
+
+use crate::{
+    color::{gradient::ColorStops, LinearSrgb},
+    layout::{Layout1d, Layout2d, Layout3d},
+    markers::{Dim1d, Dim2d, Dim3d},
+    pattern::Pattern,
+};
+
+/// Configuration parameters for the Gradient pattern.
+///
+/// # Type Parameters
+///
+/// - `N` - The maximum number of color stops
+#[derive(Debug, Clone, Default)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct GradientParams<const N: usize> {
+    /// The color stops, from 0.0 at the start to 1.0 at the end
+    pub stops: ColorStops<N>,
+    /// How fast the gradient scrolls, in lengths per second (0.0 for still)
+    pub speed: f32,
+}
+
+/// Gradient pattern implementation.
+///
+/// Samples the color stops by position, offset by time.
+///
+/// # Type Parameters
+///
+/// - `N` - The maximum number of color stops
+#[derive(Debug, Clone)]
+pub struct Gradient<const N: usize> {
+    /// Configuration parameters
+    params: GradientParams<N>,
+}
+
+impl<const N: usize> Gradient<N> {
+    /// Returns the color at a position, from -1.0 to 1.0, at a time.
+    fn color(&self, position: f32, time_in_ms: u64) -> LinearSrgb {
+        const MILLISECONDS_PER_SECOND: f32 = 1e3;
+        let t = (position + 1.) / 2.;
+        let t = if self.params.speed == 0. {
+            t
+        } else {
+            let offset = time_in_ms as f32 * self.params.speed / MILLISECONDS_PER_SECOND;
+            Euclid::rem_euclid(&(t - offset), &1.)
+        };
+        self.params.stops.sample(t)
+    }
+}
+
+impl<const N: usize, Layout> Pattern<Dim1d, Layout> for Gradient<N>
+where
+    Layout: Layout1d,
+{
+    type Params = GradientParams<N>;
+    type Color = LinearSrgb;
+
+    /// Creates a new Gradient pattern with the specified parameters.
+    fn new(params: Self::Params) -> Self {
+        Self { params }
+    }
+
+    /// Generates colors for a 1D layout.
+    fn tick(&self, time_in_ms: u64) -> impl Iterator<Item = Self::Color> {
+        Layout::points().map(move |x| self.color(x, time_in_ms))
+    }
+}
+
+impl<const N: usize, Layout> Pattern<Dim2d, Layout> for Gradient<N>
+where
+    Layout: Layout2d,
+{
+    type Params = GradientParams<N>;
+    type Color = LinearSrgb;
+
+    /// Creates a new Gradient pattern with the specified parameters.
+    fn new(params: Self::Params) -> Self {
+        Self { params }
+    }
+
+    /// Generates colors for a 2D layout.
+    fn tick(&self, time_in_ms: u64) -> impl Iterator<Item = Self::Color> {
+        Layout::points().map(move |point| self.color(point.x, time_in_ms))
+    }
+}
+
+impl<const N: usize, Layout> Pattern<Dim3d, Layout> for Gradient<N>
+where
+    Layout: Layout3d,
+{
+    type Params = GradientParams<N>;
+    type Color = LinearSrgb;
+
+    /// Creates a new Gradient pattern with the specified parameters.
+    fn new(params: Self::Params) -> Self {
+        Self { params }
+    }
+
+    /// Generates colors for a 3D layout.
+    fn tick(&self, time_in_ms: u64) -> impl Iterator<Item = Self::Color> {
+        Layout::points().map(move |point| self.color(point.x, time_in_ms))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use heapless::Vec;
+
+    use super::*;
+    use crate::layout1d;
+
+    layout1d!(Strip, 5);
+
+    #[test]
+    fn test_scroll() {
+        let red = LinearSrgb::new(1., 0., 0.);
+        let green = LinearSrgb::new(0., 1., 0.);
+        let blue = LinearSrgb::new(0., 0., 1.);
+        let pattern: Gradient<3> = Pattern::<Dim1d, Strip>::new(GradientParams {
+            stops: ColorStops::from([(0., red), (0.5, green), (1., blue)]),
+            speed: 0.25,
+        });
+
+        let colors: Vec<LinearSrgb, 5> = Pattern::<Dim1d, Strip>::tick(&pattern, 0).collect();
+        assert_eq!(colors[0], red);
+        assert_eq!(colors[2], green);
+        assert_eq!(colors[3], LinearSrgb::new(0., 0.5, 0.5));
+
+        // After a second, the gradient has scrolled a quarter along.
+        let colors: Vec<LinearSrgb, 5> = Pattern::<Dim1d, Strip>::tick(&pattern, 1000).collect();
+        assert_eq!(colors[1], red);
+        assert_eq!(colors[3], green);
+        // Wrapped around, from the end.
+        assert_eq!(colors[0], LinearSrgb::new(0., 0.5, 0.5));
+    }
+}
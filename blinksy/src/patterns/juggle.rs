@@ -0,0 +1,173 @@
+//! # Juggle Pattern
+//!
+//! The juggle pattern swings several colored dots back and forth along a strip, each at
+//! a different speed, like FastLED's `juggle` demo.
+//!
+//! Each dot follows a sine wave over time, like FastLED's `beatsin`: the first dot at
+//! [`JuggleParams::speed`], and each next dot a little faster, in the ratios 7, 8, 9, ...
+//! from FastLED. Each dot is a different hue, [`JuggleParams::hue_step`] apart, and
+//! overlapping dots add together.
+//!
+//! ## Example
+//!
+//! ```rust,ignore
+//! use blinksy::{
+//!     ControlBuilder,
+//!     layout::Layout1d,
+//!     layout1d,
+//!     patterns::juggle::{Juggle, JuggleParams}
+//! };
+//!
+//! // Define a 1D layout
+//! layout1d!(Layout, 60);
+//!
+//! let control = ControlBuilder::new_1d()
+//!     .with_layout::<Layout, { Layout::PIXEL_COUNT }>()
+//!     .with_pattern::<Juggle>(JuggleParams::default())
+//!     .with_driver(/* Your driver */)
+//!     .with_frame_buffer_size::</* Length of frame buffer */>()
+//!     .build();
+//! ```
+
+use crate::{
+    color::{FromColor, LinearSrgb, RainbowHsv},
+    layout::Layout1d,
+    markers::Dim1d,
+    pattern::Pattern,
+    time::BeatClock,
+};
+
+/// Configuration parameters for the Juggle pattern.
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct JuggleParams {
+    /// The number of dots, up to [`Juggle::MAX_COUNT`]
+    pub count: usize,
+    /// The hue difference between each dot (0.0 to 1.0)
+    pub hue_step: f32,
+    /// How fast the first dot swings, in swings per minute
+    pub speed: f32,
+    /// The width of each dot, in layout units, where the layout spans from -1.0 to 1.0
+    pub dot_size: f32,
+}
+
+impl Default for JuggleParams {
+    fn default() -> Self {
+        Self {
+            count: 8,
+            hue_step: 1. / 8.,
+            speed: 7.,
+            dot_size: 0.1,
+        }
+    }
+}
+
+/// Juggle pattern implementation.
+///
+/// Dots swinging along the strip on sine waves, added together.
+#[derive(Debug, Clone)]
+pub struct Juggle {
+    /// Configuration parameters
+    params: JuggleParams,
+}
+
+impl Juggle {
+    /// The most dots, to bound the work per LED.
+    pub const MAX_COUNT: usize = 16;
+
+    /// Returns the position (-1.0 to 1.0) of a dot at a time.
+    fn position(&self, dot: usize, time_in_ms: u64) -> f32 {
+        let bpm = self.params.speed * (7 + dot) as f32 / 7.;
+        BeatClock::new(bpm).beat_sin(time_in_ms) * 2. - 1.
+    }
+}
+
+impl<Layout> Pattern<Dim1d, Layout> for Juggle
+where
+    Layout: Layout1d,
+{
+    type Params = JuggleParams;
+    type Color = LinearSrgb;
+
+    /// Creates a new Juggle pattern with the specified parameters.
+    fn new(params: Self::Params) -> Self {
+        Self { params }
+    }
+
+    /// Generates colors for a 1D layout.
+    fn tick(&self, time_in_ms: u64) -> impl Iterator<Item = Self::Color> {
+        let JuggleParams {
+            count,
+            hue_step,
+            dot_size,
+            ..
+        } = self.params;
+        let count = count.min(Self::MAX_COUNT);
+        let positions: [f32; Self::MAX_COUNT] =
+            core::array::from_fn(|dot| self.position(dot, time_in_ms));
+        let radius = dot_size / 2.;
+
+        Layout::points().map(move |x| {
+            positions[..count].iter().enumerate().fold(
+                LinearSrgb::new(0., 0., 0.),
+                |sum, (dot, position)| {
+                    let distance = (x - position).abs();
+                    if distance > radius {
+                        return sum;
+                    }
+                    let value = if radius > 0. {
+                        1. - distance / radius
+                    } else {
+                        1.
+                    };
+                    let color =
+                        LinearSrgb::from_color(RainbowHsv::new(dot as f32 * hue_step, 1., value));
+                    LinearSrgb::new(
+                        (sum.red + color.red).min(1.),
+                        (sum.green + color.green).min(1.),
+                        (sum.blue + color.blue).min(1.),
+                    )
+                },
+            )
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::layout1d;
+
+    layout1d!(Strip, 9);
+
+    /// Returns the index of the brightest LED.
+    fn dot_index(pattern: &Juggle, time_in_ms: u64) -> usize {
+        Pattern::<Dim1d, Strip>::tick(pattern, time_in_ms)
+            .enumerate()
+            .max_by(|(_, a), (_, b)| a.red.total_cmp(&b.red))
+            .map(|(index, _)| index)
+            .unwrap()
+    }
+
+    #[test]
+    fn test_single_dot_range() {
+        let pattern: Juggle = Pattern::<Dim1d, Strip>::new(JuggleParams {
+            count: 1,
+            speed: 60.,
+            dot_size: 0.5,
+            ..Default::default()
+        });
+
+        // At 60 swings per minute, the dot swings from the end to the start and back each
+        // second.
+        assert_eq!(dot_index(&pattern, 0), 8);
+        assert_eq!(dot_index(&pattern, 500), 0);
+        assert_eq!(dot_index(&pattern, 1000), 8);
+
+        let indices = (0..1000)
+            .step_by(10)
+            .map(|time_in_ms| dot_index(&pattern, time_in_ms));
+        assert_eq!(indices.clone().min(), Some(0));
+        assert_eq!(indices.max(), Some(8));
+    }
+}
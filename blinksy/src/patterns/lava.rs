@@ -0,0 +1,196 @@
+//! # Lava Lamp Pattern
+//!
+//! The lava lamp pattern creates slow, organic blobs of warm color drifting upward across a
+//! 2D layout.
+//!
+//! Unlike the [`noise`](crate::patterns::noise) pattern, which colors every LED by the raw
+//! noise, the lava lamp:
+//!
+//! - Layers two octaves of Perlin noise into a field, which flows upward over time.
+//! - Lights only where the field is above a threshold, so the lit regions form blobs.
+//! - Colors each blob from a palette, hotter toward its center.
+//!
+//! ## Example
+//!
+//! ```rust,ignore
+//! use blinksy::{
+//!     ControlBuilder,
+//!     layout2d,
+//!     layout::{Layout2d, Shape2d, Vec2},
+//!     patterns::lava::{LavaLamp, LavaLampParams}
+//! };
+//!
+//! // Define a 2D layout
+//! layout2d!(
+//!     Layout,
+//!     [Shape2d::Grid {
+//!         start: Vec2::new(-1., -1.),
+//!         horizontal_end: Vec2::new(1., -1.),
+//!         vertical_end: Vec2::new(-1., 1.),
+//!         horizontal_pixel_count: 16,
+//!         vertical_pixel_count: 16,
+//!         serpentine: true,
+//!     }]
+//! );
+//!
+//! // Create a LavaLamp pattern with faster flow and bigger blobs
+//! let control = ControlBuilder::new_2d()
+//!     .with_layout::<Layout, { Layout::PIXEL_COUNT }>()
+//!     .with_pattern::<LavaLamp>(LavaLampParams {
+//!         flow_speed: 0.2,
+//!         blob_threshold: 0.45,
+//!         ..Default::default()
+//!     })
+//!     .with_driver(/* Your driver */)
+//!     .with_frame_buffer_size::</* Length of frame buffer */>()
+//!     .build();
+//! ```
+
+use noise_functions::{modifiers::Seeded, Noise as NoiseTrait, Perlin};
+
+use crate::{
+    color::{palette::GradientPalette, LinearSrgb},
+    layout::Layout2d,
+    markers::Dim2d,
+    pattern::Pattern,
+};
+
+/// A warm palette of deep red, through orange, to yellow.
+pub const LAVA_PALETTE: GradientPalette<'static> = GradientPalette::from_fastled(&[
+    (0, 96, 0, 0),
+    (96, 255, 32, 0),
+    (192, 255, 128, 0),
+    (255, 255, 224, 64),
+]);
+
+/// The width of the soft edge of each blob, as a fraction of the noise field.
+const EDGE_WIDTH: f32 = 0.08;
+
+/// How fast the blobs change shape, in noise units per second.
+const MORPH_SPEED: f32 = 0.05;
+
+/// Configuration parameters for the LavaLamp pattern.
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct LavaLampParams {
+    /// How fast the blobs drift upward, in layout units per second
+    pub flow_speed: f32,
+    /// The level of the noise field (0.0 to 1.0) above which the LEDs are lit
+    /// (higher = smaller blobs)
+    pub blob_threshold: f32,
+    /// The palette to color the blobs, from the edge (index 0) to the center (index 255)
+    pub palette: GradientPalette<'static>,
+}
+
+impl Default for LavaLampParams {
+    fn default() -> Self {
+        Self {
+            flow_speed: 0.1,
+            blob_threshold: 0.55,
+            palette: LAVA_PALETTE,
+        }
+    }
+}
+
+/// LavaLamp pattern implementation.
+///
+/// Thresholds a layered Perlin noise field which flows upward over time.
+#[derive(Debug, Clone)]
+pub struct LavaLamp {
+    /// The noise function for large blobs
+    coarse_noise: Seeded<Perlin>,
+    /// The noise function for the detail on each blob
+    fine_noise: Seeded<Perlin>,
+    /// Configuration parameters
+    params: LavaLampParams,
+}
+
+impl LavaLamp {
+    /// Returns the level of the noise field (0.0 to 1.0) at a position, at a time in seconds.
+    fn field(&self, x: f32, y: f32, time: f32) -> f32 {
+        let flow = time * self.params.flow_speed;
+        let morph = time * MORPH_SPEED;
+        let coarse = self.coarse_noise.sample3([x, y - flow, morph]);
+        let fine = self
+            .fine_noise
+            .sample3([2. * x, 2. * (y - 1.5 * flow), 2. * morph]);
+        ((0.7 * coarse + 0.3 * fine + 1.) / 2.).clamp(0., 1.)
+    }
+
+    /// Returns the color of a level of the noise field.
+    fn color(&self, level: f32) -> LinearSrgb {
+        let threshold = self.params.blob_threshold.clamp(0., 1.);
+        if level <= threshold {
+            return LinearSrgb::new(0., 0., 0.);
+        }
+        let heat = ((level - threshold) / (1. - threshold).max(f32::EPSILON)).min(1.);
+        let edge = ((level - threshold) / EDGE_WIDTH).min(1.);
+        let color = self.params.palette.sample((heat * 255.) as u8);
+        color * edge
+    }
+}
+
+impl<Layout> Pattern<Dim2d, Layout> for LavaLamp
+where
+    Layout: Layout2d,
+{
+    type Params = LavaLampParams;
+    type Color = LinearSrgb;
+
+    /// Creates a new LavaLamp pattern with the specified parameters.
+    fn new(params: Self::Params) -> Self {
+        Self {
+            coarse_noise: Perlin.seed(0),
+            fine_noise: Perlin.seed(1),
+            params,
+        }
+    }
+
+    /// Generates colors for a 2D layout.
+    fn tick(&self, time_in_ms: u64) -> impl Iterator<Item = Self::Color> {
+        const MILLISECONDS_PER_SECOND: f32 = 1e3;
+        let time = time_in_ms as f32 / MILLISECONDS_PER_SECOND;
+        Layout::points().map(move |point| self.color(self.field(point.x, point.y, time)))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        layout::{Shape2d, Vec2},
+        layout2d,
+    };
+
+    layout2d!(
+        Layout,
+        [Shape2d::Grid {
+            start: Vec2::new(-1., -1.),
+            horizontal_end: Vec2::new(1., -1.),
+            vertical_end: Vec2::new(-1., 1.),
+            horizontal_pixel_count: 16,
+            vertical_pixel_count: 16,
+            serpentine: false,
+        }]
+    );
+
+    fn lit_count(blob_threshold: f32) -> usize {
+        let pattern: LavaLamp = Pattern::<Dim2d, Layout>::new(LavaLampParams {
+            blob_threshold,
+            ..Default::default()
+        });
+        Pattern::<Dim2d, Layout>::tick(&pattern, 12_345)
+            .filter(|color| color.red > 0.)
+            .count()
+    }
+
+    #[test]
+    fn test_higher_threshold_reduces_lit_area() {
+        let low = lit_count(0.4);
+        let medium = lit_count(0.5);
+        let high = lit_count(0.6);
+        assert!(low > medium, "{low} > {medium}");
+        assert!(medium > high, "{medium} > {high}");
+        assert_eq!(lit_count(1.), 0);
+    }
+}
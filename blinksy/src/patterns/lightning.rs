@@ -0,0 +1,266 @@
+//! # Lightning Pattern
+//!
+//! The lightning pattern flashes random strikes of lightning against a dim sky, like a
+//! thunderstorm.
+//!
+//! Each strike lights a random segment of the strip with 2 to 5 rapid flashes of
+//! blue-white light, each flash decaying fast, for a flickery look. Between strikes, the
+//! LEDs show the dim base color of the sky.
+//!
+//! Strikes are random, but deterministic: the same seed gives the same storm.
+//!
+//! ## Example
+//!
+//! ```rust,ignore
+//! use blinksy::{
+//!     ControlBuilder,
+//!     color::Hsv,
+//!     layout::Layout1d,
+//!     layout1d,
+//!     patterns::lightning::{Lightning, LightningParams}
+//! };
+//!
+//! // Define a 1D layout
+//! layout1d!(Layout, 60);
+//!
+//! // A strike about every 2 seconds, over a dim purple sky
+//! let control = ControlBuilder::new_1d()
+//!     .with_layout::<Layout, { Layout::PIXEL_COUNT }>()
+//!     .with_pattern::<Lightning>(LightningParams {
+//!         strike_rate: 0.5,
+//!         base: Hsv::new(0.75, 0.8, 0.05),
+//!         ..Default::default()
+//!     })
+//!     .with_driver(/* Your driver */)
+//!     .with_frame_buffer_size::</* Length of frame buffer */>()
+//!     .build();
+//! ```
+
+#[allow(unused_imports)]
+use num_traits::Float;
+
+use crate::{
+    color::{blend, FromColor, Hsv, LinearSrgb},
+    layout::Layout1d,
+    markers::Dim1d,
+    pattern::Pattern,
+    util::rng::{hash, Rng},
+};
+
+/// The time is divided into slots, each with at most one strike.
+const SLOT_IN_MS: u64 = 500;
+
+/// The number of slots before the current slot which may have a strike still flashing.
+const SLOTS_BEHIND: u64 = 2;
+
+/// The most flashes in a strike.
+const MAX_FLASHES: usize = 5;
+
+/// The time for a flash to decay to about a third, in milliseconds.
+const DECAY_IN_MS: f32 = 40.;
+
+/// The color of a flash, a blue-white.
+const FLASH_COLOR: LinearSrgb = LinearSrgb {
+    red: 0.8,
+    green: 0.85,
+    blue: 1.,
+};
+
+/// Configuration parameters for the Lightning pattern.
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct LightningParams {
+    /// How often strikes happen, on average, in strikes per second (at most 2)
+    pub strike_rate: f32,
+    /// The color of the sky between strikes
+    pub base: Hsv,
+    /// Seed for the random strikes
+    pub seed: u32,
+}
+
+impl Default for LightningParams {
+    fn default() -> Self {
+        Self {
+            strike_rate: 0.25,
+            base: Hsv::new(0.66, 0.6, 0.03),
+            seed: 0,
+        }
+    }
+}
+
+/// A strike of lightning: a burst of flashes on a segment of the strip.
+#[derive(Debug, Clone, Copy)]
+struct Strike {
+    /// The start of the segment, from 0.0 (first LED) to 1.0 (last LED)
+    segment_start: f32,
+    /// The end of the segment, from 0.0 (first LED) to 1.0 (last LED)
+    segment_end: f32,
+    /// The time of each flash, in milliseconds
+    flash_times: [u64; MAX_FLASHES],
+    /// The brightness of each flash (0.0 to 1.0)
+    flash_levels: [f32; MAX_FLASHES],
+    /// The number of flashes
+    flash_count: usize,
+}
+
+impl Strike {
+    /// Returns the brightness of the strike (0.0 to 1.0) at a time.
+    fn level(&self, time_in_ms: u64) -> f32 {
+        let level: f32 = self.flash_times[..self.flash_count]
+            .iter()
+            .zip(self.flash_levels)
+            .filter(|(flash_time, _)| **flash_time <= time_in_ms)
+            .map(|(flash_time, level)| {
+                let elapsed_in_ms = (time_in_ms - flash_time) as f32;
+                level * (-elapsed_in_ms / DECAY_IN_MS).exp()
+            })
+            .sum();
+        level.min(1.)
+    }
+
+    /// Returns whether a position (0.0 to 1.0) is within the segment.
+    fn contains(&self, position: f32) -> bool {
+        (self.segment_start..=self.segment_end).contains(&position)
+    }
+}
+
+/// Lightning pattern implementation.
+///
+/// Flashes random strikes on random segments, over a dim base color.
+#[derive(Debug, Clone)]
+pub struct Lightning {
+    /// Configuration parameters
+    params: LightningParams,
+}
+
+impl Lightning {
+    /// Returns the strike in a time slot, if any.
+    fn strike(&self, slot: u64) -> Option<Strike> {
+        const SLOT_IN_S: f32 = SLOT_IN_MS as f32 / 1e3;
+
+        let mut rng = Rng::new(self.params.seed ^ hash(slot as u32));
+        if rng.next_f32() >= self.params.strike_rate * SLOT_IN_S {
+            return None;
+        }
+
+        let length = 0.2 + 0.4 * rng.next_f32();
+        let segment_start = (1. - length) * rng.next_f32();
+        let flash_count = 2 + (rng.next_u32() % (MAX_FLASHES as u32 - 1)) as usize;
+
+        let mut flash_times = [0; MAX_FLASHES];
+        let mut flash_levels = [0.; MAX_FLASHES];
+        let mut flash_time = slot * SLOT_IN_MS + (rng.next_f32() * SLOT_IN_MS as f32) as u64;
+        for index in 0..flash_count {
+            flash_times[index] = flash_time;
+            // The first flash is the brightest.
+            flash_levels[index] = if index == 0 {
+                1.
+            } else {
+                0.4 + 0.6 * rng.next_f32()
+            };
+            flash_time += 30 + (rng.next_f32() * 120.) as u64;
+        }
+
+        Some(Strike {
+            segment_start,
+            segment_end: segment_start + length,
+            flash_times,
+            flash_levels,
+            flash_count,
+        })
+    }
+
+    /// Generates the colors for `pixel_count` LEDs.
+    fn colors(&self, time_in_ms: u64, pixel_count: usize) -> impl Iterator<Item = LinearSrgb> {
+        let slot = time_in_ms / SLOT_IN_MS;
+        let mut strikes = [None; SLOTS_BEHIND as usize + 1];
+        for (strike, slot) in strikes
+            .iter_mut()
+            .zip(slot.saturating_sub(SLOTS_BEHIND)..=slot)
+        {
+            *strike = self.strike(slot);
+        }
+        let base = LinearSrgb::from_color(self.params.base);
+        let last_index = pixel_count.saturating_sub(1).max(1) as f32;
+
+        (0..pixel_count).map(move |index| {
+            let position = index as f32 / last_index;
+            let level = strikes
+                .iter()
+                .flatten()
+                .filter(|strike| strike.contains(position))
+                .map(|strike| strike.level(time_in_ms))
+                .fold(0., f32::max);
+            blend::lerp(base, FLASH_COLOR, level)
+        })
+    }
+}
+
+impl<Layout> Pattern<Dim1d, Layout> for Lightning
+where
+    Layout: Layout1d,
+{
+    type Params = LightningParams;
+    type Color = LinearSrgb;
+
+    /// Creates a new Lightning pattern with the specified parameters.
+    fn new(params: Self::Params) -> Self {
+        Self { params }
+    }
+
+    /// Generates colors for a 1D layout.
+    fn tick(&self, time_in_ms: u64) -> impl Iterator<Item = Self::Color> {
+        self.colors(time_in_ms, Layout::PIXEL_COUNT)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::layout1d;
+
+    layout1d!(Layout, 20);
+
+    /// Returns the brightest blue channel of any LED at a time.
+    fn max_blue(pattern: &Lightning, time_in_ms: u64) -> f32 {
+        Pattern::<Dim1d, Layout>::tick(pattern, time_in_ms)
+            .map(|color| color.blue)
+            .fold(0., f32::max)
+    }
+
+    #[test]
+    fn test_strikes_at_seeded_times() {
+        let pattern: Lightning = Pattern::<Dim1d, Layout>::new(LightningParams {
+            strike_rate: 1.,
+            seed: 7,
+            ..Default::default()
+        });
+
+        let strikes: heapless::Vec<Strike, 20> =
+            (0..20).filter_map(|slot| pattern.strike(slot)).collect();
+        assert!(!strikes.is_empty());
+
+        for strike in strikes {
+            assert!((2..=MAX_FLASHES).contains(&strike.flash_count));
+
+            // Near-full brightness at the first flash.
+            let first_flash = strike.flash_times[0];
+            assert!(max_blue(&pattern, first_flash) > 0.95);
+
+            // The flash decays fast.
+            assert!(strike.level(first_flash + 200) < strike.level(first_flash));
+        }
+    }
+
+    #[test]
+    fn test_no_strikes_shows_base() {
+        let pattern: Lightning = Pattern::<Dim1d, Layout>::new(LightningParams {
+            strike_rate: 0.,
+            ..Default::default()
+        });
+        let base = LinearSrgb::from_color(LightningParams::default().base);
+        for time_in_ms in (0..10_000).step_by(50) {
+            assert!(Pattern::<Dim1d, Layout>::tick(&pattern, time_in_ms).all(|color| color == base));
+        }
+    }
+}
@@ -0,0 +1,232 @@
+//! # Matrix Rain Pattern
+//!
+//! The matrix rain pattern drops streams of light down each column of a 2D grid, like the
+//! digital rain of The Matrix.
+//!
+//! Each column has a drop which falls from the top of the grid to the bottom, with a
+//! bright, whiter head and a trail fading behind it. Each column falls at its own random
+//! speed, and waits a random gap before its next drop.
+//!
+//! Columns and rows are found by the position of each LED, so the pattern works the same
+//! on serpentine and progressive wiring. The grid size is taken from the first
+//! [`Shape2d::Grid`] of the layout.
+//!
+//! ## Example
+//!
+//! ```rust,ignore
+//! use blinksy::{
+//!     ControlBuilder,
+//!     layout::{Layout2d, Shape2d, Vec2},
+//!     layout2d,
+//!     patterns::matrix_rain::{MatrixRain, MatrixRainParams}
+//! };
+//!
+//! // Define a 2D layout
+//! layout2d!(
+//!     Layout,
+//!     [Shape2d::Grid {
+//!         start: Vec2::new(-1., -1.),
+//!         horizontal_end: Vec2::new(1., -1.),
+//!         vertical_end: Vec2::new(-1., 1.),
+//!         horizontal_pixel_count: 16,
+//!         vertical_pixel_count: 16,
+//!         serpentine: true,
+//!     }]
+//! );
+//!
+//! let control = ControlBuilder::new_2d()
+//!     .with_layout::<Layout, { Layout::PIXEL_COUNT }>()
+//!     .with_pattern::<MatrixRain>(MatrixRainParams::default())
+//!     .with_driver(/* Your driver */)
+//!     .with_frame_buffer_size::</* Length of frame buffer */>()
+//!     .build();
+//! ```
+
+use num_traits::Euclid;
+
+use crate::{
+    color::Okhsv,
+    layout::{Layout2d, Shape2d},
+    markers::Dim2d,
+    pattern::Pattern,
+    patterns::util::nearest,
+    util::rng::{hash, Rng},
+};
+
+/// Configuration parameters for the MatrixRain pattern.
+///
+/// Distances are in rows of the grid.
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct MatrixRainParams {
+    /// The color of the trail
+    pub color: Okhsv,
+    /// The color of the head of each drop
+    pub head_color: Okhsv,
+    /// The length of the trail behind the head, in rows
+    pub trail_length: f32,
+    /// The slowest a drop falls, in rows per second
+    pub min_speed: f32,
+    /// The fastest a drop falls, in rows per second
+    pub max_speed: f32,
+    /// Seed for the random speeds and gaps of each column
+    pub seed: u32,
+}
+
+impl Default for MatrixRainParams {
+    fn default() -> Self {
+        Self {
+            color: Okhsv::new(0.4, 1., 1.),
+            head_color: Okhsv::new(0.4, 0.3, 1.),
+            trail_length: 6.,
+            min_speed: 6.,
+            max_speed: 18.,
+            seed: 0,
+        }
+    }
+}
+
+/// MatrixRain pattern implementation.
+///
+/// A drop falling down each column of a grid, at a random speed per column.
+#[derive(Debug, Clone)]
+pub struct MatrixRain {
+    /// Configuration parameters
+    params: MatrixRainParams,
+}
+
+impl MatrixRain {
+    /// Returns the row of the head of a column's drop (from 0.0 at the top), at a time.
+    ///
+    /// The head falls past the bottom of the grid until its trail has left, then waits
+    /// a random gap (up to the height of the grid) before the next drop starts at the
+    /// top. The head is beyond the grid while the column is empty.
+    fn head(&self, column: usize, height: usize, time_in_ms: u64) -> f32 {
+        const MILLISECONDS_PER_SECOND: f32 = 1e3;
+        let MatrixRainParams {
+            trail_length,
+            min_speed,
+            max_speed,
+            seed,
+            ..
+        } = self.params;
+
+        let mut rng = Rng::new(seed ^ hash(column as u32));
+        let speed = min_speed + (max_speed - min_speed) * rng.next_f32();
+        let gap = height as f32 * rng.next_f32();
+        let cycle = height as f32 + trail_length.max(0.) + 1. + gap;
+        let offset = cycle * rng.next_f32();
+
+        let fallen = time_in_ms as f32 * speed / MILLISECONDS_PER_SECOND;
+        Euclid::rem_euclid(&(fallen + offset), &cycle)
+    }
+
+    /// Returns the color of an LED at a row, given the row of the head of its column.
+    fn color(&self, head: f32, row: usize) -> Okhsv {
+        let MatrixRainParams {
+            color,
+            head_color,
+            trail_length,
+            ..
+        } = self.params;
+
+        let distance = head - row as f32;
+        if (0. ..1.).contains(&distance) {
+            head_color
+        } else if distance >= 1. && distance < 1. + trail_length {
+            let fade = 1. - (distance - 1.) / trail_length;
+            Okhsv::new(color.h, color.s, color.v * fade)
+        } else {
+            Okhsv::new(0., 0., 0.)
+        }
+    }
+}
+
+impl<Layout> Pattern<Dim2d, Layout> for MatrixRain
+where
+    Layout: Layout2d,
+{
+    type Params = MatrixRainParams;
+    type Color = Okhsv;
+
+    /// Creates a new MatrixRain pattern with the specified parameters.
+    fn new(params: Self::Params) -> Self {
+        Self { params }
+    }
+
+    /// Generates colors for a 2D layout.
+    ///
+    /// Each LED is mapped to the nearest column and row of the grid, with rows counted
+    /// down from the top (1.0).
+    fn tick(&self, time_in_ms: u64) -> impl Iterator<Item = Self::Color> {
+        let (width, height) = Layout::shapes()
+            .find_map(|shape| match shape {
+                Shape2d::Grid {
+                    horizontal_pixel_count,
+                    vertical_pixel_count,
+                    ..
+                } => Some((horizontal_pixel_count, vertical_pixel_count)),
+                _ => None,
+            })
+            .unwrap_or((0, 0));
+
+        Layout::points().map(move |point| {
+            let column = nearest(point.x, width);
+            let row = nearest(-point.y, height);
+            self.color(self.head(column, height, time_in_ms), row)
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{layout::Vec2, layout2d};
+
+    layout2d!(
+        Layout,
+        [Shape2d::Grid {
+            start: Vec2::new(-1., -1.),
+            horizontal_end: Vec2::new(1., -1.),
+            vertical_end: Vec2::new(-1., 1.),
+            horizontal_pixel_count: 3,
+            vertical_pixel_count: 8,
+            serpentine: true,
+        }]
+    );
+
+    /// Returns the row of the head in the middle column, if on the grid.
+    fn head_row(pattern: &MatrixRain, time_in_ms: u64) -> Option<usize> {
+        let head_color = pattern.params.head_color;
+        let mut heads = Layout::points()
+            .zip(Pattern::<Dim2d, Layout>::tick(pattern, time_in_ms))
+            .filter(|(point, color)| point.x == 0. && *color == head_color)
+            .map(|(point, _)| nearest(-point.y, 8));
+        let row = heads.next();
+        assert_eq!(heads.next(), None);
+        row
+    }
+
+    #[test]
+    fn test_head_falls() {
+        let pattern: MatrixRain = Pattern::<Dim2d, Layout>::new(MatrixRainParams {
+            min_speed: 10.,
+            max_speed: 10.,
+            ..Default::default()
+        });
+
+        let mut previous = None;
+        let mut advances = 0;
+        for step in 0..100 {
+            let row = head_row(&pattern, step * 50);
+            if let (Some(previous), Some(row)) = (previous, row) {
+                assert!(row >= previous, "head moved up from {previous} to {row}");
+                if row > previous {
+                    advances += 1;
+                }
+            }
+            previous = row;
+        }
+        assert!(advances > 0);
+    }
+}
@@ -0,0 +1,233 @@
+//! # Meteor Rain Pattern
+//!
+//! The meteor pattern streaks a bright head across the LEDs, followed by a fading trail
+//! which sparkles as it decays at random.
+//!
+//! - In 1D, the meteor travels along the strip, from start to end.
+//! - In 2D, the meteor falls along the Y axis, from top to bottom.
+//!
+//! Once the trail has left the layout, the next meteor starts.
+//!
+//! ## Example
+//!
+//! ```rust,ignore
+//! use blinksy::{
+//!     ControlBuilder,
+//!     layout::Layout1d,
+//!     layout1d,
+//!     patterns::meteor::{Meteor, MeteorParams}
+//! };
+//!
+//! // Define a 1D layout
+//! layout1d!(Layout, 60);
+//!
+//! let control = ControlBuilder::new_1d()
+//!     .with_layout::<Layout, { Layout::PIXEL_COUNT }>()
+//!     .with_pattern::<Meteor>(MeteorParams {
+//!         trail_length: 1.,
+//!         ..Default::default()
+//!     })
+//!     .with_driver(/* Your driver */)
+//!     .with_frame_buffer_size::</* Length of frame buffer */>()
+//!     .build();
+//! ```
+
+use num_traits::Euclid;
+
+use crate::{
+    color::Okhsv,
+    layout::{Layout1d, Layout2d},
+    markers::{Dim1d, Dim2d},
+    pattern::Pattern,
+    util::rng::{hash, Rng},
+};
+
+/// Configuration parameters for the Meteor pattern.
+///
+/// Distances are in layout units, where the layout spans from -1.0 to 1.0 (2.0 in total).
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct MeteorParams {
+    /// The color of the meteor head
+    pub color: Okhsv,
+    /// How fast the meteor travels, in layout units per second
+    pub speed: f32,
+    /// The length of the trail, in layout units
+    pub trail_length: f32,
+    /// How much the trail sparkles as it decays (0.0 for a smooth trail, 1.0 for fully
+    /// random)
+    pub sparkle: f32,
+    /// Seed for the random decay
+    pub seed: u32,
+}
+
+impl Default for MeteorParams {
+    fn default() -> Self {
+        Self {
+            color: Okhsv::new(0.6, 0.3, 1.),
+            speed: 1.,
+            trail_length: 0.5,
+            sparkle: 0.5,
+            seed: 0,
+        }
+    }
+}
+
+/// Meteor pattern implementation.
+///
+/// A head travelling with time, and a trail with a random per-pixel decay.
+#[derive(Debug, Clone)]
+pub struct Meteor {
+    /// Configuration parameters
+    params: MeteorParams,
+}
+
+impl Meteor {
+    /// How often the sparkle of the trail changes, in milliseconds.
+    const SPARKLE_INTERVAL_IN_MS: u64 = 50;
+
+    /// Returns the distance the head has travelled (from 0.0), at a time.
+    ///
+    /// The head travels across the layout (2.0) and then the length of the trail, so the
+    /// trail leaves the layout before the next meteor starts.
+    fn head(&self, time_in_ms: u64) -> f32 {
+        const MILLISECONDS_PER_SECOND: f32 = 1e3;
+        let cycle = 2. + self.params.trail_length.max(0.);
+        let travelled = time_in_ms as f32 * self.params.speed / MILLISECONDS_PER_SECOND;
+        Euclid::rem_euclid(&travelled, &cycle)
+    }
+
+    /// Returns the random number generator for the sparkle at a time.
+    fn rng(&self, time_in_ms: u64) -> Rng {
+        let step = (time_in_ms / Self::SPARKLE_INTERVAL_IN_MS) as u32;
+        Rng::new(self.params.seed ^ hash(step))
+    }
+
+    /// Returns the color of an LED at a distance along the path of the meteor.
+    fn color(&self, head: f32, position: f32, rng: &mut Rng) -> Okhsv {
+        let MeteorParams {
+            color,
+            trail_length,
+            sparkle,
+            ..
+        } = self.params;
+        // Draw for every LED, so each LED keeps its own random value.
+        let random = rng.next_f32();
+
+        let distance = head - position;
+        if distance < 0. || distance > trail_length {
+            return Okhsv::new(color.h, color.s, 0.);
+        }
+        let fade = if trail_length > 0. {
+            1. - distance / trail_length
+        } else {
+            1.
+        };
+        let decay = 1. - sparkle.clamp(0., 1.) * random;
+        Okhsv::new(color.h, color.s, color.v * fade * decay)
+    }
+}
+
+impl<Layout> Pattern<Dim1d, Layout> for Meteor
+where
+    Layout: Layout1d,
+{
+    type Params = MeteorParams;
+    type Color = Okhsv;
+
+    /// Creates a new Meteor pattern with the specified parameters.
+    fn new(params: Self::Params) -> Self {
+        Self { params }
+    }
+
+    /// Generates colors for a 1D layout.
+    ///
+    /// The meteor travels from the start of the strip (-1.0) to the end (1.0).
+    fn tick(&self, time_in_ms: u64) -> impl Iterator<Item = Self::Color> {
+        let head = self.head(time_in_ms);
+        let mut rng = self.rng(time_in_ms);
+
+        Layout::points().map(move |x| self.color(head, x + 1., &mut rng))
+    }
+}
+
+impl<Layout> Pattern<Dim2d, Layout> for Meteor
+where
+    Layout: Layout2d,
+{
+    type Params = MeteorParams;
+    type Color = Okhsv;
+
+    /// Creates a new Meteor pattern with the specified parameters.
+    fn new(params: Self::Params) -> Self {
+        Self { params }
+    }
+
+    /// Generates colors for a 2D layout.
+    ///
+    /// The meteor falls from the top of the layout (1.0) to the bottom (-1.0).
+    fn tick(&self, time_in_ms: u64) -> impl Iterator<Item = Self::Color> {
+        let head = self.head(time_in_ms);
+        let mut rng = self.rng(time_in_ms);
+
+        Layout::points().map(move |point| self.color(head, 1. - point.y, &mut rng))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        layout::{Shape2d, Vec2},
+        layout1d, layout2d,
+    };
+
+    layout1d!(Strip, 9);
+
+    layout2d!(
+        Grid,
+        [Shape2d::Grid {
+            start: Vec2::new(-1., -1.),
+            horizontal_end: Vec2::new(1., -1.),
+            vertical_end: Vec2::new(-1., 1.),
+            horizontal_pixel_count: 2,
+            vertical_pixel_count: 9,
+            serpentine: false,
+        }]
+    );
+
+    #[test]
+    fn test_trail_behind_head() {
+        let pattern: Meteor = Pattern::<Dim1d, Strip>::new(MeteorParams {
+            sparkle: 0.,
+            ..Default::default()
+        });
+
+        // After 1 second, the head is at the middle of the strip.
+        let values: [f32; 9] = {
+            let mut values = [0.; 9];
+            for (value, color) in values
+                .iter_mut()
+                .zip(Pattern::<Dim1d, Strip>::tick(&pattern, 1000))
+            {
+                *value = color.v;
+            }
+            values
+        };
+        assert_eq!(values, [0., 0., 0., 0.5, 1., 0., 0., 0., 0.]);
+    }
+
+    #[test]
+    fn test_meteor_at_bottom() {
+        let pattern: Meteor = Pattern::<Dim2d, Grid>::new(MeteorParams::default());
+
+        // After 2 seconds, the head is at the bottom of the grid.
+        for (point, color) in Grid::points().zip(Pattern::<Dim2d, Grid>::tick(&pattern, 2000)) {
+            if point.y > -0.5 {
+                assert_eq!(color.v, 0., "top pixels have faded");
+            } else if point.y == -1. {
+                assert!(color.v > 0., "head is lit");
+            }
+        }
+    }
+}
@@ -3,9 +3,59 @@
 //! This is the library of built-in patterns.
 //!
 //! - [`rainbow`][]: A basic scrolling rainbow.
+//! - [`raster`]: An external image, such as live video, shown on a 2D layout.
+//! - [`alternate`]: Segments taking turns to light up, like police lights.
+//! - [`clock`]: The time of day as a progress bar, color-coded by the hour.
+//! - [`gradient`]: A gradient of any number of color stops, optionally scrolling.
+//! - [`juggle`]: Colored dots swinging back and forth at different speeds.
+//! - [`lava`]: Slow, organic blobs of warm color drifting upward, like a lava lamp.
+//! - [`lightning`]: Random strikes of lightning flashing against a dim sky.
+//! - [`matrix_rain`]: Digital rain falling down the columns of a 2D grid.
+//! - [`meteor`]: A meteor streaking across the LEDs, with a sparkling trail.
 //! - [`noise`]: A flow through random noise functions.
+//! - [`radar`]: A sweep rotating around the center of a 2D layout.
+//! - [`shader`]: Any closure of each LED's position and the time, for quick experiments.
+//! - [`spectrum`]: Audio frequency bars across a 2D grid.
+//! - [`starfield`]: Stars flying toward the viewer through a 3D layout.
+//! - [`strobe`]: Every LED flashing on and off, like a strobe light.
+//! - [`switch`]: A switch between the built-in patterns, selected at runtime.
+//! - [`text`]: A message scrolling across a 2D grid.
+//! - [`theater`]: Every Nth LED lit and chasing along, like a marquee.
+//! - [`touch`]: Discrete input zones that light up and fade.
+//! - [`vu`]: An audio level meter with a decaying peak dot.
+//! - [`wave`]: A sine wave of brightness moving along a strip.
+//! - [`wipe`]: A color filling the LEDs one at a time.
+//! - `canvas`: An [`embedded-graphics`](https://docs.rs/embedded-graphics) draw target for 2D grids.
+//!   (Requires the `embedded-graphics` feature.)
+//!
+//! To write your own pattern, [`util`] has shared helpers, such as
+//! [`fill_by_position`](util::fill_by_position).
 //!
 //! If you want help to port a pattern from FastLED / WLED to Rust, [make an issue](https://github.com/ahdinosaur/blinksy/issues)!
 
+pub mod alternate;
+#[cfg(feature = "embedded-graphics")]
+pub mod canvas;
+pub mod clock;
+pub mod gradient;
+pub mod juggle;
+pub mod lava;
+pub mod lightning;
+pub mod matrix_rain;
+pub mod meteor;
 pub mod noise;
+pub mod radar;
 pub mod rainbow;
+pub mod raster;
+pub mod shader;
+pub mod spectrum;
+pub mod starfield;
+pub mod strobe;
+pub mod switch;
+pub mod text;
+pub mod theater;
+pub mod touch;
+pub mod util;
+pub mod vu;
+pub mod wave;
+pub mod wipe;
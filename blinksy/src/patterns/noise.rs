@@ -72,8 +72,9 @@ pub mod noise_fns {
 }
 
 /// Configuration parameters for noise patterns.
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 #[cfg_attr(feature = "defmt", derive(defmt::Format))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct NoiseParams {
     /// Controls the speed of animation (higher = faster)
     pub time_scalar: f32,
@@ -95,7 +96,7 @@ impl Default for NoiseParams {
 ///
 /// Creates flowing animations based on a 2D noise function, using
 /// time and the 1D position for the input coordinates.
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 #[cfg_attr(feature = "defmt", derive(defmt::Format))]
 pub struct Noise1d<Noise> {
     /// The noise function used to get hue
@@ -155,7 +156,7 @@ where
 ///
 /// Creates flowing animations based on a 3D noise function, using
 /// time and the 2D position for the input coordinates.
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 #[cfg_attr(feature = "defmt", derive(defmt::Format))]
 pub struct Noise2d<Noise> {
     /// The noise function used to get hue
@@ -219,7 +220,7 @@ where
 ///
 /// Creates flowing animations based on a 4D noise function, using
 /// time and the 3D position for the input coordinates.
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 #[cfg_attr(feature = "defmt", derive(defmt::Format))]
 pub struct Noise3d<Noise>
 where
@@ -0,0 +1,191 @@
+//! # Radar Pattern
+//!
+//! The radar pattern renders a sweep line rotating around the center of a 2D layout,
+//! leaving a fading wedge behind it, like a radar or sonar screen.
+//!
+//! Each LED's brightness depends on how far its angle (around the center) trails behind
+//! the current angle of the sweep: brightest on the sweep line, fading to dark at the
+//! edge of the wedge.
+//!
+//! ## Example
+//!
+//! ```rust,ignore
+//! use blinksy::{
+//!     ControlBuilder,
+//!     layout::{Layout2d, Shape2d, Vec2},
+//!     layout2d,
+//!     patterns::radar::{Radar, RadarParams}
+//! };
+//!
+//! // Define a 2D layout
+//! layout2d!(
+//!     Layout,
+//!     [Shape2d::Grid {
+//!         start: Vec2::new(-1., -1.),
+//!         horizontal_end: Vec2::new(1., -1.),
+//!         vertical_end: Vec2::new(-1., 1.),
+//!         horizontal_pixel_count: 16,
+//!         vertical_pixel_count: 16,
+//!         serpentine: true,
+//!     }]
+//! );
+//!
+//! // Create a Radar pattern with custom parameters
+//! let control = ControlBuilder::new_2d()
+//!     .with_layout::<Layout, { Layout::PIXEL_COUNT }>()
+//!     .with_pattern::<Radar>(RadarParams {
+//!         speed: 0.25,
+//!         fade: 0.5,
+//!         ..Default::default()
+//!     })
+//!     .with_driver(/* Your driver */)
+//!     .with_frame_buffer_size::</* Length of frame buffer */>()
+//!     .build();
+//! ```
+
+use core::f32::consts::TAU;
+
+use num_traits::Euclid;
+#[allow(unused_imports)]
+use num_traits::Float;
+
+use crate::{color::Okhsv, layout::Layout2d, markers::Dim2d, pattern::Pattern};
+
+/// Configuration parameters for the Radar pattern.
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct RadarParams {
+    /// The color of the sweep
+    pub color: Okhsv,
+    /// Rotations of the sweep per second (negative for clockwise)
+    pub speed: f32,
+    /// The angular size of the fading wedge behind the sweep, as a fraction of a full
+    /// rotation (0.0 to 1.0)
+    pub fade: f32,
+}
+
+impl Default for RadarParams {
+    fn default() -> Self {
+        Self {
+            color: Okhsv::new(0.4, 1., 1.),
+            speed: 0.5,
+            fade: 0.25,
+        }
+    }
+}
+
+/// Radar pattern implementation.
+///
+/// Rotates a bright sweep line around the center of a 2D layout, with a fading wedge
+/// trailing behind it.
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct Radar {
+    /// Configuration parameters
+    params: RadarParams,
+}
+
+impl Radar {
+    /// Returns the brightness (0.0 to 1.0) of a position at a given sweep angle.
+    ///
+    /// Angles are in rotations (0.0 to 1.0), counter-clockwise from the positive x-axis.
+    fn brightness(&self, x: f32, y: f32, sweep: f32) -> f32 {
+        let fade = self.params.fade;
+        let angle = Euclid::rem_euclid(&(y.atan2(x) / TAU), &1.);
+        // How far the position trails behind the sweep, wrapping across the 0/1 boundary.
+        let behind = if self.params.speed < 0. {
+            Euclid::rem_euclid(&(angle - sweep), &1.)
+        } else {
+            Euclid::rem_euclid(&(sweep - angle), &1.)
+        };
+
+        if fade <= 0. || behind >= fade {
+            return 0.;
+        }
+        1. - behind / fade
+    }
+}
+
+impl<Layout> Pattern<Dim2d, Layout> for Radar
+where
+    Layout: Layout2d,
+{
+    type Params = RadarParams;
+    type Color = Okhsv;
+
+    /// Creates a new Radar pattern with the specified parameters.
+    fn new(params: Self::Params) -> Self {
+        Self { params }
+    }
+
+    /// Generates colors for a 2D layout.
+    ///
+    /// Each LED fades with its angular distance behind the sweep.
+    fn tick(&self, time_in_ms: u64) -> impl Iterator<Item = Self::Color> {
+        const MILLISECONDS_PER_SECOND: f32 = 1e3;
+
+        let color = self.params.color;
+        let rotations = time_in_ms as f32 / MILLISECONDS_PER_SECOND * self.params.speed;
+        let sweep = Euclid::rem_euclid(&rotations, &1.);
+
+        Layout::points().map(move |point| {
+            let brightness = self.brightness(point.x, point.y, sweep);
+            Okhsv::new(color.h, color.s, color.v * brightness)
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        layout::{Shape2d, Vec2},
+        layout2d,
+    };
+
+    // A single LED, straight up from the center (a quarter rotation).
+    layout2d!(Layout, [Shape2d::Point(Vec2::new(0., 1.))]);
+
+    fn brightness(pattern: &Radar, time_in_ms: u64) -> f32 {
+        Pattern::<Dim2d, Layout>::tick(pattern, time_in_ms)
+            .next()
+            .unwrap()
+            .v
+    }
+
+    #[test]
+    fn test_sweep_passes_pixel() {
+        let pattern: Radar = Pattern::<Dim2d, Layout>::new(RadarParams {
+            color: Okhsv::new(0., 0., 1.),
+            speed: 1.,
+            fade: 0.5,
+        });
+
+        // Before the sweep reaches the pixel
+        assert_eq!(brightness(&pattern, 0), 0.);
+        assert_eq!(brightness(&pattern, 200), 0.);
+        // On the sweep line
+        assert!((brightness(&pattern, 250) - 1.).abs() < 1e-4);
+        // Fading behind the sweep
+        assert!((brightness(&pattern, 500) - 0.5).abs() < 1e-4);
+        // Past the wedge
+        assert_eq!(brightness(&pattern, 800), 0.);
+        // Again on the next rotation
+        assert!((brightness(&pattern, 1250) - 1.).abs() < 1e-4);
+    }
+
+    #[test]
+    fn test_fade_wraps_boundary() {
+        let pattern: Radar = Pattern::<Dim2d, Layout>::new(RadarParams {
+            color: Okhsv::new(0., 0., 1.),
+            speed: -1.,
+            fade: 0.5,
+        });
+
+        // Clockwise, the sweep reaches the pixel (at 0.25) after 0.75 rotations, having
+        // wrapped from 0.0 to 1.0.
+        assert_eq!(brightness(&pattern, 600), 0.);
+        assert!((brightness(&pattern, 750) - 1.).abs() < 1e-4);
+        assert!((brightness(&pattern, 1000) - 0.5).abs() < 1e-4);
+    }
+}
@@ -4,6 +4,10 @@
 //! The colors flow through the full [`Hsv`] spectrum, creating a classic rainbow
 //! visual.
 //!
+//! By default, hues are mapped to colors like FastLED's `hsv2rgb_rainbow` (see
+//! [`RainbowHsv`](crate::color::RainbowHsv)), which matches the look of FastLED sketches.
+//! For a mathematically straight spectrum, use `Rainbow<HsvHueSpectrum>`.
+//!
 //! ## Example
 //!
 //! ```rust,ignore
@@ -23,27 +27,39 @@
 //!     .with_pattern::<Rainbow>(RainbowParams {
 //!         time_scalar: 0.1,
 //!         position_scalar: 1.0,
+//!         reverse: true,
+//!         ..Default::default()
 //!     })
 //!     .with_driver(/* Your driver */)
 //!     .with_frame_buffer_size::</* Length of frame buffer */>()
 //!     .build();
 //! ```
 
+use core::marker::PhantomData;
+
 use crate::{
-    color::{Hsv, HsvHueRainbow},
-    layout::{Layout1d, Layout2d, Layout3d},
+    color::{Hsv, HsvHueMap, HsvHueRainbow},
+    layout::{Layout1d, Layout2d, Layout3d, Vec2, Vec3},
     markers::{Dim1d, Dim2d, Dim3d},
     pattern::Pattern,
+    patterns::util::fill_by_position,
 };
 
 /// Configuration parameters for the Rainbow pattern.
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 #[cfg_attr(feature = "defmt", derive(defmt::Format))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct RainbowParams {
     /// Controls the speed of the animation (higher = faster)
     pub time_scalar: f32,
     /// Controls the spatial density of the rainbow (higher = more compressed)
     pub position_scalar: f32,
+    /// Reverses the direction of the rainbow across the layout
+    pub reverse: bool,
+    /// Mirrors the rainbow, so the hues are symmetric about the center of the layout
+    pub mirror: bool,
+    /// The axis along which the rainbow runs, for 2D and 3D layouts
+    pub axis: RainbowAxis,
 }
 
 impl Default for RainbowParams {
@@ -52,6 +68,62 @@ impl Default for RainbowParams {
         Self {
             time_scalar: 0.3 / MILLISECONDS_PER_SECOND,
             position_scalar: 1.,
+            reverse: false,
+            mirror: false,
+            axis: RainbowAxis::default(),
+        }
+    }
+}
+
+impl RainbowParams {
+    /// Returns the hue for a position along the rainbow, at a time.
+    fn hue(&self, position: f32, time: f32) -> f32 {
+        let position = if self.mirror {
+            position.abs()
+        } else {
+            position
+        };
+        let position = if self.reverse { -position } else { position };
+        position * 0.5 * self.position_scalar + time
+    }
+}
+
+/// The axis along which the rainbow runs, for 2D and 3D layouts.
+///
+/// 1D layouts always run along the strip.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum RainbowAxis {
+    /// Along the diagonal: the sum of every coordinate
+    #[default]
+    Diagonal,
+    /// Along the x axis
+    X,
+    /// Along the y axis
+    Y,
+    /// Along the z axis (for 2D layouts, the same hue everywhere)
+    Z,
+}
+
+impl RainbowAxis {
+    /// Returns the position of a 2D point along the axis.
+    fn position_2d(self, point: Vec2) -> f32 {
+        match self {
+            RainbowAxis::Diagonal => point.x + point.y,
+            RainbowAxis::X => point.x,
+            RainbowAxis::Y => point.y,
+            RainbowAxis::Z => 0.,
+        }
+    }
+
+    /// Returns the position of a 3D point along the axis.
+    fn position_3d(self, point: Vec3) -> f32 {
+        match self {
+            RainbowAxis::Diagonal => point.x + point.y + point.z,
+            RainbowAxis::X => point.x,
+            RainbowAxis::Y => point.y,
+            RainbowAxis::Z => point.z,
         }
     }
 }
@@ -59,23 +131,33 @@ impl Default for RainbowParams {
 /// Rainbow pattern implementation.
 ///
 /// Creates a smooth transition through the full HSV spectrum across the LED layout.
-#[derive(Debug)]
+///
+/// # Type Parameters
+///
+/// - `M` - The [`HsvHueMap`] used to map hues to colors, defaults to [`HsvHueRainbow`]
+#[derive(Debug, Clone)]
 #[cfg_attr(feature = "defmt", derive(defmt::Format))]
-pub struct Rainbow {
+pub struct Rainbow<M: HsvHueMap = HsvHueRainbow> {
     /// Configuration parameters
     params: RainbowParams,
+    /// Marker for the hue map
+    hue_map: PhantomData<M>,
 }
 
-impl<Layout> Pattern<Dim1d, Layout> for Rainbow
+impl<Layout, M> Pattern<Dim1d, Layout> for Rainbow<M>
 where
     Layout: Layout1d,
+    M: HsvHueMap,
 {
     type Params = RainbowParams;
-    type Color = Hsv<HsvHueRainbow>;
+    type Color = Hsv<M>;
 
     /// Creates a new Rainbow pattern with the specified parameters.
     fn new(params: Self::Params) -> Self {
-        Self { params }
+        Self {
+            params,
+            hue_map: PhantomData,
+        }
     }
 
     /// Generates colors for a 1D layout.
@@ -83,17 +165,11 @@ where
     /// The rainbow pattern creates a smooth transition of hues across the layout,
     /// which shifts over time to create a flowing effect.
     fn tick(&self, time_in_ms: u64) -> impl Iterator<Item = Self::Color> {
-        let Self { params } = self;
-        let RainbowParams {
-            time_scalar,
-            position_scalar,
-        } = params;
+        let Self { params, .. } = self;
+        let time = time_in_ms as f32 * params.time_scalar;
 
-        let time = time_in_ms as f32 * time_scalar;
-        let step = 0.5 * position_scalar;
-
-        Layout::points().map(move |x| {
-            let hue = x * step + time;
+        fill_by_position::<Dim1d, Layout, _>(move |x| {
+            let hue = params.hue(x, time);
             let saturation = 1.;
             let value = 1.;
             Self::Color::new(hue, saturation, value)
@@ -101,34 +177,32 @@ where
     }
 }
 
-impl<Layout> Pattern<Dim2d, Layout> for Rainbow
+impl<Layout, M> Pattern<Dim2d, Layout> for Rainbow<M>
 where
     Layout: Layout2d,
+    M: HsvHueMap,
 {
     type Params = RainbowParams;
-    type Color = Hsv<HsvHueRainbow>;
+    type Color = Hsv<M>;
 
     /// Creates a new Rainbow pattern with the specified parameters.
     fn new(params: Self::Params) -> Self {
-        Self { params }
+        Self {
+            params,
+            hue_map: PhantomData,
+        }
     }
 
     /// Generates colors for a 2D layout.
     ///
-    /// In 2D, the rainbow pattern uses the x-coordinate to determine hue,
+    /// In 2D, the rainbow pattern uses the position along its axis to determine hue,
     /// creating bands of color that move across the layout over time.
     fn tick(&self, time_in_ms: u64) -> impl Iterator<Item = Self::Color> {
-        let Self { params } = self;
-        let RainbowParams {
-            time_scalar,
-            position_scalar,
-        } = params;
+        let Self { params, .. } = self;
+        let time = time_in_ms as f32 * params.time_scalar;
 
-        let time = time_in_ms as f32 * time_scalar;
-        let step = 0.5 * position_scalar;
-
-        Layout::points().map(move |point| {
-            let hue = (point.x + point.y) * step + time;
+        fill_by_position::<Dim2d, Layout, _>(move |point| {
+            let hue = params.hue(params.axis.position_2d(point), time);
             let saturation = 1.;
             let value = 1.;
             Self::Color::new(hue, saturation, value)
@@ -136,37 +210,83 @@ where
     }
 }
 
-impl<Layout> Pattern<Dim3d, Layout> for Rainbow
+impl<Layout, M> Pattern<Dim3d, Layout> for Rainbow<M>
 where
     Layout: Layout3d,
+    M: HsvHueMap,
 {
     type Params = RainbowParams;
-    type Color = Hsv<HsvHueRainbow>;
+    type Color = Hsv<M>;
 
     /// Creates a new Rainbow pattern with the specified parameters.
     fn new(params: Self::Params) -> Self {
-        Self { params }
+        Self {
+            params,
+            hue_map: PhantomData,
+        }
     }
 
     /// Generates colors for a 3D layout.
     ///
-    /// In 3D, the rainbow pattern uses the x-coordinate to determine hue,
+    /// In 3D, the rainbow pattern uses the position along its axis to determine hue,
     /// creating bands of color that move across the layout over time.
     fn tick(&self, time_in_ms: u64) -> impl Iterator<Item = Self::Color> {
-        let Self { params } = self;
-        let RainbowParams {
-            time_scalar,
-            position_scalar,
-        } = params;
-
-        let time = time_in_ms as f32 * time_scalar;
-        let step = 0.5 * position_scalar;
+        let Self { params, .. } = self;
+        let time = time_in_ms as f32 * params.time_scalar;
 
-        Layout::points().map(move |point| {
-            let hue = (point.x + point.y + point.z) * step + time;
+        fill_by_position::<Dim3d, Layout, _>(move |point| {
+            let hue = params.hue(params.axis.position_3d(point), time);
             let saturation = 1.;
             let value = 1.;
             Self::Color::new(hue, saturation, value)
         })
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use heapless::Vec;
+
+    use super::*;
+    use crate::layout1d;
+
+    layout1d!(Layout, 5);
+
+    fn hues(params: RainbowParams) -> Vec<f32, 5> {
+        let rainbow: Rainbow = Pattern::<Dim1d, Layout>::new(params);
+        Pattern::<Dim1d, Layout>::tick(&rainbow, 1000)
+            .map(|color| color.hue.inner())
+            .collect()
+    }
+
+    fn assert_hues_eq(a: &[f32], b: &[f32]) {
+        for (a, b) in a.iter().zip(b) {
+            // Hues wrap around, so compare their distance on the color wheel.
+            let distance = (a - b).abs();
+            assert!(distance.min(1. - distance) < 1e-5, "{a:?} != {b:?}");
+        }
+    }
+
+    #[test]
+    fn test_reverse() {
+        let forward = hues(RainbowParams::default());
+        let mut reversed = hues(RainbowParams {
+            reverse: true,
+            ..Default::default()
+        });
+        reversed.reverse();
+        assert_hues_eq(&forward, &reversed);
+    }
+
+    #[test]
+    fn test_mirror() {
+        let mirrored = hues(RainbowParams {
+            mirror: true,
+            ..Default::default()
+        });
+        let mut reversed = mirrored.clone();
+        reversed.reverse();
+        assert_hues_eq(&mirrored, &reversed);
+        assert_ne!(mirrored[0], mirrored[2]);
+    }
+}
@@ -0,0 +1,272 @@
+//! # Raster Pattern
+//!
+//! The raster pattern shows an external image, such as a frame of live video or an 8-bit
+//! scene, on a 2D layout.
+//!
+//! [`Raster2d`] holds a `width` x `height` image of [`LinearSrgb`] pixels, by row from the
+//! left top corner. Update the image with [`Raster2d::set_frame`], and it is shown on the
+//! LEDs on the next tick.
+//!
+//! Each LED is mapped to the image by its position, so the order of LEDs (including
+//! serpentine wiring) is handled by the layout. When the image is a different size than
+//! the LED grid, choose how to sample the image with [`RasterSampling`].
+//!
+//! ## Example
+//!
+//! ```rust,ignore
+//! use blinksy::{
+//!     ControlBuilder,
+//!     color::LinearSrgb,
+//!     layout2d,
+//!     layout::{Layout2d, Shape2d, Vec2},
+//!     patterns::raster::{Raster2d, RasterParams, RasterSampling},
+//! };
+//!
+//! // Define a 2D layout
+//! layout2d!(
+//!     Layout,
+//!     [Shape2d::Grid {
+//!         start: Vec2::new(-1., -1.),
+//!         horizontal_end: Vec2::new(1., -1.),
+//!         vertical_end: Vec2::new(-1., 1.),
+//!         horizontal_pixel_count: 16,
+//!         vertical_pixel_count: 16,
+//!         serpentine: true,
+//!     }]
+//! );
+//!
+//! let mut control = ControlBuilder::new_2d()
+//!     .with_layout::<Layout, { Layout::PIXEL_COUNT }>()
+//!     .with_pattern::<Raster2d<4096>>(RasterParams {
+//!         width: 64,
+//!         height: 64,
+//!         sampling: RasterSampling::Bilinear,
+//!     })
+//!     .with_driver(/* Your driver */)
+//!     .with_frame_buffer_size::</* Length of frame buffer */>()
+//!     .build();
+//!
+//! loop {
+//!     let frame: [LinearSrgb; 4096] = /* Your image */;
+//!     control.pattern_mut().set_frame(&frame);
+//!     control.tick(/* current time in milliseconds */).unwrap();
+//! }
+//! ```
+
+use heapless::Vec;
+#[allow(unused_imports)]
+use num_traits::Float;
+
+use crate::{
+    color::{blend::lerp, LinearSrgb},
+    layout::Layout2d,
+    markers::Dim2d,
+    pattern::Pattern,
+    patterns::util::nearest,
+};
+
+/// Configuration parameters for the Raster pattern.
+#[derive(Debug, Clone, Copy, Default)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct RasterParams {
+    /// Number of image pixels along each horizontal row
+    pub width: usize,
+    /// Number of image pixels along each vertical column
+    pub height: usize,
+    /// How to sample the image at the position of each LED
+    pub sampling: RasterSampling,
+}
+
+/// How to sample a [`Raster2d`] image at the position of each LED.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum RasterSampling {
+    /// The color of the nearest image pixel: sharp, for an image the size of the LED grid
+    #[default]
+    Nearest,
+    /// Interpolated between the 4 nearest image pixels, in [`LinearSrgb`]: smooth, for an
+    /// image larger or smaller than the LED grid
+    Bilinear,
+}
+
+/// Raster pattern implementation.
+///
+/// A `width` x `height` image of pixels, sampled at the position of each LED.
+///
+/// # Type Parameters
+///
+/// - `N` - The maximum number of image pixels (at least `width` x `height`)
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct Raster2d<const N: usize> {
+    /// Image pixels, by row from the top
+    pixels: Vec<LinearSrgb, N>,
+    /// Configuration parameters
+    params: RasterParams,
+}
+
+impl<const N: usize> Raster2d<N> {
+    /// Sets the image, by row from the left top corner.
+    ///
+    /// Pixels beyond `N` are ignored. Pixels missing from the image are black.
+    ///
+    /// # Arguments
+    ///
+    /// - `frame` - The image pixels, `width` x `height` long
+    pub fn set_frame(&mut self, frame: &[LinearSrgb]) {
+        self.pixels.clear();
+        self.pixels.extend(frame.iter().copied().take(N));
+    }
+
+    /// Returns the image pixels, by row from the left top corner.
+    pub fn frame(&self) -> &[LinearSrgb] {
+        &self.pixels
+    }
+
+    /// Returns the image pixel at the given column and row, if within bounds.
+    pub fn pixel(&self, column: usize, row: usize) -> Option<LinearSrgb> {
+        let RasterParams { width, height, .. } = self.params;
+        if column >= width || row >= height {
+            return None;
+        }
+        Some(
+            self.pixels
+                .get(row * width + column)
+                .copied()
+                .unwrap_or(LinearSrgb::new(0., 0., 0.)),
+        )
+    }
+
+    /// Returns the color of the image at a 2D layout position.
+    fn sample(&self, x: f32, y: f32) -> LinearSrgb {
+        // Layout y increases towards the top, image rows increase towards the bottom.
+        match self.params.sampling {
+            RasterSampling::Nearest => {
+                let column = nearest(x, self.params.width);
+                let row = nearest(-y, self.params.height);
+                self.pixel_or_black(column, row)
+            }
+            RasterSampling::Bilinear => {
+                let (left, right, horizontal) = Self::between(x, self.params.width);
+                let (top, bottom, vertical) = Self::between(-y, self.params.height);
+                let upper = lerp(
+                    self.pixel_or_black(left, top),
+                    self.pixel_or_black(right, top),
+                    horizontal,
+                );
+                let lower = lerp(
+                    self.pixel_or_black(left, bottom),
+                    self.pixel_or_black(right, bottom),
+                    horizontal,
+                );
+                lerp(upper, lower, vertical)
+            }
+        }
+    }
+
+    /// Returns the image pixel at the given column and row, or black if out of bounds.
+    fn pixel_or_black(&self, column: usize, row: usize) -> LinearSrgb {
+        self.pixel(column, row)
+            .unwrap_or(LinearSrgb::new(0., 0., 0.))
+    }
+
+    /// Maps a position between -1.0 and 1.0 to the indices on either side, of `count`
+    /// indices, and the fraction of the way between them.
+    fn between(position: f32, count: usize) -> (usize, usize, f32) {
+        let max_index = count.saturating_sub(1);
+        let index = ((position + 1.) / 2. * max_index as f32).clamp(0., max_index as f32);
+        let before = index.floor();
+        let before_index = before as usize;
+        let after_index = (before_index + 1).min(max_index);
+        (before_index, after_index, index - before)
+    }
+}
+
+impl<const N: usize, Layout> Pattern<Dim2d, Layout> for Raster2d<N>
+where
+    Layout: Layout2d,
+{
+    type Params = RasterParams;
+    type Color = LinearSrgb;
+
+    /// Creates a new, black image.
+    fn new(params: Self::Params) -> Self {
+        Self {
+            pixels: Vec::new(),
+            params,
+        }
+    }
+
+    /// Generates colors for a 2D layout.
+    ///
+    /// Each LED takes the color of the image pixel nearest its position.
+    fn tick(&self, _time_in_ms: u64) -> impl Iterator<Item = Self::Color> {
+        Layout::points().map(move |point| self.sample(point.x, point.y))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        layout::{Shape2d, Vec2},
+        layout2d,
+    };
+
+    layout2d!(
+        Layout,
+        [Shape2d::Grid {
+            start: Vec2::new(-1., -1.),
+            horizontal_end: Vec2::new(1., -1.),
+            vertical_end: Vec2::new(-1., 1.),
+            horizontal_pixel_count: 2,
+            vertical_pixel_count: 2,
+            serpentine: true,
+        }]
+    );
+
+    #[test]
+    fn test_2x2_raster_on_serpentine_2x2_grid() {
+        let red = LinearSrgb::new(1., 0., 0.);
+        let green = LinearSrgb::new(0., 1., 0.);
+        let blue = LinearSrgb::new(0., 0., 1.);
+        let white = LinearSrgb::new(1., 1., 1.);
+
+        let mut raster: Raster2d<4> = Pattern::<Dim2d, Layout>::new(RasterParams {
+            width: 2,
+            height: 2,
+            sampling: RasterSampling::Nearest,
+        });
+        // Top row: red, green. Bottom row: blue, white.
+        raster.set_frame(&[red, green, blue, white]);
+
+        let colors: Vec<LinearSrgb, 4> = Pattern::<Dim2d, Layout>::tick(&raster, 0).collect();
+        // LEDs start at the left bottom, with the second row running back from the right.
+        assert_eq!(colors.as_slice(), [blue, white, green, red]);
+    }
+
+    layout2d!(FractionalLayout, [Shape2d::Point(Vec2::new(-0.5, 0.5))]);
+
+    #[test]
+    fn test_bilinear_at_fractional_coordinate() {
+        let black = LinearSrgb::new(0., 0., 0.);
+        let red = LinearSrgb::new(1., 0., 0.);
+        let blue = LinearSrgb::new(0., 0., 1.);
+
+        let mut raster: Raster2d<4> = Pattern::<Dim2d, FractionalLayout>::new(RasterParams {
+            width: 2,
+            height: 2,
+            sampling: RasterSampling::Bilinear,
+        });
+        // Top row: black, red. Bottom row: blue, black.
+        raster.set_frame(&[black, red, blue, black]);
+
+        // A quarter of the way to the right, and a quarter of the way down.
+        let color = Pattern::<Dim2d, FractionalLayout>::tick(&raster, 0)
+            .next()
+            .unwrap();
+        assert_eq!(color, LinearSrgb::new(0.75 * 0.25, 0., 0.25 * 0.75));
+    }
+}
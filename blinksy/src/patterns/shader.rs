@@ -0,0 +1,128 @@
+//! # Shader Pattern
+//!
+//! The shader pattern computes each LED's color from a closure of its position and the
+//! time, like a fragment shader. For quick experiments and one-off effects, without
+//! defining a pattern type.
+//!
+//! The closure receives a [`Position`] and the time in milliseconds, for a layout of any
+//! dimension. To get the point of the dimension, convert the position with
+//! [`From`]: an `f32` from -1.0 to 1.0 for 1D, a [`Vec2`](crate::layout::Vec2) for 2D, or a
+//! [`Vec3`](crate::layout::Vec3) for 3D.
+//!
+//! To define a reusable pattern with params, see [`pattern!`](crate::pattern!).
+//!
+//! ## Example
+//!
+//! ```rust,ignore
+//! use blinksy::{
+//!     ControlBuilder,
+//!     color::Hsv,
+//!     layout::{Layout1d, Position},
+//!     layout1d,
+//!     patterns::shader::Shader
+//! };
+//!
+//! // Define a 1D layout
+//! layout1d!(Layout, 60);
+//!
+//! // A rainbow, shifting over time
+//! let control = ControlBuilder::new_1d()
+//!     .with_layout::<Layout, { Layout::PIXEL_COUNT }>()
+//!     .with_pattern::<Shader<_>>(|position: Position, time_in_ms: u64| {
+//!         Hsv::new(position.normalized + time_in_ms as f32 * 1e-4, 1., 1.)
+//!     })
+//!     .with_driver(/* Your driver */)
+//!     .with_frame_buffer_size::</* Length of frame buffer */>()
+//!     .build();
+//! ```
+
+use crate::{
+    layout::{LayoutForDim, Position},
+    pattern::Pattern,
+};
+
+/// Shader pattern implementation.
+///
+/// Maps each LED's position through a closure.
+///
+/// # Type Parameters
+///
+/// - `F` - The closure, from a [`Position`] and the time in milliseconds to a color
+#[derive(Debug, Clone)]
+pub struct Shader<F> {
+    /// The closure
+    shader: F,
+}
+
+impl<F, Color, Dim, Layout> Pattern<Dim, Layout> for Shader<F>
+where
+    F: Fn(Position, u64) -> Color,
+    Layout: LayoutForDim<Dim>,
+{
+    type Params = F;
+    type Color = Color;
+
+    /// Creates a new Shader pattern from a closure.
+    fn new(shader: Self::Params) -> Self {
+        Self { shader }
+    }
+
+    /// Generates colors for a layout of any dimension.
+    fn tick(&self, time_in_ms: u64) -> impl Iterator<Item = Self::Color> {
+        Layout::layout_positions().map(move |position| (self.shader)(position, time_in_ms))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use heapless::Vec;
+
+    use super::*;
+    use crate::{
+        layout::{Shape2d, Vec2},
+        layout1d, layout2d,
+        markers::{Dim1d, Dim2d},
+    };
+
+    layout1d!(Strip, 5);
+
+    layout2d!(
+        Grid,
+        [Shape2d::Grid {
+            start: Vec2::new(-1., -1.),
+            horizontal_end: Vec2::new(1., -1.),
+            vertical_end: Vec2::new(-1., 1.),
+            horizontal_pixel_count: 2,
+            vertical_pixel_count: 2,
+            serpentine: false,
+        }]
+    );
+
+    #[test]
+    fn test_1d() {
+        let shader = |position: Position, time_in_ms: u64| {
+            (
+                position.index,
+                f32::from(position),
+                position.normalized,
+                time_in_ms,
+            )
+        };
+        let pattern = <Shader<_> as Pattern<Dim1d, Strip>>::new(shader);
+        let values: Vec<_, 5> = Pattern::<Dim1d, Strip>::tick(&pattern, 7).collect();
+        assert_eq!(values[0], (0, -1., 0., 7));
+        assert_eq!(values[2], (2, 0., 0.5, 7));
+        assert_eq!(values[4], (4, 1., 1., 7));
+    }
+
+    #[test]
+    fn test_2d() {
+        let shader = |position: Position, _| {
+            let point = Vec2::from(position);
+            point.x + point.y
+        };
+        let pattern = <Shader<_> as Pattern<Dim2d, Grid>>::new(shader);
+        let values: Vec<f32, 4> = Pattern::<Dim2d, Grid>::tick(&pattern, 0).collect();
+        assert_eq!(values.as_slice(), [-2., 0., 0., 2.]);
+    }
+}
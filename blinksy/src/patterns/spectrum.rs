@@ -0,0 +1,290 @@
+//! # Spectrum Pattern
+//!
+//! The spectrum pattern shows the frequencies of audio as bars on a 2D grid, like the
+//! spectrum analyzer of a graphic equalizer.
+//!
+//! The grid is divided into `COLUMNS` equal columns across its width, from the lowest
+//! frequency on the left to the highest on the right. Each column is lit from the bottom,
+//! up to a height proportional to the magnitude of its frequencies. The color of each lit
+//! LED goes from [`SpectrumParams::low_color`] at the bottom to
+//! [`SpectrumParams::high_color`] at the top.
+//!
+//! To avoid flicker, each column rises towards a new magnitude over
+//! [`SpectrumParams::attack_in_ms`], and falls over [`SpectrumParams::decay_in_ms`].
+//!
+//! The spectrum is read from an [`AudioSource`] with [`Spectrum::update`], or set
+//! directly with [`Spectrum::set_spectrum`], which groups any number of frequency bins
+//! into the columns.
+//!
+//! ## Example
+//!
+//! ```rust,ignore
+//! use blinksy::{
+//!     ControlBuilder,
+//!     layout::{Layout2d, Shape2d, Vec2},
+//!     layout2d,
+//!     patterns::spectrum::{Spectrum, SpectrumParams}
+//! };
+//!
+//! // Define a 2D layout
+//! layout2d!(
+//!     Layout,
+//!     [Shape2d::Grid {
+//!         start: Vec2::new(-1., -1.),
+//!         horizontal_end: Vec2::new(1., -1.),
+//!         vertical_end: Vec2::new(-1., 1.),
+//!         horizontal_pixel_count: 16,
+//!         vertical_pixel_count: 16,
+//!         serpentine: true,
+//!     }]
+//! );
+//!
+//! // Create a Spectrum pattern with a column for each column of the grid
+//! let mut control = ControlBuilder::new_2d()
+//!     .with_layout::<Layout, { Layout::PIXEL_COUNT }>()
+//!     .with_pattern::<Spectrum<16>>(SpectrumParams::default())
+//!     .with_driver(/* Your driver */)
+//!     .with_frame_buffer_size::</* Length of frame buffer */>()
+//!     .build();
+//!
+//! loop {
+//!     control.pattern_mut().update(&mut /* Your audio source */);
+//!     control.tick(/* current time in milliseconds */).unwrap();
+//! }
+//! ```
+
+use core::cell::Cell;
+
+#[allow(unused_imports)]
+use num_traits::Float;
+
+use crate::{audio::AudioSource, color::Okhsv, layout::Layout2d, markers::Dim2d, pattern::Pattern};
+
+/// Configuration parameters for the Spectrum pattern.
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct SpectrumParams {
+    /// The color at the bottom of the grid (low intensity)
+    pub low_color: Okhsv,
+    /// The color at the top of the grid (high intensity)
+    pub high_color: Okhsv,
+    /// Time constant for a column to rise, in milliseconds (0 for instant)
+    pub attack_in_ms: u64,
+    /// Time constant for a column to fall, in milliseconds (0 for instant)
+    pub decay_in_ms: u64,
+}
+
+impl Default for SpectrumParams {
+    fn default() -> Self {
+        Self {
+            low_color: Okhsv::new(0.4, 1., 1.),
+            high_color: Okhsv::new(0.08, 1., 1.),
+            attack_in_ms: 20,
+            decay_in_ms: 200,
+        }
+    }
+}
+
+/// Spectrum pattern implementation.
+///
+/// Lights a bar in each column, proportional to the magnitude of its frequencies.
+///
+/// # Type Parameters
+///
+/// - `COLUMNS` - The number of columns the grid is divided into
+#[derive(Debug, Clone)]
+pub struct Spectrum<const COLUMNS: usize> {
+    /// Configuration parameters
+    params: SpectrumParams,
+    /// The latest magnitude of each column (0.0 to 1.0)
+    targets: [f32; COLUMNS],
+    /// The smoothed magnitude of each column (0.0 to 1.0)
+    magnitudes: [Cell<f32>; COLUMNS],
+    /// The time of the last tick
+    last_tick_in_ms: Cell<Option<u64>>,
+}
+
+impl<const COLUMNS: usize> Spectrum<COLUMNS> {
+    /// Sets the magnitudes of the frequency bins, from lowest to highest frequency.
+    ///
+    /// The bins are grouped into the columns: each column takes the average of its bins.
+    /// If there are fewer bins than columns, neighbouring columns share a bin.
+    ///
+    /// # Arguments
+    ///
+    /// - `bins` - The magnitude of each frequency bin (0.0 to 1.0), clamped
+    pub fn set_spectrum(&mut self, bins: &[f32]) {
+        if bins.is_empty() {
+            return;
+        }
+        for (column, target) in self.targets.iter_mut().enumerate() {
+            let start = column * bins.len() / COLUMNS;
+            let end = ((column + 1) * bins.len() / COLUMNS).max(start + 1);
+            let group = &bins[start..end];
+            let average = group.iter().sum::<f32>() / group.len() as f32;
+            *target = average.clamp(0., 1.);
+        }
+    }
+
+    /// Reads the current spectrum from an audio source, with a bin for each column.
+    ///
+    /// # Arguments
+    ///
+    /// - `source` - The audio source
+    pub fn update(&mut self, source: &mut impl AudioSource) {
+        let bins: [f32; COLUMNS] = source.spectrum();
+        self.set_spectrum(&bins);
+    }
+
+    /// Moves each column's magnitude towards its target, by the time since the last tick.
+    fn smooth(&self, time_in_ms: u64) -> [f32; COLUMNS] {
+        let elapsed_in_ms = self.last_tick_in_ms.get().map_or(0, |last_tick_in_ms| {
+            time_in_ms.saturating_sub(last_tick_in_ms)
+        });
+        self.last_tick_in_ms.set(Some(time_in_ms));
+
+        let alpha = |time_constant_in_ms: u64| {
+            if time_constant_in_ms == 0 {
+                1.
+            } else {
+                1. - (-(elapsed_in_ms as f32) / time_constant_in_ms as f32).exp()
+            }
+        };
+        let attack = alpha(self.params.attack_in_ms);
+        let decay = alpha(self.params.decay_in_ms);
+
+        core::array::from_fn(|column| {
+            let magnitude = self.magnitudes[column].get();
+            let target = self.targets[column];
+            let alpha = if target > magnitude { attack } else { decay };
+            let magnitude = magnitude + (target - magnitude) * alpha;
+            // Snap to the target, so a column falls all the way to zero.
+            let magnitude = if (target - magnitude).abs() < 1e-3 {
+                target
+            } else {
+                magnitude
+            };
+            self.magnitudes[column].set(magnitude);
+            magnitude
+        })
+    }
+
+    /// Returns the column index for a horizontal position between -1.0 and 1.0.
+    fn column_index(x: f32) -> usize {
+        let normalized = (x + 1.) / 2.;
+        ((normalized * COLUMNS as f32) as usize).min(COLUMNS.saturating_sub(1))
+    }
+}
+
+impl<const COLUMNS: usize, Layout> Pattern<Dim2d, Layout> for Spectrum<COLUMNS>
+where
+    Layout: Layout2d,
+{
+    type Params = SpectrumParams;
+    type Color = Okhsv;
+
+    /// Creates a new Spectrum pattern with the specified parameters.
+    fn new(params: Self::Params) -> Self {
+        Self {
+            params,
+            targets: [0.; COLUMNS],
+            magnitudes: core::array::from_fn(|_| Cell::new(0.)),
+            last_tick_in_ms: Cell::new(None),
+        }
+    }
+
+    /// Generates colors for a 2D layout.
+    ///
+    /// Each LED is lit if its height (from -1.0 at the bottom to 1.0 at the top) is within
+    /// the magnitude of its column.
+    fn tick(&self, time_in_ms: u64) -> impl Iterator<Item = Self::Color> {
+        let magnitudes = self.smooth(time_in_ms);
+        let SpectrumParams {
+            low_color,
+            high_color,
+            ..
+        } = self.params;
+
+        Layout::points().map(move |point| {
+            let magnitude = magnitudes[Self::column_index(point.x)];
+            let height = (point.y + 1.) / 2.;
+            if magnitude <= 0. || height > magnitude {
+                return Okhsv::new(0., 0., 0.);
+            }
+            Okhsv::new(
+                low_color.h + (high_color.h - low_color.h) * height,
+                low_color.s + (high_color.s - low_color.s) * height,
+                low_color.v + (high_color.v - low_color.v) * height,
+            )
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        layout::{Shape2d, Vec2},
+        layout2d,
+    };
+
+    layout2d!(
+        Layout,
+        [Shape2d::Grid {
+            start: Vec2::new(-1., -1.),
+            horizontal_end: Vec2::new(1., -1.),
+            vertical_end: Vec2::new(-1., 1.),
+            horizontal_pixel_count: 4,
+            vertical_pixel_count: 8,
+            serpentine: false,
+        }]
+    );
+
+    /// Returns the number of lit LEDs in each column.
+    fn heights(pattern: &Spectrum<4>, time_in_ms: u64) -> [usize; 4] {
+        let mut heights = [0; 4];
+        for (point, color) in
+            Layout::points().zip(Pattern::<Dim2d, Layout>::tick(pattern, time_in_ms))
+        {
+            if color.v > 0. {
+                heights[Spectrum::<4>::column_index(point.x)] += 1;
+            }
+        }
+        heights
+    }
+
+    #[test]
+    fn test_column_heights() {
+        let mut pattern: Spectrum<4> = Pattern::<Dim2d, Layout>::new(SpectrumParams {
+            attack_in_ms: 0,
+            decay_in_ms: 0,
+            ..Default::default()
+        });
+
+        // 8 bins are grouped into 4 columns.
+        pattern.set_spectrum(&[1., 1., 0.5, 0.5, 0.25, 0.25, 0., 0.]);
+        assert_eq!(heights(&pattern, 0), [8, 4, 2, 0]);
+
+        // 2 bins are shared by 4 columns.
+        pattern.set_spectrum(&[1., 0.5]);
+        assert_eq!(heights(&pattern, 10), [8, 8, 4, 4]);
+    }
+
+    #[test]
+    fn test_smoothing() {
+        let mut pattern: Spectrum<4> = Pattern::<Dim2d, Layout>::new(SpectrumParams {
+            attack_in_ms: 0,
+            decay_in_ms: 100,
+            ..Default::default()
+        });
+
+        pattern.set_spectrum(&[1.; 4]);
+        assert_eq!(heights(&pattern, 0), [8; 4]);
+
+        // After one time constant, a column has fallen to about 37% of the way.
+        pattern.set_spectrum(&[0.; 4]);
+        assert_eq!(heights(&pattern, 0), [8; 4]);
+        assert_eq!(heights(&pattern, 100), [3; 4]);
+        assert_eq!(heights(&pattern, 1000), [0; 4]);
+    }
+}
@@ -0,0 +1,235 @@
+//! # Starfield Pattern
+//!
+//! The starfield pattern flies stars toward the viewer through a 3D layout, like a
+//! spaceship travelling through space.
+//!
+//! Each of the `STARS` stars moves along +Z, from the far plane (-1.0) to the near plane
+//! (1.0). When a star passes the near plane, it respawns at the far plane at a new random
+//! position. Stars brighten as they come closer.
+//!
+//! Each LED is colored by its nearest star, if within [`StarfieldParams::radius`].
+//!
+//! Movement is based on the time given to each tick, not on the number of ticks, so stars
+//! move at the same speed regardless of frame rate.
+//!
+//! ## Example
+//!
+//! ```rust,ignore
+//! use blinksy::{
+//!     ControlBuilder,
+//!     layout::{Layout3d, Shape3d, Vec3},
+//!     layout3d,
+//!     patterns::starfield::{Starfield, StarfieldParams}
+//! };
+//!
+//! // Define a 3D layout
+//! layout3d!(
+//!     Layout,
+//!     [/* Your shapes */]
+//! );
+//!
+//! // Create a Starfield pattern with 8 stars
+//! let control = ControlBuilder::new_3d()
+//!     .with_layout::<Layout, { Layout::PIXEL_COUNT }>()
+//!     .with_pattern::<Starfield<8>>(StarfieldParams::default())
+//!     .with_driver(/* Your driver */)
+//!     .with_frame_buffer_size::</* Length of frame buffer */>()
+//!     .build();
+//! ```
+
+use core::cell::Cell;
+
+use heapless::Vec;
+use num_traits::Euclid;
+
+use crate::{
+    color::Okhsv,
+    layout::{Layout3d, Vec3},
+    markers::Dim3d,
+    pattern::Pattern,
+    util::rng::{hash, Rng},
+};
+
+/// Configuration parameters for the Starfield pattern.
+///
+/// Distances are in layout units, where the layout spans from -1.0 to 1.0 (2.0 in total).
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct StarfieldParams {
+    /// The color of a star at the near plane
+    pub color: Okhsv,
+    /// How fast the stars fly toward the viewer, in layout units per second
+    pub speed: f32,
+    /// How far from a star an LED is lit, in layout units
+    pub radius: f32,
+    /// Seed for the random star positions
+    pub seed: u32,
+}
+
+impl Default for StarfieldParams {
+    fn default() -> Self {
+        Self {
+            color: Okhsv::new(0.6, 0.1, 1.),
+            speed: 1.,
+            radius: 0.3,
+            seed: 0,
+        }
+    }
+}
+
+/// Starfield pattern implementation.
+///
+/// Stars flying along +Z, respawning at the far plane once past the near plane.
+///
+/// # Type Parameters
+///
+/// - `STARS` - The number of stars
+#[derive(Debug, Clone)]
+pub struct Starfield<const STARS: usize> {
+    /// Configuration parameters
+    params: StarfieldParams,
+    /// The position of each star
+    stars: Vec<Cell<Vec3>, STARS>,
+    /// The number of stars spawned, to seed the position of the next
+    spawn_count: Cell<u32>,
+    /// The time of the last tick
+    last_tick_in_ms: Cell<Option<u64>>,
+}
+
+impl<const STARS: usize> Starfield<STARS> {
+    /// Returns a random star position, with Z from the far plane.
+    ///
+    /// # Arguments
+    ///
+    /// - `z` - The depth of the star
+    fn spawn(&self, z: f32) -> Vec3 {
+        let spawn_count = self.spawn_count.get();
+        self.spawn_count.set(spawn_count.wrapping_add(1));
+
+        let mut rng = Rng::new(self.params.seed ^ hash(spawn_count));
+        let x = rng.next_f32() * 2. - 1.;
+        let y = rng.next_f32() * 2. - 1.;
+        Vec3::new(x, y, z)
+    }
+
+    /// Moves each star toward the viewer, by the time since the last tick.
+    ///
+    /// A star which passes the near plane respawns at the far plane, carrying over how far
+    /// it passed.
+    fn update(&self, time_in_ms: u64) {
+        const MILLISECONDS_PER_SECOND: f32 = 1e3;
+
+        let elapsed_in_ms = self.last_tick_in_ms.get().map_or(0, |last_tick_in_ms| {
+            time_in_ms.saturating_sub(last_tick_in_ms)
+        });
+        self.last_tick_in_ms.set(Some(time_in_ms));
+
+        let distance = elapsed_in_ms as f32 * self.params.speed / MILLISECONDS_PER_SECOND;
+        for star in self.stars.iter() {
+            let position = star.get();
+            let z = position.z + distance;
+            if z > 1. {
+                let z = Euclid::rem_euclid(&(z + 1.), &2.) - 1.;
+                star.set(self.spawn(z));
+            } else {
+                star.set(Vec3::new(position.x, position.y, z));
+            }
+        }
+    }
+
+    /// Returns the color of an LED at a position, from its nearest star.
+    fn color(&self, point: Vec3) -> Okhsv {
+        let StarfieldParams { color, radius, .. } = self.params;
+
+        let nearest = self
+            .stars
+            .iter()
+            .map(|star| star.get())
+            .map(|star| (star, star.distance(point)))
+            .min_by(|(_, a), (_, b)| a.total_cmp(b));
+
+        match nearest {
+            Some((star, distance)) if distance < radius => {
+                let proximity = (star.z + 1.) / 2.;
+                let falloff = 1. - distance / radius;
+                Okhsv::new(color.h, color.s, color.v * proximity * falloff)
+            }
+            _ => Okhsv::new(0., 0., 0.),
+        }
+    }
+}
+
+impl<const STARS: usize, Layout> Pattern<Dim3d, Layout> for Starfield<STARS>
+where
+    Layout: Layout3d,
+{
+    type Params = StarfieldParams;
+    type Color = Okhsv;
+
+    /// Creates a new Starfield pattern with the specified parameters.
+    ///
+    /// The stars start spread between the far and near planes.
+    fn new(params: Self::Params) -> Self {
+        let mut starfield = Self {
+            params,
+            stars: Vec::new(),
+            spawn_count: Cell::new(0),
+            last_tick_in_ms: Cell::new(None),
+        };
+        for index in 0..STARS {
+            let z = -1. + 2. * index as f32 / STARS as f32;
+            let star = Cell::new(starfield.spawn(z));
+            // The Vec has a capacity of exactly STARS.
+            let _ = starfield.stars.push(star);
+        }
+        starfield
+    }
+
+    /// Generates colors for a 3D layout.
+    fn tick(&self, time_in_ms: u64) -> impl Iterator<Item = Self::Color> {
+        self.update(time_in_ms);
+
+        Layout::points().map(move |point| self.color(point))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{layout::Shape3d, layout3d};
+
+    layout3d!(
+        Layout,
+        [Shape3d::Line {
+            start: Vec3::new(0., 0., -1.),
+            end: Vec3::new(0., 0., 1.),
+            pixel_count: 9,
+        }]
+    );
+
+    fn stars(pattern: &Starfield<4>) -> [Vec3; 4] {
+        core::array::from_fn(|index| pattern.stars[index].get())
+    }
+
+    #[test]
+    fn test_respawn() {
+        let pattern: Starfield<4> = Pattern::<Dim3d, Layout>::new(StarfieldParams::default());
+        Pattern::<Dim3d, Layout>::tick(&pattern, 0).for_each(drop);
+        let before = stars(&pattern);
+        // Stars start at -1.0, -0.5, 0.0, and 0.5.
+        assert_eq!(before.map(|star| star.z), [-1., -0.5, 0., 0.5]);
+
+        // Move 0.75 forward, so the last star passes the near plane.
+        Pattern::<Dim3d, Layout>::tick(&pattern, 750).for_each(drop);
+        let after = stars(&pattern);
+        assert_eq!(after.map(|star| star.z), [-0.25, 0.25, 0.75, -0.75]);
+
+        // Stars keep their position, until respawned at a new position.
+        for index in 0..3 {
+            assert_eq!(after[index].x, before[index].x);
+            assert_eq!(after[index].y, before[index].y);
+        }
+        assert_ne!(after[3].x, before[3].x);
+        assert_ne!(after[3].y, before[3].y);
+    }
+}
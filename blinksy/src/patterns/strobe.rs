@@ -0,0 +1,206 @@
+//! # Strobe Pattern
+//!
+//! The strobe pattern flashes every LED on and off, like a strobe light.
+//!
+//! Each flash is a period of `1 / frequency_hz` seconds: the LEDs are on for the first
+//! `duty` fraction of the period, and off for the rest. To flash on the beat instead, set a
+//! [`BeatClock`], which replaces the frequency with one flash per beat.
+//!
+//! Note: The strobe is only as fast as the frame rate. A flash shorter than a frame may be
+//! missed, and a frequency near or above the frame rate aliases into a slower, irregular
+//! flicker. Keep the frequency well below the frame rate, such as below a third of it.
+//!
+//! ## Example
+//!
+//! ```rust,ignore
+//! use blinksy::{
+//!     ControlBuilder,
+//!     color::Hsv,
+//!     layout::Layout1d,
+//!     layout1d,
+//!     patterns::strobe::{Strobe, StrobeParams}
+//! };
+//!
+//! // Define a 1D layout
+//! layout1d!(Layout, 60);
+//!
+//! // Create a white Strobe pattern, flashing 10 times per second
+//! let control = ControlBuilder::new_1d()
+//!     .with_layout::<Layout, { Layout::PIXEL_COUNT }>()
+//!     .with_pattern::<Strobe>(StrobeParams {
+//!         frequency_hz: 10.,
+//!         duty: 0.2,
+//!         ..Default::default()
+//!     })
+//!     .with_driver(/* Your driver */)
+//!     .with_frame_buffer_size::</* Length of frame buffer */>()
+//!     .build();
+//! ```
+
+use num_traits::Euclid;
+
+use crate::{
+    color::Hsv,
+    layout::{Layout1d, Layout2d, Layout3d},
+    markers::{Dim1d, Dim2d, Dim3d},
+    pattern::Pattern,
+    time::BeatClock,
+};
+
+/// Configuration parameters for the Strobe pattern.
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct StrobeParams {
+    /// The color of a flash
+    pub color: Hsv,
+    /// How many times to flash per second
+    pub frequency_hz: f32,
+    /// The fraction of each period which is on (0.0 to 1.0)
+    pub duty: f32,
+    /// A clock to flash once per beat, instead of at `frequency_hz`
+    pub beat: Option<BeatClock>,
+}
+
+impl Default for StrobeParams {
+    fn default() -> Self {
+        Self {
+            color: Hsv::new(0., 0., 1.),
+            frequency_hz: 5.,
+            duty: 0.25,
+            beat: None,
+        }
+    }
+}
+
+/// Strobe pattern implementation.
+///
+/// Flashes every LED on and off, with a duty cycle.
+#[derive(Debug, Clone)]
+pub struct Strobe {
+    /// Configuration parameters
+    params: StrobeParams,
+}
+
+impl Strobe {
+    /// Returns the phase within the current period, from 0.0 to 1.0.
+    fn phase(&self, time_in_ms: u64) -> f32 {
+        const MILLISECONDS_PER_SECOND: f64 = 1e3;
+
+        match self.params.beat {
+            Some(beat) => beat.phase(time_in_ms),
+            None => {
+                // In f64, so the phase stays precise for long running times.
+                let periods =
+                    time_in_ms as f64 * self.params.frequency_hz as f64 / MILLISECONDS_PER_SECOND;
+                Euclid::rem_euclid(&periods, &1.) as f32
+            }
+        }
+    }
+
+    /// Returns the color of every LED at a time.
+    fn color(&self, time_in_ms: u64) -> Hsv {
+        let color = self.params.color;
+        if self.phase(time_in_ms) < self.params.duty {
+            color
+        } else {
+            Hsv::from_hue(color.hue, color.saturation, 0.)
+        }
+    }
+}
+
+impl<Layout> Pattern<Dim1d, Layout> for Strobe
+where
+    Layout: Layout1d,
+{
+    type Params = StrobeParams;
+    type Color = Hsv;
+
+    /// Creates a new Strobe pattern with the specified parameters.
+    fn new(params: Self::Params) -> Self {
+        Self { params }
+    }
+
+    /// Generates colors for a 1D layout.
+    fn tick(&self, time_in_ms: u64) -> impl Iterator<Item = Self::Color> {
+        core::iter::repeat_n(self.color(time_in_ms), Layout::PIXEL_COUNT)
+    }
+}
+
+impl<Layout> Pattern<Dim2d, Layout> for Strobe
+where
+    Layout: Layout2d,
+{
+    type Params = StrobeParams;
+    type Color = Hsv;
+
+    /// Creates a new Strobe pattern with the specified parameters.
+    fn new(params: Self::Params) -> Self {
+        Self { params }
+    }
+
+    /// Generates colors for a 2D layout.
+    fn tick(&self, time_in_ms: u64) -> impl Iterator<Item = Self::Color> {
+        core::iter::repeat_n(self.color(time_in_ms), Layout::PIXEL_COUNT)
+    }
+}
+
+impl<Layout> Pattern<Dim3d, Layout> for Strobe
+where
+    Layout: Layout3d,
+{
+    type Params = StrobeParams;
+    type Color = Hsv;
+
+    /// Creates a new Strobe pattern with the specified parameters.
+    fn new(params: Self::Params) -> Self {
+        Self { params }
+    }
+
+    /// Generates colors for a 3D layout.
+    fn tick(&self, time_in_ms: u64) -> impl Iterator<Item = Self::Color> {
+        core::iter::repeat_n(self.color(time_in_ms), Layout::PIXEL_COUNT)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::layout1d;
+
+    layout1d!(Layout, 3);
+
+    /// Returns whether every LED is on at a time.
+    fn is_on(pattern: &Strobe, time_in_ms: u64) -> bool {
+        Pattern::<Dim1d, Layout>::tick(pattern, time_in_ms).all(|pixel| pixel.value > 0.)
+    }
+
+    #[test]
+    fn test_phase_sweep() {
+        // A period of 100 ms, on for the first 25 ms.
+        let pattern: Strobe = Pattern::<Dim1d, Layout>::new(StrobeParams {
+            frequency_hz: 10.,
+            duty: 0.25,
+            ..Default::default()
+        });
+        let on: [bool; 10] = core::array::from_fn(|step| is_on(&pattern, 1000 + step as u64 * 10));
+        assert_eq!(
+            on,
+            [true, true, true, false, false, false, false, false, false, false]
+        );
+    }
+
+    #[test]
+    fn test_beat() {
+        // At 120 BPM, a beat is every 500 ms, on for the first 250 ms.
+        let pattern: Strobe = Pattern::<Dim1d, Layout>::new(StrobeParams {
+            frequency_hz: 100.,
+            duty: 0.5,
+            beat: Some(BeatClock::new(120.)),
+            ..Default::default()
+        });
+        assert!(is_on(&pattern, 0));
+        assert!(is_on(&pattern, 240));
+        assert!(!is_on(&pattern, 260));
+        assert!(is_on(&pattern, 500));
+    }
+}
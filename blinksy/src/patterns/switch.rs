@@ -0,0 +1,242 @@
+//! # Pattern Switch
+//!
+//! A switch between the built-in patterns, selected by index at runtime.
+//!
+//! [`BuiltinSwitch`] holds each of the built-in patterns which work on any layout, and
+//! ticks the selected one. Since the patterns have different color types, every color is
+//! converted to [`LinearSrgb`].
+//!
+//! The built-in patterns, by index:
+//!
+//! 0. [`Rainbow`]
+//! 1. Noise: [`Noise1d`], [`Noise2d`], or [`Noise3d`], depending on the dimension
+//!
+//! Use [`Control::next_pattern`] or [`Control::select_pattern`] to switch patterns, such as
+//! from a button press.
+//!
+//! ## Example
+//!
+//! ```rust,ignore
+//! use blinksy::{
+//!     ControlBuilder,
+//!     layout::Layout1d,
+//!     layout1d,
+//!     patterns::switch::{BuiltinSwitch1d, BuiltinSwitchParams}
+//! };
+//!
+//! // Define a 1D layout
+//! layout1d!(Layout, 60);
+//!
+//! let mut control = ControlBuilder::new_1d()
+//!     .with_layout::<Layout, { Layout::PIXEL_COUNT }>()
+//!     .with_pattern::<BuiltinSwitch1d>(BuiltinSwitchParams::default())
+//!     .with_driver(/* Your driver */)
+//!     .with_frame_buffer_size::</* Length of frame buffer */>()
+//!     .build();
+//!
+//! loop {
+//!     if /* button pressed */ {
+//!         control.next_pattern();
+//!     }
+//!     control.tick(/* current time in milliseconds */).unwrap();
+//! }
+//! ```
+//!
+//! [`Control::next_pattern`]: crate::control::Control::next_pattern
+//! [`Control::select_pattern`]: crate::control::Control::select_pattern
+
+use crate::{
+    color::{FromColor, LinearSrgb},
+    layout::LayoutForDim,
+    pattern::{FrameTime, Pattern},
+    patterns::{
+        noise::{noise_fns, Noise1d, Noise2d, Noise3d, NoiseParams},
+        rainbow::{Rainbow, RainbowParams},
+    },
+};
+
+/// Trait for patterns which switch between multiple patterns, selected by index.
+///
+/// Used by [`Control::next_pattern`](crate::control::Control::next_pattern) and
+/// [`Control::select_pattern`](crate::control::Control::select_pattern).
+pub trait PatternSwitch {
+    /// Returns the number of patterns to switch between.
+    fn pattern_count(&self) -> usize;
+
+    /// Returns the index of the selected pattern.
+    fn pattern_index(&self) -> usize;
+
+    /// Selects a pattern by index.
+    ///
+    /// # Arguments
+    ///
+    /// - `index` - The index of the pattern, wrapped around the number of patterns
+    fn select_pattern(&mut self, index: usize);
+}
+
+/// Configuration parameters for the BuiltinSwitch pattern.
+#[derive(Debug, Clone, Default)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct BuiltinSwitchParams {
+    /// The index of the initially selected pattern
+    pub index: usize,
+    /// Parameters for the rainbow pattern
+    pub rainbow: RainbowParams,
+    /// Parameters for the noise pattern
+    pub noise: NoiseParams,
+}
+
+/// BuiltinSwitch pattern implementation.
+///
+/// Ticks the selected built-in pattern.
+///
+/// # Type Parameters
+///
+/// - `Noise` - The noise pattern for the dimension, such as [`Noise2d<noise_fns::Perlin>`]
+#[derive(Debug, Clone)]
+pub struct BuiltinSwitch<Noise> {
+    /// The index of the selected pattern
+    index: usize,
+    /// The rainbow pattern
+    rainbow: Rainbow,
+    /// The noise pattern
+    noise: Noise,
+}
+
+/// A [`BuiltinSwitch`] for 1D layouts, with Perlin noise.
+pub type BuiltinSwitch1d = BuiltinSwitch<Noise1d<noise_fns::Perlin>>;
+
+/// A [`BuiltinSwitch`] for 2D layouts, with Perlin noise.
+pub type BuiltinSwitch2d = BuiltinSwitch<Noise2d<noise_fns::Perlin>>;
+
+/// A [`BuiltinSwitch`] for 3D layouts, with Perlin noise.
+pub type BuiltinSwitch3d = BuiltinSwitch<Noise3d<noise_fns::Perlin>>;
+
+impl<Noise> BuiltinSwitch<Noise> {
+    /// The number of built-in patterns.
+    pub const PATTERN_COUNT: usize = 2;
+}
+
+impl<Noise> PatternSwitch for BuiltinSwitch<Noise> {
+    fn pattern_count(&self) -> usize {
+        Self::PATTERN_COUNT
+    }
+
+    fn pattern_index(&self) -> usize {
+        self.index
+    }
+
+    fn select_pattern(&mut self, index: usize) {
+        self.index = index % Self::PATTERN_COUNT;
+    }
+}
+
+impl<Dim, Layout, Noise> Pattern<Dim, Layout> for BuiltinSwitch<Noise>
+where
+    Layout: LayoutForDim<Dim>,
+    Rainbow: Pattern<Dim, Layout, Params = RainbowParams>,
+    Noise: Pattern<Dim, Layout, Params = NoiseParams>,
+    LinearSrgb: FromColor<<Rainbow as Pattern<Dim, Layout>>::Color>
+        + FromColor<<Noise as Pattern<Dim, Layout>>::Color>,
+{
+    type Params = BuiltinSwitchParams;
+    type Color = LinearSrgb;
+
+    /// Creates a new BuiltinSwitch pattern with the specified parameters.
+    fn new(params: Self::Params) -> Self {
+        Self {
+            index: params.index % Self::PATTERN_COUNT,
+            rainbow: <Rainbow as Pattern<Dim, Layout>>::new(params.rainbow),
+            noise: <Noise as Pattern<Dim, Layout>>::new(params.noise),
+        }
+    }
+
+    /// Generates colors from the selected pattern.
+    fn tick(&self, time_in_ms: u64) -> impl Iterator<Item = Self::Color> {
+        match self.index {
+            0 => SwitchIter::First(
+                Pattern::<Dim, Layout>::tick(&self.rainbow, time_in_ms).map(LinearSrgb::from_color),
+            ),
+            _ => SwitchIter::Second(
+                Pattern::<Dim, Layout>::tick(&self.noise, time_in_ms).map(LinearSrgb::from_color),
+            ),
+        }
+    }
+
+    /// Generates colors from the selected pattern for a frame.
+    fn tick_frame(&self, frame: FrameTime) -> impl Iterator<Item = Self::Color> {
+        match self.index {
+            0 => SwitchIter::First(
+                Pattern::<Dim, Layout>::tick_frame(&self.rainbow, frame)
+                    .map(LinearSrgb::from_color),
+            ),
+            _ => SwitchIter::Second(
+                Pattern::<Dim, Layout>::tick_frame(&self.noise, frame).map(LinearSrgb::from_color),
+            ),
+        }
+    }
+}
+
+/// An iterator over the colors of one of two patterns.
+enum SwitchIter<First, Second> {
+    First(First),
+    Second(Second),
+}
+
+impl<First, Second> Iterator for SwitchIter<First, Second>
+where
+    First: Iterator<Item = LinearSrgb>,
+    Second: Iterator<Item = LinearSrgb>,
+{
+    type Item = LinearSrgb;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        match self {
+            SwitchIter::First(iter) => iter.next(),
+            SwitchIter::Second(iter) => iter.next(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{layout1d, markers::Dim1d};
+
+    layout1d!(Layout, 4);
+
+    fn colors(pattern: &BuiltinSwitch1d) -> [LinearSrgb; 4] {
+        let mut colors = [LinearSrgb::new(0., 0., 0.); 4];
+        for (color, pixel) in colors
+            .iter_mut()
+            .zip(Pattern::<Dim1d, Layout>::tick(pattern, 1000))
+        {
+            *color = pixel;
+        }
+        colors
+    }
+
+    #[test]
+    fn test_select_pattern() {
+        let mut pattern: BuiltinSwitch1d =
+            Pattern::<Dim1d, Layout>::new(BuiltinSwitchParams::default());
+        let rainbow: Rainbow = Pattern::<Dim1d, Layout>::new(RainbowParams::default());
+        let rainbow_colors: [LinearSrgb; 4] = core::array::from_fn(|index| {
+            let color = Pattern::<Dim1d, Layout>::tick(&rainbow, 1000)
+                .nth(index)
+                .unwrap();
+            LinearSrgb::from_color(color)
+        });
+        assert_eq!(pattern.pattern_index(), 0);
+        assert_eq!(colors(&pattern), rainbow_colors);
+
+        pattern.select_pattern(1);
+        assert_eq!(pattern.pattern_index(), 1);
+        assert_ne!(colors(&pattern), rainbow_colors);
+
+        // Indexes wrap around.
+        pattern.select_pattern(2);
+        assert_eq!(pattern.pattern_index(), 0);
+        assert_eq!(colors(&pattern), rainbow_colors);
+    }
+}
@@ -0,0 +1,355 @@
+//! # Scrolling Text Pattern
+//!
+//! The scrolling text pattern renders a message onto a 2D grid layout, scrolling it
+//! horizontally over time.
+//!
+//! Text is rendered with a built-in 5x7 pixel font for printable ASCII characters, with
+//! one blank column between each character. Any other byte is shown as `?`.
+//!
+//! The message loops seamlessly: once the end of the message has scrolled past, the
+//! start follows right behind. To add a gap between repeats, end the message with spaces.
+//!
+//! The pattern uses the first [`Shape2d::Grid`] in the layout to know how many LEDs are
+//! in each row and column. Each LED is mapped to the text pixel nearest its position, so
+//! the order of LEDs (including serpentine wiring) is handled by the layout. If the grid
+//! has more than 7 rows, the text is vertically centered.
+//!
+//! ## Example
+//!
+//! ```rust,ignore
+//! use blinksy::{
+//!     ControlBuilder,
+//!     color::Hsv,
+//!     layout2d,
+//!     layout::{Layout2d, Shape2d, Vec2},
+//!     patterns::text::{ScrollingText, TextParams},
+//! };
+//!
+//! // Define a 2D layout
+//! layout2d!(
+//!     Layout,
+//!     [Shape2d::Grid {
+//!         start: Vec2::new(-1., -1.),
+//!         horizontal_end: Vec2::new(1., -1.),
+//!         vertical_end: Vec2::new(-1., 1.),
+//!         horizontal_pixel_count: 32,
+//!         vertical_pixel_count: 8,
+//!         serpentine: true,
+//!     }]
+//! );
+//!
+//! let control = ControlBuilder::new_2d()
+//!     .with_layout::<Layout, { Layout::PIXEL_COUNT }>()
+//!     .with_pattern::<ScrollingText>(TextParams {
+//!         text: "Hello world! ",
+//!         color: Hsv::new(0.5, 1., 1.),
+//!         speed: 8.,
+//!     })
+//!     .with_driver(/* Your driver */)
+//!     .with_frame_buffer_size::</* Length of frame buffer */>()
+//!     .build();
+//! ```
+//!
+//! [`Shape2d::Grid`]: crate::layout::Shape2d::Grid
+
+use num_traits::Euclid;
+#[allow(unused_imports)]
+use num_traits::Float;
+
+use crate::{
+    color::Hsv,
+    layout::{Layout2d, Shape2d},
+    markers::Dim2d,
+    pattern::Pattern,
+    patterns::util::nearest,
+};
+
+/// Width of a glyph in the font, in pixels.
+const GLYPH_WIDTH: usize = 5;
+
+/// Height of a glyph in the font, in pixels.
+const GLYPH_HEIGHT: usize = 7;
+
+/// Width of a character on screen, including spacing, in pixels.
+const CHAR_WIDTH: usize = GLYPH_WIDTH + 1;
+
+/// 5x7 font for printable ASCII (`' '` to `'~'`).
+///
+/// Each glyph is 5 columns, left to right. Each column is a bitmask of 7 rows, where the
+/// least significant bit is the top row.
+const FONT_5X7: [[u8; GLYPH_WIDTH]; 95] = [
+    [0x00, 0x00, 0x00, 0x00, 0x00], // ' '
+    [0x00, 0x00, 0x5F, 0x00, 0x00], // '!'
+    [0x00, 0x07, 0x00, 0x07, 0x00], // '"'
+    [0x14, 0x7F, 0x14, 0x7F, 0x14], // '#'
+    [0x24, 0x2A, 0x7F, 0x2A, 0x12], // '$'
+    [0x23, 0x13, 0x08, 0x64, 0x62], // '%'
+    [0x36, 0x49, 0x55, 0x22, 0x50], // '&'
+    [0x00, 0x05, 0x03, 0x00, 0x00], // '''
+    [0x00, 0x1C, 0x22, 0x41, 0x00], // '('
+    [0x00, 0x41, 0x22, 0x1C, 0x00], // ')'
+    [0x08, 0x2A, 0x1C, 0x2A, 0x08], // '*'
+    [0x08, 0x08, 0x3E, 0x08, 0x08], // '+'
+    [0x00, 0x50, 0x30, 0x00, 0x00], // ','
+    [0x08, 0x08, 0x08, 0x08, 0x08], // '-'
+    [0x00, 0x60, 0x60, 0x00, 0x00], // '.'
+    [0x20, 0x10, 0x08, 0x04, 0x02], // '/'
+    [0x3E, 0x51, 0x49, 0x45, 0x3E], // '0'
+    [0x00, 0x42, 0x7F, 0x40, 0x00], // '1'
+    [0x42, 0x61, 0x51, 0x49, 0x46], // '2'
+    [0x21, 0x41, 0x45, 0x4B, 0x31], // '3'
+    [0x18, 0x14, 0x12, 0x7F, 0x10], // '4'
+    [0x27, 0x45, 0x45, 0x45, 0x39], // '5'
+    [0x3C, 0x4A, 0x49, 0x49, 0x30], // '6'
+    [0x01, 0x71, 0x09, 0x05, 0x03], // '7'
+    [0x36, 0x49, 0x49, 0x49, 0x36], // '8'
+    [0x06, 0x49, 0x49, 0x29, 0x1E], // '9'
+    [0x00, 0x36, 0x36, 0x00, 0x00], // ':'
+    [0x00, 0x56, 0x36, 0x00, 0x00], // ';'
+    [0x08, 0x14, 0x22, 0x41, 0x00], // '<'
+    [0x14, 0x14, 0x14, 0x14, 0x14], // '='
+    [0x00, 0x41, 0x22, 0x14, 0x08], // '>'
+    [0x02, 0x01, 0x51, 0x09, 0x06], // '?'
+    [0x32, 0x49, 0x79, 0x41, 0x3E], // '@'
+    [0x7E, 0x11, 0x11, 0x11, 0x7E], // 'A'
+    [0x7F, 0x49, 0x49, 0x49, 0x36], // 'B'
+    [0x3E, 0x41, 0x41, 0x41, 0x22], // 'C'
+    [0x7F, 0x41, 0x41, 0x22, 0x1C], // 'D'
+    [0x7F, 0x49, 0x49, 0x49, 0x41], // 'E'
+    [0x7F, 0x09, 0x09, 0x09, 0x01], // 'F'
+    [0x3E, 0x41, 0x49, 0x49, 0x7A], // 'G'
+    [0x7F, 0x08, 0x08, 0x08, 0x7F], // 'H'
+    [0x00, 0x41, 0x7F, 0x41, 0x00], // 'I'
+    [0x20, 0x40, 0x41, 0x3F, 0x01], // 'J'
+    [0x7F, 0x08, 0x14, 0x22, 0x41], // 'K'
+    [0x7F, 0x40, 0x40, 0x40, 0x40], // 'L'
+    [0x7F, 0x02, 0x0C, 0x02, 0x7F], // 'M'
+    [0x7F, 0x04, 0x08, 0x10, 0x7F], // 'N'
+    [0x3E, 0x41, 0x41, 0x41, 0x3E], // 'O'
+    [0x7F, 0x09, 0x09, 0x09, 0x06], // 'P'
+    [0x3E, 0x41, 0x51, 0x21, 0x5E], // 'Q'
+    [0x7F, 0x09, 0x19, 0x29, 0x46], // 'R'
+    [0x46, 0x49, 0x49, 0x49, 0x31], // 'S'
+    [0x01, 0x01, 0x7F, 0x01, 0x01], // 'T'
+    [0x3F, 0x40, 0x40, 0x40, 0x3F], // 'U'
+    [0x1F, 0x20, 0x40, 0x20, 0x1F], // 'V'
+    [0x3F, 0x40, 0x38, 0x40, 0x3F], // 'W'
+    [0x63, 0x14, 0x08, 0x14, 0x63], // 'X'
+    [0x07, 0x08, 0x70, 0x08, 0x07], // 'Y'
+    [0x61, 0x51, 0x49, 0x45, 0x43], // 'Z'
+    [0x00, 0x7F, 0x41, 0x41, 0x00], // '['
+    [0x02, 0x04, 0x08, 0x10, 0x20], // '\'
+    [0x00, 0x41, 0x41, 0x7F, 0x00], // ']'
+    [0x04, 0x02, 0x01, 0x02, 0x04], // '^'
+    [0x40, 0x40, 0x40, 0x40, 0x40], // '_'
+    [0x00, 0x01, 0x02, 0x04, 0x00], // '`'
+    [0x20, 0x54, 0x54, 0x54, 0x78], // 'a'
+    [0x7F, 0x48, 0x44, 0x44, 0x38], // 'b'
+    [0x38, 0x44, 0x44, 0x44, 0x20], // 'c'
+    [0x38, 0x44, 0x44, 0x48, 0x7F], // 'd'
+    [0x38, 0x54, 0x54, 0x54, 0x18], // 'e'
+    [0x08, 0x7E, 0x09, 0x01, 0x02], // 'f'
+    [0x0C, 0x52, 0x52, 0x52, 0x3E], // 'g'
+    [0x7F, 0x08, 0x04, 0x04, 0x78], // 'h'
+    [0x00, 0x44, 0x7D, 0x40, 0x00], // 'i'
+    [0x20, 0x40, 0x44, 0x3D, 0x00], // 'j'
+    [0x7F, 0x10, 0x28, 0x44, 0x00], // 'k'
+    [0x00, 0x41, 0x7F, 0x40, 0x00], // 'l'
+    [0x7C, 0x04, 0x18, 0x04, 0x78], // 'm'
+    [0x7C, 0x08, 0x04, 0x04, 0x78], // 'n'
+    [0x38, 0x44, 0x44, 0x44, 0x38], // 'o'
+    [0x7C, 0x14, 0x14, 0x14, 0x08], // 'p'
+    [0x08, 0x14, 0x14, 0x18, 0x7C], // 'q'
+    [0x7C, 0x08, 0x04, 0x04, 0x08], // 'r'
+    [0x48, 0x54, 0x54, 0x54, 0x20], // 's'
+    [0x04, 0x3F, 0x44, 0x40, 0x20], // 't'
+    [0x3C, 0x40, 0x40, 0x20, 0x7C], // 'u'
+    [0x1C, 0x20, 0x40, 0x20, 0x1C], // 'v'
+    [0x3C, 0x40, 0x30, 0x40, 0x3C], // 'w'
+    [0x44, 0x28, 0x10, 0x28, 0x44], // 'x'
+    [0x0C, 0x50, 0x50, 0x50, 0x3C], // 'y'
+    [0x44, 0x64, 0x54, 0x4C, 0x44], // 'z'
+    [0x00, 0x08, 0x36, 0x41, 0x00], // '{'
+    [0x00, 0x00, 0x7F, 0x00, 0x00], // '|'
+    [0x00, 0x41, 0x36, 0x08, 0x00], // '}'
+    [0x08, 0x04, 0x08, 0x10, 0x08], // '~'
+];
+
+/// Returns the font glyph for an ASCII byte, or `?` if not printable.
+fn glyph(byte: u8) -> &'static [u8; GLYPH_WIDTH] {
+    let index = match byte {
+        b' '..=b'~' => byte - b' ',
+        _ => b'?' - b' ',
+    };
+    &FONT_5X7[index as usize]
+}
+
+/// Configuration parameters for the ScrollingText pattern.
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct TextParams {
+    /// The message to show
+    pub text: &'static str,
+    /// The color of lit text pixels
+    pub color: Hsv,
+    /// Scroll speed, in pixel columns per second
+    pub speed: f32,
+}
+
+impl Default for TextParams {
+    fn default() -> Self {
+        Self {
+            text: "Blinksy ",
+            color: Hsv::new(0., 0., 1.),
+            speed: 8.,
+        }
+    }
+}
+
+/// Scrolling text pattern implementation.
+///
+/// Scrolls a message from right to left across a 2D grid.
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct ScrollingText {
+    /// Configuration parameters
+    params: TextParams,
+}
+
+impl ScrollingText {
+    /// Returns whether the text pixel at a (looped) column and row is lit.
+    ///
+    /// Rows outside of the glyph height are never lit.
+    fn is_lit(&self, column: usize, row: usize) -> bool {
+        let bytes = self.params.text.as_bytes();
+        if bytes.is_empty() || row >= GLYPH_HEIGHT {
+            return false;
+        }
+
+        let column = column % (bytes.len() * CHAR_WIDTH);
+        let glyph_column = column % CHAR_WIDTH;
+        if glyph_column >= GLYPH_WIDTH {
+            return false;
+        }
+
+        let glyph = glyph(bytes[column / CHAR_WIDTH]);
+        glyph[glyph_column] & (1 << row) != 0
+    }
+}
+
+impl<Layout> Pattern<Dim2d, Layout> for ScrollingText
+where
+    Layout: Layout2d,
+{
+    type Params = TextParams;
+    type Color = Hsv;
+
+    /// Creates a new ScrollingText pattern with the specified parameters.
+    fn new(params: Self::Params) -> Self {
+        Self { params }
+    }
+
+    /// Generates colors for a 2D grid layout.
+    ///
+    /// Each LED is lit if the text pixel nearest its position is lit.
+    fn tick(&self, time_in_ms: u64) -> impl Iterator<Item = Self::Color> {
+        const MILLISECONDS_PER_SECOND: f32 = 1e3;
+
+        let (width, height) = Layout::shapes()
+            .find_map(|shape| match shape {
+                Shape2d::Grid {
+                    horizontal_pixel_count,
+                    vertical_pixel_count,
+                    ..
+                } => Some((horizontal_pixel_count, vertical_pixel_count)),
+                _ => None,
+            })
+            .unwrap_or((0, 0));
+
+        let text_width = self.params.text.len() * CHAR_WIDTH;
+        let offset = (time_in_ms as f32 / MILLISECONDS_PER_SECOND * self.params.speed).floor();
+        let offset = Euclid::rem_euclid(&offset, &(text_width.max(1) as f32)) as usize;
+        let top_padding = height.saturating_sub(GLYPH_HEIGHT) / 2;
+
+        let color = self.params.color;
+        let off = Hsv::from_hue(color.hue, color.saturation, 0.);
+
+        Layout::points().map(move |point| {
+            if width == 0 || height == 0 {
+                return off;
+            }
+            let column = nearest(point.x, width);
+            // Layout y increases towards the top, text rows increase towards the bottom.
+            let row = nearest(-point.y, height);
+            match row.checked_sub(top_padding) {
+                Some(row) if self.is_lit(column + offset, row) => color,
+                _ => off,
+            }
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{layout::Vec2, layout2d};
+
+    layout2d!(
+        Layout,
+        [Shape2d::Grid {
+            start: Vec2::new(-1., -1.),
+            horizontal_end: Vec2::new(1., -1.),
+            vertical_end: Vec2::new(-1., 1.),
+            horizontal_pixel_count: 6,
+            vertical_pixel_count: 7,
+            serpentine: false,
+        }]
+    );
+
+    fn lit(pattern: &ScrollingText, time_in_ms: u64) -> [bool; 42] {
+        let mut lit = [false; 42];
+        for (is_lit, color) in lit
+            .iter_mut()
+            .zip(Pattern::<Dim2d, Layout>::tick(pattern, time_in_ms))
+        {
+            *is_lit = color.value > 0.;
+        }
+        lit
+    }
+
+    #[test]
+    fn test_renders_glyph() {
+        let pattern: ScrollingText = Pattern::<Dim2d, Layout>::new(TextParams {
+            text: "I",
+            color: Hsv::new(0., 0., 1.),
+            speed: 0.,
+        });
+
+        // Rows from the bottom, as the grid starts at the left bottom.
+        #[rustfmt::skip]
+        let expected = [
+            false, true, true, true, false, false,
+            false, false, true, false, false, false,
+            false, false, true, false, false, false,
+            false, false, true, false, false, false,
+            false, false, true, false, false, false,
+            false, false, true, false, false, false,
+            false, true, true, true, false, false,
+        ];
+        assert_eq!(lit(&pattern, 0), expected);
+    }
+
+    #[test]
+    fn test_scroll_loops_seamlessly() {
+        let pattern: ScrollingText = Pattern::<Dim2d, Layout>::new(TextParams {
+            text: "AB",
+            color: Hsv::new(0., 0., 1.),
+            speed: 1.,
+        });
+
+        // "AB" is 12 columns wide, so at 1 column per second it repeats every 12 seconds.
+        assert_ne!(lit(&pattern, 0), lit(&pattern, 6_000));
+        assert_eq!(lit(&pattern, 0), lit(&pattern, 12_000));
+        assert_eq!(lit(&pattern, 3_000), lit(&pattern, 15_000));
+    }
+}
@@ -0,0 +1,197 @@
+//! # Theater Chase Pattern
+//!
+//! The theater chase pattern lights every Nth LED and shifts them along over time, like
+//! the marquee lights of a theater.
+//!
+//! The LEDs are lit in index order, so 2D and 3D layouts chase in the order of their
+//! shapes.
+//!
+//! ## Example
+//!
+//! ```rust,ignore
+//! use blinksy::{
+//!     ControlBuilder,
+//!     color::Hsv,
+//!     layout::Layout1d,
+//!     layout1d,
+//!     patterns::theater::{TheaterChase, TheaterParams}
+//! };
+//!
+//! // Define a 1D layout
+//! layout1d!(Layout, 60);
+//!
+//! // Create a warm white Theater Chase pattern, lighting every 4th LED
+//! let control = ControlBuilder::new_1d()
+//!     .with_layout::<Layout, { Layout::PIXEL_COUNT }>()
+//!     .with_pattern::<TheaterChase>(TheaterParams {
+//!         color: Hsv::new(0.1, 0.5, 1.),
+//!         spacing: 4,
+//!         ..Default::default()
+//!     })
+//!     .with_driver(/* Your driver */)
+//!     .with_frame_buffer_size::</* Length of frame buffer */>()
+//!     .build();
+//! ```
+
+use crate::{
+    color::Hsv,
+    layout::{Layout1d, Layout2d, Layout3d},
+    markers::{Dim1d, Dim2d, Dim3d},
+    pattern::Pattern,
+};
+
+/// Configuration parameters for the TheaterChase pattern.
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct TheaterParams {
+    /// The color of the lit LEDs
+    pub color: Hsv,
+    /// Lights every `spacing`th LED (at least 1)
+    pub spacing: usize,
+    /// How fast the lit LEDs shift along, in steps per second
+    pub speed: f32,
+    /// The color of the unlit LEDs
+    pub background: Hsv,
+}
+
+impl Default for TheaterParams {
+    fn default() -> Self {
+        Self {
+            color: Hsv::new(0., 0., 1.),
+            spacing: 3,
+            speed: 10.,
+            background: Hsv::new(0., 0., 0.),
+        }
+    }
+}
+
+/// TheaterChase pattern implementation.
+///
+/// Lights every Nth LED, in index order, shifting over time.
+#[derive(Debug, Clone)]
+pub struct TheaterChase {
+    /// Configuration parameters
+    params: TheaterParams,
+}
+
+impl TheaterChase {
+    /// Returns the phase (from 0 to `spacing - 1`) at a time.
+    fn phase(&self, time_in_ms: u64) -> usize {
+        const MILLISECONDS_PER_SECOND: f32 = 1e3;
+        let steps = (time_in_ms as f32 * self.params.speed / MILLISECONDS_PER_SECOND) as usize;
+        steps % self.params.spacing.max(1)
+    }
+
+    /// Generates the colors for `pixel_count` LEDs.
+    fn colors(&self, time_in_ms: u64, pixel_count: usize) -> impl Iterator<Item = Hsv> {
+        let phase = self.phase(time_in_ms);
+        let TheaterParams {
+            color,
+            spacing,
+            background,
+            ..
+        } = self.params;
+        let spacing = spacing.max(1);
+
+        (0..pixel_count).map(move |index| {
+            if (index + phase).is_multiple_of(spacing) {
+                color
+            } else {
+                background
+            }
+        })
+    }
+}
+
+impl<Layout> Pattern<Dim1d, Layout> for TheaterChase
+where
+    Layout: Layout1d,
+{
+    type Params = TheaterParams;
+    type Color = Hsv;
+
+    /// Creates a new TheaterChase pattern with the specified parameters.
+    fn new(params: Self::Params) -> Self {
+        Self { params }
+    }
+
+    /// Generates colors for a 1D layout.
+    fn tick(&self, time_in_ms: u64) -> impl Iterator<Item = Self::Color> {
+        self.colors(time_in_ms, Layout::PIXEL_COUNT)
+    }
+}
+
+impl<Layout> Pattern<Dim2d, Layout> for TheaterChase
+where
+    Layout: Layout2d,
+{
+    type Params = TheaterParams;
+    type Color = Hsv;
+
+    /// Creates a new TheaterChase pattern with the specified parameters.
+    fn new(params: Self::Params) -> Self {
+        Self { params }
+    }
+
+    /// Generates colors for a 2D layout, in index order.
+    fn tick(&self, time_in_ms: u64) -> impl Iterator<Item = Self::Color> {
+        self.colors(time_in_ms, Layout::PIXEL_COUNT)
+    }
+}
+
+impl<Layout> Pattern<Dim3d, Layout> for TheaterChase
+where
+    Layout: Layout3d,
+{
+    type Params = TheaterParams;
+    type Color = Hsv;
+
+    /// Creates a new TheaterChase pattern with the specified parameters.
+    fn new(params: Self::Params) -> Self {
+        Self { params }
+    }
+
+    /// Generates colors for a 3D layout, in index order.
+    fn tick(&self, time_in_ms: u64) -> impl Iterator<Item = Self::Color> {
+        self.colors(time_in_ms, Layout::PIXEL_COUNT)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use heapless::Vec;
+
+    use super::*;
+    use crate::layout1d;
+
+    layout1d!(Layout, 7);
+
+    /// Returns the indices of the lit LEDs.
+    fn lit(pattern: &TheaterChase, time_in_ms: u64) -> Vec<usize, 7> {
+        let color = pattern.params.color;
+        Pattern::<Dim1d, Layout>::tick(pattern, time_in_ms)
+            .enumerate()
+            .filter(|&(_, pixel)| pixel == color)
+            .map(|(index, _)| index)
+            .collect()
+    }
+
+    #[test]
+    fn test_lit_indices() {
+        // 1 step per second
+        let pattern: TheaterChase = Pattern::<Dim1d, Layout>::new(TheaterParams {
+            spacing: 3,
+            speed: 1.,
+            ..Default::default()
+        });
+
+        // Phase 0
+        assert_eq!(lit(&pattern, 0), [0, 3, 6]);
+        // Phase 1
+        assert_eq!(lit(&pattern, 1000), [2, 5]);
+        // Phase 2
+        assert_eq!(lit(&pattern, 2000), [1, 4]);
+        // Back to phase 0
+        assert_eq!(lit(&pattern, 3000), [0, 3, 6]);
+    }
+}
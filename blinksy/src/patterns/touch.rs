@@ -0,0 +1,220 @@
+//! # Touch Zones Pattern
+//!
+//! The touch zones pattern maps discrete inputs, such as the pads of a capacitive
+//! touch strip, onto spatial regions of a 1D layout.
+//!
+//! The layout is divided into `ZONES` equal ranges along its length. While a zone is
+//! active, every LED within its range is lit. When a zone is released, its LEDs fade
+//! out over [`TouchZonesParams::fade_duration_in_ms`].
+//!
+//! Fading is based on the time given to each tick, not on the number of ticks, so the
+//! fade takes the same duration regardless of frame rate.
+//!
+//! A zone touched and released between ticks is still lit on the next tick, then fades,
+//! so a short tap isn't missed.
+//!
+//! ## Example
+//!
+//! ```rust,ignore
+//! use blinksy::{
+//!     ControlBuilder,
+//!     layout::Layout1d,
+//!     layout1d,
+//!     patterns::touch::{TouchZones, TouchZonesParams}
+//! };
+//!
+//! // Define a 1D layout
+//! layout1d!(Layout, 60);
+//!
+//! // Create a TouchZones pattern with 4 zones
+//! let mut control = ControlBuilder::new_1d()
+//!     .with_layout::<Layout, { Layout::PIXEL_COUNT }>()
+//!     .with_pattern::<TouchZones<4>>(TouchZonesParams::default())
+//!     .with_driver(/* Your driver */)
+//!     .with_frame_buffer_size::</* Length of frame buffer */>()
+//!     .build();
+//!
+//! loop {
+//!     // Update zones from your touch sensor
+//!     control.pattern_mut().set_zone(0, /* is zone 0 touched? */);
+//!
+//!     control.tick(/* current time in milliseconds */).unwrap();
+//! }
+//! ```
+
+use core::cell::Cell;
+
+use crate::{color::Okhsv, layout::Layout1d, markers::Dim1d, pattern::Pattern};
+
+/// Configuration parameters for the TouchZones pattern.
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct TouchZonesParams {
+    /// The color of an active zone
+    pub color: Okhsv,
+    /// How long a released zone takes to fade out, in milliseconds
+    pub fade_duration_in_ms: u64,
+}
+
+impl Default for TouchZonesParams {
+    fn default() -> Self {
+        Self {
+            color: Okhsv::new(0., 0., 1.),
+            fade_duration_in_ms: 500,
+        }
+    }
+}
+
+/// The state of a single input zone.
+#[derive(Debug, Clone, Default)]
+struct Zone {
+    /// Whether the zone is currently active
+    is_active: Cell<bool>,
+    /// Whether the zone was activated since the last tick
+    was_pressed: Cell<bool>,
+    /// The last tick time at which the zone was active
+    last_active_in_ms: Cell<Option<u64>>,
+}
+
+/// TouchZones pattern implementation.
+///
+/// Lights the LEDs within each active zone, and fades them out when released.
+///
+/// # Type Parameters
+///
+/// - `ZONES` - The number of input zones the layout is divided into
+#[derive(Debug, Clone)]
+pub struct TouchZones<const ZONES: usize> {
+    /// State of each zone
+    zones: [Zone; ZONES],
+    /// Configuration parameters
+    params: TouchZonesParams,
+}
+
+impl<const ZONES: usize> TouchZones<ZONES> {
+    /// Sets whether a zone is active.
+    ///
+    /// # Arguments
+    ///
+    /// - `zone` - The zone index, from `0` to `ZONES - 1`
+    /// - `active` - Whether the zone is active (touched)
+    ///
+    /// Zones outside of the range are ignored.
+    pub fn set_zone(&mut self, zone: usize, active: bool) {
+        if let Some(zone) = self.zones.get(zone) {
+            zone.is_active.set(active);
+            if active {
+                zone.was_pressed.set(true);
+            }
+        }
+    }
+
+    /// Returns the zone index for a 1D position between -1.0 and 1.0.
+    fn zone_index(x: f32) -> usize {
+        let normalized = (x + 1.) / 2.;
+        ((normalized * ZONES as f32) as usize).min(ZONES.saturating_sub(1))
+    }
+
+    /// Returns the intensity (0.0 to 1.0) of a zone at the given time.
+    fn zone_intensity(&self, zone: &Zone, time_in_ms: u64) -> f32 {
+        // Consume the press, so a zone released since the last tick still lights.
+        let was_pressed = zone.was_pressed.replace(false);
+        if zone.is_active.get() || was_pressed {
+            zone.last_active_in_ms.set(Some(time_in_ms));
+            return 1.;
+        }
+
+        let Some(last_active_in_ms) = zone.last_active_in_ms.get() else {
+            return 0.;
+        };
+
+        let fade_duration_in_ms = self.params.fade_duration_in_ms;
+        let elapsed_in_ms = time_in_ms.saturating_sub(last_active_in_ms);
+        if elapsed_in_ms >= fade_duration_in_ms {
+            zone.last_active_in_ms.set(None);
+            return 0.;
+        }
+
+        1. - (elapsed_in_ms as f32 / fade_duration_in_ms as f32)
+    }
+}
+
+impl<const ZONES: usize, Layout> Pattern<Dim1d, Layout> for TouchZones<ZONES>
+where
+    Layout: Layout1d,
+{
+    type Params = TouchZonesParams;
+    type Color = Okhsv;
+
+    /// Creates a new TouchZones pattern with the specified parameters.
+    fn new(params: Self::Params) -> Self {
+        Self {
+            zones: core::array::from_fn(|_| Zone::default()),
+            params,
+        }
+    }
+
+    /// Generates colors for a 1D layout.
+    ///
+    /// Each LED takes the intensity of the zone its position falls within.
+    fn tick(&self, time_in_ms: u64) -> impl Iterator<Item = Self::Color> {
+        let intensities: [f32; ZONES] =
+            core::array::from_fn(|index| self.zone_intensity(&self.zones[index], time_in_ms));
+        let color = self.params.color;
+
+        Layout::points().map(move |x| {
+            let intensity = intensities[Self::zone_index(x)];
+            Okhsv::new(color.h, color.s, color.v * intensity)
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::layout1d;
+
+    layout1d!(Layout, 8);
+
+    fn values(pattern: &TouchZones<4>, time_in_ms: u64) -> [f32; 8] {
+        let mut values = [0.; 8];
+        for (value, color) in values
+            .iter_mut()
+            .zip(Pattern::<Dim1d, Layout>::tick(pattern, time_in_ms))
+        {
+            *value = color.v;
+        }
+        values
+    }
+
+    #[test]
+    fn test_zone_lights_and_fades() {
+        let mut pattern: TouchZones<4> = Pattern::<Dim1d, Layout>::new(TouchZonesParams {
+            color: Okhsv::new(0., 0., 1.),
+            fade_duration_in_ms: 100,
+        });
+
+        assert_eq!(values(&pattern, 0), [0.; 8]);
+
+        pattern.set_zone(1, true);
+        assert_eq!(values(&pattern, 10), [0., 0., 1., 1., 0., 0., 0., 0.]);
+
+        pattern.set_zone(1, false);
+        assert_eq!(values(&pattern, 60), [0., 0., 0.5, 0.5, 0., 0., 0., 0.]);
+        assert_eq!(values(&pattern, 110), [0.; 8]);
+    }
+
+    #[test]
+    fn test_press_between_ticks() {
+        let mut pattern: TouchZones<4> = Pattern::<Dim1d, Layout>::new(TouchZonesParams {
+            color: Okhsv::new(0., 0., 1.),
+            fade_duration_in_ms: 100,
+        });
+
+        pattern.set_zone(1, true);
+        pattern.set_zone(1, false);
+        assert_eq!(values(&pattern, 10), [0., 0., 1., 1., 0., 0., 0., 0.]);
+        assert_eq!(values(&pattern, 60), [0., 0., 0.5, 0.5, 0., 0., 0., 0.]);
+        assert_eq!(values(&pattern, 110), [0.; 8]);
+    }
+}
@@ -0,0 +1,163 @@
+//! # Pattern Utilities
+//!
+//! Shared helpers for writing patterns.
+//!
+//! Most patterns map each LED's point in the layout to a color. [`fill_by_position`] does
+//! just that, as an iterator of exactly one color per LED, so patterns don't need to
+//! collect their colors into an array or [`heapless::Vec`].
+//!
+//! ## Example
+//!
+//! ```rust
+//! use blinksy::{
+//!     color::LinearSrgb,
+//!     layout1d,
+//!     markers::Dim1d,
+//!     patterns::util::fill_by_position,
+//! };
+//!
+//! layout1d!(Layout, 5);
+//!
+//! // Brighter from the first LED to the last.
+//! let colors = fill_by_position::<Dim1d, Layout, _>(|x| {
+//!     let level = (x + 1.) / 2.;
+//!     LinearSrgb::new(level, level, level)
+//! });
+//! assert_eq!(colors.len(), 5);
+//! ```
+
+use crate::layout::LayoutForDim;
+
+/// Returns an iterator of `f(point)`, for the point of each LED in a layout.
+///
+/// The iterator yields exactly `PIXEL_COUNT` colors, in LED order, and knows its length
+/// (as an [`ExactSizeIterator`]), so a caller can size a buffer up front.
+///
+/// Each color is computed as the iterator is consumed: nothing is allocated or buffered.
+///
+/// # Type Parameters
+///
+/// - `Dim` - The dimension marker, such as [`Dim1d`](crate::markers::Dim1d)
+/// - `L` - The layout
+/// - `C` - The color type
+///
+/// # Arguments
+///
+/// - `f` - The closure, from the point of an LED to its color
+pub fn fill_by_position<Dim, L, C>(f: impl FnMut(L::Point) -> C) -> impl ExactSizeIterator<Item = C>
+where
+    L: LayoutForDim<Dim>,
+{
+    FillByPosition {
+        points: L::layout_points(),
+        remaining: L::pixel_count(),
+        f,
+    }
+}
+
+/// The iterator from [`fill_by_position`].
+struct FillByPosition<I, F> {
+    /// The points of the layout
+    points: I,
+    /// The number of LEDs left to yield
+    remaining: usize,
+    /// The closure, from point to color
+    f: F,
+}
+
+impl<I, F, C> Iterator for FillByPosition<I, F>
+where
+    I: Iterator,
+    F: FnMut(I::Item) -> C,
+{
+    type Item = C;
+
+    fn next(&mut self) -> Option<C> {
+        if self.remaining == 0 {
+            return None;
+        }
+        self.remaining -= 1;
+        self.points.next().map(&mut self.f)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.remaining, Some(self.remaining))
+    }
+}
+
+impl<I, F, C> ExactSizeIterator for FillByPosition<I, F>
+where
+    I: Iterator,
+    F: FnMut(I::Item) -> C,
+{
+}
+
+/// Maps a position between -1.0 and 1.0 to the nearest of `count` evenly spaced indices,
+/// such as the nearest column or row of a grid.
+///
+/// Positions outside -1.0 to 1.0 are mapped to the first or last index.
+///
+/// # Arguments
+///
+/// - `position` - The position, from -1.0 (the first index) to 1.0 (the last index)
+/// - `count` - The number of indices
+pub fn nearest(position: f32, count: usize) -> usize {
+    let max_index = count.saturating_sub(1);
+    let index = (position + 1.) / 2. * max_index as f32 + 0.5;
+    (index.max(0.) as usize).min(max_index)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        layout::{Shape2d, Vec2},
+        layout1d, layout2d,
+        markers::{Dim1d, Dim2d},
+    };
+
+    layout1d!(Strip, 3);
+
+    layout2d!(
+        Grid,
+        [Shape2d::Grid {
+            start: Vec2::new(-1., -1.),
+            horizontal_end: Vec2::new(1., -1.),
+            vertical_end: Vec2::new(-1., 1.),
+            horizontal_pixel_count: 2,
+            vertical_pixel_count: 2,
+            serpentine: false,
+        }]
+    );
+
+    #[test]
+    fn test_fill_1d() {
+        let mut colors = fill_by_position::<Dim1d, Strip, _>(|x| x * 2.);
+        assert_eq!(colors.len(), 3);
+        assert_eq!(colors.next(), Some(-2.));
+        assert_eq!(colors.len(), 2);
+        assert_eq!(colors.next(), Some(0.));
+        assert_eq!(colors.next(), Some(2.));
+        assert_eq!(colors.next(), None);
+    }
+
+    #[test]
+    fn test_fill_2d() {
+        let colors = fill_by_position::<Dim2d, Grid, _>(|point| point.x + point.y);
+        assert_eq!(colors.len(), 4);
+        let sums: heapless::Vec<f32, 4> = colors.collect();
+        assert_eq!(sums.as_slice(), [-2., 0., 0., 2.]);
+    }
+
+    #[test]
+    fn test_nearest() {
+        assert_eq!(nearest(-1., 5), 0);
+        assert_eq!(nearest(-0.4, 5), 1);
+        assert_eq!(nearest(0., 5), 2);
+        assert_eq!(nearest(1., 5), 4);
+        // Outside -1.0 to 1.0, clamped to the first or last index.
+        assert_eq!(nearest(-2., 5), 0);
+        assert_eq!(nearest(2., 5), 4);
+        assert_eq!(nearest(0.5, 0), 0);
+    }
+}
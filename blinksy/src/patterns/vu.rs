@@ -0,0 +1,215 @@
+//! # VU Meter Pattern
+//!
+//! The VU meter pattern shows the loudness of audio as a bar along a 1D layout, like the
+//! level meter of a mixing desk.
+//!
+//! A fraction of the strip proportional to the level is lit. A peak dot marks the highest
+//! recent level, and falls back down over time.
+//!
+//! The level is read from an [`AudioSource`] with [`VuMeter::update`], or set directly
+//! with [`VuMeter::set_level`].
+//!
+//! ## Example
+//!
+//! ```rust,ignore
+//! use blinksy::{
+//!     ControlBuilder,
+//!     layout::Layout1d,
+//!     layout1d,
+//!     patterns::vu::{VuMeter, VuMeterParams}
+//! };
+//!
+//! // Define a 1D layout
+//! layout1d!(Layout, 60);
+//!
+//! let mut control = ControlBuilder::new_1d()
+//!     .with_layout::<Layout, { Layout::PIXEL_COUNT }>()
+//!     .with_pattern::<VuMeter>(VuMeterParams::default())
+//!     .with_driver(/* Your driver */)
+//!     .with_frame_buffer_size::</* Length of frame buffer */>()
+//!     .build();
+//!
+//! loop {
+//!     control.pattern_mut().update(&mut /* Your audio source */);
+//!     control.tick(/* current time in milliseconds */).unwrap();
+//! }
+//! ```
+
+use core::cell::Cell;
+
+#[allow(unused_imports)]
+use num_traits::Float;
+
+use crate::{audio::AudioSource, color::Okhsv, layout::Layout1d, markers::Dim1d, pattern::Pattern};
+
+/// Configuration parameters for the VuMeter pattern.
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct VuMeterParams {
+    /// The color of the bar
+    pub color: Okhsv,
+    /// The color of the peak dot
+    pub peak_color: Okhsv,
+    /// How fast the peak dot falls, as a fraction of the strip per second
+    pub peak_decay: f32,
+}
+
+impl Default for VuMeterParams {
+    fn default() -> Self {
+        Self {
+            color: Okhsv::new(0.4, 1., 1.),
+            peak_color: Okhsv::new(0.08, 1., 1.),
+            peak_decay: 0.5,
+        }
+    }
+}
+
+/// VuMeter pattern implementation.
+///
+/// Lights a bar proportional to the audio level, with a decaying peak dot.
+#[derive(Debug, Clone)]
+pub struct VuMeter {
+    /// Configuration parameters
+    params: VuMeterParams,
+    /// The current level (0.0 to 1.0)
+    level: f32,
+    /// The level of the peak dot (0.0 to 1.0)
+    peak: Cell<f32>,
+    /// The time of the last tick
+    last_tick_in_ms: Cell<Option<u64>>,
+}
+
+impl VuMeter {
+    /// Sets the current audio level.
+    ///
+    /// # Arguments
+    ///
+    /// - `level` - The audio level (0.0 to 1.0), clamped
+    pub fn set_level(&mut self, level: f32) {
+        self.level = level.clamp(0., 1.);
+    }
+
+    /// Reads the current audio level from an audio source.
+    ///
+    /// # Arguments
+    ///
+    /// - `source` - The audio source
+    pub fn update(&mut self, source: &mut impl AudioSource) {
+        self.set_level(source.level());
+    }
+
+    /// Returns the current audio level.
+    pub fn level(&self) -> f32 {
+        self.level
+    }
+
+    /// Decays the peak by the time since the last tick, and raises it to the level.
+    fn update_peak(&self, time_in_ms: u64) -> f32 {
+        const MILLISECONDS_PER_SECOND: f32 = 1e3;
+
+        let elapsed_in_ms = self.last_tick_in_ms.get().map_or(0, |last_tick_in_ms| {
+            time_in_ms.saturating_sub(last_tick_in_ms)
+        });
+        self.last_tick_in_ms.set(Some(time_in_ms));
+
+        let decay = self.params.peak_decay * elapsed_in_ms as f32 / MILLISECONDS_PER_SECOND;
+        let peak = (self.peak.get() - decay).max(self.level);
+        self.peak.set(peak);
+        peak
+    }
+}
+
+impl<Layout> Pattern<Dim1d, Layout> for VuMeter
+where
+    Layout: Layout1d,
+{
+    type Params = VuMeterParams;
+    type Color = Okhsv;
+
+    /// Creates a new VuMeter pattern with the specified parameters.
+    fn new(params: Self::Params) -> Self {
+        Self {
+            params,
+            level: 0.,
+            peak: Cell::new(0.),
+            last_tick_in_ms: Cell::new(None),
+        }
+    }
+
+    /// Generates colors for a 1D layout.
+    ///
+    /// The bar grows from the start of the layout.
+    fn tick(&self, time_in_ms: u64) -> impl Iterator<Item = Self::Color> {
+        let peak = self.update_peak(time_in_ms);
+
+        let pixel_count = Layout::PIXEL_COUNT as f32;
+        let lit_count = (self.level * pixel_count).round() as usize;
+        let peak_count = (peak * pixel_count).round() as usize;
+        let VuMeterParams {
+            color, peak_color, ..
+        } = self.params;
+        let off = Okhsv::new(0., 0., 0.);
+
+        (0..Layout::PIXEL_COUNT).map(move |index| {
+            if index + 1 == peak_count {
+                peak_color
+            } else if index < lit_count {
+                color
+            } else {
+                off
+            }
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{audio::ScriptedAudio, layout1d};
+
+    layout1d!(Layout, 10);
+
+    /// Returns 'p' for the peak dot, 'x' for the bar, and '.' for off.
+    fn render(pattern: &VuMeter, time_in_ms: u64) -> [char; 10] {
+        let mut pixels = ['.'; 10];
+        for (pixel, color) in pixels
+            .iter_mut()
+            .zip(Pattern::<Dim1d, Layout>::tick(pattern, time_in_ms))
+        {
+            if color == VuMeterParams::default().peak_color {
+                *pixel = 'p';
+            } else if color.v > 0. {
+                *pixel = 'x';
+            }
+        }
+        pixels
+    }
+
+    #[test]
+    fn test_level_and_decaying_peak() {
+        let mut audio = ScriptedAudio::new(&[0.5, 0.2, 0.2, 0.]);
+        let mut pattern: VuMeter = Pattern::<Dim1d, Layout>::new(VuMeterParams::default());
+
+        pattern.update(&mut audio);
+        assert_eq!(
+            render(&pattern, 0),
+            ['x', 'x', 'x', 'x', 'p', '.', '.', '.', '.', '.']
+        );
+
+        // The bar drops immediately, the peak falls at 0.5 of the strip per second.
+        pattern.update(&mut audio);
+        assert_eq!(
+            render(&pattern, 200),
+            ['x', 'x', '.', 'p', '.', '.', '.', '.', '.', '.']
+        );
+
+        pattern.update(&mut audio);
+        assert_eq!(
+            render(&pattern, 1000),
+            ['x', 'p', '.', '.', '.', '.', '.', '.', '.', '.']
+        );
+
+        pattern.update(&mut audio);
+        assert_eq!(render(&pattern, 2000), ['.'; 10]);
+    }
+}
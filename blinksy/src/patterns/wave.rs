@@ -0,0 +1,187 @@
+//! # Wave Pattern
+//!
+//! The wave pattern moves a sine wave of brightness along a strip.
+//!
+//! The brightness at each LED is `0.5 + 0.5 * sin(2π * (x / wavelength - t * speed))`,
+//! where `x` is the position along the strip (from -1.0 to 1.0) and `t` is the time in
+//! seconds.
+//!
+//! With a [`WaveParams::second_wavelength`], a second wave is summed with the first, for
+//! beats and interference.
+//!
+//! ## Example
+//!
+//! ```rust,ignore
+//! use blinksy::{
+//!     ControlBuilder,
+//!     color::Hsv,
+//!     layout::Layout1d,
+//!     layout1d,
+//!     patterns::wave::{Wave, WaveParams}
+//! };
+//!
+//! // Define a 1D layout
+//! layout1d!(Layout, 60);
+//!
+//! // Create an ocean blue Wave pattern, beating with a second wave
+//! let control = ControlBuilder::new_1d()
+//!     .with_layout::<Layout, { Layout::PIXEL_COUNT }>()
+//!     .with_pattern::<Wave>(WaveParams {
+//!         color: Hsv::new(0.6, 1., 1.),
+//!         second_wavelength: Some(0.45),
+//!         ..Default::default()
+//!     })
+//!     .with_driver(/* Your driver */)
+//!     .with_frame_buffer_size::</* Length of frame buffer */>()
+//!     .build();
+//! ```
+
+use core::f32::consts::TAU;
+
+#[allow(unused_imports)]
+use num_traits::Float;
+
+use crate::{color::Hsv, layout::Layout1d, markers::Dim1d, pattern::Pattern};
+
+/// Configuration parameters for the Wave pattern.
+///
+/// Distances are in layout units, where the layout spans from -1.0 to 1.0 (2.0 in total).
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct WaveParams {
+    /// The color at the crest of the wave
+    pub color: Hsv,
+    /// The distance from one crest to the next, in layout units
+    pub wavelength: f32,
+    /// How fast the wave moves, in wavelengths per second
+    pub speed: f32,
+    /// The wavelength of a second wave to sum with the first, at the same speed (`None`
+    /// for a single wave)
+    pub second_wavelength: Option<f32>,
+}
+
+impl Default for WaveParams {
+    fn default() -> Self {
+        Self {
+            color: Hsv::new(0.6, 1., 1.),
+            wavelength: 0.5,
+            speed: 1.,
+            second_wavelength: None,
+        }
+    }
+}
+
+/// Wave pattern implementation.
+///
+/// A sine wave of brightness, moving along the strip.
+#[derive(Debug, Clone)]
+pub struct Wave {
+    /// Configuration parameters
+    params: WaveParams,
+}
+
+impl Wave {
+    /// Returns the brightness (0.0 to 1.0) of a wave at a position, at a time in seconds.
+    fn wave(wavelength: f32, speed: f32, x: f32, time_in_secs: f32) -> f32 {
+        if wavelength == 0. {
+            return 1.;
+        }
+        0.5 + 0.5 * (TAU * (x / wavelength - time_in_secs * speed)).sin()
+    }
+
+    /// Returns the brightness (0.0 to 1.0) at a position, at a time in seconds.
+    ///
+    /// Two waves are averaged, so the sum stays within 0.0 to 1.0.
+    fn value(&self, x: f32, time_in_secs: f32) -> f32 {
+        let WaveParams {
+            wavelength,
+            speed,
+            second_wavelength,
+            ..
+        } = self.params;
+        let first = Self::wave(wavelength, speed, x, time_in_secs);
+        match second_wavelength {
+            None => first,
+            Some(second_wavelength) => {
+                (first + Self::wave(second_wavelength, speed, x, time_in_secs)) / 2.
+            }
+        }
+    }
+}
+
+impl<Layout> Pattern<Dim1d, Layout> for Wave
+where
+    Layout: Layout1d,
+{
+    type Params = WaveParams;
+    type Color = Hsv;
+
+    /// Creates a new Wave pattern with the specified parameters.
+    fn new(params: Self::Params) -> Self {
+        Self { params }
+    }
+
+    /// Generates colors for a 1D layout.
+    fn tick(&self, time_in_ms: u64) -> impl Iterator<Item = Self::Color> {
+        const MILLISECONDS_PER_SECOND: f32 = 1e3;
+        let time_in_secs = time_in_ms as f32 / MILLISECONDS_PER_SECOND;
+        let color = self.params.color;
+
+        Layout::points().map(move |x| {
+            let value = self.value(x, time_in_secs);
+            Hsv::from_hue(color.hue, color.saturation, color.value * value)
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use heapless::Vec;
+
+    use super::*;
+    use crate::layout1d;
+
+    // Points 0.25 apart.
+    layout1d!(Strip, 9);
+
+    fn values(params: WaveParams, time_in_ms: u64) -> Vec<f32, 9> {
+        let pattern: Wave = Pattern::<Dim1d, Strip>::new(params);
+        Pattern::<Dim1d, Strip>::tick(&pattern, time_in_ms)
+            .map(|color| color.value)
+            .collect()
+    }
+
+    #[test]
+    fn test_spatial_period() {
+        // A wavelength of 4 points.
+        let values = values(
+            WaveParams {
+                wavelength: 1.,
+                ..Default::default()
+            },
+            123,
+        );
+        for index in 0..5 {
+            assert!((values[index] - values[index + 4]).abs() < 1e-4);
+        }
+        // Half a wavelength apart is the opposite brightness.
+        for index in 0..7 {
+            assert!((values[index] + values[index + 2] - 1.).abs() < 1e-4);
+        }
+    }
+
+    #[test]
+    fn test_second_wave() {
+        // Two waves in opposite phase cancel out.
+        let values = values(
+            WaveParams {
+                wavelength: 1.,
+                second_wavelength: Some(-1.),
+                speed: 0.,
+                ..Default::default()
+            },
+            0,
+        );
+        assert!(values.iter().all(|value| (value - 0.5).abs() < 1e-4));
+    }
+}
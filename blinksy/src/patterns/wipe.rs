@@ -0,0 +1,217 @@
+//! # Color Wipe Pattern
+//!
+//! The color wipe pattern fills the LEDs with a color, one at a time, then clears them and
+//! starts again. It's useful as a transition, or to check the order of your LEDs.
+//!
+//! The LEDs fill in index order, so 2D and 3D layouts fill in the order of their shapes.
+//!
+//! ## Example
+//!
+//! ```rust,ignore
+//! use blinksy::{
+//!     ControlBuilder,
+//!     color::Hsv,
+//!     layout::Layout1d,
+//!     layout1d,
+//!     patterns::wipe::{ColorWipe, WipeParams}
+//! };
+//!
+//! // Define a 1D layout
+//! layout1d!(Layout, 60);
+//!
+//! // Create a green Color Wipe pattern, filling 30 LEDs per second
+//! let control = ControlBuilder::new_1d()
+//!     .with_layout::<Layout, { Layout::PIXEL_COUNT }>()
+//!     .with_pattern::<ColorWipe>(WipeParams {
+//!         color: Hsv::new(0.33, 1., 1.),
+//!         speed: 30.,
+//!         ..Default::default()
+//!     })
+//!     .with_driver(/* Your driver */)
+//!     .with_frame_buffer_size::</* Length of frame buffer */>()
+//!     .build();
+//! ```
+
+use crate::{
+    color::Hsv,
+    layout::{Layout1d, Layout2d, Layout3d},
+    markers::{Dim1d, Dim2d, Dim3d},
+    pattern::Pattern,
+};
+
+/// Configuration parameters for the ColorWipe pattern.
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct WipeParams {
+    /// The color which fills the LEDs
+    pub color: Hsv,
+    /// How fast the fill front advances, in LEDs per second
+    pub speed: f32,
+    /// The color of LEDs not yet filled
+    pub clear_color: Hsv,
+    /// Fills from the last LED to the first
+    pub reverse: bool,
+}
+
+impl Default for WipeParams {
+    fn default() -> Self {
+        Self {
+            color: Hsv::new(0., 1., 1.),
+            speed: 30.,
+            clear_color: Hsv::new(0., 0., 0.),
+            reverse: false,
+        }
+    }
+}
+
+/// ColorWipe pattern implementation.
+///
+/// Fills the LEDs one at a time, in index order.
+#[derive(Debug, Clone)]
+pub struct ColorWipe {
+    /// Configuration parameters
+    params: WipeParams,
+}
+
+impl ColorWipe {
+    /// Returns the number of filled LEDs at a time.
+    ///
+    /// The front advances from 0 up to `pixel_count` (all filled), then resets.
+    fn front(&self, time_in_ms: u64, pixel_count: usize) -> usize {
+        const MILLISECONDS_PER_SECOND: f32 = 1e3;
+        let steps = (time_in_ms as f32 * self.params.speed / MILLISECONDS_PER_SECOND) as usize;
+        steps % (pixel_count + 1)
+    }
+
+    /// Generates the colors for `pixel_count` LEDs.
+    fn colors(&self, time_in_ms: u64, pixel_count: usize) -> impl Iterator<Item = Hsv> {
+        let front = self.front(time_in_ms, pixel_count);
+        let WipeParams {
+            color,
+            clear_color,
+            reverse,
+            ..
+        } = self.params;
+
+        (0..pixel_count).map(move |index| {
+            let index = if reverse {
+                pixel_count - 1 - index
+            } else {
+                index
+            };
+            if index < front {
+                color
+            } else {
+                clear_color
+            }
+        })
+    }
+}
+
+impl<Layout> Pattern<Dim1d, Layout> for ColorWipe
+where
+    Layout: Layout1d,
+{
+    type Params = WipeParams;
+    type Color = Hsv;
+
+    /// Creates a new ColorWipe pattern with the specified parameters.
+    fn new(params: Self::Params) -> Self {
+        Self { params }
+    }
+
+    /// Generates colors for a 1D layout.
+    fn tick(&self, time_in_ms: u64) -> impl Iterator<Item = Self::Color> {
+        self.colors(time_in_ms, Layout::PIXEL_COUNT)
+    }
+}
+
+impl<Layout> Pattern<Dim2d, Layout> for ColorWipe
+where
+    Layout: Layout2d,
+{
+    type Params = WipeParams;
+    type Color = Hsv;
+
+    /// Creates a new ColorWipe pattern with the specified parameters.
+    fn new(params: Self::Params) -> Self {
+        Self { params }
+    }
+
+    /// Generates colors for a 2D layout, filling in index order.
+    fn tick(&self, time_in_ms: u64) -> impl Iterator<Item = Self::Color> {
+        self.colors(time_in_ms, Layout::PIXEL_COUNT)
+    }
+}
+
+impl<Layout> Pattern<Dim3d, Layout> for ColorWipe
+where
+    Layout: Layout3d,
+{
+    type Params = WipeParams;
+    type Color = Hsv;
+
+    /// Creates a new ColorWipe pattern with the specified parameters.
+    fn new(params: Self::Params) -> Self {
+        Self { params }
+    }
+
+    /// Generates colors for a 3D layout, filling in index order.
+    fn tick(&self, time_in_ms: u64) -> impl Iterator<Item = Self::Color> {
+        self.colors(time_in_ms, Layout::PIXEL_COUNT)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use heapless::Vec;
+
+    use super::*;
+    use crate::layout1d;
+
+    layout1d!(Layout, 5);
+
+    fn params(reverse: bool) -> WipeParams {
+        // 1 LED per millisecond, so the front is at `k` after `k` milliseconds.
+        WipeParams {
+            color: Hsv::new(0.5, 1., 1.),
+            speed: 1000.,
+            clear_color: Hsv::new(0., 0., 0.),
+            reverse,
+        }
+    }
+
+    fn tick(pattern: &ColorWipe, time_in_ms: u64) -> Vec<Hsv, 5> {
+        Pattern::<Dim1d, Layout>::tick(pattern, time_in_ms).collect()
+    }
+
+    #[test]
+    fn test_fill_front() {
+        let WipeParams {
+            color, clear_color, ..
+        } = params(false);
+        let pattern: ColorWipe = Pattern::<Dim1d, Layout>::new(params(false));
+
+        for front in 0..=5 {
+            let colors = tick(&pattern, front as u64);
+            assert!(colors[..front].iter().all(|&pixel| pixel == color));
+            assert!(colors[front..].iter().all(|&pixel| pixel == clear_color));
+        }
+
+        // After filling every LED, the wipe starts again.
+        assert!(tick(&pattern, 6).iter().all(|&pixel| pixel == clear_color));
+    }
+
+    #[test]
+    fn test_reverse() {
+        let WipeParams {
+            color, clear_color, ..
+        } = params(true);
+        let pattern: ColorWipe = Pattern::<Dim1d, Layout>::new(params(true));
+
+        assert_eq!(
+            tick(&pattern, 2),
+            [clear_color, clear_color, clear_color, color, color]
+        );
+    }
+}
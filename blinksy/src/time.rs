@@ -5,8 +5,27 @@
 //! - [`Megahertz`]: For specifying clock rates in MHz
 //! - [`Nanoseconds`]: For specifying timing durations in nanoseconds
 //!
+//! Helpers to convert between frames and milliseconds, at a target frames per second:
+//!
+//! - [`frames_to_ms`]: Frame index to milliseconds
+//! - [`ms_to_frames`]: Milliseconds to frame index
+//!
+//! A source of the current time, for [`Control::tick_auto`](crate::control::Control::tick_auto):
+//!
+//! - [`TimeSource`]: Monotonic time in milliseconds
+//!
+//! Helpers for rhythmic patterns:
+//!
+//! - [`BeatClock`]: Beat phase at a tempo (beats per minute)
+//!
 //! [`fugit`]: https://docs.rs/fugit
 
+use core::f32::consts::TAU;
+
+use num_traits::Euclid;
+#[allow(unused_imports)]
+use num_traits::Float;
+
 /// Represents a frequency in megahertz (MHz).
 ///
 /// Used for specifying clock speeds for drivers and timing calculations.
@@ -16,3 +35,215 @@ pub use fugit::MegahertzU32 as Megahertz;
 ///
 /// Used for precise timing control in LED driver protocols and animations.
 pub use fugit::NanosDurationU32 as Nanoseconds;
+
+/// Trait for a source of the current time, in milliseconds.
+///
+/// The time must be monotonic: it must never go backwards. A wall clock, which can jump
+/// backwards when adjusted, is not a valid time source. Use a monotonic timer instead, such
+/// as the time since boot.
+///
+/// Any function returning the time in milliseconds is a time source.
+///
+/// # Example
+///
+/// ```rust
+/// use blinksy::time::TimeSource;
+///
+/// let mut time_in_ms = 0;
+/// let mut source = move || {
+///     time_in_ms += 16;
+///     time_in_ms
+/// };
+/// assert_eq!(source.now_in_ms(), 16);
+/// assert_eq!(source.now_in_ms(), 32);
+/// ```
+pub trait TimeSource {
+    /// Returns the current time in milliseconds.
+    fn now_in_ms(&mut self) -> u64;
+}
+
+impl<F> TimeSource for F
+where
+    F: FnMut() -> u64,
+{
+    fn now_in_ms(&mut self) -> u64 {
+        self()
+    }
+}
+
+const MILLISECONDS_PER_SECOND: u128 = 1_000;
+
+/// Converts a frame index to the time in milliseconds at which that frame is due, at a
+/// target frames per second.
+///
+/// Frame `n` starts at exactly `n * 1000 / fps` milliseconds. As this is rarely a whole
+/// number of milliseconds, the result is rounded **up**: the first whole millisecond at
+/// or after the start of the frame.
+///
+/// Rounding up means this is the inverse of [`ms_to_frames`], for any `fps` up to 1000:
+/// `ms_to_frames(frames_to_ms(frame, fps), fps) == frame`.
+///
+/// # Arguments
+///
+/// - `frame` - The frame index
+/// - `fps` - The target frames per second
+///
+/// # Panics
+///
+/// Panics if `fps` is zero.
+///
+/// # Example
+///
+/// ```
+/// use blinksy::time::frames_to_ms;
+///
+/// assert_eq!(frames_to_ms(60, 60), 1000);
+/// // Frame 1 at 30 FPS starts at 33.33.. ms
+/// assert_eq!(frames_to_ms(1, 30), 34);
+/// ```
+pub fn frames_to_ms(frame: u64, fps: u32) -> u64 {
+    let numerator = frame as u128 * MILLISECONDS_PER_SECOND;
+    numerator.div_ceil(fps as u128) as u64
+}
+
+/// Converts a time in milliseconds to the index of the frame being shown at that time, at
+/// a target frames per second.
+///
+/// The exact frame position is `ms * fps / 1000`, which is rounded **down**: a frame is
+/// shown from its start until the start of the next frame.
+///
+/// # Arguments
+///
+/// - `ms` - The time in milliseconds
+/// - `fps` - The target frames per second
+///
+/// # Example
+///
+/// ```
+/// use blinksy::time::ms_to_frames;
+///
+/// assert_eq!(ms_to_frames(1000, 60), 60);
+/// // At 33 ms, frame 1 at 30 FPS (starts at 33.33.. ms) is not yet due
+/// assert_eq!(ms_to_frames(33, 30), 0);
+/// assert_eq!(ms_to_frames(34, 30), 1);
+/// ```
+pub fn ms_to_frames(ms: u64, fps: u32) -> u64 {
+    (ms as u128 * fps as u128 / MILLISECONDS_PER_SECOND) as u64
+}
+
+const MILLISECONDS_PER_MINUTE: f64 = 60_000.;
+
+/// A clock for a tempo, in beats per minute (BPM).
+///
+/// Rhythmic and audio-reactive patterns can take a `BeatClock` in their params, to sync
+/// their animation to a beat.
+///
+/// The clock is pure: the phase depends only on the given time, so is deterministic.
+///
+/// # Example
+///
+/// ```
+/// use blinksy::time::BeatClock;
+///
+/// let clock = BeatClock::new(120.);
+/// // At 120 BPM, a beat is every 500 ms
+/// assert_eq!(clock.phase(250), 0.5);
+/// assert_eq!(clock.beat_sin(500), 1.);
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct BeatClock {
+    /// Tempo, in beats per minute
+    pub bpm: f32,
+}
+
+impl BeatClock {
+    /// Creates a new beat clock.
+    ///
+    /// # Arguments
+    ///
+    /// - `bpm` - Tempo, in beats per minute
+    pub fn new(bpm: f32) -> Self {
+        Self { bpm }
+    }
+
+    /// Returns the number of beats since time zero, including the fraction of the current
+    /// beat.
+    ///
+    /// # Arguments
+    ///
+    /// - `time_in_ms` - The current time in milliseconds
+    pub fn beats(&self, time_in_ms: u64) -> f64 {
+        // In f64, so the phase stays precise for long running times.
+        time_in_ms as f64 * self.bpm as f64 / MILLISECONDS_PER_MINUTE
+    }
+
+    /// Returns the phase within the current beat, from 0.0 (on the beat) to 1.0 (the next
+    /// beat).
+    ///
+    /// # Arguments
+    ///
+    /// - `time_in_ms` - The current time in milliseconds
+    pub fn phase(&self, time_in_ms: u64) -> f32 {
+        Euclid::rem_euclid(&self.beats(time_in_ms), &1.) as f32
+    }
+
+    /// Returns a sine wave synced to the beat, from 0.0 to 1.0.
+    ///
+    /// The wave peaks (1.0) on each beat, and is lowest (0.0) half way between beats.
+    ///
+    /// # Arguments
+    ///
+    /// - `time_in_ms` - The current time in milliseconds
+    pub fn beat_sin(&self, time_in_ms: u64) -> f32 {
+        0.5 + 0.5 * (self.phase(time_in_ms) * TAU).cos()
+    }
+}
+
+impl Default for BeatClock {
+    fn default() -> Self {
+        Self { bpm: 120. }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_frames_to_ms_round_trip() {
+        for fps in [1, 24, 25, 30, 60, 144, 1000] {
+            for frame in [0, 1, 2, 7, 59, 60, 61, 1_000, 123_456] {
+                let ms = frames_to_ms(frame, fps);
+                assert_eq!(ms_to_frames(ms, fps), frame, "frame {frame} at {fps} fps");
+            }
+        }
+    }
+
+    #[test]
+    fn test_ms_to_frames_round_trip() {
+        // A time maps to the start of the frame shown at that time.
+        for fps in [24, 30, 60] {
+            for ms in [0, 1, 16, 17, 33, 34, 999, 1_000, 1_001] {
+                let frame = ms_to_frames(ms, fps);
+                assert!(frames_to_ms(frame, fps) <= ms);
+                assert!(frames_to_ms(frame + 1, fps) > ms);
+            }
+        }
+    }
+
+    #[test]
+    fn test_beat_clock() {
+        let clock = BeatClock::new(60.);
+        assert_eq!(clock.phase(0), 0.);
+        assert_eq!(clock.phase(250), 0.25);
+        assert_eq!(clock.phase(1_000), 0.);
+        assert_eq!(clock.phase(1_750), 0.75);
+        assert_eq!(clock.beat_sin(3_000), 1.);
+        assert!(clock.beat_sin(3_500) < 1e-6);
+
+        // Still precise after a day.
+        let day_in_ms = 24 * 60 * 60 * 1_000;
+        assert_eq!(clock.phase(day_in_ms + 500), 0.5);
+    }
+}
@@ -5,22 +5,40 @@
 ///
 /// This trait enables conversions between these two cases.
 pub trait Component: Copy {
+    /// The normalized size of one level, such as 1 / 255 for `u8`, or 0.0 for a continuous
+    /// component such as `f32`.
+    const STEP: f32 = 0.;
+
     /// Converts the component value to a normalized f32 in range [0.0, 1.0].
     fn to_normalized_f32(self) -> f32;
 
-    /// Creates a component value from a normalized f32 in range [0.0, 1.0].
+    /// Creates a component value from a normalized f32 in range [0.0, 1.0], rounded to
+    /// the nearest value.
     fn from_normalized_f32(value: f32) -> Self;
+
+    /// Creates a component value from a normalized f32 in range [0.0, 1.0], rounded down.
+    ///
+    /// By default, rounds to the nearest value half a [`Component::STEP`] lower.
+    fn from_normalized_f32_floor(value: f32) -> Self {
+        Self::from_normalized_f32(value - Self::STEP / 2.)
+    }
 }
 
 macro_rules! impl_component_for_uint {
     ($T:ident) => {
         impl Component for $T {
+            const STEP: f32 = 1. / ($T::MAX as f32);
+
             fn to_normalized_f32(self) -> f32 {
                 self as f32 / ($T::MAX as f32)
             }
 
             fn from_normalized_f32(value: f32) -> Self {
-                (value * ($T::MAX as f32)) as $T
+                (value.clamp(0., 1.) * ($T::MAX as f32) + 0.5) as $T
+            }
+
+            fn from_normalized_f32_floor(value: f32) -> Self {
+                (value.clamp(0., 1.) * ($T::MAX as f32)) as $T
             }
         }
     };
@@ -39,3 +57,105 @@ impl Component for f32 {
         value
     }
 }
+
+/// How to quantize a normalized `f32` into an integer [`Component`].
+///
+/// Rounding changes the output most at low brightness, where a level is a large fraction
+/// of the value: a value of 0.6 / 255 is level 1 when rounded, but off when rounded down.
+///
+/// Encoders honor this as:
+///
+/// - [`ClocklessDriver`](crate::driver::ClocklessDriver) (WS2812, SK6812, and other
+///   clockless chipsets): any mode, with
+///   [`with_quantize_mode`](crate::driver::ClocklessDriver::with_quantize_mode).
+///   [`QuantizeMode::Dither`] needs an error per word, so is set by
+///   [`with_temporal_dithering`](crate::driver::ClocklessDriver::with_temporal_dithering):
+///   without it, there is no error to carry forward, so values are rounded down.
+/// - Clocked chipsets (APA102, WS2801, LPD8806, P9813) and [`LedColor`](crate::color::LedColor)
+///   conversions: always [`QuantizeMode::Round`].
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum QuantizeMode {
+    /// Round to the nearest level
+    #[default]
+    Round,
+    /// Round down, truncating the remainder
+    Floor,
+    /// Round down, carrying the remainder forward into the next frame (temporal error
+    /// diffusion), so the average output over many frames matches the value
+    Dither,
+}
+
+impl QuantizeMode {
+    /// Quantizes a normalized value into a component.
+    ///
+    /// # Arguments
+    ///
+    /// - `value` - The normalized value (0.0 to 1.0)
+    /// - `error` - The remainder carried forward between frames, for [`QuantizeMode::Dither`]
+    ///   (other modes reset it to zero)
+    ///
+    /// # Returns
+    ///
+    /// The quantized component
+    pub fn quantize<C: Component>(self, value: f32, error: &mut f32) -> C {
+        match self {
+            QuantizeMode::Round => {
+                *error = 0.;
+                C::from_normalized_f32(value)
+            }
+            QuantizeMode::Floor => {
+                *error = 0.;
+                C::from_normalized_f32_floor(value)
+            }
+            QuantizeMode::Dither => {
+                let target = (value + *error).clamp(0., 1.);
+                let component = C::from_normalized_f32_floor(target);
+                *error = target - component.to_normalized_f32();
+                component
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_round_and_floor_at_half_level() {
+        let quantize =
+            |mode: QuantizeMode, level: f32| -> u8 { mode.quantize(level / 255., &mut 0.) };
+
+        // Just below and above half a level.
+        assert_eq!(quantize(QuantizeMode::Round, 0.49), 0);
+        assert_eq!(quantize(QuantizeMode::Round, 0.51), 1);
+        assert_eq!(quantize(QuantizeMode::Floor, 0.49), 0);
+        assert_eq!(quantize(QuantizeMode::Floor, 0.51), 0);
+
+        assert_eq!(quantize(QuantizeMode::Round, 10.49), 10);
+        assert_eq!(quantize(QuantizeMode::Round, 10.51), 11);
+        assert_eq!(quantize(QuantizeMode::Floor, 10.51), 10);
+        assert_eq!(quantize(QuantizeMode::Floor, 10.99), 10);
+
+        // The ends are exact.
+        for mode in [QuantizeMode::Round, QuantizeMode::Floor] {
+            assert_eq!(quantize(mode, 0.), 0);
+            assert_eq!(quantize(mode, 255.), 255);
+        }
+    }
+
+    #[test]
+    fn test_dither_carries_remainder() {
+        let mut error = 0.;
+        let levels: [u8; 4] =
+            core::array::from_fn(|_| QuantizeMode::Dither.quantize(0.5 / 255., &mut error));
+        // Half a level, on every other frame.
+        assert_eq!(levels, [0, 1, 0, 1]);
+
+        // Other modes don't carry the remainder.
+        let _: u8 = QuantizeMode::Round.quantize(0.5 / 255., &mut error);
+        assert_eq!(error, 0.);
+    }
+}
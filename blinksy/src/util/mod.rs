@@ -1,5 +1,6 @@
 pub mod bits;
 pub mod component;
 mod map_range;
+pub mod rng;
 
 pub use self::map_range::*;
@@ -0,0 +1,97 @@
+//! # Random Numbers
+//!
+//! A small pseudo-random number generator for patterns, such as for sparkles and flicker.
+//!
+//! It's not cryptographically secure, but it's fast, no-alloc, and deterministic: the same
+//! seed gives the same sequence, so patterns can be reproduced in tests.
+
+/// Hashes a value into a well-mixed 32-bit value.
+///
+/// Useful to derive a random value from an index or a time step, without keeping state.
+///
+/// (Reference: [lowbias32](https://nullprogram.com/blog/2018/07/31/))
+pub const fn hash(value: u32) -> u32 {
+    let mut x = value;
+    x ^= x >> 16;
+    x = x.wrapping_mul(0x7feb_352d);
+    x ^= x >> 15;
+    x = x.wrapping_mul(0x846c_a68b);
+    x ^= x >> 16;
+    x
+}
+
+/// A pseudo-random number generator, using xorshift32.
+///
+/// # Example
+///
+/// ```rust
+/// use blinksy::util::rng::Rng;
+///
+/// let mut rng = Rng::new(42);
+/// let value = rng.next_f32();
+/// assert!((0. ..1.).contains(&value));
+/// ```
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct Rng {
+    /// The current state (never zero)
+    state: u32,
+}
+
+impl Rng {
+    /// Creates a new random number generator from a seed.
+    ///
+    /// # Arguments
+    ///
+    /// - `seed` - Any value, including zero
+    pub const fn new(seed: u32) -> Self {
+        let state = hash(seed);
+        // Xorshift gets stuck at zero.
+        let state = if state == 0 { 0x9e37_79b9 } else { state };
+        Self { state }
+    }
+
+    /// Returns the next random `u32`.
+    pub fn next_u32(&mut self) -> u32 {
+        let mut x = self.state;
+        x ^= x << 13;
+        x ^= x >> 17;
+        x ^= x << 5;
+        self.state = x;
+        x
+    }
+
+    /// Returns the next random `f32`, from 0.0 (inclusive) to 1.0 (exclusive).
+    pub fn next_f32(&mut self) -> f32 {
+        // Use the top 24 bits, which an f32 represents exactly.
+        (self.next_u32() >> 8) as f32 / (1 << 24) as f32
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_deterministic() {
+        let mut a = Rng::new(7);
+        let mut b = Rng::new(7);
+        for _ in 0..100 {
+            assert_eq!(a.next_u32(), b.next_u32());
+        }
+        assert_ne!(Rng::new(7).next_u32(), Rng::new(8).next_u32());
+    }
+
+    #[test]
+    fn test_f32_range() {
+        let mut rng = Rng::new(0);
+        let mut sum = 0.;
+        for _ in 0..1000 {
+            let value = rng.next_f32();
+            assert!((0. ..1.).contains(&value));
+            sum += value;
+        }
+        // Roughly uniform
+        assert!((400. ..600.).contains(&sum));
+    }
+}
@@ -0,0 +1,80 @@
+#![no_std]
+
+//! # nRF52 Blinksy Extensions
+//!
+//! nRF52-specific extensions for the [Blinksy][blinksy] LED control library using [`nrf-hal-common`][nrf_hal_common].
+//!
+//! ## Features
+//!
+//! - nRF-specific driver for clockless (e.g. WS2812) LEDs, using the [PWM] peripheral with
+//!   EasyDMA sequences
+//!
+//! [PWM]: https://docs.nordicsemi.com/bundle/ps_nrf52840/page/pwm.html
+//!
+//! ## Example
+//!
+//! ```rust,ignore
+//! #![no_std]
+//! #![no_main]
+//!
+//! use nrf52840_hal as hal;
+//!
+//! use blinksy::{
+//!     driver::ClocklessDriver,
+//!     layout::Layout1d,
+//!     layout1d,
+//!     leds::Ws2812,
+//!     patterns::rainbow::{Rainbow, RainbowParams},
+//!     ControlBuilder,
+//! };
+//! use blinksy_nrf::{PwmTiming, PwmWs2812};
+//! use hal::gpio::Level;
+//!
+//! #[cortex_m_rt::entry]
+//! fn main() -> ! {
+//!     let p = hal::pac::Peripherals::take().unwrap();
+//!
+//!     // Run the high frequency clock from the crystal, for accurate timing.
+//!     hal::Clocks::new(p.CLOCK).enable_ext_hfosc();
+//!
+//!     let port0 = hal::gpio::p0::Parts::new(p.P0);
+//!
+//!     // Define the LED layout (1D strip of 300 pixels)
+//!     layout1d!(Layout, 60 * 5);
+//!
+//!     // Setup the WS2812 driver using PWM, with a buffer for every bit of a frame.
+//!     const BUFFER_SIZE: usize =
+//!         PwmTiming::buffer_size::<Ws2812>(Ws2812::frame_buffer_size(Layout::PIXEL_COUNT));
+//!     let ws2812_driver = {
+//!         // IMPORTANT: Change `port0.p0_16` to the GPIO pin connected to your WS2812 data line.
+//!         let data_pin = port0.p0_16.into_push_pull_output(Level::Low).degrade();
+//!
+//!         ClocklessDriver::default()
+//!             .with_led::<Ws2812>()
+//!             .with_writer(PwmWs2812::<BUFFER_SIZE, _>::new(p.PWM0, data_pin))
+//!     };
+//!
+//!     // Build the Blinky controller
+//!     let mut control = ControlBuilder::new_1d()
+//!         .with_layout::<Layout, { Layout::PIXEL_COUNT }>()
+//!         .with_pattern::<Rainbow>(RainbowParams {
+//!             ..Default::default()
+//!         })
+//!         .with_driver(ws2812_driver)
+//!         .with_frame_buffer_size::<{ Ws2812::frame_buffer_size(Layout::PIXEL_COUNT) }>()
+//!         .build();
+//!
+//!     control.set_brightness(0.2); // Set initial brightness (0.0 to 1.0)
+//!
+//!     loop {
+//!         let elapsed_in_ms = /* Milliseconds from a timer or RTC */;
+//!         control.tick(elapsed_in_ms).unwrap();
+//!     }
+//! }
+//! ```
+//!
+//! [nrf_hal_common]: https://docs.rs/nrf-hal-common
+
+pub mod pwm;
+
+pub use crate::pwm::{ClocklessPwm, ClocklessPwmError, PwmTiming, PwmWs2812};
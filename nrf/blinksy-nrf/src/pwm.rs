@@ -0,0 +1,338 @@
+//! # PWM-based LED Driver
+//!
+//! This module provides a driver for clockless LED protocols (like WS2812)
+//! using the nRF52's PWM peripheral with EasyDMA. Each bit of LED data is one
+//! PWM period, with a duty cycle for the high time of the bit. The PWM
+//! peripheral reads the duty cycles from RAM by DMA, so the CPU is free while
+//! a frame is sent.
+//!
+//! ## Features
+//!
+//! - Hardware-accelerated LED control
+//! - Precise timing for WS2812 and similar protocols, derived from the
+//!   [`ClocklessLed`] timings
+//! - Blocking and async (feature "async") APIs with equivalent behavior
+//!
+//! ## Technical Details
+//!
+//! The PWM runs from the 16 MHz high frequency clock with no prescaler, so
+//! each PWM tick is 62.5 ns. For accurate timing, the high frequency clock
+//! should run from the external crystal (HFXO), rather than the internal RC
+//! oscillator.
+//!
+//! From the LED timings:
+//!
+//! - PWM period (`COUNTERTOP`) = `T_0H + T_0L`
+//! - Duty cycle for a 0 bit = `T_0H`
+//! - Duty cycle for a 1 bit = `T_1H`
+//!
+//! each rounded to the nearest tick. For example, a WS2812 (`T_0H` = 400 ns,
+//! `T_0L` = 850 ns, `T_1H` = 800 ns) has a period of 20 ticks, a 0 bit of 6
+//! ticks, and a 1 bit of 13 ticks. See [`PwmTiming`].
+//!
+//! Each bit is one 16-bit value in the PWM sequence, with the polarity bit set
+//! so the output is high for the duty cycle and then low. After the frame,
+//! the sequence has fully low periods for the reset.
+//!
+//! The frame and the reset are sent as one EasyDMA sequence. If a sequence
+//! ended mid-frame, the PWM would repeat its last value until the next
+//! sequence started, sending spurious bits. So the buffer must hold a value
+//! for each bit of the frame, plus the reset (see [`PwmTiming::buffer_size`]),
+//! or a write returns [`ClocklessPwmError::FrameTooLong`]. An EasyDMA sequence
+//! holds at most 32767 values, so a frame is at most about 1300 RGB LEDs.
+//! After the sequence, the PWM repeats the last reset value, so the data line
+//! stays low.
+
+#[cfg(feature = "async")]
+use blinksy::driver::ClocklessWriterAsync;
+use blinksy::{
+    driver::{clockless::ClocklessLed, ClocklessWriter},
+    leds::Ws2812,
+    util::bits::{word_to_bits_msb, Word},
+};
+use core::marker::PhantomData;
+use embedded_dma::ReadBuffer;
+use heapless::Vec;
+use nrf_hal_common::{
+    gpio::{Output, Pin, PushPull},
+    pwm::{
+        Channel, CounterMode, Error as PwmError, Instance, LoadMode, Prescaler, Pwm, PwmEvent,
+        PwmSeq, Seq, StepMode,
+    },
+};
+
+/// The frequency of the PWM clock, with no prescaler.
+const PWM_CLOCK_HZ: u32 = 16_000_000;
+
+/// The most values in an EasyDMA sequence.
+const MAX_SEQUENCE_LENGTH: usize = (1 << 15) - 1;
+
+/// The polarity bit of a PWM value: high for the duty cycle, then low.
+const POLARITY_HIGH_FIRST: u16 = 1 << 15;
+
+/// The PWM timing for a clockless LED, derived from its timings.
+///
+/// See the [module docs](self) for how the timing is derived.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct PwmTiming {
+    /// Ticks per PWM period, one bit
+    pub period: u16,
+    /// Ticks high for a 0 bit
+    pub zero: u16,
+    /// Ticks high for a 1 bit
+    pub one: u16,
+    /// Fully low periods for the reset
+    pub reset_periods: usize,
+}
+
+impl PwmTiming {
+    /// Derives the PWM timing for a clockless LED.
+    pub const fn new<Led: ClocklessLed>() -> Self {
+        let period = ticks(Led::T_0H.to_nanos() + Led::T_0L.to_nanos());
+        let period = if period == 0 { 1 } else { period };
+        let reset = ticks(Led::T_RESET.to_nanos());
+
+        Self {
+            period,
+            zero: ticks(Led::T_0H.to_nanos()),
+            one: ticks(Led::T_1H.to_nanos()),
+            reset_periods: reset.div_ceil(period) as usize,
+        }
+    }
+
+    /// A compile-time function to get the `BUFFER_SIZE` of a [`ClocklessPwm`]
+    /// for a frame: one value for each bit, then the reset.
+    ///
+    /// # Arguments
+    ///
+    /// - `frame_buffer_size` - Length of the frame buffer, in words, such as
+    ///   from `Ws2812::frame_buffer_size`
+    pub const fn buffer_size<Led>(frame_buffer_size: usize) -> usize
+    where
+        Led: ClocklessLed,
+        Led::Word: Word,
+    {
+        frame_buffer_size * <Led::Word as Word>::BITS as usize + Self::new::<Led>().reset_periods
+    }
+
+    /// Returns the PWM value for a bit.
+    fn value(&self, bit: bool) -> u16 {
+        POLARITY_HIGH_FIRST
+            | match bit {
+                false => self.zero,
+                true => self.one,
+            }
+    }
+}
+
+/// Converts nanoseconds to PWM ticks, rounded to the nearest tick.
+const fn ticks(nanos: u32) -> u16 {
+    const NANOS_PER_SECOND: u64 = 1_000_000_000;
+    let numerator = nanos as u64 * PWM_CLOCK_HZ as u64;
+    let ticks = (numerator + NANOS_PER_SECOND / 2) / NANOS_PER_SECOND;
+    if ticks > 0x7fff {
+        0x7fff
+    } else {
+        ticks as u16
+    }
+}
+
+/// All types of errors that can happen during the transmission of LED
+/// commands
+#[derive(Debug)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub enum ClocklessPwmError {
+    /// Raised if the PWM can't load the sequence
+    SequenceError(PwmError),
+    /// Raised if the frame and the reset don't fit in the buffer (or in one
+    /// EasyDMA sequence)
+    FrameTooLong,
+}
+
+/// A DMA source of values in the driver's buffer.
+struct DmaValues {
+    address: *const u16,
+    len: usize,
+}
+
+impl DmaValues {
+    fn new<const BUFFER_SIZE: usize>(buffer: &Vec<u16, BUFFER_SIZE>) -> Self {
+        Self {
+            address: buffer.as_ptr(),
+            len: buffer.len(),
+        }
+    }
+}
+
+// Safety: the driver doesn't touch its buffer until the sequence is done.
+unsafe impl ReadBuffer for DmaValues {
+    type Word = u16;
+
+    unsafe fn read_buffer(&self) -> (*const u16, usize) {
+        (self.address, self.len)
+    }
+}
+
+/// PWM-based driver for clockless LED protocols.
+///
+/// This driver uses the nRF52's PWM peripheral, with EasyDMA, to generate
+/// the precisely timed signals required by protocols like WS2812.
+///
+/// # Type Parameters
+///
+/// - `BUFFER_SIZE` - Size of the sequence buffer, in bits (one 16-bit value
+///   per bit). Must hold every bit of a frame, plus the reset, and at most
+///   32767 values: see [`PwmTiming::buffer_size`]. A longer frame isn't sent,
+///   and the write returns [`ClocklessPwmError::FrameTooLong`].
+/// - `Led` - The LED protocol implementation (must implement ClocklessLed)
+/// - `T` - The PWM instance
+pub struct ClocklessPwm<const BUFFER_SIZE: usize, Led, T>
+where
+    Led: ClocklessLed,
+    T: Instance,
+{
+    led: PhantomData<Led>,
+    pwm: Option<Pwm<T>>,
+    buffer: Vec<u16, BUFFER_SIZE>,
+    timing: PwmTiming,
+}
+
+/// PWM-based driver for WS2812 LEDs.
+pub type PwmWs2812<const BUFFER_SIZE: usize, T> = ClocklessPwm<BUFFER_SIZE, Ws2812, T>;
+
+impl<const BUFFER_SIZE: usize, Led, T> ClocklessPwm<BUFFER_SIZE, Led, T>
+where
+    Led: ClocklessLed,
+    Led::Word: Word,
+    T: Instance,
+{
+    /// Create a new adapter object that drives the pin using the PWM
+    /// peripheral.
+    ///
+    /// # Arguments
+    ///
+    /// - `pwm` - PWM instance
+    /// - `pin` - GPIO pin connected to the LED data line
+    ///
+    /// # Returns
+    ///
+    /// A configured ClocklessPwm instance
+    pub fn new(pwm: T, pin: Pin<Output<PushPull>>) -> Self {
+        let timing = PwmTiming::new::<Led>();
+
+        let pwm = Pwm::new(pwm);
+        pwm.set_output_pin(Channel::C0, pin)
+            .set_prescaler(Prescaler::Div1)
+            .set_counter_mode(CounterMode::Up)
+            .set_max_duty(timing.period)
+            .set_load_mode(LoadMode::Common)
+            .set_step_mode(StepMode::Auto);
+
+        Self {
+            led: PhantomData,
+            pwm: Some(pwm),
+            buffer: Vec::new(),
+            timing,
+        }
+    }
+
+    /// Fills the buffer with the PWM values for a frame, then the reset.
+    ///
+    /// The reset values are last, so the data line stays low after the
+    /// sequence.
+    fn fill_buffer<const FRAME_BUFFER_SIZE: usize>(
+        &mut self,
+        frame: Vec<Led::Word, FRAME_BUFFER_SIZE>,
+    ) -> Result<(), ClocklessPwmError> {
+        let timing = self.timing;
+        let length = frame.len() * <Led::Word as Word>::BITS as usize + timing.reset_periods;
+        if length > BUFFER_SIZE.min(MAX_SEQUENCE_LENGTH) {
+            return Err(ClocklessPwmError::FrameTooLong);
+        }
+
+        let bits = frame
+            .into_iter()
+            .flat_map(move |word| word_to_bits_msb(word).map(move |bit| timing.value(bit)));
+        let reset = core::iter::repeat_n(POLARITY_HIGH_FIRST, timing.reset_periods);
+        self.buffer.clear();
+        for value in bits.chain(reset) {
+            // The buffer has room for the frame, as checked above.
+            let _ = self.buffer.push(value);
+        }
+        Ok(())
+    }
+
+    /// Starts the PWM sequence of the buffer.
+    fn start_sequence(&mut self) -> Result<PwmSeq<T, DmaValues, DmaValues>, ClocklessPwmError> {
+        let pwm = self.pwm.take().unwrap();
+        pwm.reset_event(PwmEvent::SeqEnd(Seq::Seq0));
+        pwm.load(Some(DmaValues::new(&self.buffer)), None, true)
+            .map_err(|(error, pwm, _, _)| {
+                self.pwm = Some(pwm);
+                ClocklessPwmError::SequenceError(error)
+            })
+    }
+
+    /// Returns whether the PWM sequence is done.
+    fn is_sequence_done(sequence: &PwmSeq<T, DmaValues, DmaValues>) -> bool {
+        sequence.is_event_triggered(PwmEvent::SeqEnd(Seq::Seq0))
+    }
+
+    /// Returns the PWM from a finished sequence.
+    fn finish_sequence(&mut self, sequence: PwmSeq<T, DmaValues, DmaValues>) {
+        let (_, _, pwm) = sequence.split();
+        pwm.reset_event(PwmEvent::SeqEnd(Seq::Seq0));
+        self.pwm = Some(pwm);
+    }
+}
+
+impl<const BUFFER_SIZE: usize, Led, T> ClocklessWriter<Led> for ClocklessPwm<BUFFER_SIZE, Led, T>
+where
+    Led: ClocklessLed,
+    Led::Word: Word,
+    T: Instance,
+{
+    type Error = ClocklessPwmError;
+
+    fn write<const FRAME_BUFFER_SIZE: usize>(
+        &mut self,
+        frame: Vec<Led::Word, FRAME_BUFFER_SIZE>,
+    ) -> Result<(), Self::Error> {
+        self.fill_buffer(frame)?;
+        let sequence = self.start_sequence()?;
+        while !Self::is_sequence_done(&sequence) {}
+        self.finish_sequence(sequence);
+
+        Ok(())
+    }
+}
+
+#[cfg(feature = "async")]
+impl<const BUFFER_SIZE: usize, Led, T> ClocklessWriterAsync<Led>
+    for ClocklessPwm<BUFFER_SIZE, Led, T>
+where
+    Led: ClocklessLed,
+    Led::Word: Word,
+    T: Instance,
+{
+    type Error = ClocklessPwmError;
+
+    /// Writes a frame, yielding while it is sent by DMA.
+    ///
+    /// The future must be run to completion: if dropped mid-frame, the
+    /// driver is left without its PWM, and panics on the next write.
+    async fn write<const FRAME_BUFFER_SIZE: usize>(
+        &mut self,
+        frame: Vec<Led::Word, FRAME_BUFFER_SIZE>,
+    ) -> Result<(), Self::Error> {
+        self.fill_buffer(frame)?;
+        let sequence = self.start_sequence()?;
+        while !Self::is_sequence_done(&sequence) {
+            embassy_futures::yield_now().await;
+        }
+        self.finish_sequence(sequence);
+
+        Ok(())
+    }
+}
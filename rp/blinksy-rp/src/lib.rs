@@ -0,0 +1,99 @@
+#![no_std]
+
+//! # RP2040 Blinksy Extensions
+//!
+//! RP2040-specific extensions for the [Blinksy][blinksy] LED control library using [`rp2040-hal`][rp2040_hal].
+//!
+//! ## Features
+//!
+//! - RP-specific driver for clockless (e.g. WS2812) LEDs, using a [PIO (Programmable I/O)][PIO]
+//!   state machine fed by DMA
+//!
+//! [PIO]: https://datasheets.raspberrypi.com/rp2040/rp2040-datasheet.pdf#section_pio
+//!
+//! ## Example
+//!
+//! ```rust,ignore
+//! #![no_std]
+//! #![no_main]
+//!
+//! use rp2040_hal as hal;
+//!
+//! use blinksy::{
+//!     driver::ClocklessDriver,
+//!     layout::Layout1d,
+//!     layout1d,
+//!     leds::Ws2812,
+//!     patterns::rainbow::{Rainbow, RainbowParams},
+//!     ControlBuilder,
+//! };
+//! use blinksy_rp::PioWs2812;
+//! use hal::{clocks::Clock, dma::DMAExt, pio::PIOExt};
+//!
+//! #[hal::entry]
+//! fn main() -> ! {
+//!     let mut p = hal::pac::Peripherals::take().unwrap();
+//!     let mut watchdog = hal::Watchdog::new(p.WATCHDOG);
+//!     let clocks = hal::clocks::init_clocks_and_plls(
+//!         12_000_000,
+//!         p.XOSC,
+//!         p.CLOCKS,
+//!         p.PLL_SYS,
+//!         p.PLL_USB,
+//!         &mut p.RESETS,
+//!         &mut watchdog,
+//!     )
+//!     .unwrap();
+//!     let sio = hal::Sio::new(p.SIO);
+//!     let pins = hal::gpio::Pins::new(p.IO_BANK0, p.PADS_BANK0, sio.gpio_bank0, &mut p.RESETS);
+//!     let timer = hal::Timer::new(p.TIMER, &mut p.RESETS, &clocks);
+//!
+//!     // Define the LED layout (1D strip of 300 pixels)
+//!     layout1d!(Layout, 60 * 5);
+//!
+//!     // Setup the WS2812 driver using PIO and DMA.
+//!     let ws2812_driver = {
+//!         let (mut pio, sm0, _, _, _) = p.PIO0.split(&mut p.RESETS);
+//!         let dma = p.DMA.split(&mut p.RESETS);
+//!
+//!         // IMPORTANT: Change `pins.gpio16` to the GPIO pin connected to your WS2812 data line.
+//!         let data_pin = pins.gpio16.into_function();
+//!
+//!         let writer = PioWs2812::<{ Layout::PIXEL_COUNT * 3 }, _, _, _>::new(
+//!             &mut pio,
+//!             sm0,
+//!             data_pin,
+//!             dma.ch0,
+//!             clocks.system_clock.freq(),
+//!         )
+//!         .unwrap();
+//!
+//!         ClocklessDriver::default()
+//!             .with_led::<Ws2812>()
+//!             .with_writer(writer)
+//!     };
+//!
+//!     // Build the Blinky controller
+//!     let mut control = ControlBuilder::new_1d()
+//!         .with_layout::<Layout, { Layout::PIXEL_COUNT }>()
+//!         .with_pattern::<Rainbow>(RainbowParams {
+//!             ..Default::default()
+//!         })
+//!         .with_driver(ws2812_driver)
+//!         .with_frame_buffer_size::<{ Ws2812::frame_buffer_size(Layout::PIXEL_COUNT) }>()
+//!         .build();
+//!
+//!     control.set_brightness(0.2); // Set initial brightness (0.0 to 1.0)
+//!
+//!     loop {
+//!         let elapsed_in_ms = timer.get_counter().ticks() / 1_000;
+//!         control.tick(elapsed_in_ms).unwrap();
+//!     }
+//! }
+//! ```
+//!
+//! [rp2040_hal]: https://docs.rs/rp2040-hal
+
+pub mod pio;
+
+pub use crate::pio::{ClocklessPio, ClocklessPioError, PioTiming, PioWs2812};
@@ -0,0 +1,437 @@
+//! # PIO-based LED Driver
+//!
+//! This module provides a driver for clockless LED protocols (like WS2812)
+//! using one of the RP2040's PIO (Programmable I/O) state machines, fed by
+//! DMA. The state machine generates the precisely timed pulses of the
+//! protocol, while DMA keeps its FIFO full, so the CPU is free while a frame
+//! is sent.
+//!
+//! ## Features
+//!
+//! - Hardware-accelerated LED control
+//! - Precise timing for WS2812 and similar protocols, derived from the
+//!   [`ClocklessLed`] timings
+//! - Blocking and async (feature "async") APIs with equivalent behavior
+//!
+//! ## Technical Details
+//!
+//! The PIO program is the classic WS2812 program, with one side-set pin for
+//! the data line. Each bit is sent in three phases:
+//!
+//! ```text
+//!         |<- T1 ->|<- T2 ->|<- T3 ->|
+//! bit 0:  ‾‾‾‾‾‾‾‾‾|________|________|
+//! bit 1:  ‾‾‾‾‾‾‾‾‾‾‾‾‾‾‾‾‾‾|________|
+//! ```
+//!
+//! So from the LED timings:
+//!
+//! - `T1` = `T_0H`
+//! - `T2` = `T_1H - T_0H`
+//! - `T3` = `T_1L` (`T_0L` is then `T2 + T3`)
+//!
+//! Each phase is a delay of whole state machine cycles, and the PIO program
+//! has room for at most 16 cycles per phase. So the state machine cycle is
+//! the longest phase divided by 16, and the clock divider of the state
+//! machine (from the system clock) is:
+//!
+//! ```text
+//! clock divider = system clock frequency × longest phase / 16
+//! ```
+//!
+//! as a 16.8 fixed point number, no less than 1. Then each phase is rounded to
+//! the nearest whole number of cycles.
+//!
+//! For example, a WS2812 (`T_0H` = 400 ns, `T_1H` = 800 ns, `T_1L` = 450 ns)
+//! with a 125 MHz system clock: the longest phase is 450 ns, so the clock
+//! divider is 125 MHz × 450 ns / 16 = 3.515625, for a cycle of 28.125 ns, and
+//! the phases are 14, 14, and 16 cycles. See [`PioTiming`].
+//!
+//! Each LED word is written to the state machine's FIFO as one 32-bit word,
+//! left-aligned, and pulled by the state machine one word at a time.
+
+#[cfg(feature = "async")]
+use blinksy::driver::ClocklessWriterAsync;
+use blinksy::{
+    driver::{clockless::ClocklessLed, ClocklessWriter},
+    leds::Ws2812,
+    util::bits::{word_to_bits_msb, Word},
+};
+use core::marker::PhantomData;
+use fugit::HertzU32;
+use heapless::Vec;
+use rp2040_hal::{
+    dma::{single_buffer, ReadTarget, SingleChannel},
+    gpio::{Pin, PinId, PullType},
+    pio::{
+        Buffers, InstallError, PIOBuilder, PIOExt, PinDir, Running, ShiftDirection, StateMachine,
+        StateMachineIndex, Tx, UninitStateMachine, PIO,
+    },
+};
+
+const NANOS_PER_SECOND: u64 = 1_000_000_000;
+
+/// The most system clock cycles to busy wait for before yielding, while an async write
+/// waits for the reset.
+#[cfg(feature = "async")]
+const LATCH_STEP_CYCLES: u32 = 1_000;
+
+/// The PIO timing for a clockless LED, derived from its timings.
+///
+/// See the [module docs](self) for how the timing is derived.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct PioTiming {
+    /// Integer part of the state machine clock divider
+    pub clock_divider_int: u16,
+    /// Fractional part of the state machine clock divider, in 1/256ths
+    pub clock_divider_frac: u8,
+    /// Cycles the data line is high, for both bits
+    pub t1: u8,
+    /// Cycles the data line is high for a 1 bit, low for a 0 bit
+    pub t2: u8,
+    /// Cycles the data line is low, for both bits
+    pub t3: u8,
+}
+
+impl PioTiming {
+    /// The most cycles per phase, limited by the delay field of the PIO
+    /// instructions (with one side-set bit).
+    pub const MAX_CYCLES: u8 = 16;
+
+    /// Derives the PIO timing for a clockless LED.
+    ///
+    /// # Arguments
+    ///
+    /// - `system_clock` - Frequency of the system clock, which clocks the PIO
+    ///
+    /// # Returns
+    ///
+    /// The clock divider and cycles for each phase of a bit
+    pub fn new<Led: ClocklessLed>(system_clock: HertzU32) -> Self {
+        let system_clock_hz = system_clock.to_Hz() as u64;
+        let t_0h = Led::T_0H.to_nanos() as u64;
+        let t_1h = Led::T_1H.to_nanos() as u64;
+        let t_1l = Led::T_1L.to_nanos() as u64;
+        let phases = [t_0h, t_1h.saturating_sub(t_0h), t_1l];
+        let longest = phases.iter().copied().max().unwrap_or(0);
+
+        // Clock divider in 16.8 fixed point: system clock × longest phase / 16.
+        let divider_256 =
+            (system_clock_hz * longest * 256) / (Self::MAX_CYCLES as u64 * NANOS_PER_SECOND);
+        let divider_256 = divider_256.clamp(256, u32::MAX as u64 >> 8);
+
+        // Cycles per phase: phase / (clock divider / system clock), rounded.
+        let cycles = |phase: u64| {
+            let numerator = phase * system_clock_hz * 256;
+            let denominator = divider_256 * NANOS_PER_SECOND;
+            ((numerator + denominator / 2) / denominator).clamp(1, Self::MAX_CYCLES as u64) as u8
+        };
+
+        Self {
+            clock_divider_int: (divider_256 >> 8) as u16,
+            clock_divider_frac: (divider_256 & 0xff) as u8,
+            t1: cycles(phases[0]),
+            t2: cycles(phases[1]),
+            t3: cycles(phases[2]),
+        }
+    }
+
+    /// Assembles the PIO program for this timing.
+    fn program(&self) -> pio::Program<{ pio::RP2040_MAX_PROGRAM_SIZE }> {
+        let side_set = pio::SideSet::new(false, 1, false);
+        let mut a = pio::Assembler::<{ pio::RP2040_MAX_PROGRAM_SIZE }>::new_with_side_set(side_set);
+
+        let mut wrap_target = a.label();
+        let mut wrap_source = a.label();
+        let mut do_zero = a.label();
+
+        a.bind(&mut wrap_target);
+        // Low for T3, while shifting out the next bit.
+        a.out_with_delay_and_side_set(pio::OutDestination::X, 1, self.t3 - 1, 0);
+        // High for T1.
+        a.jmp_with_delay_and_side_set(pio::JmpCondition::XIsZero, &mut do_zero, self.t1 - 1, 1);
+        // A 1 bit: high for T2.
+        a.jmp_with_delay_and_side_set(pio::JmpCondition::Always, &mut wrap_target, self.t2 - 1, 1);
+        a.bind(&mut do_zero);
+        // A 0 bit: low for T2.
+        a.nop_with_delay_and_side_set(self.t2 - 1, 0);
+        a.bind(&mut wrap_source);
+
+        a.assemble_with_wrap(wrap_source, wrap_target)
+    }
+}
+
+/// All types of errors that can happen during the transmission of LED
+/// commands.
+#[derive(Debug)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub enum ClocklessPioError {
+    /// Raised if an async write was dropped mid-frame, so the driver lost
+    /// its DMA channel
+    DmaChannelLost,
+}
+
+/// A DMA source of words in the driver's buffer.
+struct DmaWords {
+    address: u32,
+    count: u32,
+}
+
+impl DmaWords {
+    fn new<const BUFFER_SIZE: usize>(buffer: &Vec<u32, BUFFER_SIZE>) -> Self {
+        Self {
+            address: buffer.as_ptr() as u32,
+            count: buffer.len() as u32,
+        }
+    }
+}
+
+// Safety: the driver doesn't touch its buffer until the transfer is done.
+unsafe impl ReadTarget for DmaWords {
+    type ReceivedWord = u32;
+
+    fn rx_treq() -> Option<u8> {
+        None
+    }
+
+    fn rx_address_count(&self) -> (u32, u32) {
+        (self.address, self.count)
+    }
+
+    fn rx_increment(&self) -> bool {
+        true
+    }
+}
+
+/// PIO-based driver for clockless LED protocols.
+///
+/// This driver uses a RP2040 PIO state machine to generate the precisely
+/// timed signals required by protocols like WS2812, with a DMA channel to
+/// feed the state machine.
+///
+/// # Type Parameters
+///
+/// - `BUFFER_SIZE` - Size of the DMA buffer, in LED words. Frames longer than
+///   the buffer are sent in chunks.
+/// - `Led` - The LED protocol implementation (must implement ClocklessLed)
+/// - `P` - The PIO block
+/// - `SM` - The state machine of the PIO block
+/// - `Ch` - The DMA channel
+pub struct ClocklessPio<const BUFFER_SIZE: usize, Led, P, SM, Ch>
+where
+    Led: ClocklessLed,
+    P: PIOExt,
+    SM: StateMachineIndex,
+{
+    led: PhantomData<Led>,
+    _state_machine: StateMachine<(P, SM), Running>,
+    dma: Option<(Ch, Tx<(P, SM)>)>,
+    buffer: Vec<u32, BUFFER_SIZE>,
+    reset_cycles: u32,
+}
+
+/// PIO-based driver for WS2812 LEDs.
+pub type PioWs2812<const BUFFER_SIZE: usize, P, SM, Ch> =
+    ClocklessPio<BUFFER_SIZE, Ws2812, P, SM, Ch>;
+
+impl<const BUFFER_SIZE: usize, Led, P, SM, Ch> ClocklessPio<BUFFER_SIZE, Led, P, SM, Ch>
+where
+    Led: ClocklessLed,
+    Led::Word: Word,
+    P: PIOExt,
+    SM: StateMachineIndex,
+    Ch: SingleChannel,
+{
+    /// Create a new adapter object that drives the pin using the PIO state
+    /// machine and DMA channel.
+    ///
+    /// # Arguments
+    ///
+    /// - `pio` - PIO block, to install the program in
+    /// - `state_machine` - Unused state machine of the PIO block
+    /// - `pin` - GPIO pin connected to the LED data line, set to the PIO block's function
+    /// - `channel` - DMA channel
+    /// - `system_clock` - Frequency of the system clock
+    ///
+    /// # Returns
+    ///
+    /// A configured ClocklessPio instance, or an error if the PIO block has no
+    /// room for the program (4 instructions)
+    pub fn new<I, Pull>(
+        pio: &mut PIO<P>,
+        state_machine: UninitStateMachine<(P, SM)>,
+        pin: Pin<I, P::PinFunction, Pull>,
+        channel: Ch,
+        system_clock: HertzU32,
+    ) -> Result<Self, InstallError>
+    where
+        I: PinId,
+        Pull: PullType,
+    {
+        let timing = PioTiming::new::<Led>(system_clock);
+        let program = pio.install(&timing.program())?;
+        let pin_id = pin.id().num;
+
+        let (mut state_machine, _, tx) = PIOBuilder::from_installed_program(program)
+            .side_set_pin_base(pin_id)
+            .out_shift_direction(ShiftDirection::Left)
+            .autopull(true)
+            // 32 bits is written as 0.
+            .pull_threshold((Led::Word::BITS % 32) as u8)
+            .buffers(Buffers::OnlyTx)
+            .clock_divisor_fixed_point(timing.clock_divider_int, timing.clock_divider_frac)
+            .build(state_machine);
+        state_machine.set_pindirs([(pin_id, PinDir::Output)]);
+
+        // After the FIFO is empty, the state machine has pulled the last word, so wait for
+        // all its bits to be shifted out, and then the reset.
+        let system_clock_hz = system_clock.to_Hz() as u64;
+        let bit_nanos = (Led::T_0H + Led::T_0L)
+            .to_nanos()
+            .max((Led::T_1H + Led::T_1L).to_nanos()) as u64;
+        let word_nanos = bit_nanos * Led::Word::BITS as u64;
+        let reset_nanos = Led::T_RESET.to_nanos() as u64;
+        let reset_cycles = ((word_nanos + reset_nanos) * system_clock_hz / NANOS_PER_SECOND) as u32;
+
+        Ok(Self {
+            led: PhantomData,
+            _state_machine: state_machine.start(),
+            dma: Some((channel, tx)),
+            buffer: Vec::new(),
+            reset_cycles,
+        })
+    }
+
+    /// Converts a LED word to a FIFO word, left-aligned.
+    fn fifo_word(word: Led::Word) -> u32 {
+        let bits = word_to_bits_msb(word).fold(0u32, |acc, bit| (acc << 1) | bit as u32);
+        bits << (32 - Led::Word::BITS)
+    }
+
+    /// Fills the buffer with the next chunk of LED words.
+    ///
+    /// # Returns
+    ///
+    /// Whether the buffer has any words
+    fn fill_buffer(&mut self, words: &mut impl Iterator<Item = Led::Word>) -> bool {
+        self.buffer.clear();
+        for word in words.take(BUFFER_SIZE) {
+            // The buffer has room for the chunk.
+            let _ = self.buffer.push(Self::fifo_word(word));
+        }
+        !self.buffer.is_empty()
+    }
+
+    /// Starts a DMA transfer of the buffer to the state machine.
+    fn start_transfer(&mut self) -> single_buffer::Transfer<Ch, DmaWords, Tx<(P, SM)>> {
+        let (channel, tx) = self.dma.take().unwrap();
+        single_buffer::Config::new(channel, DmaWords::new(&self.buffer), tx).start()
+    }
+
+    /// Returns the DMA channel and state machine FIFO from a finished transfer.
+    fn finish_transfer(&mut self, transfer: single_buffer::Transfer<Ch, DmaWords, Tx<(P, SM)>>) {
+        let (channel, _, tx) = transfer.wait();
+        self.dma = Some((channel, tx));
+    }
+
+    /// Returns an error if the driver lost its DMA channel.
+    fn check_dma(&self) -> Result<(), ClocklessPioError> {
+        match self.dma {
+            Some(_) => Ok(()),
+            None => Err(ClocklessPioError::DmaChannelLost),
+        }
+    }
+
+    /// Waits for the state machine to send the last word, then the reset.
+    fn latch(&self) {
+        let (_, tx) = self.dma.as_ref().unwrap();
+        while !tx.is_empty() {}
+        cortex_m::asm::delay(self.reset_cycles);
+    }
+
+    /// Waits for the state machine to send the last word, then the reset,
+    /// yielding in between.
+    ///
+    /// Without a timer, the reset is waited for in short busy waits, yielding
+    /// after each so other tasks can run. A yield only adds to the wait, so
+    /// the reset is never cut short.
+    #[cfg(feature = "async")]
+    async fn latch_async(&self) {
+        let (_, tx) = self.dma.as_ref().unwrap();
+        while !tx.is_empty() {
+            embassy_futures::yield_now().await;
+        }
+        let mut remaining_cycles = self.reset_cycles;
+        while remaining_cycles > 0 {
+            let cycles = remaining_cycles.min(LATCH_STEP_CYCLES);
+            cortex_m::asm::delay(cycles);
+            remaining_cycles -= cycles;
+            embassy_futures::yield_now().await;
+        }
+    }
+}
+
+impl<const BUFFER_SIZE: usize, Led, P, SM, Ch> ClocklessWriter<Led>
+    for ClocklessPio<BUFFER_SIZE, Led, P, SM, Ch>
+where
+    Led: ClocklessLed,
+    Led::Word: Word,
+    P: PIOExt,
+    SM: StateMachineIndex,
+    Ch: SingleChannel,
+{
+    type Error = ClocklessPioError;
+
+    fn write<const FRAME_BUFFER_SIZE: usize>(
+        &mut self,
+        frame: Vec<Led::Word, FRAME_BUFFER_SIZE>,
+    ) -> Result<(), Self::Error> {
+        self.check_dma()?;
+        let mut words = frame.into_iter();
+        while self.fill_buffer(&mut words) {
+            let transfer = self.start_transfer();
+            self.finish_transfer(transfer);
+        }
+        self.latch();
+
+        Ok(())
+    }
+}
+
+#[cfg(feature = "async")]
+impl<const BUFFER_SIZE: usize, Led, P, SM, Ch> ClocklessWriterAsync<Led>
+    for ClocklessPio<BUFFER_SIZE, Led, P, SM, Ch>
+where
+    Led: ClocklessLed,
+    Led::Word: Word,
+    P: PIOExt,
+    SM: StateMachineIndex,
+    Ch: SingleChannel,
+{
+    type Error = ClocklessPioError;
+
+    /// Writes a frame, yielding while each chunk is sent by DMA, and while
+    /// waiting for the reset at the end of the frame.
+    ///
+    /// The future must be run to completion: if dropped mid-frame, the
+    /// driver is left without its DMA channel, and each later write returns
+    /// [`ClocklessPioError::DmaChannelLost`].
+    async fn write<const FRAME_BUFFER_SIZE: usize>(
+        &mut self,
+        frame: Vec<Led::Word, FRAME_BUFFER_SIZE>,
+    ) -> Result<(), Self::Error> {
+        self.check_dma()?;
+        let mut words = frame.into_iter();
+        while self.fill_buffer(&mut words) {
+            let transfer = self.start_transfer();
+            while !transfer.is_done() {
+                embassy_futures::yield_now().await;
+            }
+            self.finish_transfer(transfer);
+        }
+        self.latch_async().await;
+
+        Ok(())
+    }
+}